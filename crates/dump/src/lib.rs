@@ -305,6 +305,8 @@ pub(crate) mod test {
             localized_attributes: Setting::NotSet,
             facet_search: Setting::NotSet,
             prefix_search: Setting::NotSet,
+            query_rules: Setting::NotSet,
+            rerankers: Setting::NotSet,
             _kind: std::marker::PhantomData,
         };
         settings.check()
@@ -357,7 +357,16 @@ impl<T> From<v5::Settings<T>> for v6::Settings<v6::Unchecked> {
             non_separator_tokens: v6::Setting::NotSet,
             separator_tokens: v6::Setting::NotSet,
             dictionary: v6::Setting::NotSet,
-            synonyms: settings.synonyms.into(),
+            synonyms: match settings.synonyms {
+                v5::Setting::Set(synonyms) => v6::Setting::Set(
+                    synonyms
+                        .into_iter()
+                        .map(|(word, synonyms)| (word, v6::Synonyms::Words(synonyms)))
+                        .collect(),
+                ),
+                v5::Setting::Reset => v6::Setting::Reset,
+                v5::Setting::NotSet => v6::Setting::NotSet,
+            },
             distinct_attribute: settings.distinct_attribute.into(),
             proximity_precision: v6::Setting::NotSet,
             typo_tolerance: match settings.typo_tolerance {
@@ -398,6 +407,8 @@ impl<T> From<v5::Settings<T>> for v6::Settings<v6::Unchecked> {
             search_cutoff_ms: v6::Setting::NotSet,
             facet_search: v6::Setting::NotSet,
             prefix_search: v6::Setting::NotSet,
+            query_rules: v6::Setting::NotSet,
+            rerankers: v6::Setting::NotSet,
             _kind: std::marker::PhantomData,
         }
     }
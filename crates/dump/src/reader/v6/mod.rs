@@ -47,6 +47,7 @@ pub type Code = meilisearch_types::error::Code;
 pub type RankingRuleView = meilisearch_types::settings::RankingRuleView;
 
 pub type FilterableAttributesRule = meilisearch_types::milli::FilterableAttributesRule;
+pub type Synonyms = meilisearch_types::milli::Synonyms;
 
 pub struct V6Reader {
     dump: TempDir,
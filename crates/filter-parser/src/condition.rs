@@ -12,6 +12,7 @@ use nom::combinator::cut;
 use nom::sequence::{terminated, tuple};
 use Condition::*;
 
+use crate::value::parse_regex;
 use crate::{parse_value, FilterCondition, IResult, Span, Token};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -28,6 +29,8 @@ pub enum Condition<'a> {
     Between { from: Token<'a>, to: Token<'a> },
     Contains { keyword: Token<'a>, word: Token<'a> },
     StartsWith { keyword: Token<'a>, word: Token<'a> },
+    EndsWith { keyword: Token<'a>, word: Token<'a> },
+    Matches { keyword: Token<'a>, regex: Token<'a> },
 }
 
 impl Condition<'_> {
@@ -45,6 +48,8 @@ impl Condition<'_> {
             Condition::Between { .. } => "TO",
             Condition::Contains { .. } => "CONTAINS",
             Condition::StartsWith { .. } => "STARTS WITH",
+            Condition::EndsWith { .. } => "ENDS WITH",
+            Condition::Matches { .. } => "MATCHES",
         }
     }
 }
@@ -169,6 +174,62 @@ pub fn parse_not_starts_with(input: Span) -> IResult<FilterCondition> {
     ))
 }
 
+/// ends with        = value "ENDS WITH" value
+pub fn parse_ends_with(input: Span) -> IResult<FilterCondition> {
+    let (input, (fid, ends_with, value)) =
+        tuple((parse_value, tag("ENDS WITH"), cut(parse_value)))(input)?;
+    Ok((
+        input,
+        FilterCondition::Condition {
+            fid,
+            op: EndsWith { keyword: Token { span: ends_with, value: None }, word: value },
+        },
+    ))
+}
+
+/// ends with        = value "NOT" WS+ "ENDS WITH" value
+pub fn parse_not_ends_with(input: Span) -> IResult<FilterCondition> {
+    let keyword = tuple((tag("NOT"), multispace1, tag("ENDS WITH")));
+    let (input, (fid, (_not, _spaces, ends_with), value)) =
+        tuple((parse_value, keyword, cut(parse_value)))(input)?;
+
+    Ok((
+        input,
+        FilterCondition::Not(Box::new(FilterCondition::Condition {
+            fid,
+            op: EndsWith { keyword: Token { span: ends_with, value: None }, word: value },
+        })),
+    ))
+}
+
+/// matches        = value "MATCHES" (regex | value)
+pub fn parse_matches(input: Span) -> IResult<FilterCondition> {
+    let (input, (fid, matches, regex)) =
+        tuple((parse_value, tag("MATCHES"), cut(alt((parse_regex, parse_value)))))(input)?;
+    Ok((
+        input,
+        FilterCondition::Condition {
+            fid,
+            op: Matches { keyword: Token { span: matches, value: None }, regex },
+        },
+    ))
+}
+
+/// matches        = value "NOT" WS+ "MATCHES" (regex | value)
+pub fn parse_not_matches(input: Span) -> IResult<FilterCondition> {
+    let keyword = tuple((tag("NOT"), multispace1, tag("MATCHES")));
+    let (input, (fid, (_not, _spaces, matches), regex)) =
+        tuple((parse_value, keyword, cut(alt((parse_regex, parse_value)))))(input)?;
+
+    Ok((
+        input,
+        FilterCondition::Not(Box::new(FilterCondition::Condition {
+            fid,
+            op: Matches { keyword: Token { span: matches, value: None }, regex },
+        })),
+    ))
+}
+
 /// to             = value value "TO" WS+ value
 pub fn parse_to(input: Span) -> IResult<FilterCondition> {
     let (input, (key, from, _, _, to)) =
@@ -819,7 +819,14 @@ impl IndexScheduler {
             .map(
                 |IndexEmbeddingConfig {
                      name,
-                     config: milli::vector::EmbeddingConfig { embedder_options, prompt, quantized },
+                     config:
+                         milli::vector::EmbeddingConfig {
+                             embedder_options,
+                             prompt,
+                             quantized,
+                             distance,
+                             ..
+                         },
                      ..
                  }| {
                     let prompt = Arc::new(
@@ -834,7 +841,12 @@ impl IndexScheduler {
                         if let Some(embedder) = embedders.get(&embedder_options) {
                             return Ok((
                                 name,
-                                (embedder.clone(), prompt, quantized.unwrap_or_default()),
+                                (
+                                    embedder.clone(),
+                                    prompt,
+                                    quantized.unwrap_or_default(),
+                                    distance.unwrap_or_default(),
+                                ),
                             ));
                         }
                     }
@@ -851,7 +863,15 @@ impl IndexScheduler {
                         let mut embedders = self.embedders.write().unwrap();
                         embedders.insert(embedder_options, embedder.clone());
                     }
-                    Ok((name, (embedder, prompt, quantized.unwrap_or_default())))
+                    Ok((
+                        name,
+                        (
+                            embedder,
+                            prompt,
+                            quantized.unwrap_or_default(),
+                            distance.unwrap_or_default(),
+                        ),
+                    ))
                 },
             )
             .collect();
@@ -6,7 +6,7 @@ use std::sync::atomic::Ordering;
 use dump::IndexMetadata;
 use meilisearch_types::milli::constants::RESERVED_VECTORS_FIELD_NAME;
 use meilisearch_types::milli::progress::{Progress, VariableNameStep};
-use meilisearch_types::milli::vector::parsed_vectors::{ExplicitVectors, VectorOrArrayOfVectors};
+use meilisearch_types::milli::vector::parsed_vectors::ExplicitVectors;
 use meilisearch_types::milli::{self};
 use meilisearch_types::tasks::{Details, KindWithContent, Status, Task};
 use time::macros::format_description;
@@ -191,7 +191,7 @@ impl IndexScheduler {
 
                 'inject_vectors: {
                     let embeddings = index
-                        .embeddings(&rtxn, id)
+                        .document_vectors(&rtxn, id)
                         .map_err(|e| Error::from_milli(e, Some(uid.to_string())))?;
 
                     if embeddings.is_empty() {
@@ -227,9 +227,7 @@ impl IndexScheduler {
                             .find(|conf| conf.name == embedder_name)
                             .is_some_and(|conf| conf.user_provided.contains(id));
                         let embeddings = ExplicitVectors {
-                            embeddings: Some(VectorOrArrayOfVectors::from_array_of_vectors(
-                                embeddings,
-                            )),
+                            embeddings: Some(embeddings),
                             regenerate: !user_provided,
                         };
                         vectors.insert(embedder_name, serde_json::to_value(embeddings).unwrap());
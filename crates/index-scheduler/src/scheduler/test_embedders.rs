@@ -103,7 +103,7 @@ fn import_vectors() {
         let simple_hf_name = name.clone();
 
         let configs = index_scheduler.embedders("doggos".to_string(), configs).unwrap();
-        let (hf_embedder, _, _) = configs.get(&simple_hf_name).unwrap();
+        let (hf_embedder, _, _, _) = configs.get(&simple_hf_name).unwrap();
         let beagle_embed = hf_embedder.embed_search("Intel the beagle best doggo", None).unwrap();
         let lab_embed = hf_embedder.embed_search("Max the lab best doggo", None).unwrap();
         let patou_embed = hf_embedder.embed_search("kefir the patou best doggo", None).unwrap();
@@ -423,8 +423,12 @@ fn import_vectors_first_and_embedder_later() {
                     max_bytes: Some(
                         400,
                     ),
+                    chunking: None,
                 },
                 quantized: None,
+                distance: None,
+                scalar_quantized: None,
+                full_precision_rescoring: None,
             },
             user_provided: RoaringBitmap<[1, 2]>,
         },
@@ -620,8 +624,12 @@ fn delete_document_containing_vector() {
                         max_bytes: Some(
                             400,
                         ),
+                        chunking: None,
                     },
                     quantized: None,
+                    distance: None,
+                    scalar_quantized: None,
+                    full_precision_rescoring: None,
                 },
                 user_provided: RoaringBitmap<[0]>,
             },
@@ -664,8 +672,12 @@ fn delete_document_containing_vector() {
                         max_bytes: Some(
                             400,
                         ),
+                        chunking: None,
                     },
                     quantized: None,
+                    distance: None,
+                    scalar_quantized: None,
+                    full_precision_rescoring: None,
                 },
                 user_provided: RoaringBitmap<[]>,
             },
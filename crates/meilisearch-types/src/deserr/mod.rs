@@ -8,8 +8,8 @@ use deserr::{take_cf_content, DeserializeError, IntoValue, MergeWithError, Value
 
 use crate::error::deserr_codes::*;
 use crate::error::{
-    Code, DeserrParseBoolError, DeserrParseIntError, ErrorCode, InvalidTaskDateError,
-    ParseOffsetDateTimeError,
+    Code, DeserrParseBoolError, DeserrParseIntError, ErrorCode, InvalidSearchBoostWeight,
+    InvalidTaskDateError, ParseOffsetDateTimeError,
 };
 use crate::index_uid::IndexUidFormatError;
 use crate::tasks::{ParseTaskKindError, ParseTaskStatusError};
@@ -197,5 +197,8 @@ merge_with_error_impl_take_error_message!(InvalidNetworkUrl);
 merge_with_error_impl_take_error_message!(InvalidNetworkSearchApiKey);
 merge_with_error_impl_take_error_message!(InvalidSearchSemanticRatio);
 merge_with_error_impl_take_error_message!(InvalidSearchRankingScoreThreshold);
+merge_with_error_impl_take_error_message!(InvalidSearchFacetHistograms);
+merge_with_error_impl_take_error_message!(InvalidSearchFacetPercentiles);
+merge_with_error_impl_take_error_message!(InvalidSearchBoostWeight);
 merge_with_error_impl_take_error_message!(InvalidSimilarRankingScoreThreshold);
 merge_with_error_impl_take_error_message!(InvalidSimilarId);
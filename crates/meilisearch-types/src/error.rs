@@ -246,6 +246,7 @@ InvalidDocumentLimit                  , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentOffset                 , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchEmbedder                 , InvalidRequest       , BAD_REQUEST ;
 InvalidSimilarEmbedder                , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchReranker                 , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchHybridQuery              , InvalidRequest       , BAD_REQUEST ;
 InvalidIndexLimit                     , InvalidRequest       , BAD_REQUEST ;
 InvalidIndexOffset                    , InvalidRequest       , BAD_REQUEST ;
@@ -280,6 +281,11 @@ InvalidSearchRetrieveVectors          , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchCropLength               , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchCropMarker               , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchFacets                   , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchFacetHistograms          , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchFacetPercentiles         , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchHierarchicalFacets       , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchDisjunctiveFacets        , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchBoost                    , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchSemanticRatio            , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchLocales                  , InvalidRequest       , BAD_REQUEST ;
 InvalidFacetSearchExhaustiveFacetCount, InvalidRequest       , BAD_REQUEST ;
@@ -300,6 +306,9 @@ InvalidSearchQ                        , InvalidRequest       , BAD_REQUEST ;
 InvalidFacetSearchQuery               , InvalidRequest       , BAD_REQUEST ;
 InvalidFacetSearchName                , InvalidRequest       , BAD_REQUEST ;
 FacetSearchDisabled                   , InvalidRequest       , BAD_REQUEST ;
+InvalidSuggestQ                       , InvalidRequest       , BAD_REQUEST ;
+InvalidSuggestLimit                   , InvalidRequest       , BAD_REQUEST ;
+InvalidSuggestFilter                  , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchVector                   , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchShowMatchesPosition      , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchShowRankingScore         , InvalidRequest       , BAD_REQUEST ;
@@ -313,6 +322,8 @@ InvalidSettingsDistinctAttribute      , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsProximityPrecision     , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsFacetSearch            , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsPrefixSearch           , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsQueryRules             , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsRerankers              , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsFaceting               , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsFilterableAttributes   , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsPagination             , InvalidRequest       , BAD_REQUEST ;
@@ -430,6 +441,7 @@ impl ErrorCode for milli::Error {
                     | UserError::InvalidSettingsDimensions { .. }
                     | UserError::InvalidUrl { .. }
                     | UserError::InvalidSettingsDocumentTemplateMaxBytes { .. }
+                    | UserError::InvalidSettingsDocumentTemplateChunking { .. }
                     | UserError::InvalidPrompt(_)
                     | UserError::InvalidDisableBinaryQuantization { .. }
                     | UserError::InvalidSourceForNested { .. }
@@ -444,11 +456,20 @@ impl ErrorCode for milli::Error {
                     UserError::PrimaryKeyCannotBeChanged(_) => Code::IndexPrimaryKeyAlreadyExists,
                     UserError::InvalidDistinctAttribute { .. } => Code::InvalidSearchDistinct,
                     UserError::SortRankingRuleMissing => Code::InvalidSearchSort,
-                    UserError::InvalidFacetsDistribution { .. } => Code::InvalidSearchFacets,
+                    UserError::InvalidFacetsDistribution { .. }
+                    | UserError::InvalidFacetRange { .. } => Code::InvalidSearchFacets,
+                    UserError::InvalidFacetHierarchy => Code::InvalidSearchHierarchicalFacets,
+                    UserError::InvalidQueryRules(_) => Code::InvalidSettingsQueryRules,
+                    UserError::InvalidRerankers(_) => Code::InvalidSettingsRerankers,
+                    UserError::InvalidFacetHistogramInterval { .. } => {
+                        Code::InvalidSearchFacetHistograms
+                    }
                     UserError::InvalidSortableAttribute { .. } => Code::InvalidSearchSort,
                     UserError::InvalidSearchableAttribute { .. } => {
                         Code::InvalidSearchAttributesToSearchOn
                     }
+                    UserError::InvalidSearchableAttributeInQuery { .. }
+                    | UserError::InvalidQuerySlop { .. } => Code::InvalidSearchQ,
                     UserError::InvalidFacetSearchFacetName { .. } => {
                         Code::InvalidFacetSearchFacetName
                     }
@@ -465,8 +486,10 @@ impl ErrorCode for milli::Error {
                     UserError::InvalidMinTypoWordLenSetting(_, _) => {
                         Code::InvalidSettingsTypoTolerance
                     }
+                    UserError::InvalidSearchVector { .. } => Code::InvalidSearchVector,
                     UserError::InvalidSearchEmbedder(_) => Code::InvalidSearchEmbedder,
                     UserError::InvalidSimilarEmbedder(_) => Code::InvalidSimilarEmbedder,
+                    UserError::InvalidSearchReranker(_) => Code::InvalidSearchReranker,
                     UserError::VectorEmbeddingError(_) | UserError::DocumentEmbeddingError(_) => {
                         Code::VectorEmbeddingError
                     }
@@ -597,6 +620,33 @@ impl fmt::Display for deserr_codes::InvalidSearchRankingScoreThreshold {
     }
 }
 
+impl fmt::Display for deserr_codes::InvalidSearchFacetHistograms {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the interval of a histogram in `facetHistograms` is invalid, expected a positive float."
+        )
+    }
+}
+
+/// Deserialization error when the weight of a boost isn't a positive float.
+#[derive(Debug)]
+pub struct InvalidSearchBoostWeight;
+impl fmt::Display for InvalidSearchBoostWeight {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the weight of a boost in `boost` is invalid, expected a positive float.")
+    }
+}
+
+impl fmt::Display for deserr_codes::InvalidSearchFacetPercentiles {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "a percentile in `facetPercentiles` is invalid, expected a float between `0.0` and `100.0`."
+        )
+    }
+}
+
 impl fmt::Display for deserr_codes::InvalidSimilarRankingScoreThreshold {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         deserr_codes::InvalidSearchRankingScoreThreshold.fmt(f)
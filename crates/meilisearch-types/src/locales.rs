@@ -9,6 +9,10 @@ use utoipa::ToSchema;
 pub struct LocalizedAttributesRuleView {
     pub attribute_patterns: AttributePatterns,
     pub locales: Vec<Locale>,
+    /// Also match the words sharing the same stem in the locales of the rule, e.g. `run` for `running`.
+    #[deserr(default)]
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub stemming: bool,
 }
 
 impl From<LocalizedAttributesRule> for LocalizedAttributesRuleView {
@@ -16,6 +20,7 @@ impl From<LocalizedAttributesRule> for LocalizedAttributesRuleView {
         Self {
             attribute_patterns: rule.attribute_patterns,
             locales: rule.locales.into_iter().map(|l| l.into()).collect(),
+            stemming: rule.stemming,
         }
     }
 }
@@ -25,6 +30,7 @@ impl From<LocalizedAttributesRuleView> for LocalizedAttributesRule {
         Self {
             attribute_patterns: view.attribute_patterns,
             locales: view.locales.into_iter().map(|l| l.into()).collect(),
+            stemming: view.stemming,
         }
     }
 }
@@ -12,7 +12,11 @@ use milli::disabled_typos_terms::DisabledTyposTerms;
 use milli::index::{IndexEmbeddingConfig, PrefixSearch};
 use milli::proximity::ProximityPrecision;
 use milli::update::Setting;
-use milli::{Criterion, CriterionError, FilterableAttributesRule, Index, DEFAULT_VALUES_PER_FACET};
+use milli::vector::rerank::{Reranker, RerankerSettings};
+use milli::{
+    Criterion, CriterionError, FilterableAttributesRule, Index, QueryRule, Synonyms,
+    DEFAULT_VALUES_PER_FACET,
+};
 use serde::{Deserialize, Serialize, Serializer};
 use utoipa::ToSchema;
 
@@ -240,11 +244,12 @@ pub struct Settings<T> {
     #[deserr(default, error = DeserrJsonError<InvalidSettingsDictionary>)]
     #[schema(value_type = Option<Vec<String>>, example = json!(["iPhone pro"]))]
     pub dictionary: Setting<BTreeSet<String>>,
-    /// List of associated words treated similarly. A word associated to an array of word as synonyms.
+    /// List of associated words treated similarly. A word associated to an array of word as synonyms,
+    /// or to a rule telling how the word and its synonyms match each other.
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsSynonyms>)]
-    #[schema(value_type = Option<BTreeMap<String, Vec<String>>>, example = json!({ "he": ["she", "they", "them"], "phone": ["iPhone", "android"]}))]
-    pub synonyms: Setting<BTreeMap<String, Vec<String>>>,
+    #[schema(value_type = Option<BTreeMap<String, Synonyms>>, example = json!({ "he": ["she", "they", "them"], "tv": { "synonyms": ["television"], "kind": "multiWay", "rankAsTypo": true }}))]
+    pub synonyms: Setting<BTreeMap<String, Synonyms>>,
     /// Search returns documents with distinct (different) values of the given field.
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsDistinctAttribute>)]
@@ -293,6 +298,16 @@ pub struct Settings<T> {
     #[deserr(default, error = DeserrJsonError<InvalidSettingsPrefixSearch>)]
     #[schema(value_type = Option<PrefixSearchSettings>, example = json!("Hemlo"))]
     pub prefix_search: Setting<PrefixSearchSettings>,
+    /// Rules pinning, hiding or boosting documents for the searches meeting their conditions.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsQueryRules>)]
+    #[schema(value_type = Option<Vec<QueryRule>>, example = json!([{ "id": "iphone", "conditions": { "pattern": "iphone" }, "pin": [{ "id": "42", "position": 0 }] }]))]
+    pub query_rules: Setting<Vec<QueryRule>>,
+    /// Remote models reordering the best results of the searches selecting them with `hybrid.reranker`.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsRerankers>)]
+    #[schema(value_type = Option<BTreeMap<String, RerankerSettings>>, example = json!({ "cohere": { "url": "https://api.cohere.com/v2/rerank", "request": { "model": "rerank-v3.5", "query": "{{query}}", "documents": ["{{text}}", "{{..}}"] }, "response": { "results": [{ "index": "{{index}}", "relevance_score": "{{score}}" }, "{{..}}"] } } }))]
+    pub rerankers: Setting<BTreeMap<String, RerankerSettings>>,
 
     #[serde(skip)]
     #[deserr(skip)]
@@ -301,6 +316,12 @@ pub struct Settings<T> {
 
 impl<T> Settings<T> {
     pub fn hide_secrets(&mut self) {
+        if let Setting::Set(rerankers) = &mut self.rerankers {
+            for api_key in rerankers.values_mut().filter_map(|reranker| reranker.api_key.as_mut()) {
+                Self::hide_secret(api_key);
+            }
+        }
+
         let Setting::Set(embedders) = &mut self.embedders else {
             return;
         };
@@ -359,6 +380,8 @@ impl Settings<Checked> {
             localized_attributes: Setting::Reset,
             facet_search: Setting::Reset,
             prefix_search: Setting::Reset,
+            query_rules: Setting::Reset,
+            rerankers: Setting::Reset,
             _kind: PhantomData,
         }
     }
@@ -385,6 +408,8 @@ impl Settings<Checked> {
             localized_attributes: localized_attributes_rules,
             facet_search,
             prefix_search,
+            query_rules,
+            rerankers,
             _kind,
         } = self;
 
@@ -409,6 +434,8 @@ impl Settings<Checked> {
             localized_attributes: localized_attributes_rules,
             facet_search,
             prefix_search,
+            query_rules,
+            rerankers,
             _kind: PhantomData,
         }
     }
@@ -459,12 +486,28 @@ impl Settings<Unchecked> {
             localized_attributes: self.localized_attributes,
             facet_search: self.facet_search,
             prefix_search: self.prefix_search,
+            query_rules: self.query_rules,
+            rerankers: self.rerankers,
             _kind: PhantomData,
         }
     }
 
     pub fn validate(self) -> Result<Self, milli::Error> {
-        self.validate_embedding_settings()
+        self.validate_query_rules()?.validate_rerankers()?.validate_embedding_settings()
+    }
+
+    fn validate_query_rules(self) -> Result<Self, milli::Error> {
+        if let Setting::Set(rules) = &self.query_rules {
+            QueryRule::validate(rules).map_err(milli::UserError::from)?;
+        }
+        Ok(self)
+    }
+
+    fn validate_rerankers(self) -> Result<Self, milli::Error> {
+        if let Setting::Set(rerankers) = &self.rerankers {
+            Reranker::validate(rerankers).map_err(milli::UserError::from)?;
+        }
+        Ok(self)
     }
 
     fn validate_embedding_settings(mut self) -> Result<Self, milli::Error> {
@@ -535,6 +578,8 @@ impl Settings<Unchecked> {
             },
             prefix_search: other.prefix_search.or(self.prefix_search),
             facet_search: other.facet_search.or(self.facet_search),
+            query_rules: other.query_rules.clone().or(self.query_rules.clone()),
+            rerankers: other.rerankers.clone().or(self.rerankers.clone()),
             _kind: PhantomData,
         }
     }
@@ -573,6 +618,8 @@ pub fn apply_settings_to_builder(
         localized_attributes: localized_attributes_rules,
         facet_search,
         prefix_search,
+        query_rules,
+        rerankers,
         _kind,
     } = settings;
 
@@ -639,7 +686,7 @@ pub fn apply_settings_to_builder(
     }
 
     match synonyms {
-        Setting::Set(ref synonyms) => builder.set_synonyms(synonyms.clone().into_iter().collect()),
+        Setting::Set(ref synonyms) => builder.set_synonyms(synonyms.clone()),
         Setting::Reset => builder.reset_synonyms(),
         Setting::NotSet => (),
     }
@@ -783,6 +830,18 @@ pub fn apply_settings_to_builder(
         Setting::Reset => builder.reset_facet_search(),
         Setting::NotSet => (),
     }
+
+    match query_rules {
+        Setting::Set(rules) => builder.set_query_rules(rules.clone()),
+        Setting::Reset => builder.reset_query_rules(),
+        Setting::NotSet => (),
+    }
+
+    match rerankers {
+        Setting::Set(rerankers) => builder.set_rerankers(rerankers.clone()),
+        Setting::Reset => builder.reset_rerankers(),
+        Setting::NotSet => (),
+    }
 }
 
 pub enum SecretPolicy {
@@ -889,6 +948,10 @@ pub fn settings(
 
     let facet_search = index.facet_search(rtxn)?;
 
+    let query_rules = index.query_rules(rtxn)?.unwrap_or_default();
+
+    let rerankers = index.rerankers(rtxn)?.unwrap_or_default();
+
     let mut settings = Settings {
         displayed_attributes: match displayed_attributes {
             Some(attrs) => Setting::Set(attrs),
@@ -927,6 +990,8 @@ pub fn settings(
         },
         prefix_search: Setting::Set(prefix_search.unwrap_or_default()),
         facet_search: Setting::Set(facet_search),
+        query_rules: Setting::Set(query_rules),
+        rerankers: Setting::Set(rerankers),
         _kind: PhantomData,
     };
 
@@ -958,6 +1023,11 @@ pub enum RankingRuleView {
     Asc(String),
     /// Sorted by the decreasing value of the field specified.
     Desc(String),
+    /// Sorted by the decreasing value of the expression specified,
+    /// computed from the numeric fields of the documents.
+    Expression(String),
+    /// Sorted by the decreasing closeness of the value of the field specified to an origin.
+    Decay(String),
 }
 impl Serialize for RankingRuleView {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -1014,6 +1084,8 @@ impl From<Criterion> for RankingRuleView {
             Criterion::Exactness => RankingRuleView::Exactness,
             Criterion::Asc(x) => RankingRuleView::Asc(x),
             Criterion::Desc(x) => RankingRuleView::Desc(x),
+            Criterion::Expression(x) => RankingRuleView::Expression(x),
+            Criterion::Decay(x) => RankingRuleView::Decay(x),
         }
     }
 }
@@ -1028,6 +1100,8 @@ impl From<RankingRuleView> for Criterion {
             RankingRuleView::Exactness => Criterion::Exactness,
             RankingRuleView::Asc(x) => Criterion::Asc(x),
             RankingRuleView::Desc(x) => Criterion::Desc(x),
+            RankingRuleView::Expression(x) => Criterion::Expression(x),
+            RankingRuleView::Decay(x) => Criterion::Decay(x),
         }
     }
 }
@@ -1154,6 +1228,8 @@ pub(crate) mod test {
             search_cutoff_ms: Setting::NotSet,
             facet_search: Setting::NotSet,
             prefix_search: Setting::NotSet,
+            query_rules: Setting::NotSet,
+            rerankers: Setting::NotSet,
             _kind: PhantomData::<Unchecked>,
         };
 
@@ -1185,6 +1261,8 @@ pub(crate) mod test {
             search_cutoff_ms: Setting::NotSet,
             facet_search: Setting::NotSet,
             prefix_search: Setting::NotSet,
+            query_rules: Setting::NotSet,
+            rerankers: Setting::NotSet,
             _kind: PhantomData::<Unchecked>,
         };
 
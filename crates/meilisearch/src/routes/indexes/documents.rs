@@ -1468,13 +1468,13 @@ fn some_documents<'a, 't: 'a>(
                         Some(Value::Object(map)) => map,
                         _ => Default::default(),
                     };
-                    for (name, vector) in index.embeddings(rtxn, key)? {
+                    for (name, vector) in index.document_vectors(rtxn, key)? {
                         let user_provided = embedding_configs
                             .iter()
                             .find(|conf| conf.name == name)
                             .is_some_and(|conf| conf.user_provided.contains(key));
                         let embeddings = ExplicitVectors {
-                            embeddings: Some(vector.into()),
+                            embeddings: Some(vector),
                             regenerate: !user_provided,
                         };
                         vectors.insert(
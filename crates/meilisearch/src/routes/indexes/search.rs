@@ -9,6 +9,7 @@ use meilisearch_types::error::ResponseError;
 use meilisearch_types::index_uid::IndexUid;
 use meilisearch_types::locales::Locale;
 use meilisearch_types::milli;
+use meilisearch_types::milli::vector::parsed_vectors::SearchVector;
 use meilisearch_types::serde_cs::vec::CS;
 use serde_json::Value;
 use tracing::debug;
@@ -22,10 +23,10 @@ use crate::extractors::sequential_extractor::SeqHandler;
 use crate::metrics::MEILISEARCH_DEGRADED_SEARCH_REQUESTS;
 use crate::routes::indexes::search_analytics::{SearchAggregator, SearchGET, SearchPOST};
 use crate::search::{
-    add_search_rules, perform_search, HybridQuery, MatchingStrategy, RankingScoreThreshold,
-    RetrieveVectors, SearchKind, SearchQuery, SearchResult, SemanticRatio, DEFAULT_CROP_LENGTH,
-    DEFAULT_CROP_MARKER, DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG,
-    DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET, DEFAULT_SEMANTIC_RATIO,
+    perform_search, HybridQuery, MatchingStrategy, RankingScoreThreshold, RetrieveVectors,
+    SearchKind, SearchQuery, SearchResult, SemanticRatio, DEFAULT_CROP_LENGTH, DEFAULT_CROP_MARKER,
+    DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG, DEFAULT_SEARCH_LIMIT,
+    DEFAULT_SEARCH_OFFSET, DEFAULT_SEMANTIC_RATIO,
 };
 use crate::search_queue::SearchQueue;
 
@@ -188,11 +189,13 @@ impl TryFrom<SearchQueryGet> for SearchQuery {
                     meilisearch_types::error::Code::InvalidSearchHybridQuery,
                 ));
             }
-            (Some(embedder), None) => {
-                Some(HybridQuery { semantic_ratio: DEFAULT_SEMANTIC_RATIO(), embedder })
-            }
+            (Some(embedder), None) => Some(HybridQuery {
+                semantic_ratio: DEFAULT_SEMANTIC_RATIO(),
+                embedder,
+                reranker: None,
+            }),
             (Some(embedder), Some(semantic_ratio)) => {
-                Some(HybridQuery { semantic_ratio: *semantic_ratio, embedder })
+                Some(HybridQuery { semantic_ratio: *semantic_ratio, embedder, reranker: None })
             }
         };
 
@@ -205,7 +208,7 @@ impl TryFrom<SearchQueryGet> for SearchQuery {
 
         Ok(Self {
             q: other.q,
-            vector: other.vector.map(CS::into_inner),
+            vector: other.vector.map(CS::into_inner).map(SearchVector::Dense),
             offset: other.offset.0,
             limit: other.limit.0,
             page: other.page.as_deref().copied(),
@@ -217,11 +220,18 @@ impl TryFrom<SearchQueryGet> for SearchQuery {
             attributes_to_highlight: other.attributes_to_highlight.map(|o| o.into_iter().collect()),
             filter,
             sort: other.sort.map(|attr| fix_sort_query_parameters(&attr)),
+            // the boosts are objects and can only be given with a POST
+            boost: None,
             distinct: other.distinct,
             show_matches_position: other.show_matches_position.0,
             show_ranking_score: other.show_ranking_score.0,
             show_ranking_score_details: other.show_ranking_score_details.0,
             facets: other.facets.map(|o| o.into_iter().collect()),
+            // the facet stats and hierarchies are objects and can only be requested with a POST
+            facet_histograms: None,
+            facet_percentiles: None,
+            hierarchical_facets: None,
+            disjunctive_facets: None,
             highlight_pre_tag: other.highlight_pre_tag,
             highlight_post_tag: other.highlight_post_tag,
             crop_marker: other.crop_marker,
@@ -326,12 +336,10 @@ pub async fn search_with_url_query(
     debug!(parameters = ?params, "Search get");
     let index_uid = IndexUid::try_from(index_uid.into_inner())?;
 
-    let mut query: SearchQuery = params.into_inner().try_into()?;
+    let query: SearchQuery = params.into_inner().try_into()?;
 
     // Tenant token search_rules.
-    if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(&index_uid) {
-        add_search_rules(&mut query.filter, search_rules);
-    }
+    let search_rules = index_scheduler.filters().get_index_search_rules(&index_uid);
 
     let mut aggregate = SearchAggregator::<SearchGET>::from_query(&query);
 
@@ -349,6 +357,7 @@ pub async fn search_with_url_query(
             search_kind,
             retrieve_vector,
             index_scheduler.features(),
+            search_rules,
         )
     })
     .await;
@@ -431,13 +440,11 @@ pub async fn search_with_post(
 ) -> Result<HttpResponse, ResponseError> {
     let index_uid = IndexUid::try_from(index_uid.into_inner())?;
 
-    let mut query = params.into_inner();
+    let query = params.into_inner();
     debug!(parameters = ?query, "Search post");
 
     // Tenant token search_rules.
-    if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(&index_uid) {
-        add_search_rules(&mut query.filter, search_rules);
-    }
+    let search_rules = index_scheduler.filters().get_index_search_rules(&index_uid);
 
     let mut aggregate = SearchAggregator::<SearchPOST>::from_query(&query);
 
@@ -456,6 +463,7 @@ pub async fn search_with_post(
             search_kind,
             retrieve_vectors,
             index_scheduler.features(),
+            search_rules,
         )
     })
     .await;
@@ -482,34 +490,29 @@ pub fn search_kind(
     index: &milli::Index,
 ) -> Result<SearchKind, ResponseError> {
     // handle with care, the order of cases matters, the semantics is subtle
-    match (query.q.as_deref(), &query.hybrid, query.vector.as_deref()) {
+    match (query.q.as_deref(), &query.hybrid, query.vector.as_ref()) {
         // empty query, no vector => placeholder search
         (Some(q), _, None) if q.trim().is_empty() => Ok(SearchKind::KeywordOnly),
         // no query, no vector => placeholder search
         (None, _, None) => Ok(SearchKind::KeywordOnly),
         // hybrid.semantic_ratio == 1.0 => vector
-        (_, Some(HybridQuery { semantic_ratio, embedder }), v) if **semantic_ratio == 1.0 => {
-            SearchKind::semantic(index_scheduler, index_uid, index, embedder, v.map(|v| v.len()))
+        (_, Some(HybridQuery { semantic_ratio, embedder, .. }), v) if **semantic_ratio == 1.0 => {
+            SearchKind::semantic(index_scheduler, index_uid, index, embedder, v)
         }
         // hybrid.semantic_ratio == 0.0 => keyword
-        (_, Some(HybridQuery { semantic_ratio, embedder: _ }), _) if **semantic_ratio == 0.0 => {
+        (_, Some(HybridQuery { semantic_ratio, .. }), _) if **semantic_ratio == 0.0 => {
             Ok(SearchKind::KeywordOnly)
         }
         // no query, hybrid, vector => semantic
-        (None, Some(HybridQuery { semantic_ratio: _, embedder }), Some(v)) => {
-            SearchKind::semantic(index_scheduler, index_uid, index, embedder, Some(v.len()))
+        (None, Some(HybridQuery { embedder, .. }), Some(v)) => {
+            SearchKind::semantic(index_scheduler, index_uid, index, embedder, Some(v))
         }
         // query, no hybrid, no vector => keyword
         (Some(_), None, None) => Ok(SearchKind::KeywordOnly),
         // query, hybrid, maybe vector => hybrid
-        (Some(_), Some(HybridQuery { semantic_ratio, embedder }), v) => SearchKind::hybrid(
-            index_scheduler,
-            index_uid,
-            index,
-            embedder,
-            **semantic_ratio,
-            v.map(|v| v.len()),
-        ),
+        (Some(_), Some(HybridQuery { semantic_ratio, embedder, .. }), v) => {
+            SearchKind::hybrid(index_scheduler, index_uid, index, embedder, **semantic_ratio, v)
+        }
 
         (_, None, Some(_)) => Err(MeilisearchHttpError::MissingSearchHybrid.into()),
     }
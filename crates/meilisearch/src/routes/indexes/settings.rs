@@ -390,7 +390,7 @@ make_setting_routes!(
     {
         route: "/synonyms",
         update_verb: put,
-        value_type: std::collections::BTreeMap<String, Vec<String>>,
+        value_type: std::collections::BTreeMap<String, meilisearch_types::milli::Synonyms>,
         err_type: meilisearch_types::deserr::DeserrJsonError<
             meilisearch_types::error::deserr_codes::InvalidSettingsSynonyms,
         >,
@@ -508,6 +508,28 @@ make_setting_routes!(
         camelcase_attr: "prefixSearch",
         analytics: PrefixSearchAnalytics
     },
+    {
+        route: "/query-rules",
+        update_verb: put,
+        value_type: Vec<meilisearch_types::milli::QueryRule>,
+        err_type: meilisearch_types::deserr::DeserrJsonError<
+            meilisearch_types::error::deserr_codes::InvalidSettingsQueryRules,
+        >,
+        attr: query_rules,
+        camelcase_attr: "queryRules",
+        analytics: QueryRulesAnalytics
+    },
+    {
+        route: "/rerankers",
+        update_verb: put,
+        value_type: std::collections::BTreeMap<String, meilisearch_types::milli::vector::rerank::RerankerSettings>,
+        err_type: meilisearch_types::deserr::DeserrJsonError<
+            meilisearch_types::error::deserr_codes::InvalidSettingsRerankers,
+        >,
+        attr: rerankers,
+        camelcase_attr: "rerankers",
+        analytics: RerankersAnalytics
+    },
 );
 
 #[utoipa::path(
@@ -597,6 +619,8 @@ pub async fn update_all(
             ),
             facet_search: FacetSearchAnalytics::new(new_settings.facet_search.as_ref().set()),
             prefix_search: PrefixSearchAnalytics::new(new_settings.prefix_search.as_ref().set()),
+            query_rules: QueryRulesAnalytics::new(new_settings.query_rules.as_ref().set()),
+            rerankers: RerankersAnalytics::new(new_settings.rerankers.as_ref().set()),
         },
         &req,
     );
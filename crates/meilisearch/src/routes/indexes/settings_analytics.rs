@@ -8,7 +8,8 @@ use std::collections::{BTreeMap, BTreeSet, HashSet};
 use meilisearch_types::facet_values_sort::FacetValuesSort;
 use meilisearch_types::locales::{Locale, LocalizedAttributesRuleView};
 use meilisearch_types::milli::update::Setting;
-use meilisearch_types::milli::FilterableAttributesRule;
+use meilisearch_types::milli::vector::rerank::RerankerSettings;
+use meilisearch_types::milli::{FilterableAttributesRule, QueryRule, Synonyms};
 use meilisearch_types::settings::{
     FacetingSettings, PaginationSettings, PrefixSearchSettings, ProximityPrecisionView,
     RankingRuleView, SettingEmbeddingSettings, TypoSettings,
@@ -39,6 +40,8 @@ pub struct SettingsAnalytics {
     pub non_separator_tokens: NonSeparatorTokensAnalytics,
     pub facet_search: FacetSearchAnalytics,
     pub prefix_search: PrefixSearchAnalytics,
+    pub query_rules: QueryRulesAnalytics,
+    pub rerankers: RerankersAnalytics,
 }
 
 impl Aggregate for SettingsAnalytics {
@@ -165,6 +168,14 @@ impl Aggregate for SettingsAnalytics {
                     (Some(bytes), None) | (None, Some(bytes)) => Some(bytes),
                     (Some(this), Some(other)) => Some(this.max(other)),
                 },
+                document_template_chunking_used: match (
+                    self.embedders.document_template_chunking_used,
+                    new.embedders.document_template_chunking_used,
+                ) {
+                    (None, None) => None,
+                    (Some(used), None) | (None, Some(used)) => Some(used),
+                    (Some(this), Some(other)) => Some(this | other),
+                },
                 binary_quantization_used: match (
                     self.embedders.binary_quantization_used,
                     new.embedders.binary_quantization_used,
@@ -173,6 +184,19 @@ impl Aggregate for SettingsAnalytics {
                     (Some(bq), None) | (None, Some(bq)) => Some(bq),
                     (Some(this), Some(other)) => Some(this | other),
                 },
+                distances: match (self.embedders.distances, new.embedders.distances) {
+                    (None, None) => None,
+                    (Some(distances), None) | (None, Some(distances)) => Some(distances),
+                    (Some(this), Some(other)) => Some(this.union(&other).cloned().collect()),
+                },
+                scalar_quantization_used: match (
+                    self.embedders.scalar_quantization_used,
+                    new.embedders.scalar_quantization_used,
+                ) {
+                    (None, None) => None,
+                    (Some(sq), None) | (None, Some(sq)) => Some(sq),
+                    (Some(this), Some(other)) => Some(this | other),
+                },
             },
             search_cutoff_ms: SearchCutoffMsAnalytics {
                 search_cutoff_ms: new
@@ -198,6 +222,13 @@ impl Aggregate for SettingsAnalytics {
                 set: new.prefix_search.set | self.prefix_search.set,
                 value: new.prefix_search.value.or(self.prefix_search.value),
             },
+            query_rules: QueryRulesAnalytics {
+                total: new.query_rules.total.or(self.query_rules.total),
+            },
+            rerankers: RerankersAnalytics {
+                total: new.rerankers.total.or(self.rerankers.total),
+                top_k: new.rerankers.top_k.or(self.rerankers.top_k),
+            },
         })
     }
 
@@ -483,7 +514,7 @@ pub struct SynonymsAnalytics {
 }
 
 impl SynonymsAnalytics {
-    pub fn new(synonyms: Option<&BTreeMap<String, Vec<String>>>) -> Self {
+    pub fn new(synonyms: Option<&BTreeMap<String, Synonyms>>) -> Self {
         Self { total: synonyms.as_ref().map(|synonyms| synonyms.len()) }
     }
 
@@ -503,7 +534,13 @@ pub struct EmbeddersAnalytics {
     // max
     pub document_template_max_bytes: Option<usize>,
     // |=
+    pub document_template_chunking_used: Option<bool>,
+    // |=
     pub binary_quantization_used: Option<bool>,
+    // Merge the distances
+    pub distances: Option<HashSet<String>>,
+    // |=
+    pub scalar_quantization_used: Option<bool>,
 }
 
 impl EmbeddersAnalytics {
@@ -542,11 +579,28 @@ impl EmbeddersAnalytics {
                     .filter_map(|config| config.document_template_max_bytes.set())
                     .max()
             }),
+            document_template_chunking_used: setting.as_ref().map(|map| {
+                map.values()
+                    .filter_map(|config| config.inner.clone().set())
+                    .any(|config| config.document_template_chunking.set().is_some())
+            }),
             binary_quantization_used: setting.as_ref().map(|map| {
                 map.values()
                     .filter_map(|config| config.inner.clone().set())
                     .any(|config| config.binary_quantized.set().is_some())
             }),
+            distances: setting.as_ref().map(|map| {
+                map.values()
+                    .filter_map(|config| config.inner.clone().set())
+                    .filter_map(|config| config.distance.set())
+                    .map(|distance| distance.to_string())
+                    .collect()
+            }),
+            scalar_quantization_used: setting.as_ref().map(|map| {
+                map.values()
+                    .filter_map(|config| config.inner.clone().set())
+                    .any(|config| config.scalar_quantized.set().is_some())
+            }),
         }
     }
 
@@ -674,3 +728,39 @@ impl PrefixSearchAnalytics {
         SettingsAnalytics { prefix_search: self, ..Default::default() }
     }
 }
+
+#[derive(Serialize, Default)]
+pub struct QueryRulesAnalytics {
+    pub total: Option<usize>,
+}
+
+impl QueryRulesAnalytics {
+    pub fn new(rules: Option<&Vec<QueryRule>>) -> Self {
+        Self { total: rules.as_ref().map(|rules| rules.len()) }
+    }
+
+    pub fn into_settings(self) -> SettingsAnalytics {
+        SettingsAnalytics { query_rules: self, ..Default::default() }
+    }
+}
+
+#[derive(Serialize, Default)]
+pub struct RerankersAnalytics {
+    pub total: Option<usize>,
+    pub top_k: Option<usize>,
+}
+
+impl RerankersAnalytics {
+    pub fn new(rerankers: Option<&BTreeMap<String, RerankerSettings>>) -> Self {
+        Self {
+            total: rerankers.as_ref().map(|rerankers| rerankers.len()),
+            top_k: rerankers.and_then(|rerankers| {
+                rerankers.values().filter_map(|reranker| reranker.top_k).max()
+            }),
+        }
+    }
+
+    pub fn into_settings(self) -> SettingsAnalytics {
+        SettingsAnalytics { rerankers: self, ..Default::default() }
+    }
+}
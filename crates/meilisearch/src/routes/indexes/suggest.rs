@@ -0,0 +1,210 @@
+use std::collections::BinaryHeap;
+
+use actix_web::web::{self, Data};
+use actix_web::{HttpRequest, HttpResponse};
+use deserr::actix_web::AwebQueryParameter;
+use index_scheduler::IndexScheduler;
+use meilisearch_types::deserr::query_params::Param;
+use meilisearch_types::deserr::DeserrQueryParamError;
+use meilisearch_types::error::deserr_codes::*;
+use meilisearch_types::error::ResponseError;
+use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::keys::actions;
+use serde_json::Value;
+use tracing::debug;
+use utoipa::{IntoParams, OpenApi};
+
+use super::ActionPolicy;
+use crate::analytics::{Aggregate, Analytics};
+use crate::extractors::authentication::GuardedData;
+use crate::extractors::sequential_extractor::SeqHandler;
+use crate::search::{add_search_rules, perform_suggest, QuerySuggestion, SuggestResult};
+use crate::search_queue::SearchQueue;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(suggest),
+    tags(
+        (
+            name = "Suggest",
+            description = "The `/suggest` route completes a query with the words of the index, suggesting multi-word queries along with the number of documents they match.",
+        ),
+    ),
+    components(schemas(SuggestResult, QuerySuggestion)),
+)]
+pub struct SuggestApi;
+
+pub const DEFAULT_SUGGEST_LIMIT: fn() -> usize = || 10;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::get().to(SeqHandler(suggest))));
+}
+
+#[derive(Debug, deserr::Deserr, IntoParams)]
+#[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
+#[into_params(parameter_in = Query)]
+pub struct SuggestQuery {
+    /// The query to complete. The last word is completed unless it is followed by a space.
+    #[deserr(default, error = DeserrQueryParamError<InvalidSuggestQ>)]
+    #[param(value_type = String, example = "harry po")]
+    pub q: String,
+    /// The maximum number of suggestions to return.
+    #[deserr(default = Param(DEFAULT_SUGGEST_LIMIT()), error = DeserrQueryParamError<InvalidSuggestLimit>)]
+    #[param(value_type = usize, default = DEFAULT_SUGGEST_LIMIT)]
+    pub limit: Param<usize>,
+    /// Only suggests queries matching the documents selected by the filter.
+    #[deserr(default, error = DeserrQueryParamError<InvalidSuggestFilter>)]
+    #[param(value_type = String, example = "genres = fantasy")]
+    pub filter: Option<String>,
+}
+
+#[derive(Default)]
+pub struct SuggestAggregator {
+    // requests
+    total_received: usize,
+    total_succeeded: usize,
+    time_spent: BinaryHeap<usize>,
+
+    // filter
+    filter_provided: bool,
+}
+
+impl SuggestAggregator {
+    pub fn from_query(query: &SuggestQuery) -> Self {
+        let SuggestQuery { q: _, limit: _, filter } = query;
+        Self { total_received: 1, filter_provided: filter.is_some(), ..Default::default() }
+    }
+
+    pub fn succeed(&mut self, result: &SuggestResult) {
+        let SuggestResult { suggestions: _, query: _, processing_time_ms } = result;
+        self.total_succeeded = 1;
+        self.time_spent.push(*processing_time_ms as usize);
+    }
+}
+
+impl Aggregate for SuggestAggregator {
+    fn event_name(&self) -> &'static str {
+        "Suggest GET"
+    }
+
+    fn aggregate(mut self: Box<Self>, new: Box<Self>) -> Box<Self> {
+        for time in new.time_spent {
+            self.time_spent.push(time);
+        }
+
+        Box::new(Self {
+            total_received: self.total_received.saturating_add(new.total_received),
+            total_succeeded: self.total_succeeded.saturating_add(new.total_succeeded),
+            time_spent: self.time_spent,
+            filter_provided: self.filter_provided | new.filter_provided,
+        })
+    }
+
+    fn into_event(self: Box<Self>) -> serde_json::Value {
+        let Self { total_received, total_succeeded, time_spent, filter_provided } = *self;
+        // the index of the 99th percentage of value
+        let percentile_99th = 0.99 * (total_succeeded as f64 - 1.) + 1.;
+        // we get all the values in a sorted manner
+        let time_spent = time_spent.into_sorted_vec();
+        // We are only interested by the slowest value of the 99th fastest results
+        let time_spent = time_spent.get(percentile_99th as usize);
+
+        serde_json::json!({
+            "requests": {
+                "99th_response_time":  time_spent.map(|t| format!("{:.2}", t)),
+                "total_succeeded": total_succeeded,
+                "total_failed": total_received.saturating_sub(total_succeeded), // just to be sure we never panics
+                "total_received": total_received,
+            },
+            "filter": {
+                "filter_provided": filter_provided,
+            },
+        })
+    }
+}
+
+/// Suggest queries
+///
+/// Complete a query with the words of the index. The last word of the query is completed
+/// with the most frequent words it prefixes, followed by the word most often coming next.
+#[utoipa::path(
+    get,
+    path = "{indexUid}/suggest",
+    tag = "Suggest",
+    security(("Bearer" = ["search", "*"])),
+    params(
+        ("indexUid" = String, Path, example = "movies", description = "Index Unique Identifier", nullable = false),
+        SuggestQuery
+    ),
+    responses(
+        (status = 200, description = "The suggestions are returned", body = SuggestResult, content_type = "application/json", example = json!(
+            {
+              "suggestions": [
+                { "query": "harry potter", "count": 8 },
+                { "query": "harry potter and", "count": 7 },
+                { "query": "harry potts", "count": 1 }
+              ],
+              "query": "harry po",
+              "processingTimeMs": 1
+            }
+        )),
+        (status = 404, description = "Index not found", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "Index `movies` not found.",
+                "code": "index_not_found",
+                "type": "invalid_request",
+                "link": "https://docs.meilisearch.com/errors#index_not_found"
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+pub async fn suggest(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SEARCH }>, Data<IndexScheduler>>,
+    search_queue: Data<SearchQueue>,
+    index_uid: web::Path<String>,
+    params: AwebQueryParameter<SuggestQuery, DeserrQueryParamError>,
+    req: HttpRequest,
+    analytics: web::Data<Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+
+    let query = params.0;
+    debug!(parameters = ?query, "Suggest");
+
+    let mut aggregate = SuggestAggregator::from_query(&query);
+
+    let SuggestQuery { q, limit, filter } = query;
+    let mut filter = filter.map(Value::String);
+
+    // Tenant token search_rules.
+    if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(&index_uid) {
+        add_search_rules(&mut filter, search_rules);
+    }
+
+    let index = index_scheduler.index(&index_uid)?;
+    let features = index_scheduler.features();
+    let permit = search_queue.try_get_search_permit().await?;
+    let suggest_result =
+        tokio::task::spawn_blocking(move || perform_suggest(&index, q, limit.0, filter, features))
+            .await;
+    permit.drop().await;
+    let suggest_result = suggest_result?;
+
+    if let Ok(ref suggest_result) = suggest_result {
+        aggregate.succeed(suggest_result);
+    }
+    analytics.publish(aggregate, &req);
+
+    let suggest_result = suggest_result?;
+
+    debug!(returns = ?suggest_result, "Suggest");
+    Ok(HttpResponse::Ok().json(suggest_result))
+}
@@ -160,7 +160,8 @@ pub async fn multi_search_with_post(
 
     let features = index_scheduler.features();
 
-    // regardless of federation, check authorization and apply search rules
+    // regardless of federation, check authorization
+    // the search rules of a non-federated query are applied when performing its search
     let auth = 'check_authorization: {
         for (query_index, federated_query) in queries.iter_mut().enumerate() {
             let index_uid = federated_query.index_uid.as_str();
@@ -169,7 +170,10 @@ pub async fn multi_search_with_post(
                 break 'check_authorization Err(AuthenticationError::InvalidToken)
                     .with_index(query_index);
             }
-            // Apply search rules from tenant token
+            // Apply search rules from tenant token to the queries of a federated search
+            if federation.is_none() {
+                continue;
+            }
             if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(index_uid)
             {
                 add_search_rules(&mut federated_query.filter, search_rules);
@@ -249,6 +253,7 @@ pub async fn multi_search_with_post(
                     )
                     .with_index(query_index)?;
                     let retrieve_vector = RetrieveVectors::new(query.retrieve_vectors);
+                    let search_rules = index_scheduler.filters().get_index_search_rules(&index_uid);
 
                     let search_result = tokio::task::spawn_blocking(move || {
                         perform_search(
@@ -258,6 +263,7 @@ pub async fn multi_search_with_post(
                             search_kind,
                             retrieve_vector,
                             features,
+                            search_rules,
                         )
                     })
                     .await
@@ -56,8 +56,13 @@ impl MultiSearchAggregator {
             show_matches_position: _,
             filter: _,
             sort: _,
+            boost: _,
             distinct: _,
             facets: _,
+            facet_histograms: _,
+            facet_percentiles: _,
+            hierarchical_facets: _,
+            disjunctive_facets: _,
             highlight_pre_tag: _,
             highlight_post_tag: _,
             crop_marker: _,
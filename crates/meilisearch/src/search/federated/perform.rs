@@ -1,5 +1,5 @@
 use std::cmp::Ordering;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::iter::Zip;
 use std::rc::Rc;
 use std::str::FromStr as _;
@@ -104,8 +104,14 @@ pub async fn perform_federated_search(
 
     // 3. merge hits and metadata across indexes and hosts
     // 3.1. merge metadata
-    let (estimated_total_hits, degraded, used_negative_operator, facets, max_remote_duration) =
-        merge_metadata(&mut results_by_index, &remote_results);
+    let (
+        estimated_total_hits,
+        degraded,
+        used_negative_operator,
+        suggested_query,
+        facets,
+        max_remote_duration,
+    ) = merge_metadata(&mut results_by_index, &remote_results);
 
     // 3.2. merge hits
     let merged_hits: Vec<_> = merge_index_global_results(results_by_index, &mut remote_results)
@@ -123,8 +129,12 @@ pub async fn perform_federated_search(
         .collect();
 
     // 3.3. merge facets
-    let (facet_distribution, facet_stats, facets_by_index) =
-        facet_order.merge(federation.merge_facets, remote_results, facets);
+    let (facet_distribution, facet_stats, facets_by_index) = facet_order.merge(
+        federation.merge_facets,
+        remote_results,
+        facets,
+        &federation.range_facets(),
+    );
 
     let after_merge = std::time::Instant::now();
 
@@ -147,6 +157,7 @@ pub async fn perform_federated_search(
         facet_stats,
         facets_by_index,
         remote_errors: partitioned_queries.has_remote.then_some(remote_errors),
+        suggested_query,
     })
 }
 
@@ -374,16 +385,19 @@ struct SearchResultByIndex {
     estimated_total_hits: usize,
     degraded: bool,
     used_negative_operator: bool,
+    /// The suggested query of the first query of the index having one, with the position of the query.
+    suggested_query: Option<(usize, String)>,
     facets: Option<ComputedFacets>,
 }
 
 fn merge_metadata(
     results_by_index: &mut Vec<SearchResultByIndex>,
     remote_results: &Vec<FederatedSearchResult>,
-) -> (usize, bool, bool, FederatedFacets, Duration) {
+) -> (usize, bool, bool, Option<String>, FederatedFacets, Duration) {
     let mut estimated_total_hits = 0;
     let mut degraded = false;
     let mut used_negative_operator = false;
+    let mut suggested_query: Option<(usize, String)> = None;
     let mut facets: FederatedFacets = FederatedFacets::default();
     let mut max_remote_duration = Duration::ZERO;
     for SearchResultByIndex {
@@ -393,12 +407,20 @@ fn merge_metadata(
         facets: facets_by_index,
         degraded: degraded_by_index,
         used_negative_operator: used_negative_operator_by_index,
+        suggested_query: suggested_query_by_index,
     } in results_by_index
     {
         estimated_total_hits += *estimated_total_hits_by_index;
         degraded |= *degraded_by_index;
         used_negative_operator |= *used_negative_operator_by_index;
 
+        // the suggested query comes from the first query that has one
+        if let Some((query_index, query)) = suggested_query_by_index.take() {
+            if suggested_query.as_ref().is_none_or(|(first_index, _)| query_index < *first_index) {
+                suggested_query = Some((query_index, query));
+            }
+        }
+
         let facets_by_index = std::mem::take(facets_by_index);
         let index = std::mem::take(index);
 
@@ -415,6 +437,7 @@ fn merge_metadata(
         degraded: degraded_for_host,
         used_negative_operator: host_used_negative_operator,
         remote_errors: _,
+        suggested_query: _,
     } in remote_results
     {
         let this_remote_duration = Duration::from_millis(*processing_time_ms as u64);
@@ -428,7 +451,15 @@ fn merge_metadata(
         degraded |= degraded_for_host;
         used_negative_operator |= host_used_negative_operator;
     }
-    (estimated_total_hits, degraded, used_negative_operator, facets, max_remote_duration)
+    let suggested_query = suggested_query.map(|(_, query)| query);
+    (
+        estimated_total_hits,
+        degraded,
+        used_negative_operator,
+        suggested_query,
+        facets,
+        max_remote_duration,
+    )
 }
 
 type LocalQueriesByIndex = BTreeMap<String, Vec<QueryByIndex>>;
@@ -474,6 +505,14 @@ impl PartitionedQueries {
             .into());
         }
 
+        if let Some(facet_parameter) = federated_query.has_facet_parameters() {
+            return Err(MeilisearchHttpError::FacetParameterInFederatedQuery(
+                query_index,
+                facet_parameter,
+            )
+            .into());
+        }
+
         let (index_uid, query, federation_options) = federated_query.into_index_query_federation();
 
         let federation_options = federation_options.unwrap_or_default();
@@ -700,7 +739,7 @@ impl SearchByIndex {
         };
         let rtxn = index.read_txn()?;
         let criteria = index.criteria(&rtxn)?;
-        let dictionary = index.dictionary(&rtxn)?;
+        let dictionary = index.tokenizer_dictionary(&rtxn)?;
         let dictionary: Option<Vec<_>> =
             dictionary.as_ref().map(|x| x.iter().map(String::as_str).collect());
         let separators = index.allowed_separators(&rtxn)?;
@@ -709,6 +748,9 @@ impl SearchByIndex {
         let cutoff = index.search_cutoff(&rtxn)?;
         let mut degraded = false;
         let mut used_negative_operator = false;
+        let mut suggested_query: Option<(usize, String)> = None;
+        // the corrections of the misspelled words are shared by all the queries of the index
+        let spelling_corrections = milli::SpellingCorrections::default();
         let mut candidates = RoaringBitmap::new();
         let facets_by_index = self.federation.facets_by_index.remove(&index_uid).flatten();
         if let Err(mut error) =
@@ -800,18 +842,25 @@ impl SearchByIndex {
                     None => TimeBudget::default(),
                 };
 
-                let (mut search, _is_finite_pagination, _max_total_hits, _offset) = prepare_search(
-                    &index,
-                    &rtxn,
-                    &query,
-                    &search_kind,
-                    time_budget,
-                    params.features,
-                )?;
+                let (mut search, _is_finite_pagination, _max_total_hits, _offset, _limit) =
+                    prepare_search(
+                        &index,
+                        &rtxn,
+                        &query,
+                        &search_kind,
+                        time_budget,
+                        params.features,
+                    )?;
 
                 search.scoring_strategy(milli::score_details::ScoringStrategy::Detailed);
+                search.spelling_corrections(&spelling_corrections);
                 search.offset(0);
                 search.limit(params.required_hit_count);
+                if let Some(reranker) =
+                    query.hybrid.as_ref().and_then(|hybrid| hybrid.reranker.clone())
+                {
+                    search.reranker(reranker);
+                }
 
                 let (result, _semantic_hit_count) =
                     super::super::search_from_kind(index_uid.to_string(), search_kind, search)?;
@@ -838,11 +887,20 @@ impl SearchByIndex {
                     document_scores,
                     degraded: query_degraded,
                     used_negative_operator: query_used_negative_operator,
+                    suggested_query: query_suggested_query,
                 } = result;
 
                 candidates |= query_candidates;
                 degraded |= query_degraded;
                 used_negative_operator |= query_used_negative_operator;
+                if let Some(query_suggested_query) = query_suggested_query {
+                    if suggested_query
+                        .as_ref()
+                        .is_none_or(|(first_index, _)| query_index < *first_index)
+                    {
+                        suggested_query = Some((query_index, query_suggested_query));
+                    }
+                }
 
                 let tokenizer = HitMaker::tokenizer(dictionary.as_deref(), separators.as_deref());
 
@@ -920,6 +978,7 @@ impl SearchByIndex {
             .map(|facets_by_index| {
                 compute_facet_distribution_stats(
                     &facets_by_index,
+                    &[],
                     &index,
                     &rtxn,
                     candidates,
@@ -945,6 +1004,7 @@ impl SearchByIndex {
             estimated_total_hits,
             degraded,
             used_negative_operator,
+            suggested_query,
             facets,
         });
         Ok(())
@@ -985,6 +1045,7 @@ impl SearchByIndex {
             if let Some(facets) = facets {
                 if let Err(mut error) = compute_facet_distribution_stats(
                     &facets,
+                    &[],
                     &index,
                     &rtxn,
                     Default::default(),
@@ -1080,6 +1141,7 @@ impl FacetOrder {
         merge_facets: Option<MergeFacets>,
         remote_results: Vec<FederatedSearchResult>,
         mut facets: FederatedFacets,
+        range_facets: &BTreeSet<String>,
     ) -> (Option<FacetDistributions>, Option<FacetStats>, FederatedFacets) {
         let (facet_distribution, facet_stats, facets_by_index) = match (self, merge_facets) {
             (FacetOrder::ByFacet(facet_order), Some(merge_facets)) => {
@@ -1088,7 +1150,7 @@ impl FacetOrder {
                 {
                     facets.append(remote_facets_by_index);
                 }
-                let facets = facets.merge(merge_facets, facet_order);
+                let facets = facets.merge(merge_facets, facet_order, range_facets);
 
                 let (facet_distribution, facet_stats) = facets
                     .map(|ComputedFacets { distribution, stats }| (distribution, stats))
@@ -1102,7 +1164,7 @@ impl FacetOrder {
                 {
                     facets.append(remote_facets_by_index);
                 }
-                facets.sort_and_truncate(facet_order);
+                facets.sort_and_truncate(facet_order, range_facets);
                 (None, None, facets)
             }
             _ => (None, None, facets),
@@ -1,5 +1,5 @@
 use std::collections::btree_map::Entry;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt;
 use std::vec::Vec;
 
@@ -13,7 +13,7 @@ use meilisearch_types::error::deserr_codes::{
 use meilisearch_types::error::ResponseError;
 use meilisearch_types::index_uid::IndexUid;
 use meilisearch_types::milli::order_by_map::OrderByMap;
-use meilisearch_types::milli::OrderBy;
+use meilisearch_types::milli::{FacetRange, OrderBy};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
@@ -89,6 +89,20 @@ pub struct Federation {
     pub merge_facets: Option<MergeFacets>,
 }
 
+impl Federation {
+    /// The names of the facets of `facetsByIndex` written with the range-bucket syntax,
+    /// whose values must be kept in the order of their ranges.
+    pub fn range_facets(&self) -> BTreeSet<String> {
+        self.facets_by_index
+            .values()
+            .flatten()
+            .flatten()
+            .filter_map(|facet| FacetRange::parse_facet(facet).ok().flatten())
+            .map(|(name, _)| name.to_string())
+            .collect()
+    }
+}
+
 #[derive(Copy, Clone, Debug, deserr::Deserr, Serialize, Default, ToSchema)]
 #[deserr(error = DeserrJsonError<InvalidMultiSearchMergeFacets>, rename_all = camelCase, deny_unknown_fields)]
 #[schema(rename_all = "camelCase")]
@@ -131,6 +145,10 @@ pub struct FederatedSearchResult {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub remote_errors: Option<BTreeMap<String, ResponseError>>,
 
+    /// The corrected query of the first query with its misspelled words corrected.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suggested_query: Option<String>,
+
     // These fields are only used for analytics purposes
     #[serde(skip)]
     pub degraded: bool,
@@ -151,6 +169,7 @@ impl fmt::Debug for FederatedSearchResult {
             facet_stats,
             facets_by_index,
             remote_errors,
+            suggested_query,
         } = self;
 
         let mut debug = f.debug_struct("SearchResult");
@@ -179,6 +198,9 @@ impl fmt::Debug for FederatedSearchResult {
         if let Some(remote_errors) = remote_errors {
             debug.field("remote_errors", &remote_errors);
         }
+        if let Some(suggested_query) = suggested_query {
+            debug.field("suggested_query", &suggested_query);
+        }
 
         debug.finish()
     }
@@ -202,6 +224,7 @@ impl FederatedFacets {
         self,
         MergeFacets { max_values_per_facet }: MergeFacets,
         facet_order: BTreeMap<String, (String, OrderBy)>,
+        range_facets: &BTreeSet<String>,
     ) -> Option<ComputedFacets> {
         if self.is_empty() {
             return None;
@@ -246,6 +269,11 @@ impl FederatedFacets {
 
         // fixup order
         for (facet, values) in &mut distribution {
+            // the ranges are kept in the order they were requested in
+            if range_facets.contains(facet) {
+                continue;
+            }
+
             let order_by = facet_order.get(facet).map(|(_, order)| *order).unwrap_or_default();
 
             match order_by {
@@ -296,12 +324,20 @@ impl FederatedFacets {
         }
     }
 
-    pub fn sort_and_truncate(&mut self, facet_order: BTreeMap<String, (OrderByMap, usize)>) {
+    pub fn sort_and_truncate(
+        &mut self,
+        facet_order: BTreeMap<String, (OrderByMap, usize)>,
+        range_facets: &BTreeSet<String>,
+    ) {
         for (index, facets) in &mut self.0 {
             let Some((order_by, max_values_per_facet)) = facet_order.get(index) else {
                 continue;
             };
             for (facet, values) in &mut facets.distribution {
+                // the ranges are kept in the order they were requested in
+                if range_facets.contains(facet) {
+                    continue;
+                }
                 match order_by.get(facet) {
                     OrderBy::Lexicographic => {
                         values.sort_unstable_by(|left, _, right, _| left.cmp(right))
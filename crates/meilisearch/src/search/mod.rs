@@ -12,22 +12,24 @@ use indexmap::IndexMap;
 use meilisearch_auth::IndexSearchRules;
 use meilisearch_types::deserr::DeserrJsonError;
 use meilisearch_types::error::deserr_codes::*;
-use meilisearch_types::error::{Code, ResponseError};
+use meilisearch_types::error::{Code, InvalidSearchBoostWeight, ResponseError};
 use meilisearch_types::heed::RoTxn;
 use meilisearch_types::index_uid::IndexUid;
 use meilisearch_types::locales::Locale;
 use meilisearch_types::milli::score_details::{ScoreDetails, ScoringStrategy};
-use meilisearch_types::milli::vector::parsed_vectors::ExplicitVectors;
-use meilisearch_types::milli::vector::Embedder;
+use meilisearch_types::milli::vector::parsed_vectors::{ExplicitVectors, SearchVector};
+use meilisearch_types::milli::vector::{Embedder, Embedding};
 use meilisearch_types::milli::{
     FacetValueHit, InternalError, OrderBy, PatternMatch, SearchForFacetValues, TimeBudget,
 };
 use meilisearch_types::settings::DEFAULT_PAGINATION_MAX_TOTAL_HITS;
 use meilisearch_types::{milli, Document};
 use milli::tokenizer::{Language, TokenizerBuilder};
+use milli::update::new::extract_geojson_geometry_from_value;
 use milli::{
-    AscDesc, FieldId, FieldsIdsMap, Filter, FormatOptions, Index, LocalizedAttributesRule,
-    MatchBounds, MatcherBuilder, SortError, TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET,
+    AscDesc, DocumentGeometry, FacetHierarchy, FacetRange, FieldId, FieldsIdsMap, Filter,
+    FormatOptions, Index, LocalizedAttributesRule, MatchBounds, MatcherBuilder, SortError,
+    TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET,
 };
 use regex::Regex;
 use serde::{Deserialize, Serialize};
@@ -44,6 +46,8 @@ pub use federated::{
     FederationOptions, MergeFacets, PROXY_SEARCH_HEADER, PROXY_SEARCH_HEADER_VALUE,
 };
 
+mod query_rules;
+use query_rules::FiredQueryRules;
 mod ranking_rules;
 
 type MatchesPosition = BTreeMap<String, Vec<MatchBounds>>;
@@ -55,6 +59,7 @@ pub const DEFAULT_CROP_MARKER: fn() -> String = || "…".to_string();
 pub const DEFAULT_HIGHLIGHT_PRE_TAG: fn() -> String = || "<em>".to_string();
 pub const DEFAULT_HIGHLIGHT_POST_TAG: fn() -> String = || "</em>".to_string();
 pub const DEFAULT_SEMANTIC_RATIO: fn() -> SemanticRatio = || SemanticRatio(0.5);
+pub const DEFAULT_HIERARCHICAL_FACET_SEPARATOR: fn() -> String = || " > ".to_string();
 
 #[derive(Clone, Default, PartialEq, Deserr, ToSchema)]
 #[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
@@ -62,7 +67,7 @@ pub struct SearchQuery {
     #[deserr(default, error = DeserrJsonError<InvalidSearchQ>)]
     pub q: Option<String>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchVector>)]
-    pub vector: Option<Vec<f32>>,
+    pub vector: Option<SearchVector>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHybridQuery>)]
     pub hybrid: Option<HybridQuery>,
     #[deserr(default = DEFAULT_SEARCH_OFFSET(), error = DeserrJsonError<InvalidSearchOffset>)]
@@ -96,10 +101,20 @@ pub struct SearchQuery {
     pub filter: Option<Value>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchSort>)]
     pub sort: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchBoost>)]
+    pub boost: Option<Vec<SearchBoost>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchDistinct>)]
     pub distinct: Option<String>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFacets>)]
     pub facets: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFacetHistograms>)]
+    pub facet_histograms: Option<BTreeMap<String, FacetHistogramInterval>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFacetPercentiles>)]
+    pub facet_percentiles: Option<BTreeMap<String, Vec<FacetPercentile>>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchHierarchicalFacets>)]
+    pub hierarchical_facets: Option<Vec<HierarchicalFacet>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchDisjunctiveFacets>)]
+    pub disjunctive_facets: Option<Vec<String>>,
     #[deserr(error = DeserrJsonError<InvalidSearchHighlightPreTag>, default = DEFAULT_HIGHLIGHT_PRE_TAG())]
     #[schema(default = DEFAULT_HIGHLIGHT_PRE_TAG)]
     pub highlight_pre_tag: String,
@@ -154,6 +169,86 @@ impl std::convert::TryFrom<f64> for RankingScoreThresholdSimilar {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Deserr, ToSchema, Serialize)]
+#[deserr(try_from(f64) = TryFrom::try_from -> InvalidSearchFacetHistograms)]
+pub struct FacetHistogramInterval(f64);
+
+impl std::convert::TryFrom<f64> for FacetHistogramInterval {
+    type Error = InvalidSearchFacetHistograms;
+
+    fn try_from(f: f64) -> Result<Self, Self::Error> {
+        if f.is_finite() && f > 0.0 {
+            Ok(Self(f))
+        } else {
+            Err(InvalidSearchFacetHistograms)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserr, ToSchema, Serialize)]
+#[deserr(try_from(f64) = TryFrom::try_from -> InvalidSearchFacetPercentiles)]
+pub struct FacetPercentile(f64);
+
+impl std::convert::TryFrom<f64> for FacetPercentile {
+    type Error = InvalidSearchFacetPercentiles;
+
+    fn try_from(f: f64) -> Result<Self, Self::Error> {
+        // the suggested "fix" is: `!(0.0..=100.0).contains(&f)`` which is allegedly less readable
+        #[allow(clippy::manual_range_contains)]
+        if f > 100.0 || f < 0.0 {
+            Err(InvalidSearchFacetPercentiles)
+        } else {
+            Ok(Self(f))
+        }
+    }
+}
+
+/// Ranks the documents matching a filter before the other ones, when
+/// the ranking rules of the index can't tell the documents apart.
+#[derive(Debug, Clone, PartialEq, Deserr, ToSchema, Serialize)]
+#[deserr(error = DeserrJsonError<InvalidSearchBoost>, rename_all = camelCase, deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchBoost {
+    /// The filter of the boosted documents, e.g. `brand = apple`.
+    #[schema(value_type = Value)]
+    pub filter: Value,
+    /// The weight of the boost, the weights of the boosts matched by a document being summed.
+    #[schema(value_type = f64)]
+    pub weight: BoostWeight,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserr, ToSchema, Serialize)]
+#[deserr(try_from(f64) = TryFrom::try_from -> InvalidSearchBoostWeight)]
+pub struct BoostWeight(f64);
+
+impl std::convert::TryFrom<f64> for BoostWeight {
+    type Error = InvalidSearchBoostWeight;
+
+    fn try_from(f: f64) -> Result<Self, Self::Error> {
+        if f.is_finite() && f > 0.0 {
+            Ok(Self(f))
+        } else {
+            Err(InvalidSearchBoostWeight)
+        }
+    }
+}
+
+/// A category tree whose levels are stored in different attributes, e.g. `categories.lvl0`
+/// containing `Books` and `categories.lvl1` containing `Books > Science`.
+#[derive(Debug, Clone, PartialEq, Deserr, ToSchema, Serialize)]
+#[deserr(error = DeserrJsonError<InvalidSearchHierarchicalFacets>, rename_all = camelCase, deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+pub struct HierarchicalFacet {
+    /// The attributes of the levels of the tree, from the root to the leaves.
+    pub attributes: Vec<String>,
+    #[deserr(default = DEFAULT_HIERARCHICAL_FACET_SEPARATOR())]
+    #[schema(default = DEFAULT_HIERARCHICAL_FACET_SEPARATOR)]
+    pub separator: String,
+    /// The path of the selected category, e.g. `Books > Science`.
+    #[deserr(default)]
+    pub path: Option<String>,
+}
+
 // Since this structure is logged A LOT we're going to reduce the number of things it logs to the bare minimum.
 // - Only what IS used, we know everything else is set to None so there is no need to print it
 // - Re-order the most important field to debug first
@@ -177,8 +272,13 @@ impl fmt::Debug for SearchQuery {
             show_ranking_score_details,
             filter,
             sort,
+            boost,
             distinct,
             facets,
+            facet_histograms,
+            facet_percentiles,
+            hierarchical_facets,
+            disjunctive_facets,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
@@ -207,14 +307,17 @@ impl fmt::Debug for SearchQuery {
             debug.field("retrieve_vectors", &retrieve_vectors);
         }
         if let Some(v) = vector {
-            if v.len() < 10 {
-                debug.field("vector", &v);
-            } else {
-                debug.field(
+            match v {
+                SearchVector::Dense(v) if v.len() >= 10 => debug.field(
                     "vector",
                     &format!("[{}, {}, {}, ... {} dimensions]", v[0], v[1], v[2], v.len()),
-                );
-            }
+                ),
+                SearchVector::Sparse(v) if v.len() >= 10 => debug.field(
+                    "vector",
+                    &format!("[{:?}, {:?}, {:?}, ... {} tokens]", v[0], v[1], v[2], v.len()),
+                ),
+                v => debug.field("vector", &v),
+            };
         }
         if let Some(hybrid) = hybrid {
             debug.field("hybrid", &hybrid);
@@ -228,12 +331,27 @@ impl fmt::Debug for SearchQuery {
         if let Some(sort) = sort {
             debug.field("sort", &sort);
         }
+        if let Some(boost) = boost {
+            debug.field("boost", &boost);
+        }
         if let Some(distinct) = distinct {
             debug.field("distinct", &distinct);
         }
         if let Some(facets) = facets {
             debug.field("facets", &facets);
         }
+        if let Some(facet_histograms) = facet_histograms {
+            debug.field("facet_histograms", &facet_histograms);
+        }
+        if let Some(facet_percentiles) = facet_percentiles {
+            debug.field("facet_percentiles", &facet_percentiles);
+        }
+        if let Some(hierarchical_facets) = hierarchical_facets {
+            debug.field("hierarchical_facets", &hierarchical_facets);
+        }
+        if let Some(disjunctive_facets) = disjunctive_facets {
+            debug.field("disjunctive_facets", &disjunctive_facets);
+        }
         debug.field("matching_strategy", &matching_strategy);
 
         // Then everything related to the formatting
@@ -285,6 +403,10 @@ pub struct HybridQuery {
     pub semantic_ratio: SemanticRatio,
     #[deserr(error = DeserrJsonError<InvalidSearchEmbedder>)]
     pub embedder: String,
+    /// The reranker of the index reordering the best results of the search.
+    #[deserr(default, error = DeserrJsonError<InvalidSearchReranker>)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reranker: Option<String>,
 }
 
 #[derive(Clone)]
@@ -300,14 +422,14 @@ impl SearchKind {
         index_uid: String,
         index: &Index,
         embedder_name: &str,
-        vector_len: Option<usize>,
+        vector: Option<&SearchVector>,
     ) -> Result<Self, ResponseError> {
         let (embedder_name, embedder, quantized) = Self::embedder(
             index_scheduler,
             index_uid,
             index,
             embedder_name,
-            vector_len,
+            vector,
             Route::Search,
         )?;
         Ok(Self::SemanticOnly { embedder_name, embedder, quantized })
@@ -319,14 +441,14 @@ impl SearchKind {
         index: &Index,
         embedder_name: &str,
         semantic_ratio: f32,
-        vector_len: Option<usize>,
+        vector: Option<&SearchVector>,
     ) -> Result<Self, ResponseError> {
         let (embedder_name, embedder, quantized) = Self::embedder(
             index_scheduler,
             index_uid,
             index,
             embedder_name,
-            vector_len,
+            vector,
             Route::Search,
         )?;
         Ok(Self::Hybrid { embedder_name, embedder, quantized, semantic_ratio })
@@ -337,14 +459,14 @@ impl SearchKind {
         index_uid: String,
         index: &Index,
         embedder_name: &str,
-        vector_len: Option<usize>,
+        vector: Option<&SearchVector>,
         route: Route,
     ) -> Result<(String, Arc<Embedder>, bool), ResponseError> {
         let rtxn = index.read_txn()?;
         let embedder_configs = index.embedding_configs(&rtxn)?;
         let embedders = index_scheduler.embedders(index_uid, embedder_configs)?;
 
-        let (embedder, _, quantized) = embedders
+        let (embedder, _, quantized, _) = embedders
             .get(embedder_name)
             .ok_or(match route {
                 Route::Search | Route::MultiSearch => {
@@ -356,16 +478,50 @@ impl SearchKind {
             })
             .map_err(milli::Error::from)?;
 
-        if let Some(vector_len) = vector_len {
-            if vector_len != embedder.dimensions() {
+        match vector {
+            Some(SearchVector::Dense(_)) if embedder.is_sparse() => {
+                return Err(milli::Error::UserError(milli::UserError::InvalidSearchVector {
+                    embedder_name: embedder_name.to_owned(),
+                    message: "the embedder returns sparse embeddings, expected an array of \
+                              `{\"index\": <token>, \"value\": <weight>}` objects"
+                        .to_owned(),
+                })
+                .into());
+            }
+            Some(SearchVector::Sparse(_)) if !embedder.is_sparse() => {
+                return Err(milli::Error::UserError(milli::UserError::InvalidSearchVector {
+                    embedder_name: embedder_name.to_owned(),
+                    message: "the embedder returns dense embeddings, expected an array of floats"
+                        .to_owned(),
+                })
+                .into());
+            }
+            Some(SearchVector::Sparse(values)) => {
+                // the vocabulary of a sparse embedder has as many tokens as it has dimensions
+                if let Some(value) =
+                    values.iter().find(|value| value.index as usize >= embedder.dimensions())
+                {
+                    return Err(milli::Error::UserError(milli::UserError::InvalidSearchVector {
+                        embedder_name: embedder_name.to_owned(),
+                        message: format!(
+                            "the token `{}` is outside of the vocabulary of {} tokens",
+                            value.index,
+                            embedder.dimensions()
+                        ),
+                    })
+                    .into());
+                }
+            }
+            Some(SearchVector::Dense(vector)) if vector.len() != embedder.dimensions() => {
                 return Err(meilisearch_types::milli::Error::UserError(
                     meilisearch_types::milli::UserError::InvalidVectorDimensions {
                         expected: embedder.dimensions(),
-                        found: vector_len,
+                        found: vector.len(),
                     },
                 )
                 .into());
             }
+            Some(SearchVector::Dense(_)) | None => (),
         }
 
         Ok((embedder_name.to_owned(), embedder, quantized))
@@ -424,7 +580,7 @@ pub struct SearchQueryWithIndex {
     #[deserr(default, error = DeserrJsonError<InvalidSearchQ>)]
     pub q: Option<String>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchQ>)]
-    pub vector: Option<Vec<f32>>,
+    pub vector: Option<SearchVector>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHybridQuery>)]
     pub hybrid: Option<HybridQuery>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchOffset>)]
@@ -455,10 +611,20 @@ pub struct SearchQueryWithIndex {
     pub filter: Option<Value>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchSort>)]
     pub sort: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchBoost>)]
+    pub boost: Option<Vec<SearchBoost>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchDistinct>)]
     pub distinct: Option<String>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFacets>)]
     pub facets: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFacetHistograms>)]
+    pub facet_histograms: Option<BTreeMap<String, FacetHistogramInterval>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFacetPercentiles>)]
+    pub facet_percentiles: Option<BTreeMap<String, Vec<FacetPercentile>>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchHierarchicalFacets>)]
+    pub hierarchical_facets: Option<Vec<HierarchicalFacet>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchDisjunctiveFacets>)]
+    pub disjunctive_facets: Option<Vec<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPreTag>, default = DEFAULT_HIGHLIGHT_PRE_TAG())]
     pub highlight_pre_tag: String,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPostTag>, default = DEFAULT_HIGHLIGHT_POST_TAG())]
@@ -497,6 +663,22 @@ impl SearchQueryWithIndex {
         self.facets.as_deref().filter(|v| !v.is_empty())
     }
 
+    /// Returns the name of the first facet parameter of this query
+    /// that cannot be used in a federated search, if any.
+    pub fn has_facet_parameters(&self) -> Option<&'static str> {
+        if self.facet_histograms.as_ref().is_some_and(|h| !h.is_empty()) {
+            Some("facetHistograms")
+        } else if self.facet_percentiles.as_ref().is_some_and(|p| !p.is_empty()) {
+            Some("facetPercentiles")
+        } else if self.hierarchical_facets.as_ref().is_some_and(|h| !h.is_empty()) {
+            Some("hierarchicalFacets")
+        } else if self.disjunctive_facets.as_ref().is_some_and(|d| !d.is_empty()) {
+            Some("disjunctiveFacets")
+        } else {
+            None
+        }
+    }
+
     pub fn from_index_query_federation(
         index_uid: IndexUid,
         query: SearchQuery,
@@ -520,8 +702,13 @@ impl SearchQueryWithIndex {
             show_ranking_score_details,
             filter,
             sort,
+            boost,
             distinct,
             facets,
+            facet_histograms,
+            facet_percentiles,
+            hierarchical_facets,
+            disjunctive_facets,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
@@ -550,8 +737,13 @@ impl SearchQueryWithIndex {
             show_matches_position,
             filter,
             sort,
+            boost,
             distinct,
             facets,
+            facet_histograms,
+            facet_percentiles,
+            hierarchical_facets,
+            disjunctive_facets,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
@@ -583,8 +775,13 @@ impl SearchQueryWithIndex {
             show_matches_position,
             filter,
             sort,
+            boost,
             distinct,
             facets,
+            facet_histograms,
+            facet_percentiles,
+            hierarchical_facets,
+            disjunctive_facets,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
@@ -613,8 +810,13 @@ impl SearchQueryWithIndex {
                 show_matches_position,
                 filter,
                 sort,
+                boost,
                 distinct,
                 facets,
+                facet_histograms,
+                facet_percentiles,
+                hierarchical_facets,
+                disjunctive_facets,
                 highlight_pre_tag,
                 highlight_post_tag,
                 crop_marker,
@@ -751,6 +953,9 @@ pub struct SearchHit {
     pub ranking_score: Option<f64>,
     #[serde(default, rename = "_rankingScoreDetails", skip_serializing_if = "Option::is_none")]
     pub ranking_score_details: Option<serde_json::Map<String, serde_json::Value>>,
+    /// The chunk of the document that matched best, when its embedder splits the documents into chunks.
+    #[serde(default, rename = "_matchedChunk", skip_serializing_if = "Option::is_none")]
+    pub matched_chunk: Option<usize>,
 }
 
 #[derive(Serialize, Clone, PartialEq, ToSchema)]
@@ -767,9 +972,20 @@ pub struct SearchResult {
     pub facet_distribution: Option<BTreeMap<String, IndexMap<String, u64>>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub facet_stats: Option<BTreeMap<String, FacetStats>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub facet_histograms: Option<BTreeMap<String, Vec<HistogramBucket>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<BTreeMap<String, BTreeMap<String, f64>>>)]
+    pub facet_percentiles: Option<BTreeMap<String, IndexMap<String, f64>>>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub semantic_hit_count: Option<u32>,
+    /// The ids of the query rules applied to the results.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub applied_query_rules: Option<Vec<String>>,
+    /// The query with its misspelled words corrected, when the search returns few hits.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggested_query: Option<String>,
 
     // These fields are only used for analytics purposes
     #[serde(skip)]
@@ -787,7 +1003,11 @@ impl fmt::Debug for SearchResult {
             hits_info,
             facet_distribution,
             facet_stats,
+            facet_histograms,
+            facet_percentiles,
             semantic_hit_count,
+            applied_query_rules,
+            suggested_query,
             degraded,
             used_negative_operator,
         } = self;
@@ -810,9 +1030,21 @@ impl fmt::Debug for SearchResult {
         if let Some(facet_stats) = facet_stats {
             debug.field("facet_stats", &facet_stats);
         }
+        if let Some(facet_histograms) = facet_histograms {
+            debug.field("facet_histograms", &facet_histograms);
+        }
+        if let Some(facet_percentiles) = facet_percentiles {
+            debug.field("facet_percentiles", &facet_percentiles);
+        }
         if let Some(semantic_hit_count) = semantic_hit_count {
             debug.field("semantic_hit_count", &semantic_hit_count);
         }
+        if let Some(applied_query_rules) = applied_query_rules {
+            debug.field("applied_query_rules", &applied_query_rules);
+        }
+        if let Some(suggested_query) = suggested_query {
+            debug.field("suggested_query", &suggested_query);
+        }
 
         debug.finish()
     }
@@ -854,6 +1086,14 @@ pub struct FacetStats {
     pub max: f64,
 }
 
+/// The number of documents with a value in `[from, to[`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, ToSchema)]
+pub struct HistogramBucket {
+    pub from: f64,
+    pub to: f64,
+    pub count: u64,
+}
+
 #[derive(Serialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct FacetSearchResult {
@@ -862,6 +1102,22 @@ pub struct FacetSearchResult {
     pub processing_time_ms: u128,
 }
 
+/// A completed query with the number of documents it matches.
+#[derive(Serialize, Debug, Clone, PartialEq, ToSchema)]
+pub struct QuerySuggestion {
+    pub query: String,
+    pub count: u64,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(rename_all = "camelCase")]
+pub struct SuggestResult {
+    pub suggestions: Vec<QuerySuggestion>,
+    pub query: String,
+    pub processing_time_ms: u128,
+}
+
 /// Incorporate search rules in search query
 pub fn add_search_rules(filter: &mut Option<Value>, rules: IndexSearchRules) {
     *filter = match (filter.take(), rules.filter) {
@@ -889,7 +1145,7 @@ fn prepare_search<'t>(
     search_kind: &SearchKind,
     time_budget: TimeBudget,
     features: RoFeatures,
-) -> Result<(milli::Search<'t>, bool, usize, usize), ResponseError> {
+) -> Result<(milli::Search<'t>, bool, usize, usize, usize), ResponseError> {
     let mut search = index.search(rtxn);
     search.time_budget(time_budget);
     if let Some(ranking_score_threshold) = query.ranking_score_threshold {
@@ -908,18 +1164,8 @@ fn prepare_search<'t>(
         }
         SearchKind::SemanticOnly { embedder_name, embedder, quantized } => {
             let vector = match query.vector.clone() {
-                Some(vector) => vector,
-                None => {
-                    let span = tracing::trace_span!(target: "search::vector", "embed_one");
-                    let _entered = span.enter();
-
-                    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
-
-                    embedder
-                        .embed_search(query.q.as_ref().unwrap(), Some(deadline))
-                        .map_err(milli::vector::Error::from)
-                        .map_err(milli::Error::from)?
-                }
+                Some(vector) => vector.into_embedding(),
+                None => embed_query(embedder, query.q.as_ref().unwrap())?,
             };
 
             search.semantic(embedder_name.clone(), embedder.clone(), *quantized, Some(vector));
@@ -933,7 +1179,7 @@ fn prepare_search<'t>(
                 embedder_name.clone(),
                 embedder.clone(),
                 *quantized,
-                query.vector.clone(),
+                query.vector.clone().map(SearchVector::into_embedding),
             );
         }
     }
@@ -999,42 +1245,141 @@ fn prepare_search<'t>(
         search.sort_criteria(sort);
     }
 
+    if let Some(ref boost) = query.boost {
+        let mut boosts = Vec::with_capacity(boost.len());
+        for SearchBoost { filter, weight } in boost {
+            if let Some(filter) = parse_filter(filter, Code::InvalidSearchBoost, features)? {
+                boosts.push((filter, weight.0));
+            }
+        }
+        search.boosts(boosts);
+    }
+
     if let Some(ref locales) = query.locales {
         search.locales(locales.iter().copied().map(Into::into).collect());
     }
 
-    Ok((search, is_finite_pagination, max_total_hits, offset))
+    Ok((search, is_finite_pagination, max_total_hits, offset, limit))
+}
+
+fn embed_query(embedder: &Embedder, q: &str) -> Result<Embedding, ResponseError> {
+    let span = tracing::trace_span!(target: "search::vector", "embed_one");
+    let _entered = span.enter();
+
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+
+    Ok(embedder
+        .embed_search(q, Some(deadline))
+        .map_err(milli::vector::Error::from)
+        .map_err(milli::Error::from)?)
 }
 
 pub fn perform_search(
     index_uid: String,
     index: &Index,
-    query: SearchQuery,
+    mut query: SearchQuery,
     search_kind: SearchKind,
     retrieve_vectors: RetrieveVectors,
     features: RoFeatures,
+    search_rules: Option<IndexSearchRules>,
 ) -> Result<SearchResult, ResponseError> {
     let before_search = Instant::now();
     let rtxn = index.read_txn()?;
+
+    // the filter of the tenant token is kept apart from the filter of the query,
+    // so that it is never removed along with the clauses of a disjunctive facet
+    let user_filter = query.filter.clone();
+    let search_rules_filter = search_rules.as_ref().and_then(|rules| rules.filter.clone());
+    if let Some(search_rules) = search_rules {
+        add_search_rules(&mut query.filter, search_rules);
+    }
+
     let time_budget = match index.search_cutoff(&rtxn)? {
         Some(cutoff) => TimeBudget::new(Duration::from_millis(cutoff)),
         None => TimeBudget::default(),
     };
 
-    let (search, is_finite_pagination, max_total_hits, offset) =
-        prepare_search(index, &rtxn, &query, &search_kind, time_budget, features)?;
+    let query_rules = FiredQueryRules::fire(index, &rtxn, &query, features)?;
+
+    // the query is embedded once for both the hits and the boosted documents of the query rules
+    let embedder = match &search_kind {
+        SearchKind::KeywordOnly => None,
+        SearchKind::SemanticOnly { embedder, .. } | SearchKind::Hybrid { embedder, .. } => {
+            Some(embedder)
+        }
+    };
+    if let (Some(embedder), None, Some(q)) = (embedder, &query.vector, &query.q) {
+        if query_rules.as_ref().is_some_and(FiredQueryRules::boosts) {
+            query.vector = Some(SearchVector::Dense(embed_query(embedder, q)?));
+        }
+    }
+
+    let (mut search, is_finite_pagination, max_total_hits, offset, search_limit) =
+        prepare_search(index, &rtxn, &query, &search_kind, time_budget.clone(), features)?;
+
+    // only the hits are reranked, not the secondary searches computing the facets or query rules
+    if let Some(reranker) = query.hybrid.as_ref().and_then(|hybrid| hybrid.reranker.clone()) {
+        search.reranker(reranker);
+    }
+
+    // the query rules are applied on the first hits, the requested page is extracted afterward
+    let boosted_documents = match &query_rules {
+        Some(query_rules) => {
+            let window = query_rules.window(offset, search_limit);
+            search.offset(0);
+            search.limit(window);
+            query_rules.search_boosted_documents(
+                &index_uid,
+                index,
+                &rtxn,
+                &query,
+                &search_kind,
+                &time_budget,
+                features,
+                window,
+            )?
+        }
+        None => None,
+    };
 
     let (
         milli::SearchResult {
             documents_ids,
             matching_words,
-            candidates,
+            mut candidates,
             document_scores,
             degraded,
             used_negative_operator,
+            suggested_query,
         },
         semantic_hit_count,
-    ) = search_from_kind(index_uid, search_kind, search)?;
+    ) = search_from_kind(index_uid.clone(), search_kind, search)?;
+
+    let disjunctive_facets_candidates = compute_disjunctive_facets_candidates(
+        &index_uid,
+        index,
+        &rtxn,
+        &query,
+        user_filter.as_ref(),
+        search_rules_filter.as_ref(),
+        semantic_hit_count.is_some(),
+        &time_budget,
+        features,
+    )?;
+
+    let (documents_ids, document_scores): (Vec<_>, Vec<_>) = match &query_rules {
+        Some(query_rules) => query_rules
+            .apply(
+                documents_ids.into_iter().zip(document_scores).collect(),
+                boosted_documents,
+                &mut candidates,
+                offset,
+                search_limit,
+            )
+            .into_iter()
+            .unzip(),
+        None => (documents_ids, document_scores),
+    };
 
     let SearchQuery {
         q,
@@ -1052,6 +1397,10 @@ pub fn perform_search(
         show_ranking_score_details,
         sort,
         facets,
+        facet_histograms,
+        facet_percentiles,
+        hierarchical_facets,
+        disjunctive_facets,
         highlight_pre_tag,
         highlight_post_tag,
         crop_marker,
@@ -1064,6 +1413,7 @@ pub fn perform_search(
         matching_strategy: _,
         attributes_to_search_on: _,
         filter: _,
+        boost: _,
         distinct: _,
     } = query;
 
@@ -1109,13 +1459,51 @@ pub fn perform_search(
         HitsInfo::OffsetLimit { limit, offset, estimated_total_hits: number_of_hits }
     };
 
-    let (facet_distribution, facet_stats) = facets
-        .map(move |facets| {
-            compute_facet_distribution_stats(&facets, index, &rtxn, candidates, Route::Search)
-        })
-        .transpose()?
-        .map(|ComputedFacets { distribution, stats }| (distribution, stats))
-        .unzip();
+    let (facet_histograms, facet_percentiles) = match (facet_histograms, facet_percentiles) {
+        (None, None) => (None, None),
+        (facet_histograms, facet_percentiles) => compute_facet_histograms_percentiles(
+            facet_histograms,
+            facet_percentiles,
+            index,
+            &rtxn,
+            &candidates,
+        )?,
+    };
+
+    let (mut facet_distribution, mut facet_stats) = match (facets, hierarchical_facets) {
+        (None, None) => (None, None),
+        (facets, hierarchical_facets) => {
+            let ComputedFacets { distribution, stats } = compute_facet_distribution_stats(
+                &facets.unwrap_or_default(),
+                &hierarchical_facets.unwrap_or_default(),
+                index,
+                &rtxn,
+                candidates.clone(),
+                Route::Search,
+            )?;
+            (Some(distribution), Some(stats))
+        }
+    };
+
+    // the disjunctive facets replace the distribution computed with the whole filter
+    for facet in disjunctive_facets.unwrap_or_default() {
+        let facet_candidates = match disjunctive_facets_candidates.get(&facet) {
+            Some(facet_candidates) => facet_candidates.clone(),
+            None => candidates.clone(),
+        };
+        let ComputedFacets { distribution, stats } = compute_facet_distribution_stats(
+            &[&facet],
+            &[],
+            index,
+            &rtxn,
+            facet_candidates,
+            Route::Search,
+        )?;
+        facet_distribution.get_or_insert_with(BTreeMap::new).extend(distribution);
+        let facet_stats = facet_stats.get_or_insert_with(BTreeMap::new);
+        facet_stats.remove(&facet);
+        facet_stats.extend(stats);
+    }
 
     let result = SearchResult {
         hits: documents,
@@ -1124,13 +1512,111 @@ pub fn perform_search(
         processing_time_ms: before_search.elapsed().as_millis(),
         facet_distribution,
         facet_stats,
+        facet_histograms,
+        facet_percentiles,
         degraded,
         used_negative_operator,
         semantic_hit_count,
+        applied_query_rules: query_rules.map(|query_rules| query_rules.ids),
+        suggested_query,
     };
     Ok(result)
 }
 
+/// Computes, for each disjunctive facet of the query, the documents matching the query with
+/// the filter clauses on this facet removed.
+///
+/// The documents matching the query without any filter are computed once, and intersected with
+/// the filter of each facet. A semantic search matches all the documents, whether it was the only
+/// search or merged with the keyword search, so that the query is never embedded again.
+///
+/// Only the clauses of the filter of the query are removed, the documents are always restricted
+/// to the ones matching the search rules of the tenant token.
+///
+/// The facets whose clauses can't be removed from the filter are not returned,
+/// their distribution being computed on the candidates of the query itself.
+fn compute_disjunctive_facets_candidates(
+    index_uid: &str,
+    index: &Index,
+    rtxn: &RoTxn,
+    query: &SearchQuery,
+    filter: Option<&Value>,
+    search_rules_filter: Option<&Value>,
+    semantic: bool,
+    time_budget: &TimeBudget,
+    features: RoFeatures,
+) -> Result<BTreeMap<String, roaring::RoaringBitmap>, ResponseError> {
+    let mut disjunctive_facets_candidates = BTreeMap::new();
+    let (Some(disjunctive_facets), Some(filter)) = (&query.disjunctive_facets, filter) else {
+        return Ok(disjunctive_facets_candidates);
+    };
+    let Some(filter) = parse_filter(filter, Code::InvalidSearchFilter, features)? else {
+        return Ok(disjunctive_facets_candidates);
+    };
+    let search_rules_filter = match search_rules_filter {
+        Some(search_rules_filter) => {
+            parse_filter(search_rules_filter, Code::InvalidSearchFilter, features)?
+        }
+        None => None,
+    };
+
+    let mut universe = None;
+    for facet in disjunctive_facets {
+        let facet_filter = filter.without_facet(facet);
+        if facet_filter.as_ref() == Some(&filter) {
+            continue;
+        }
+
+        let universe = match &mut universe {
+            Some(universe) => universe,
+            None => {
+                let mut candidates = if semantic {
+                    index.documents_ids(rtxn)?
+                } else {
+                    // only the candidates are needed, not the documents nor the facets
+                    let keyword_query = SearchQuery {
+                        offset: 0,
+                        limit: 0,
+                        page: None,
+                        hits_per_page: None,
+                        filter: None,
+                        facets: None,
+                        facet_histograms: None,
+                        facet_percentiles: None,
+                        hierarchical_facets: None,
+                        disjunctive_facets: None,
+                        ..query.clone()
+                    };
+                    let (search, _, _, _, _) = prepare_search(
+                        index,
+                        rtxn,
+                        &keyword_query,
+                        &SearchKind::KeywordOnly,
+                        time_budget.clone(),
+                        features,
+                    )?;
+                    let milli::SearchResult { candidates, .. } = search.execute().map_err(|e| {
+                        MeilisearchHttpError::from_milli(e, Some(index_uid.to_string()))
+                    })?;
+                    candidates
+                };
+                if let Some(search_rules_filter) = &search_rules_filter {
+                    candidates &= search_rules_filter.evaluate(rtxn, index)?;
+                }
+                universe.insert(candidates)
+            }
+        };
+
+        let candidates = match facet_filter {
+            Some(facet_filter) => &*universe & facet_filter.evaluate(rtxn, index)?,
+            None => universe.clone(),
+        };
+        disjunctive_facets_candidates.insert(facet.clone(), candidates);
+    }
+
+    Ok(disjunctive_facets_candidates)
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
 pub struct ComputedFacets {
     #[schema(value_type = BTreeMap<String, BTreeMap<String, u64>>)]
@@ -1146,6 +1632,7 @@ pub enum Route {
 
 fn compute_facet_distribution_stats<S: AsRef<str>>(
     facets: &[S],
+    hierarchical_facets: &[HierarchicalFacet],
     index: &Index,
     rtxn: &RoTxn,
     candidates: roaring::RoaringBitmap,
@@ -1163,31 +1650,122 @@ fn compute_facet_distribution_stats<S: AsRef<str>>(
 
     let sort_facet_values_by = index.sort_facet_values_by(rtxn).map_err(milli::Error::from)?;
 
+    let facet_error = |error| match (error, &route) {
+        (
+            error @ milli::Error::UserError(
+                milli::UserError::InvalidFacetsDistribution { .. }
+                | milli::UserError::InvalidFacetRange { .. },
+            ),
+            Route::MultiSearch,
+        ) => ResponseError::from_msg(error.to_string(), Code::InvalidMultiSearchFacets),
+        (error, _) => error.into(),
+    };
+
+    // the facets written with the range-bucket syntax are counted by ranges of values
+    let mut fields = Vec::new();
+    let mut ranges = Vec::new();
+    for facet in facets {
+        match FacetRange::parse_facet(facet.as_ref()).map_err(facet_error)? {
+            Some((name, facet_ranges)) => ranges.push((name, facet_ranges)),
+            None => fields.push(facet.as_ref()),
+        }
+    }
+
+    let mut hierarchies = Vec::new();
+    for HierarchicalFacet { attributes, separator, path } in hierarchical_facets {
+        // the levels are added to the facets to be sorted according to their own settings
+        fields.extend(attributes.iter().map(String::as_str));
+        hierarchies.push(FacetHierarchy {
+            levels: attributes.clone(),
+            separator: separator.clone(),
+            path: path.clone(),
+        });
+    }
+
     // add specific facet if there is no placeholder
-    if facets.iter().all(|f| f.as_ref() != "*") {
-        let fields: Vec<_> =
-            facets.iter().map(|n| (n, sort_facet_values_by.get(n.as_ref()))).collect();
+    if fields.iter().all(|f| *f != "*") {
+        let fields: Vec<_> = fields.iter().map(|n| (n, sort_facet_values_by.get(n))).collect();
         facet_distribution.facets(fields);
     }
 
     let distribution = facet_distribution
+        .ranges(ranges)
+        .hierarchies(hierarchies)
         .candidates(candidates)
         .default_order_by(sort_facet_values_by.get("*"))
         .execute()
-        .map_err(|error| match (error, route) {
-            (
-                error @ milli::Error::UserError(milli::UserError::InvalidFacetsDistribution {
-                    ..
-                }),
-                Route::MultiSearch,
-            ) => ResponseError::from_msg(error.to_string(), Code::InvalidMultiSearchFacets),
-            (error, _) => error.into(),
-        })?;
+        .map_err(facet_error)?;
     let stats = facet_distribution.compute_stats()?;
     let stats = stats.into_iter().map(|(k, (min, max))| (k, FacetStats { min, max })).collect();
     Ok(ComputedFacets { distribution, stats })
 }
 
+#[allow(clippy::type_complexity)]
+fn compute_facet_histograms_percentiles(
+    facet_histograms: Option<BTreeMap<String, FacetHistogramInterval>>,
+    facet_percentiles: Option<BTreeMap<String, Vec<FacetPercentile>>>,
+    index: &Index,
+    rtxn: &RoTxn,
+    candidates: &roaring::RoaringBitmap,
+) -> Result<
+    (
+        Option<BTreeMap<String, Vec<HistogramBucket>>>,
+        Option<BTreeMap<String, IndexMap<String, f64>>>,
+    ),
+    ResponseError,
+> {
+    let mut facet_distribution = index.facets_distribution(rtxn);
+    facet_distribution.candidates(candidates.clone());
+
+    let histograms = match facet_histograms {
+        Some(facet_histograms) => {
+            let intervals = facet_histograms
+                .into_iter()
+                .map(|(field, FacetHistogramInterval(interval))| (field, interval))
+                .collect();
+            let histograms = facet_distribution.compute_histograms(&intervals)?;
+            let histograms = histograms.into_iter().map(|(field, buckets)| {
+                let buckets = buckets
+                    .into_iter()
+                    .map(|milli::HistogramBucket { from, to, count }| HistogramBucket {
+                        from,
+                        to,
+                        count,
+                    })
+                    .collect();
+                (field, buckets)
+            });
+            Some(histograms.collect())
+        }
+        None => None,
+    };
+
+    let percentiles = match facet_percentiles {
+        Some(facet_percentiles) => {
+            let requested: BTreeMap<_, Vec<_>> = facet_percentiles
+                .into_iter()
+                .map(|(field, percentiles)| {
+                    (field, percentiles.into_iter().map(|FacetPercentile(p)| p).collect())
+                })
+                .collect();
+            let computed = facet_distribution.compute_percentiles(&requested)?;
+            // the percentiles are returned in the order they were requested in
+            let percentiles = computed.into_iter().map(|(field, values)| {
+                let values = requested[&field]
+                    .iter()
+                    .zip(values)
+                    .map(|(percentile, value)| (percentile.to_string(), value))
+                    .collect();
+                (field, values)
+            });
+            Some(percentiles.collect())
+        }
+        None => None,
+    };
+
+    Ok((histograms, percentiles))
+}
+
 pub fn search_from_kind(
     index_uid: String,
     search_kind: SearchKind,
@@ -1431,14 +2009,14 @@ impl<'a> HitMaker<'a> {
                 Some(Value::Object(map)) => map,
                 _ => Default::default(),
             };
-            for (name, vector) in self.index.embeddings(self.rtxn, id)? {
+            for (name, vector) in self.index.document_vectors(self.rtxn, id)? {
                 let user_provided = self
                     .embedding_configs
                     .iter()
                     .find(|conf| conf.name == name)
                     .is_some_and(|conf| conf.user_provided.contains(id));
                 let embeddings =
-                    ExplicitVectors { embeddings: Some(vector.into()), regenerate: !user_provided };
+                    ExplicitVectors { embeddings: Some(vector), regenerate: !user_provided };
                 vectors.insert(
                     name,
                     serde_json::to_value(embeddings).map_err(InternalError::SerdeJson)?,
@@ -1469,6 +2047,7 @@ impl<'a> HitMaker<'a> {
             self.show_ranking_score.then(|| ScoreDetails::global_score(score.iter()));
         let ranking_score_details =
             self.show_ranking_score_details.then(|| ScoreDetails::to_json_map(score.iter()));
+        let matched_chunk = ScoreDetails::matched_chunk(score.iter());
 
         let hit = SearchHit {
             document,
@@ -1476,6 +2055,7 @@ impl<'a> HitMaker<'a> {
             matches_position,
             ranking_score_details,
             ranking_score,
+            matched_chunk,
         };
 
         Ok(hit)
@@ -1491,7 +2071,7 @@ fn make_hits<'a>(
 ) -> milli::Result<Vec<SearchHit>> {
     let mut documents = Vec::new();
 
-    let dictionary = index.dictionary(rtxn)?;
+    let dictionary = index.tokenizer_dictionary(rtxn)?;
     let dictionary: Option<Vec<_>> =
         dictionary.as_ref().map(|x| x.iter().map(String::as_str).collect());
     let separators = index.allowed_separators(rtxn)?;
@@ -1548,7 +2128,7 @@ pub fn perform_facet_search(
             .collect()
     });
 
-    let (search, _, _, _) =
+    let (search, _, _, _, _) =
         prepare_search(index, &rtxn, &search_query, &search_kind, time_budget, features)?;
     let mut facet_search = SearchForFacetValues::new(
         facet_name,
@@ -1573,6 +2153,37 @@ pub fn perform_facet_search(
     })
 }
 
+pub fn perform_suggest(
+    index: &Index,
+    query: String,
+    limit: usize,
+    filter: Option<Value>,
+    features: RoFeatures,
+) -> Result<SuggestResult, ResponseError> {
+    let before_suggest = Instant::now();
+    let rtxn = index.read_txn()?;
+
+    let mut suggest = milli::Suggest::new(query.clone(), index, &rtxn);
+    suggest.limit(limit);
+    if let Some(ref filter) = filter {
+        if let Some(filter) = parse_filter(filter, Code::InvalidSuggestFilter, features)? {
+            suggest.filter(filter);
+        }
+    }
+
+    let suggestions = suggest
+        .execute()?
+        .into_iter()
+        .map(|milli::Suggestion { query, count }| QuerySuggestion { query, count })
+        .collect();
+
+    Ok(SuggestResult {
+        suggestions,
+        query,
+        processing_time_ms: before_suggest.elapsed().as_millis(),
+    })
+}
+
 pub fn perform_similar(
     index: &Index,
     query: SimilarQuery,
@@ -1640,6 +2251,7 @@ pub fn perform_similar(
         document_scores,
         degraded: _,
         used_negative_operator: _,
+        suggested_query: _,
     } = similar.execute().map_err(|err| match err {
         milli::Error::UserError(milli::UserError::InvalidFilter(_)) => {
             ResponseError::from_msg(err.to_string(), Code::InvalidSimilarFilter)
@@ -1697,11 +2309,28 @@ pub fn insert_geo_distance(sorts: &[String], document: &mut Document) {
     if let Some(capture_group) = sorts.iter().find_map(|sort| GEO_REGEX.captures(sort)) {
         // TODO: TAMO: milli encountered an internal error, what do we want to do?
         let base = [capture_group[1].parse().unwrap(), capture_group[2].parse().unwrap()];
-        let geo_point = &document.get("_geo").unwrap_or(&json!(null));
-        if let Some((lat, lng)) =
-            extract_geo_value(&geo_point["lat"]).zip(extract_geo_value(&geo_point["lng"]))
-        {
-            let distance = milli::distance_between_two_points(&base, &[lat, lng]);
+        // a document can have many points and polygons, we report the distance to the nearest one
+        let mut geometry = DocumentGeometry::default();
+        let geo_points = match document.get("_geo") {
+            Some(Value::Array(points)) => points.iter().collect(),
+            Some(point) => vec![point],
+            None => Vec::new(),
+        };
+        geometry.points.extend(geo_points.into_iter().filter_map(|point| {
+            let (lat, lng) =
+                extract_geo_value(&point["lat"]).zip(extract_geo_value(&point["lng"]))?;
+            Some([lat, lng])
+        }));
+        if let Some(geojson) = document.get("_geojson") {
+            if let Ok(Some(DocumentGeometry { points, polygons })) =
+                extract_geojson_geometry_from_value(|| Value::Null, geojson.clone())
+            {
+                geometry.points.extend(points);
+                geometry.polygons.extend(polygons);
+            }
+        }
+        if let Some(point) = geometry.closest_point(&base) {
+            let distance = milli::distance_between_two_points(&base, &point);
             document.insert("_geoDistance".to_string(), json!(distance.round() as usize));
         }
     }
@@ -112,3 +112,60 @@ fn test_insert_geo_distance_with_coords_as_string() {
     insert_geo_distance(sorters, &mut document);
     assert_eq!(document.get("_geoDistance"), Some(&json!(0)));
 }
+
+#[test]
+fn test_insert_geo_distance_with_many_points() {
+    let value: Document = serde_json::from_str(
+        r#"{
+          "_geo": [
+            { "lat": 48.8566, "lng": 2.3522 },
+            { "lat": 50.629973371633746, "lng": 3.0569447399419567 },
+            { "lat": "43.2965", "lng": "5.3698" }
+          ]
+        }"#,
+    )
+    .unwrap();
+
+    // the distance to the nearest point is reported
+    let sorters = &["_geoPoint(50.629973371633746,3.0569447399419567):asc".to_string()];
+    let mut document = value.clone();
+    insert_geo_distance(sorters, &mut document);
+    assert_eq!(document.get("_geoDistance"), Some(&json!(0)));
+
+    let sorters = &["_geoPoint(48.8566,2.3522):desc".to_string()];
+    let mut document = value.clone();
+    insert_geo_distance(sorters, &mut document);
+    assert_eq!(document.get("_geoDistance"), Some(&json!(0)));
+
+    // a document without any point doesn't get a distance
+    let value: Document = serde_json::from_str(r#"{ "_geo": [] }"#).unwrap();
+    let mut document = value;
+    insert_geo_distance(sorters, &mut document);
+    assert_eq!(document.get("_geoDistance"), None);
+}
+
+#[test]
+fn test_insert_geo_distance_with_geojson() {
+    let value: Document = serde_json::from_str(
+        r#"{
+          "_geo": { "lat": 10, "lng": 10 },
+          "_geojson": {
+            "type": "Polygon",
+            "coordinates": [[[-1, -1], [1, -1], [1, 1], [-1, 1], [-1, -1]]]
+          }
+        }"#,
+    )
+    .unwrap();
+
+    // the point is inside the polygon
+    let sorters = &["_geoPoint(0,0):desc".to_string()];
+    let mut document = value.clone();
+    insert_geo_distance(sorters, &mut document);
+    assert_eq!(document.get("_geoDistance"), Some(&json!(0)));
+
+    // the point is closer to the _geo point than to the polygon
+    let sorters = &["_geoPoint(10,10):asc".to_string()];
+    let mut document = value;
+    insert_geo_distance(sorters, &mut document);
+    assert_eq!(document.get("_geoDistance"), Some(&json!(0)));
+}
@@ -0,0 +1,191 @@
+use either::Either;
+use index_scheduler::RoFeatures;
+use meilisearch_types::error::{Code, ResponseError};
+use meilisearch_types::heed::RoTxn;
+use meilisearch_types::milli::score_details::ScoreDetails;
+use meilisearch_types::milli::{
+    self, DocumentId, Filter, FilterCondition, Index, PinnedDocument, TimeBudget,
+};
+use roaring::RoaringBitmap;
+use time::OffsetDateTime;
+
+use super::{parse_filter, prepare_search, search_from_kind, SearchKind, SearchQuery};
+
+/// The query rules fired by a search, with the documents they pin, hide and boost.
+#[derive(Default)]
+pub struct FiredQueryRules {
+    /// The ids of the fired rules, in the order of the settings.
+    pub ids: Vec<String>,
+    /// The pinned documents with their position, sorted by position.
+    pinned: Vec<(usize, DocumentId)>,
+    hidden: RoaringBitmap,
+    boost: Vec<String>,
+}
+
+/// The best ranked documents matching the boost filters, and all the documents matching them.
+pub struct BoostedDocuments {
+    hits: Vec<(DocumentId, Vec<ScoreDetails>)>,
+    docids: RoaringBitmap,
+}
+
+impl FiredQueryRules {
+    /// Returns the query rules of the index fired by the query, if any.
+    ///
+    /// The pinned and hidden documents that don't exist in the index are ignored,
+    /// as are the pinned documents that don't match the filter of the query.
+    pub fn fire(
+        index: &Index,
+        rtxn: &RoTxn,
+        query: &SearchQuery,
+        features: RoFeatures,
+    ) -> Result<Option<Self>, ResponseError> {
+        let Some(rules) = index.query_rules(rtxn)? else { return Ok(None) };
+        let conditions = index.query_rules_conditions(rtxn)?.unwrap_or_default();
+        let filter = match &query.filter {
+            Some(filter) => parse_filter(filter, Code::InvalidSearchFilter, features)?,
+            None => None,
+        };
+        let filter_clauses = filter.as_ref().map(Filter::clauses).unwrap_or_default();
+        let now = OffsetDateTime::now_utc().unix_timestamp() as f64;
+        let q = query.q.as_deref().unwrap_or_default();
+        let external_documents_ids = index.external_documents_ids();
+
+        let mut fired = FiredQueryRules::default();
+        let fired_rules = rules
+            .iter()
+            .zip(&conditions)
+            .filter(|(_, conditions)| conditions.fires(q, &filter_clauses, now))
+            .map(|(rule, _)| rule);
+        for rule in fired_rules {
+            fired.ids.push(rule.id.clone());
+            for PinnedDocument { id, position } in &rule.pin {
+                let Some(docid) = external_documents_ids.get(rtxn, id)? else { continue };
+                // the first rule pinning a document decides of its position
+                if fired.pinned.iter().all(|(_, pinned)| *pinned != docid) {
+                    fired.pinned.push((*position, docid));
+                }
+            }
+            for id in &rule.hide {
+                if let Some(docid) = external_documents_ids.get(rtxn, id)? {
+                    fired.hidden.insert(docid);
+                }
+            }
+            fired.boost.extend(rule.boost.iter().cloned());
+        }
+        fired.pinned.sort_by_key(|(position, _)| *position);
+
+        if let Some(filter) = filter.filter(|_| !fired.pinned.is_empty()) {
+            let candidates = filter.evaluate(rtxn, index)?;
+            fired.pinned.retain(|(_, docid)| candidates.contains(*docid));
+        }
+
+        Ok((!fired.ids.is_empty()).then_some(fired))
+    }
+
+    /// Returns `true` if the fired rules boost some documents.
+    pub fn boosts(&self) -> bool {
+        !self.boost.is_empty()
+    }
+
+    /// Returns the number of hits to retrieve for the requested page to be
+    /// complete once the pinned and hidden documents are removed from the hits.
+    pub fn window(&self, offset: usize, limit: usize) -> usize {
+        offset + limit + self.pinned.len() + self.hidden.len() as usize
+    }
+
+    /// Searches the best ranked documents matching both the query and the boost filters.
+    #[allow(clippy::too_many_arguments)]
+    pub fn search_boosted_documents(
+        &self,
+        index_uid: &str,
+        index: &Index,
+        rtxn: &RoTxn,
+        query: &SearchQuery,
+        search_kind: &SearchKind,
+        time_budget: &TimeBudget,
+        features: RoFeatures,
+        window: usize,
+    ) -> Result<Option<BoostedDocuments>, ResponseError> {
+        let boost = [Either::<_, &str>::Left(self.boost.iter().map(String::as_str))];
+        let Some(boost) = Filter::from_array(boost)? else { return Ok(None) };
+        let docids = boost.evaluate(rtxn, index)?;
+
+        let filter = match &query.filter {
+            Some(filter) => parse_filter(filter, Code::InvalidSearchFilter, features)?,
+            None => None,
+        };
+        let filter = match filter {
+            Some(filter) => Filter::from(FilterCondition::And(vec![filter.into(), boost.into()])),
+            None => boost,
+        };
+
+        let boosted_query = SearchQuery {
+            offset: 0,
+            limit: window,
+            page: None,
+            hits_per_page: None,
+            filter: None,
+            facets: None,
+            facet_histograms: None,
+            facet_percentiles: None,
+            hierarchical_facets: None,
+            disjunctive_facets: None,
+            ..query.clone()
+        };
+        let (mut search, _, _, _, _) = prepare_search(
+            index,
+            rtxn,
+            &boosted_query,
+            search_kind,
+            time_budget.clone(),
+            features,
+        )?;
+        search.filter(filter);
+        let (milli::SearchResult { documents_ids, document_scores, .. }, _) =
+            search_from_kind(index_uid.to_string(), search_kind.clone(), search)?;
+
+        Ok(Some(BoostedDocuments {
+            hits: documents_ids.into_iter().zip(document_scores).collect(),
+            docids,
+        }))
+    }
+
+    /// Applies the rules to the hits of the search, returning the requested page.
+    ///
+    /// The boosted documents are moved before the other hits, the hidden documents
+    /// are removed and the pinned documents are inserted at their position.
+    pub fn apply(
+        &self,
+        hits: Vec<(DocumentId, Vec<ScoreDetails>)>,
+        boosted: Option<BoostedDocuments>,
+        candidates: &mut RoaringBitmap,
+        offset: usize,
+        limit: usize,
+    ) -> Vec<(DocumentId, Vec<ScoreDetails>)> {
+        let pinned: RoaringBitmap =
+            self.pinned.iter().map(|(_, docid)| *docid).collect::<RoaringBitmap>() - &self.hidden;
+        let removed = &self.hidden | &pinned;
+
+        let (boosted_hits, boosted_docids) = match boosted {
+            Some(BoostedDocuments { hits, docids }) => (hits, docids),
+            None => (Vec::new(), RoaringBitmap::new()),
+        };
+        let mut hits: Vec<_> = boosted_hits
+            .into_iter()
+            .chain(hits.into_iter().filter(|(docid, _)| !boosted_docids.contains(*docid)))
+            .filter(|(docid, _)| !removed.contains(*docid))
+            .collect();
+
+        for &(position, docid) in &self.pinned {
+            if pinned.contains(docid) {
+                // the pinned documents don't have any ranking score details
+                hits.insert(position.min(hits.len()), (docid, Vec::new()));
+            }
+        }
+
+        *candidates -= &self.hidden;
+        *candidates |= pinned;
+
+        hits.into_iter().skip(offset).take(limit).collect()
+    }
+}
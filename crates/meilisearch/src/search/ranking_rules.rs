@@ -247,6 +247,9 @@ impl RankingRules {
                         canonical_criteria.push(criterion.clone())
                     }
                 },
+                Criterion::Expression(_) | Criterion::Decay(_) => {
+                    canonical_criteria.push(criterion.clone())
+                }
             }
         }
 
@@ -327,6 +330,9 @@ impl RankingRules {
                         canonical_criteria.push(criterion.clone())
                     }
                 },
+                Criterion::Expression(_) | Criterion::Decay(_) => {
+                    canonical_criteria.push(criterion.clone())
+                }
             }
         }
 
@@ -461,6 +467,9 @@ impl RankingRules {
                         canonical_criteria.push(criterion)
                     }
                 },
+                Criterion::Expression(_) | Criterion::Decay(_) => {
+                    canonical_criteria.push(criterion)
+                }
             }
         }
 
@@ -723,13 +732,16 @@ impl RankingRule {
             | Criterion::Typo
             | Criterion::Proximity
             | Criterion::Attribute
-            | Criterion::Exactness => RankingRuleKind::Relevancy,
+            | Criterion::Exactness
+            | Criterion::Decay(_) => RankingRuleKind::Relevancy,
             Criterion::Asc(s) if s == "_geo" => RankingRuleKind::AscendingGeoSort,
 
             Criterion::Asc(_) => RankingRuleKind::AscendingSort,
             Criterion::Desc(s) if s == "_geo" => RankingRuleKind::DescendingGeoSort,
 
             Criterion::Desc(_) => RankingRuleKind::DescendingSort,
+            // the documents are sorted by the decreasing values of the expression
+            Criterion::Expression(_) => RankingRuleKind::DescendingSort,
             Criterion::Sort => {
                 return either::Right(sort.iter().flatten().enumerate().map(
                     move |(rule_index, asc_desc)| {
@@ -350,6 +350,69 @@ async fn filter_search_authorized_filter_token() {
     compute_authorized_search!(tenant_tokens, "color = yellow", 1);
 }
 
+#[actix_rt::test]
+async fn disjunctive_facets_search_authorized_filter_token() {
+    let mut server = Server::new_auth().await;
+    server.use_admin_key("MASTER_KEY").await;
+    let index = server.index("sales");
+    let documents = json!([
+        { "id": 0, "brand": "apple", "color": "red" },
+        { "id": 1, "brand": "apple", "color": "blue" },
+        { "id": 2, "brand": "samsung", "color": "red" },
+        { "id": 3, "brand": "nokia", "color": "green" },
+    ]);
+    let (task, _status_code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+    let (task, _status_code) =
+        index.update_settings(json!({"filterableAttributes": ["brand", "color"]})).await;
+    index.wait_task(task.uid()).await.succeeded();
+    drop(index);
+
+    let key_content = json!({
+        "indexes": ["sales"],
+        "actions": ["search"],
+        "expiresAt": (OffsetDateTime::now_utc() + Duration::days(1)).format(&Rfc3339).unwrap()
+    });
+    let (response, code) = server.add_api_key(key_content).await;
+    assert_eq!(code, 201);
+    let key = response["key"].as_str().unwrap();
+    let uid = response["uid"].as_str().unwrap();
+
+    let tenant_token = hashmap! {
+        "searchRules" => json!({"sales": {"filter": "brand = apple"}}),
+        "exp" => json!((OffsetDateTime::now_utc() + Duration::hours(1)).unix_timestamp())
+    };
+    let web_token = generate_tenant_token(uid, key, tenant_token);
+    server.use_api_key(&web_token);
+    let index = server.index("sales");
+
+    // the facets of the documents excluded by the tenant token are never counted,
+    // even when the filter of the query is on the same facet
+    for (filter, disjunctive_facets, expected) in [
+        (json!("brand = apple"), json!(["brand"]), json!({ "brand": { "apple": 2 } })),
+        (
+            json!("brand = apple AND color = red"),
+            json!(["brand", "color"]),
+            json!({ "brand": { "apple": 1 }, "color": { "blue": 1, "red": 1 } }),
+        ),
+        (
+            json!(["brand = apple", ["color = red", "color = green"]]),
+            json!(["color"]),
+            json!({ "color": { "blue": 1, "red": 1 } }),
+        ),
+    ] {
+        index
+            .search(
+                json!({ "filter": filter, "disjunctiveFacets": disjunctive_facets }),
+                |response, code| {
+                    assert_eq!(code, 200, "{}", response);
+                    assert_eq!(response["facetDistribution"], expected, "{}", response);
+                },
+            )
+            .await;
+    }
+}
+
 /// Tests that those Tenant Token are incompatible with the REFUSED_KEYS defined above.
 #[actix_rt::test]
 async fn error_search_token_forbidden_parent_key() {
@@ -420,6 +420,141 @@ async fn search_invalid_threshold() {
     "###);
 }
 
+#[actix_rt::test]
+async fn search_invalid_facet_histograms() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = index.search_post(json!({"facetHistograms": ["price"]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.facetHistograms`: expected an object, but found an array: `[\"price\"]`",
+      "code": "invalid_search_facet_histograms",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facet_histograms"
+    }
+    "###);
+
+    let (response, code) = index.search_post(json!({"facetHistograms": {"price": 0}})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.facetHistograms.price`: the interval of a histogram in `facetHistograms` is invalid, expected a positive float.",
+      "code": "invalid_search_facet_histograms",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facet_histograms"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_invalid_facet_percentiles() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) =
+        index.search_post(json!({"facetPercentiles": {"price": [50, 101]}})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.facetPercentiles.price[1]`: a percentile in `facetPercentiles` is invalid, expected a float between `0.0` and `100.0`.",
+      "code": "invalid_search_facet_percentiles",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facet_percentiles"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_invalid_hierarchical_facets() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) =
+        index.search_post(json!({"hierarchicalFacets": [{"attributes": "categories"}]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.hierarchicalFacets[0].attributes`: expected an array, but found a string: `\"categories\"`",
+      "code": "invalid_search_hierarchical_facets",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_hierarchical_facets"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_invalid_disjunctive_facets() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = index.search_post(json!({"disjunctiveFacets": "color"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.disjunctiveFacets`: expected an array, but found a string: `\"color\"`",
+      "code": "invalid_search_disjunctive_facets",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_disjunctive_facets"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_invalid_boost() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = index.search_post(json!({"boost": "brand = apple"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.boost`: expected an array, but found a string: `\"brand = apple\"`",
+      "code": "invalid_search_boost",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_boost"
+    }
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({"boost": [{ "filter": "brand = apple", "weight": -1.0 }]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.boost[0].weight`: the weight of a boost in `boost` is invalid, expected a positive float.",
+      "code": "invalid_search_boost",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_boost"
+    }
+    "###);
+
+    // the other errors of the boosts aren't reported as invalid weights
+    let (response, code) =
+        index.search_post(json!({"boost": [{ "filter": "brand = apple", "wieght": 2.0 }]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown field `wieght` inside `.boost[0]`: did you mean `weight`? expected one of `filter`, `weight`",
+      "code": "invalid_search_boost",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_boost"
+    }
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({"boost": [{ "filter": "brand = apple", "weight": "2" }]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.boost[0].weight`: expected a number, but found a string: `\"2\"`",
+      "code": "invalid_search_boost",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_boost"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn search_non_filterable_facets() {
     let server = Server::new_shared();
@@ -645,7 +780,7 @@ async fn filter_invalid_syntax_object() {
         |response, code| {
             snapshot!(response, @r###"
             {
-              "message": "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `title & Glass`.\n1:14 title & Glass",
+              "message": "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `ENDS WITH`, `NOT ENDS WITH`, `MATCHES`, `NOT MATCHES`, `_geoRadius`, `_geoBoundingBox`, or `_geoPolygon` at `title & Glass`.\n1:14 title & Glass",
               "code": "invalid_search_filter",
               "type": "invalid_request",
               "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
@@ -666,7 +801,7 @@ async fn filter_invalid_syntax_array() {
         |response, code| {
             snapshot!(response, @r###"
             {
-              "message": "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `title & Glass`.\n1:14 title & Glass",
+              "message": "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `ENDS WITH`, `NOT ENDS WITH`, `MATCHES`, `NOT MATCHES`, `_geoRadius`, `_geoBoundingBox`, or `_geoPolygon` at `title & Glass`.\n1:14 title & Glass",
               "code": "invalid_search_filter",
               "type": "invalid_request",
               "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
@@ -1194,6 +1329,27 @@ async fn search_on_unknown_field_plus_joker() {
     .await;
 }
 
+#[actix_rt::test]
+async fn search_scoped_to_non_searchable_field() {
+    test_settings_documents_indexing_swapping_and_search(
+        &DOCUMENTS,
+        &json!({"searchableAttributes": ["id", "title"]}),
+        &json!({"q": "title:captain color:blue"}),
+        |response, code| {
+            snapshot!(code, @"400 Bad Request");
+            snapshot!(response, @r###"
+            {
+              "message": "Index `test`: Attribute `color` is not searchable and cannot be used as `color:` in the query. Available searchable attributes are: `id, title`.",
+              "code": "invalid_search_q",
+              "type": "invalid_request",
+              "link": "https://docs.meilisearch.com/errors#invalid_search_q"
+            }
+            "###);
+        },
+    )
+    .await;
+}
+
 #[actix_rt::test]
 async fn distinct_at_search_time() {
     let server = Server::new().await;
@@ -1335,3 +1335,123 @@ async fn german_search() {
         )
         .await;
 }
+
+#[actix_rt::test]
+async fn stemming_search() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let documents = json!([
+        {"id": 1, "title": "run away"},
+        {"id": 2, "title": "running shoes"},
+        {"id": 3, "title": "runs fast"},
+    ]);
+    let (task, _) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    // without stemming only the exact word is matched
+    index
+        .search(json!({"q": "running", "attributesToRetrieve": ["id"]}), |response, code| {
+            snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 2
+              }
+            ]
+            "###);
+            snapshot!(code, @"200 OK");
+        })
+        .await;
+
+    let (task, _) = index
+        .update_settings(json!({
+            "localizedAttributes": [
+                {"attributePatterns": ["title"], "locales": ["eng"], "stemming": true}
+            ]
+        }))
+        .await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, _) = index.settings().await;
+    snapshot!(json_string!(response["localizedAttributes"]), @r###"
+    [
+      {
+        "attributePatterns": [
+          "title"
+        ],
+        "locales": [
+          "eng"
+        ],
+        "stemming": true
+      }
+    ]
+    "###);
+
+    // the words sharing the stem of the query are matched, after the exact word
+    index
+        .search(json!({"q": "running", "attributesToRetrieve": ["id"]}), |response, code| {
+            snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 2
+              },
+              {
+                "id": 1
+              },
+              {
+                "id": 3
+              }
+            ]
+            "###);
+            snapshot!(code, @"200 OK");
+        })
+        .await;
+}
+
+#[actix_rt::test]
+async fn stemming_search_only_in_stemmed_fields() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, _) = index
+        .update_settings(json!({
+            "searchableAttributes": ["title", "description"],
+            "localizedAttributes": [
+                {"attributePatterns": ["title"], "locales": ["eng"], "stemming": true}
+            ]
+        }))
+        .await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let documents = json!([
+        {"id": 1, "title": "shoes", "description": "runs fast"},
+        {"id": 2, "title": "runs away", "description": "shoes"},
+    ]);
+    let (task, _) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    // the stems are only matched in the stemmed fields
+    index
+        .search(json!({"q": "running", "attributesToRetrieve": ["id"]}), |response, code| {
+            snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 2
+              }
+            ]
+            "###);
+            snapshot!(code, @"200 OK");
+        })
+        .await;
+
+    // the word is still indexed in the description but no longer in a stemmed field
+    let (task, _) = index.delete_document(2).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    index
+        .search(json!({"q": "running", "attributesToRetrieve": ["id"]}), |response, code| {
+            snapshot!(json_string!(response["hits"]), @"[]");
+            snapshot!(code, @"200 OK");
+        })
+        .await;
+}
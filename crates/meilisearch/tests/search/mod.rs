@@ -15,6 +15,7 @@ mod multi;
 mod pagination;
 mod restrict_searchable;
 mod search_queue;
+mod suggest;
 
 use meili_snap::{json_string, snapshot};
 use meilisearch::Opt;
@@ -762,6 +763,390 @@ async fn faceting_max_values_per_facet() {
         .await;
 }
 
+#[actix_rt::test]
+async fn facet_histograms_and_percentiles() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, _status_code) =
+        index.update_settings(json!({ "filterableAttributes": ["number"] })).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let documents: Vec<_> = (0..10).map(|id| json!({ "id": id, "number": id * 10 })).collect();
+    let (task, _status_code) = index.add_documents(json!(documents), None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    // the facet stats can only be requested with a POST
+    let (response, code) = index
+        .search_post(json!({
+            "facetHistograms": { "number": 50 },
+            "facetPercentiles": { "number": [50, 90, 100] }
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["facetHistograms"]), @r###"
+    {
+      "number": [
+        {
+          "from": 0.0,
+          "to": 50.0,
+          "count": 5
+        },
+        {
+          "from": 50.0,
+          "to": 100.0,
+          "count": 5
+        }
+      ]
+    }
+    "###);
+    snapshot!(json_string!(response["facetPercentiles"]), @r###"
+    {
+      "number": {
+        "50": 40.0,
+        "90": 80.0,
+        "100": 90.0
+      }
+    }
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({ "facetHistograms": { "number": 0.01 } })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The interval `0.01` of the `number` facet histogram is too small: the histogram would be made of more than 1000 buckets.",
+      "code": "invalid_search_facet_histograms",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facet_histograms"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn facet_ranges() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, _status_code) =
+        index.update_settings(json!({ "filterableAttributes": ["number"] })).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let documents: Vec<_> = (0..10).map(|id| json!({ "id": id, "number": id * 10 })).collect();
+    let (task, _status_code) = index.add_documents(json!(documents), None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    // the ranges are separated by commas and can only be requested with a POST
+    let (response, code) =
+        index.search_post(json!({ "facets": ["number[..30, 30..60, 60..]"] })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["facetDistribution"]), @r###"
+    {
+      "number": {
+        "..30": 3,
+        "30..60": 3,
+        "60..": 4
+      }
+    }
+    "###);
+    snapshot!(json_string!(response["facetStats"]), @r###"
+    {
+      "number": {
+        "min": 0.0,
+        "max": 90.0
+      }
+    }
+    "###);
+
+    let (response, code) = index.search_post(json!({ "facets": ["number[30]"] })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid facet range `number[30]`: the ranges of a facet must be written `<attribute>[<from>..<to>, ...]`, e.g. `price[0..50, 50..100, 100..]`, with numeric bounds, the `from` bound being lower than the `to` bound.",
+      "code": "invalid_search_facets",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facets"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn hierarchical_facets() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, _status_code) = index
+        .update_settings(json!({ "filterableAttributes": ["categories.lvl0", "categories.lvl1"] }))
+        .await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let documents = json!([
+        { "id": 0, "categories": { "lvl0": "Books", "lvl1": "Books > Science" } },
+        { "id": 1, "categories": { "lvl0": "Books", "lvl1": "Books > Fiction" } },
+        { "id": 2, "categories": { "lvl0": "Books", "lvl1": "Books > Fiction" } },
+        { "id": 3, "categories": { "lvl0": "Movies", "lvl1": "Movies > Action" } },
+    ]);
+    let (task, _status_code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index
+        .search_post(json!({
+            "hierarchicalFacets": [{
+                "attributes": ["categories.lvl0", "categories.lvl1"],
+                "path": "Books"
+            }]
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["facetDistribution"]), @r###"
+    {
+      "categories.lvl0": {
+        "Books": 3,
+        "Movies": 1
+      },
+      "categories.lvl1": {
+        "Books > Fiction": 2,
+        "Books > Science": 1
+      }
+    }
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({
+            "hierarchicalFacets": [{ "attributes": ["categories.lvl0"], "separator": "" }]
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "A hierarchical facet must have at least one attribute and a non-empty separator.",
+      "code": "invalid_search_hierarchical_facets",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_hierarchical_facets"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn disjunctive_facets() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, _status_code) =
+        index.update_settings(json!({ "filterableAttributes": ["color", "size"] })).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let documents = json!([
+        { "id": 0, "color": "red", "size": 1 },
+        { "id": 1, "color": "red", "size": 2 },
+        { "id": 2, "color": "blue", "size": 1 },
+        { "id": 3, "color": "green", "size": 1 },
+        { "id": 4, "color": "green", "size": 2 },
+    ]);
+    let (task, _status_code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index
+        .search_post(json!({
+            "filter": "color = red AND size = 1",
+            "facets": ["size"],
+            "disjunctiveFacets": ["color"]
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 0,
+        "color": "red",
+        "size": 1
+      }
+    ]
+    "###);
+    snapshot!(json_string!(response["facetDistribution"]), @r###"
+    {
+      "color": {
+        "blue": 1,
+        "green": 1,
+        "red": 1
+      },
+      "size": {
+        "1": 1
+      }
+    }
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({
+            "filter": [["color = red", "color = blue"], "size = 1"],
+            "disjunctiveFacets": ["color", "size"]
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["facetDistribution"]), @r###"
+    {
+      "color": {
+        "blue": 1,
+        "green": 1,
+        "red": 1
+      },
+      "size": {
+        "1": 2,
+        "2": 1
+      }
+    }
+    "###);
+    snapshot!(json_string!(response["facetStats"]), @r###"
+    {
+      "size": {
+        "min": 1.0,
+        "max": 2.0
+      }
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn boost() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, _status_code) =
+        index.update_settings(json!({ "filterableAttributes": ["brand", "color"] })).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let documents = json!([
+        { "id": 0, "brand": "samsung", "color": "red" },
+        { "id": 1, "brand": "apple", "color": "blue" },
+        { "id": 2, "brand": "nokia", "color": "blue" },
+        { "id": 3, "brand": "apple", "color": "red" },
+    ]);
+    let (task, _status_code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index
+        .search_post(json!({
+            "boost": [
+                { "filter": "brand = apple", "weight": 2.0 },
+                { "filter": "color = red", "weight": 1.0 }
+            ],
+            "attributesToRetrieve": ["id"],
+            "showRankingScoreDetails": true
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    let ids: Vec<_> = response["hits"].as_array().unwrap().iter().map(|hit| &hit["id"]).collect();
+    // the documents are ranked by the sum of the weights of the boosts they match
+    snapshot!(json_string!(ids), @"[3, 1, 0, 2]");
+    snapshot!(json_string!(response["hits"][0]["_rankingScoreDetails"]), @r###"
+    {
+      "boost": {
+        "order": 0,
+        "weight": 3.0,
+        "maxWeight": 3.0,
+        "score": 1.0
+      }
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn query_rules() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, _status_code) = index
+        .update_settings(json!({
+            "filterableAttributes": ["brand"],
+            "queryRules": [{
+                "id": "phones",
+                "conditions": { "pattern": "Phone" },
+                "pin": [{ "id": "4", "position": 0 }],
+                "hide": ["2"],
+                "boost": ["brand = apple"]
+            }]
+        }))
+        .await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let documents = json!([
+        { "id": 0, "title": "phone", "brand": "samsung" },
+        { "id": 1, "title": "phone", "brand": "apple" },
+        { "id": 2, "title": "phone", "brand": "samsung" },
+        { "id": 3, "title": "phone", "brand": "apple" },
+        { "id": 4, "title": "charger", "brand": "nokia" },
+    ]);
+    let (task, _status_code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) =
+        index.search_post(json!({ "q": "phone", "attributesToRetrieve": ["id"] })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 4
+      },
+      {
+        "id": 1
+      },
+      {
+        "id": 3
+      },
+      {
+        "id": 0
+      }
+    ]
+    "###);
+    snapshot!(response["estimatedTotalHits"], @"4");
+    snapshot!(json_string!(response["appliedQueryRules"]), @r###"
+    [
+      "phones"
+    ]
+    "###);
+
+    let (response, code) = index
+        .search_post(
+            json!({ "q": "phone", "offset": 1, "limit": 2, "attributesToRetrieve": ["id"] }),
+        )
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 1
+      },
+      {
+        "id": 3
+      }
+    ]
+    "###);
+
+    // the pinned documents must match the filter of the search
+    let (response, code) = index
+        .search_post(
+            json!({ "q": "phone", "filter": "brand = apple", "attributesToRetrieve": ["id"] }),
+        )
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 1
+      },
+      {
+        "id": 3
+      }
+    ]
+    "###);
+    snapshot!(response["estimatedTotalHits"], @"2");
+
+    let (response, code) =
+        index.search_post(json!({ "q": "phone case", "attributesToRetrieve": ["id"] })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["appliedQueryRules"]), @"null");
+}
+
 #[actix_rt::test]
 async fn test_score_details() {
     let server = Server::new().await;
@@ -1419,6 +1804,136 @@ async fn simple_search_with_strange_synonyms() {
         .await;
 }
 
+#[actix_rt::test]
+async fn search_with_synonym_rules() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let documents = json!([
+        { "id": 1, "title": "television" },
+        { "id": 2, "title": "tv" },
+        { "id": 3, "title": "telly" },
+    ]);
+    let (task, _status_code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (task, _status_code) = index
+        .update_settings(json!({ "synonyms": {
+            "tv": { "synonyms": ["television"], "rankAsTypo": true },
+            "telly": { "synonyms": ["television"], "kind": "multiWay" },
+        }}))
+        .await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, _code) = index.settings().await;
+    meili_snap::snapshot!(meili_snap::json_string!(response["synonyms"]), @r###"
+    {
+      "telly": {
+        "synonyms": [
+          "television"
+        ],
+        "kind": "multiWay"
+      },
+      "tv": {
+        "synonyms": [
+          "television"
+        ],
+        "kind": "oneWay",
+        "rankAsTypo": true
+      }
+    }
+    "###);
+
+    // the documents matching the synonym ranked as a typo come after the exact matches
+    index
+        .search(json!({"q": "tv"}), |response, code| {
+            meili_snap::snapshot!(code, @"200 OK");
+            meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 2,
+                "title": "tv"
+              },
+              {
+                "id": 1,
+                "title": "television"
+              }
+            ]
+            "###);
+        })
+        .await;
+
+    // the one-way synonyms don't apply the other way around
+    index
+        .search(json!({"q": "television", "attributesToRetrieve": ["id"]}), |response, code| {
+            meili_snap::snapshot!(code, @"200 OK");
+            meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 1
+              },
+              {
+                "id": 3
+              }
+            ]
+            "###);
+        })
+        .await;
+}
+
+#[actix_rt::test]
+async fn search_with_suggested_query() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let documents = json!([
+        { "id": 1, "title": "The Lord of the Rings" },
+        { "id": 2, "title": "Lord of the Flies" },
+        { "id": 3, "title": "The Hobbit" },
+    ]);
+    let (task, _status_code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    index
+        .search(
+            json!({"q": "Lorrd of the rimgs", "attributesToRetrieve": ["id"]}),
+            |response, code| {
+                meili_snap::snapshot!(code, @"200 OK");
+                meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 1
+              },
+              {
+                "id": 2
+              }
+            ]
+            "###);
+                meili_snap::snapshot!(response["suggestedQuery"], @r###""lord of the rings""###);
+            },
+        )
+        .await;
+
+    // the correctly spelled queries don't come with a suggested query
+    index
+        .search(json!({"q": "the lord of the rings"}), |response, code| {
+            meili_snap::snapshot!(code, @"200 OK");
+            meili_snap::snapshot!(response["suggestedQuery"], @"null");
+        })
+        .await;
+
+    // the federated search suggests the corrected query of the first query having one
+    let (response, code) = server
+        .multi_search(json!({"federation": {}, "queries": [
+            {"indexUid": index.uid, "q": "the lord of the rings"},
+            {"indexUid": index.uid, "q": "the hobbitt"},
+            {"indexUid": index.uid, "q": "Lorrd of the rimgs"},
+        ]}))
+        .await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(response["suggestedQuery"], @r###""the hobbit""###);
+}
+
 #[actix_rt::test]
 async fn change_attributes_settings() {
     let server = Server::new().await;
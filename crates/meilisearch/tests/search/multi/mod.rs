@@ -5307,6 +5307,66 @@ async fn federation_facets_same_indexes() {
     "###);
 }
 
+#[actix_rt::test]
+async fn federation_facet_ranges() {
+    let server = Server::new().await;
+
+    for (index_uid, count) in [("numbers", 10), ("numbers-2", 5)] {
+        let index = server.index(index_uid);
+
+        let (value, _) = index.update_settings(json!({ "filterableAttributes": ["number"] })).await;
+        index.wait_task(value.uid()).await.succeeded();
+
+        let documents: Vec<_> =
+            (0..count).map(|id| json!({ "id": id, "number": id * 10 })).collect();
+        let (value, _) = index.add_documents(json!(documents), None).await;
+        index.wait_task(value.uid()).await.succeeded();
+    }
+
+    // the ranges are returned in the order they were requested in
+    let (response, code) = server
+        .multi_search(json!({"federation": {
+          "facetsByIndex": {
+            "numbers": ["number[50.., ..50]"],
+            "numbers-2": ["number[50.., ..50]"]
+          },
+          "mergeFacets": {}
+        }, "queries": [
+          {"indexUid" : "numbers", "q": "" },
+          {"indexUid" : "numbers-2", "q": "" },
+        ]}))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["facetDistribution"]), @r###"
+    {
+      "number": {
+        "50..": 5,
+        "..50": 10
+      }
+    }
+    "###);
+
+    let (response, code) = server
+        .multi_search(json!({"federation": {
+          "facetsByIndex": {
+            "numbers": ["number[50..10]"]
+          },
+          "mergeFacets": {}
+        }, "queries": [
+          {"indexUid" : "numbers", "q": "" },
+        ]}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Inside `.federation.facetsByIndex.numbers`: Invalid facet range `number[50..10]`: the ranges of a facet must be written `<attribute>[<from>..<to>, ...]`, e.g. `price[0..50, 50..100, 100..]`, with numeric bounds, the `from` bound being lower than the `to` bound.\n - Note: index `numbers` used in `.queries[0]`",
+      "code": "invalid_multi_search_facets",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_multi_search_facets"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn federation_inconsistent_merge_order() {
     let server = Server::new().await;
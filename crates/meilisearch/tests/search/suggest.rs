@@ -0,0 +1,121 @@
+use meili_snap::{json_string, snapshot};
+use once_cell::sync::Lazy;
+
+use crate::common::{Server, Value};
+use crate::json;
+
+static DOCUMENTS: Lazy<Value> = Lazy::new(|| {
+    json!([
+        { "id": 1, "title": "Harry Potter and the Chamber of Secrets", "genre": "fantasy" },
+        { "id": 2, "title": "Harry Potter and the Prisoner of Azkaban", "genre": "fantasy" },
+        { "id": 3, "title": "Harry Potts", "genre": "drama" },
+        { "id": 4, "title": "Dirty Harry", "genre": "crime" },
+    ])
+});
+
+#[actix_rt::test]
+async fn suggest() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, _status_code) = index.update_settings_filterable_attributes(json!(["genre"])).await;
+    index.wait_task(task.uid()).await.succeeded();
+    let (task, _status_code) = index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    // the last word is completed and followed by the word most often coming after it
+    let (response, code) = index.suggest("?q=Harry%20po").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response, { ".processingTimeMs" => "[duration]" }), @r###"
+    {
+      "suggestions": [
+        {
+          "query": "harry potter",
+          "count": 2
+        },
+        {
+          "query": "harry potter and",
+          "count": 2
+        },
+        {
+          "query": "harry potts",
+          "count": 1
+        }
+      ],
+      "query": "Harry po",
+      "processingTimeMs": "[duration]"
+    }
+    "###);
+
+    // a complete last word is followed by the next words
+    let (response, code) = index.suggest("?q=harry%20&limit=1").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["suggestions"]), @r###"
+    [
+      {
+        "query": "harry potter",
+        "count": 2
+      }
+    ]
+    "###);
+
+    let (response, code) = index.suggest("?q=harry%20po&filter=genre%20%3D%20drama").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["suggestions"]), @r###"
+    [
+      {
+        "query": "harry potts",
+        "count": 1
+      }
+    ]
+    "###);
+
+    let (response, code) = index.suggest("?q=wizard").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["suggestions"]), @"[]");
+}
+
+#[actix_rt::test]
+async fn suggest_most_frequent_among_all_completions() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    // many rare words come before the most frequent one in lexicographic order
+    let mut documents: Vec<_> =
+        (0..1500).map(|id| json!({ "id": id, "title": format!("pa{id:04}") })).collect();
+    documents.extend((1500..1503).map(|id| json!({ "id": id, "title": "pz" })));
+    let (task, _status_code) = index.add_documents(json!(documents), None).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index.suggest("?q=p&limit=2").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["suggestions"]), @r###"
+    [
+      {
+        "query": "pz",
+        "count": 3
+      },
+      {
+        "query": "pa0000",
+        "count": 1
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn suggest_bad_limit() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = index.suggest("?q=harry&limit=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `limit`: could not parse `doggo` as a positive integer",
+      "code": "invalid_suggest_limit",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_suggest_limit"
+    }
+    "###);
+}
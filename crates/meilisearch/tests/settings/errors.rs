@@ -197,6 +197,21 @@ async fn settings_bad_synonyms() {
       "link": "https://docs.meilisearch.com/errors#invalid_settings_synonyms"
     }
     "###);
+
+    let (response, code) = index
+        .update_settings(
+            json!({ "synonyms": { "tv": { "synonyms": ["television"], "kind": "twoWay" } } }),
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown value `twoWay` at `.synonyms.tv.kind`: expected one of `oneWay`, `multiWay`, `placeholder`, `alternativeCorrection`",
+      "code": "invalid_settings_synonyms",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_synonyms"
+    }
+    "###);
 }
 
 #[actix_rt::test]
@@ -365,3 +380,72 @@ async fn settings_bad_search_cutoff_ms() {
     }
     "###);
 }
+
+#[actix_rt::test]
+async fn settings_bad_query_rules() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) =
+        index.update_settings(json!({ "queryRules": [{ "id": "doggo", "pin": "kefir" }] })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.queryRules[0].pin`: expected an array, but found a string: `\"kefir\"`",
+      "code": "invalid_settings_query_rules",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_query_rules"
+    }
+    "###);
+
+    let (response, code) = index
+        .update_settings(json!({ "queryRules": [
+            { "id": "doggo", "boost": ["brand = apple"] },
+            { "id": "doggo", "hide": ["kefir"] }
+        ] }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "`.queryRules[1].id`: The id `doggo` is already used by another query rule.",
+      "code": "invalid_settings_query_rules",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_query_rules"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn settings_bad_rerankers() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) =
+        index.update_settings(json!({ "rerankers": { "cohere": { "url": 42 } } })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.rerankers.cohere.url`: expected a string, but found a positive integer: `42`",
+      "code": "invalid_settings_rerankers",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_rerankers"
+    }
+    "###);
+
+    let (response, code) = index
+        .update_settings(json!({ "rerankers": { "cohere": {
+            "url": "https://api.cohere.com/v2/rerank",
+            "request": { "documents": ["{{text}}", "{{..}}"] },
+            "response": { "results": [{ "relevance_score": "{{score}}" }, "{{..}}"] }
+        } } }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "`.rerankers.cohere`: in `request`: \"{{query}}\" not found",
+      "code": "invalid_settings_rerankers",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_rerankers"
+    }
+    "###);
+}
@@ -166,6 +166,16 @@ test_setting_routes!(
         update_verb: put,
         default_value: "indexingTime"
     },
+    {
+        setting: query_rules,
+        update_verb: put,
+        default_value: []
+    },
+    {
+        setting: rerankers,
+        update_verb: put,
+        default_value: {}
+    },
     {
         setting: proximity_precision,
         update_verb: put,
@@ -199,7 +209,7 @@ async fn get_settings() {
     let (response, code) = index.settings().await;
     assert_eq!(code, 200);
     let settings = response.as_object().unwrap();
-    assert_eq!(settings.keys().len(), 20);
+    assert_eq!(settings.keys().len(), 22);
     assert_eq!(settings["displayedAttributes"], json!(["*"]));
     assert_eq!(settings["searchableAttributes"], json!(["*"]));
     assert_eq!(settings["filterableAttributes"], json!([]));
@@ -233,6 +243,8 @@ async fn get_settings() {
     assert_eq!(settings["prefixSearch"], json!("indexingTime"));
     assert_eq!(settings["facetSearch"], json!(true));
     assert_eq!(settings["embedders"], json!({}));
+    assert_eq!(settings["queryRules"], json!([]));
+    assert_eq!(settings["rerankers"], json!({}));
 }
 
 #[actix_rt::test]
@@ -336,7 +348,9 @@ async fn secrets_are_hidden_in_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "queryRules": [],
+      "rerankers": {}
     }
     "###);
 
@@ -0,0 +1,251 @@
+use meili_snap::{json_string, snapshot};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+
+use crate::common::Server;
+use crate::json;
+
+#[actix_rt::test]
+async fn retrieve_distance_in_the_settings() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "manual": {
+                  "source": "userProvided",
+                  "dimensions": 3,
+                  "distance": "euclidean",
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    let (settings, code) = index.settings().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(settings["embedders"]["manual"], @r#"{"source":"userProvided","dimensions":3,"distance":"euclidean"}"#);
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "manual": {
+                  "distance": null,
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    let (settings, code) = index.settings().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(settings["embedders"]["manual"], @r#"{"source":"userProvided","dimensions":3}"#);
+}
+
+#[actix_rt::test]
+async fn dot_product_is_not_binary_quantizable() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "manual": {
+                  "source": "userProvided",
+                  "dimensions": 3,
+                  "distance": "dotProduct",
+                  "binaryQuantized": true,
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "`.embedders.manual`: `binaryQuantized` is not available for the `dotProduct` distance",
+      "code": "invalid_settings_embedders",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_embedders"
+    }
+    "###);
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "manual": {
+                  "source": "userProvided",
+                  "dimensions": 3,
+                  "distance": "hamming",
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r#""invalid_settings_embedders""#);
+}
+
+#[actix_rt::test]
+async fn change_distance_after_sending_documents() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "manual": {
+                  "source": "userProvided",
+                  "dimensions": 3,
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    let documents = json!([
+      {"id": 0, "name": "kefir", "_vectors": { "manual": [1, 0, 0] }},
+      {"id": 1, "name": "echo", "_vectors": { "manual": [10, 10, 9] }},
+      {"id": 2, "name": "intel", "_vectors": { "manual": [2, 2, 2] }},
+    ]);
+    let (value, code) = index.add_documents(documents, None).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(value.uid()).await.succeeded();
+
+    // with the cosine distance, only the direction of the embeddings matters
+    let (response, code) = index
+        .search_post(json!({
+            "vector": [1, 1, 1],
+            "hybrid": {"semanticRatio": 1.0, "embedder": "manual"},
+            "attributesToRetrieve": ["id"],
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 2
+      },
+      {
+        "id": 1
+      },
+      {
+        "id": 0
+      }
+    ]
+    "###);
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "manual": {
+                  "distance": "euclidean",
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    // the user-provided embeddings are kept, but are now compared by their euclidean distance
+    let (response, code) = index
+        .search_post(json!({
+            "vector": [1, 1, 1],
+            "hybrid": {"semanticRatio": 1.0, "embedder": "manual"},
+            "attributesToRetrieve": ["id"],
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 0
+      },
+      {
+        "id": 2
+      },
+      {
+        "id": 1
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn change_distance_without_embedding_again() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .respond_with(move |req: &Request| {
+            let text: String = req.body_json().unwrap();
+            let embedding = if text == "kefir" { [1.0, 0.0, 0.0] } else { [2.0, 2.0, 2.0] };
+            ResponseTemplate::new(200).set_body_json(json!({ "data": embedding }))
+        })
+        .mount(&mock_server)
+        .await;
+
+    let server = Server::new().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "rest": {
+                  "source": "rest",
+                  "url": mock_server.uri(),
+                  "dimensions": 3,
+                  "request": "{{text}}",
+                  "response": { "data": "{{embedding}}" },
+                  "documentTemplate": "{{doc.name}}",
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    let documents = json!([
+      {"id": 0, "name": "kefir"},
+      {"id": 1, "name": "intel"},
+    ]);
+    let (value, code) = index.add_documents(documents, None).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(value.uid()).await.succeeded();
+    let embedding_requests = mock_server.received_requests().await.unwrap().len();
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "rest": {
+                  "distance": "euclidean",
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    // the stored embeddings are reused to rebuild the trees with the new distance
+    assert_eq!(mock_server.received_requests().await.unwrap().len(), embedding_requests);
+
+    let (response, code) = index
+        .search_post(json!({
+            "vector": [1, 1, 1],
+            "hybrid": {"semanticRatio": 1.0, "embedder": "rest"},
+            "attributesToRetrieve": ["id"],
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 0
+      },
+      {
+        "id": 1
+      }
+    ]
+    "###);
+}
@@ -1,8 +1,10 @@
 mod binary_quantized;
+mod distance;
 #[cfg(feature = "test-ollama")]
 mod ollama;
 mod openai;
 mod rest;
+mod scalar_quantized;
 mod settings;
 
 use std::str::FromStr;
@@ -2111,3 +2111,705 @@ async fn searchable_reindex() {
     }
     "###);
 }
+
+async fn create_mock_sparse() -> (MockServer, Value) {
+    let mock_server = MockServer::start().await;
+
+    let text_to_embedding: BTreeMap<_, _> = vec![
+        // text -> sparse embedding
+        ("kefir", json!([{ "index": 1, "value": 1.0 }])),
+        ("intel", json!([{ "index": 2, "value": 1.0 }])),
+        ("echo", json!([{ "index": 1, "value": 0.5 }, { "index": 2, "value": 0.5 }])),
+    ]
+    // turn into btree
+    .into_iter()
+    .collect();
+
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .respond_with(move |req: &Request| {
+            let text: String = req.body_json().unwrap();
+            ResponseTemplate::new(200).set_body_json(json!({
+                "data": text_to_embedding
+                    .get(text.as_str())
+                    .cloned()
+                    .unwrap_or(json!([{ "index": 9, "value": 1.0 }]))
+            }))
+        })
+        .mount(&mock_server)
+        .await;
+    let url = mock_server.uri();
+
+    let embedder_settings = json!({
+        "source": "rest",
+        "url": url,
+        "dimensions": 10,
+        "request": "{{text}}",
+        "response": {
+          "data": "{{sparseEmbedding}}"
+        },
+        "documentTemplate": "{{doc.name}}",
+    });
+
+    (mock_server, embedder_settings)
+}
+
+#[actix_rt::test]
+async fn server_returns_sparse() {
+    let (_mock, setting) = create_mock_sparse().await;
+    let server = get_server_vector().await;
+    let index = server.index("doggo");
+
+    let mut quantized_setting = setting.clone();
+    quantized_setting["binaryQuantized"] = json!(true);
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "rest": quantized_setting,
+          },
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "`.embedders.rest`: `binaryQuantized` is not available for embedders returning a `{{sparseEmbedding}}`",
+      "code": "invalid_settings_embedders",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_embedders"
+    }
+    "###);
+
+    let mut euclidean_setting = setting.clone();
+    euclidean_setting["distance"] = json!("euclidean");
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "rest": euclidean_setting,
+          },
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "`.embedders.rest`: the `euclidean` distance is not available for embedders returning a `{{sparseEmbedding}}`, which always use the `cosine` distance",
+      "code": "invalid_settings_embedders",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_embedders"
+    }
+    "###);
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "rest": setting,
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let task = server.wait_task(response.uid()).await;
+    snapshot!(task["status"], @r###""succeeded""###);
+    let documents = json!([
+      {"id": 0, "name": "kefir"},
+      {"id": 1, "name": "echo"},
+      {"id": 2, "name": "intel"},
+    ]);
+    let (value, code) = index.add_documents(documents, None).await;
+    snapshot!(code, @"202 Accepted");
+    let task = index.wait_task(value.uid()).await;
+    snapshot!(task["status"], @r###""succeeded""###);
+
+    let (stats, _code) = index.stats().await;
+    snapshot!(json_string!(stats["numberOfEmbeddedDocuments"]), @"3");
+
+    let (response, code) = index
+        .search_post(json!({
+            "q": "kefir",
+            "limit": 2,
+            "hybrid": {"semanticRatio": 1.0, "embedder": "rest"},
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 0,
+        "name": "kefir"
+      },
+      {
+        "id": 1,
+        "name": "echo"
+      }
+    ]
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({
+            "q": "intel",
+            "limit": 2,
+            "hybrid": {"semanticRatio": 1.0, "embedder": "rest"},
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 2,
+        "name": "intel"
+      },
+      {
+        "id": 1,
+        "name": "echo"
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn user_provided_sparse_vectors() {
+    let (_mock, setting) = create_mock_sparse().await;
+    let server = get_server_vector().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "rest": setting,
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    let documents = json!([
+      {"id": 0, "name": "kefir"},
+      {"id": 1, "name": "echo"},
+      {"id": 2, "name": "intel"},
+      {"id": 3, "name": "bob", "_vectors": {"rest": {"embeddings": [{"index": 3, "value": 1.0}], "regenerate": false}}},
+    ]);
+    let (value, code) = index.add_documents(documents, None).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(value.uid()).await.succeeded();
+
+    // the sparse vectors are given as index/value pairs
+    let (response, code) = index
+        .search_post(json!({
+            "vector": [{"index": 3, "value": 1.0}],
+            "hybrid": {"semanticRatio": 1.0, "embedder": "rest"},
+            "attributesToRetrieve": ["id"],
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 3
+      }
+    ]
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({
+            "vector": [{"index": 1, "value": 1.0}],
+            "limit": 2,
+            "hybrid": {"semanticRatio": 1.0, "embedder": "rest"},
+            "attributesToRetrieve": ["id"],
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 0
+      },
+      {
+        "id": 1
+      }
+    ]
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({
+            "q": "kefir",
+            "vector": [{"index": 1, "value": 1.0}],
+            "limit": 2,
+            "hybrid": {"semanticRatio": 0.5, "embedder": "rest"},
+            "attributesToRetrieve": ["id"],
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 0
+      },
+      {
+        "id": 1
+      }
+    ]
+    "###);
+
+    // the embeddings are returned in the same format
+    let (document, code) = index.get_document(3, Some(json!({"retrieveVectors": true}))).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(document), @r###"
+    {
+      "id": 3,
+      "name": "bob",
+      "_vectors": {
+        "rest": {
+          "embeddings": [
+            [
+              {
+                "index": 3,
+                "value": 1.0
+              }
+            ]
+          ],
+          "regenerate": false
+        }
+      }
+    }
+    "###);
+
+    let (document, code) = index.get_document(1, Some(json!({"retrieveVectors": true}))).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(document), @r###"
+    {
+      "id": 1,
+      "name": "echo",
+      "_vectors": {
+        "rest": {
+          "embeddings": [
+            [
+              {
+                "index": 1,
+                "value": 0.5
+              },
+              {
+                "index": 2,
+                "value": 0.5
+              }
+            ]
+          ],
+          "regenerate": true
+        }
+      }
+    }
+    "###);
+
+    // the kind of the vectors must match the embedder
+    let (response, code) = index
+        .search_post(json!({
+            "vector": [0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+            "hybrid": {"semanticRatio": 1.0, "embedder": "rest"},
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "Invalid vector for embedder `rest`: the embedder returns sparse embeddings, expected an array of `{\"index\": <token>, \"value\": <weight>}` objects.",
+      "code": "invalid_search_vector",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_vector"
+    }
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({
+            "vector": [{"index": 10, "value": 1.0}],
+            "hybrid": {"semanticRatio": 1.0, "embedder": "rest"},
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "Invalid vector for embedder `rest`: the token `10` is outside of the vocabulary of 10 tokens.",
+      "code": "invalid_search_vector",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_vector"
+    }
+    "###);
+
+    let documents = json!([
+      {"id": 4, "name": "max", "_vectors": {"rest": {"embeddings": [0.0, 1.0], "regenerate": false}}},
+    ]);
+    let (value, code) = index.add_documents(documents, None).await;
+    snapshot!(code, @"202 Accepted");
+    let task = index.wait_task(value.uid()).await.failed();
+    snapshot!(task["error"], @r###"
+    {
+      "message": "Index `doggo`: Bad embedder configuration in the document with id: `4`. Could not parse `._vectors.rest`: the embedder returns sparse embeddings, expected an array of `{\"index\": <token>, \"value\": <weight>}` objects, or an array of such arrays",
+      "code": "invalid_vectors_type",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_vectors_type"
+    }
+    "###);
+}
+
+async fn create_mock_chunked() -> (MockServer, Value) {
+    let mock_server = MockServer::start().await;
+
+    let text_to_embedding: BTreeMap<_, _> = vec![
+        // text -> embedding
+        ("kefir", [1.0, 0.0, 0.0]),
+        ("intel", [0.0, 1.0, 0.0]),
+        ("echo", [0.0, 0.5, 1.0]),
+    ]
+    // turn into btree
+    .into_iter()
+    .collect();
+
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .respond_with(move |req: &Request| {
+            let text: String = req.body_json().unwrap();
+            ResponseTemplate::new(200).set_body_json(
+                json!({ "data": text_to_embedding.get(text.as_str()).unwrap_or(&[99., 99., 99.]) }),
+            )
+        })
+        .mount(&mock_server)
+        .await;
+    let url = mock_server.uri();
+
+    let embedder_settings = json!({
+        "source": "rest",
+        "url": url,
+        "dimensions": 3,
+        "request": "{{text}}",
+        "response": {
+          "data": "{{embedding}}"
+        },
+        "documentTemplate": "{{doc.name}}",
+        "documentTemplateMaxBytes": 6,
+        "documentTemplateChunking": { "maxBytes": 6 },
+    });
+
+    (mock_server, embedder_settings)
+}
+
+#[actix_rt::test]
+async fn chunked_documents_rank_by_best_chunk() {
+    let (_mock, setting) = create_mock_chunked().await;
+    let server = get_server_vector().await;
+    let index = server.index("doggo");
+
+    // the documents are embedded when the embedder is added
+    let documents = json!([
+      {"id": 0, "name": "echo kefir"},
+      {"id": 1, "name": "kefir intel"},
+      {"id": 2, "name": "kefir"},
+    ]);
+    let (value, code) = index.add_documents(documents, None).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(value.uid()).await.succeeded();
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "rest": setting,
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    // the chunks are truncated to the `documentTemplateMaxBytes` separately,
+    // so the second chunk of `kefir intel` is embedded
+    let (response, code) = index
+        .search_post(json!({
+            "q": "intel",
+            "hybrid": {"semanticRatio": 1.0, "embedder": "rest"},
+            "attributesToRetrieve": ["id"],
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 1,
+        "_matchedChunk": 1
+      },
+      {
+        "id": 0,
+        "_matchedChunk": 0
+      },
+      {
+        "id": 2,
+        "_matchedChunk": 0
+      }
+    ]
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({
+            "q": "intel",
+            "hybrid": {"semanticRatio": 0.9, "embedder": "rest"},
+            "attributesToRetrieve": ["id"],
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 1,
+        "_matchedChunk": 1
+      },
+      {
+        "id": 0,
+        "_matchedChunk": 0
+      },
+      {
+        "id": 2,
+        "_matchedChunk": 0
+      }
+    ]
+    "###);
+}
+
+async fn create_mock_reranker() -> (MockServer, Value) {
+    let mock_server = MockServer::start().await;
+
+    let name_to_score: BTreeMap<_, _> = vec![
+        // name -> relevance score
+        ("intel the dog", 1.0),
+        ("echo the dog", 0.5),
+    ]
+    // turn into btree
+    .into_iter()
+    .collect();
+
+    Mock::given(method("POST"))
+        .and(path("/rerank"))
+        .respond_with(move |req: &Request| {
+            let body: serde_json::Value = req.body_json().unwrap();
+            let results: Vec<_> = body["documents"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .enumerate()
+                .map(|(index, document)| {
+                    let score = name_to_score.get(document.as_str().unwrap()).unwrap_or(&0.25);
+                    json!({ "index": index, "relevance_score": score })
+                })
+                // the reranker returns the documents in a different order than it received them
+                .rev()
+                .collect();
+            ResponseTemplate::new(200).set_body_json(json!({ "results": results }))
+        })
+        .mount(&mock_server)
+        .await;
+    let url = format!("{}/rerank", mock_server.uri());
+
+    let reranker_settings = json!({
+        "url": url,
+        "request": {
+            "query": "{{query}}",
+            "documents": ["{{text}}", "{{..}}"]
+        },
+        "response": {
+            "results": [{ "index": "{{index}}", "relevance_score": "{{score}}" }, "{{..}}"]
+        },
+        "documentTemplate": "{{doc.name}}",
+    });
+
+    (mock_server, reranker_settings)
+}
+
+#[actix_rt::test]
+async fn search_with_reranker() {
+    let (_mock, embedder) = create_mock().await;
+    let (_reranker_mock, reranker) = create_mock_reranker().await;
+    let mut top_reranker = reranker.clone();
+    top_reranker["topK"] = json!(2);
+    let server = get_server_vector().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "rest": embedder,
+          },
+          "rerankers": {
+              "mock": reranker,
+              "top": top_reranker,
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let task = server.wait_task(response.uid()).await;
+    snapshot!(task["status"], @r###""succeeded""###);
+    let documents = json!([
+      {"id": 0, "name": "kefir the dog"},
+      {"id": 1, "name": "echo the dog"},
+      {"id": 2, "name": "intel the dog"},
+    ]);
+    let (value, code) = index.add_documents(documents, None).await;
+    snapshot!(code, @"202 Accepted");
+    let task = index.wait_task(value.uid()).await;
+    snapshot!(task["status"], @r###""succeeded""###);
+
+    let (response, code) = index
+        .search_post(json!({
+            "q": "dog",
+            "hybrid": {"semanticRatio": 0.0, "embedder": "rest"},
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 0,
+        "name": "kefir the dog"
+      },
+      {
+        "id": 1,
+        "name": "echo the dog"
+      },
+      {
+        "id": 2,
+        "name": "intel the dog"
+      }
+    ]
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({
+            "q": "dog",
+            "hybrid": {"semanticRatio": 0.0, "embedder": "rest", "reranker": "mock"},
+            "showRankingScoreDetails": true,
+            "attributesToRetrieve": ["name"],
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    let reranked: Vec<_> = response["hits"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|hit| json!({ "name": hit["name"], "rerank": hit["_rankingScoreDetails"]["rerank"] }))
+        .collect();
+    snapshot!(json_string!(reranked), @r###"
+    [
+      {
+        "name": "intel the dog",
+        "rerank": {
+          "order": 0,
+          "reranker": "mock",
+          "score": 0.8655292893150024,
+          "rerankerScore": 1.0
+        }
+      },
+      {
+        "name": "echo the dog",
+        "rerank": {
+          "order": 0,
+          "reranker": "mock",
+          "score": 0.8112296656009272,
+          "rerankerScore": 0.5
+        }
+      },
+      {
+        "name": "kefir the dog",
+        "rerank": {
+          "order": 0,
+          "reranker": "mock",
+          "score": 0.7810882504428991,
+          "rerankerScore": 0.25
+        }
+      }
+    ]
+    "###);
+
+    // the reranked documents are ranked the same way whatever the semantic ratio
+    for semantic_ratio in [0.5, 1.0] {
+        let (response, code) = index
+            .search_post(json!({
+                "q": "dog",
+                "hybrid": {"semanticRatio": semantic_ratio, "embedder": "rest", "reranker": "mock"},
+                "showRankingScore": true,
+                "attributesToRetrieve": ["name"],
+            }))
+            .await;
+        snapshot!(code, @"200 OK");
+        snapshot!(json_string!(response["hits"]), @r###"
+        [
+          {
+            "name": "intel the dog",
+            "_rankingScore": 0.8655292893150024
+          },
+          {
+            "name": "echo the dog",
+            "_rankingScore": 0.8112296656009272
+          },
+          {
+            "name": "kefir the dog",
+            "_rankingScore": 0.7810882504428991
+          }
+        ]
+        "###);
+    }
+
+    // the documents after the `topK` reranked ones are scored below them
+    let (response, code) = index
+        .search_post(json!({
+            "q": "dog",
+            "hybrid": {"semanticRatio": 0.0, "embedder": "rest", "reranker": "top"},
+            "showRankingScoreDetails": true,
+            "showRankingScore": true,
+            "attributesToRetrieve": ["name"],
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    let hits = response["hits"].as_array().unwrap();
+    let names: Vec<_> = hits.iter().map(|hit| hit["name"].clone()).collect();
+    snapshot!(json_string!(names), @r###"
+    [
+      "echo the dog",
+      "kefir the dog",
+      "intel the dog"
+    ]
+    "###);
+    let scores: Vec<_> = hits.iter().map(|hit| hit["_rankingScore"].as_f64().unwrap()).collect();
+    assert!(scores[1] > 0.5 && scores[2] <= 0.5, "{scores:?}");
+    snapshot!(json_string!(hits[2]["_rankingScoreDetails"]["rerank"], { ".score" => "[score]" }), @r###"
+    {
+      "order": 0,
+      "reranker": "top",
+      "score": "[score]"
+    }
+    "###);
+
+    // the pages are taken from the reranked results
+    let (response, code) = index
+        .search_post(json!({
+            "q": "dog",
+            "offset": 1,
+            "limit": 1,
+            "hybrid": {"semanticRatio": 0.0, "embedder": "rest", "reranker": "mock"},
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 1,
+        "name": "echo the dog"
+      }
+    ]
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({
+            "q": "dog",
+            "hybrid": {"semanticRatio": 0.0, "embedder": "rest", "reranker": "unknown"},
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "Cannot find reranker with name `unknown`.",
+      "code": "invalid_search_reranker",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_reranker"
+    }
+    "###);
+}
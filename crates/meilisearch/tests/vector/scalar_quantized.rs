@@ -0,0 +1,328 @@
+use meili_snap::{json_string, snapshot};
+
+use crate::common::{GetAllDocumentsOptions, Server};
+use crate::json;
+
+#[actix_rt::test]
+async fn retrieve_scalar_quantize_status_in_the_settings() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "manual": {
+                  "source": "userProvided",
+                  "dimensions": 3,
+                  "scalarQuantized": true,
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    let (settings, code) = index.settings().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(settings["embedders"]["manual"], @r#"{"source":"userProvided","dimensions":3,"scalarQuantized":true}"#);
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "manual": {
+                  "fullPrecisionRescoring": true,
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    let (settings, code) = index.settings().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(settings["embedders"]["manual"], @r#"{"source":"userProvided","dimensions":3,"scalarQuantized":true,"fullPrecisionRescoring":true}"#);
+}
+
+#[actix_rt::test]
+async fn invalid_scalar_quantization_settings() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "manual": {
+                  "source": "userProvided",
+                  "dimensions": 3,
+                  "binaryQuantized": true,
+                  "scalarQuantized": true,
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let task = server.wait_task(response.uid()).await.failed();
+    snapshot!(task["error"], @r###"
+    {
+      "message": "Index `doggo`: `.embedders.manual`: `binaryQuantized` and `scalarQuantized` cannot be enabled together",
+      "code": "invalid_settings_embedders",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_embedders"
+    }
+    "###);
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "manual": {
+                  "source": "userProvided",
+                  "dimensions": 3,
+                  "distance": "dotProduct",
+                  "scalarQuantized": true,
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "`.embedders.manual`: `scalarQuantized` is not available for the `dotProduct` distance",
+      "code": "invalid_settings_embedders",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_embedders"
+    }
+    "###);
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "manual": {
+                  "source": "userProvided",
+                  "dimensions": 3,
+                  "fullPrecisionRescoring": true,
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let task = server.wait_task(response.uid()).await.failed();
+    snapshot!(task["error"], @r###"
+    {
+      "message": "Index `doggo`: `.embedders.manual`: `fullPrecisionRescoring` is only available when `scalarQuantized` is `true`",
+      "code": "invalid_settings_embedders",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_embedders"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn scalar_quantize_before_sending_documents() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "manual": {
+                  "source": "userProvided",
+                  "dimensions": 3,
+                  "scalarQuantized": true,
+                  "fullPrecisionRescoring": true,
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    let documents = json!([
+      {"id": 0, "name": "kefir", "_vectors": { "manual": [1, 0, 0] }},
+      {"id": 1, "name": "echo", "_vectors": { "manual": [10, 10, 9] }},
+      {"id": 2, "name": "intel", "_vectors": { "manual": [2, 2, 2] }},
+    ]);
+    let (value, code) = index.add_documents(documents, None).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(value.uid()).await.succeeded();
+
+    // the original embeddings are kept for the rescoring, and returned instead of the quantized ones
+    let (documents, _code) = index
+        .get_all_documents(GetAllDocumentsOptions {
+            retrieve_vectors: true,
+            fields: Some(vec!["id", "_vectors"]),
+            ..Default::default()
+        })
+        .await;
+    snapshot!(json_string!(documents["results"]), @r###"
+    [
+      {
+        "id": 0,
+        "_vectors": {
+          "manual": {
+            "embeddings": [
+              [
+                1.0,
+                0.0,
+                0.0
+              ]
+            ],
+            "regenerate": false
+          }
+        }
+      },
+      {
+        "id": 1,
+        "_vectors": {
+          "manual": {
+            "embeddings": [
+              [
+                10.0,
+                10.0,
+                9.0
+              ]
+            ],
+            "regenerate": false
+          }
+        }
+      },
+      {
+        "id": 2,
+        "_vectors": {
+          "manual": {
+            "embeddings": [
+              [
+                2.0,
+                2.0,
+                2.0
+              ]
+            ],
+            "regenerate": false
+          }
+        }
+      }
+    ]
+    "###);
+
+    // the binary quantized embeddings are all the same, the scalar quantized ones rank the documents
+    let (response, code) = index
+        .search_post(json!({
+            "vector": [1, 1, 1],
+            "hybrid": {"semanticRatio": 1.0, "embedder": "manual"},
+            "attributesToRetrieve": ["id"],
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 2
+      },
+      {
+        "id": 1
+      },
+      {
+        "id": 0
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn disable_scalar_quantization_restores_the_embeddings() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "manual": {
+                  "source": "userProvided",
+                  "dimensions": 3,
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    let documents = json!([
+      {"id": 0, "name": "kefir", "_vectors": { "manual": [1.5, -2.25, 3.0] }},
+      {"id": 1, "name": "echo", "_vectors": { "manual": [-0.5, 0.75, 2.0] }},
+    ]);
+    let (value, code) = index.add_documents(documents, None).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(value.uid()).await.succeeded();
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "manual": {
+                  "scalarQuantized": true,
+                  "fullPrecisionRescoring": true,
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "manual": {
+                  "scalarQuantized": false,
+                  "fullPrecisionRescoring": null,
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    let (settings, code) = index.settings().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(settings["embedders"]["manual"], @r#"{"source":"userProvided","dimensions":3,"scalarQuantized":false}"#);
+
+    // the original embeddings kept for the full-precision rescoring are restored
+    let (documents, _code) = index
+        .get_all_documents(GetAllDocumentsOptions {
+            retrieve_vectors: true,
+            fields: Some(vec!["id", "_vectors"]),
+            ..Default::default()
+        })
+        .await;
+    snapshot!(json_string!(documents["results"]), @r###"
+    [
+      {
+        "id": 0,
+        "_vectors": {
+          "manual": {
+            "embeddings": [
+              [
+                1.5,
+                -2.25,
+                3.0
+              ]
+            ],
+            "regenerate": false
+          }
+        }
+      },
+      {
+        "id": 1,
+        "_vectors": {
+          "manual": {
+            "embeddings": [
+              [
+                -0.5,
+                0.75,
+                2.0
+              ]
+            ],
+            "regenerate": false
+          }
+        }
+      }
+    ]
+    "###);
+}
@@ -15,7 +15,7 @@ use meilisearch_types::heed::{
 };
 use meilisearch_types::milli::constants::RESERVED_VECTORS_FIELD_NAME;
 use meilisearch_types::milli::documents::{obkv_to_object, DocumentsBatchReader};
-use meilisearch_types::milli::vector::parsed_vectors::{ExplicitVectors, VectorOrArrayOfVectors};
+use meilisearch_types::milli::vector::parsed_vectors::ExplicitVectors;
 use meilisearch_types::milli::{obkv_to_json, BEU32};
 use meilisearch_types::tasks::{Status, Task};
 use meilisearch_types::versioning::{get_version, parse_version};
@@ -563,7 +563,7 @@ fn export_documents(
 
                 if !ignore_vectors {
                     'inject_vectors: {
-                        let embeddings = index.embeddings(&rtxn, id)?;
+                        let embeddings = index.document_vectors(&rtxn, id)?;
 
                         if embeddings.is_empty() {
                             break 'inject_vectors;
@@ -599,9 +599,7 @@ fn export_documents(
                                 .is_some_and(|conf| conf.user_provided.contains(id));
 
                             let embeddings = ExplicitVectors {
-                                embeddings: Some(VectorOrArrayOfVectors::from_array_of_vectors(
-                                    embeddings,
-                                )),
+                                embeddings: Some(embeddings),
                                 regenerate: !user_provided,
                             };
                             vectors
@@ -4,7 +4,9 @@ use std::str::FromStr;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::{AscDesc, Member};
+use crate::{
+    AscDesc, Member, RankingDecay, RankingDecayError, RankingExpression, RankingExpressionError,
+};
 
 #[derive(Error, Debug)]
 pub enum CriterionError {
@@ -22,6 +24,10 @@ pub enum CriterionError {
 `{name}` can only be used for filtering at search time"
     )]
     ReservedNameForFilter { name: String },
+    #[error("`{name}` ranking rule is invalid. {error}")]
+    InvalidExpression { name: String, error: RankingExpressionError },
+    #[error("`{name}` ranking rule is invalid. {error}")]
+    InvalidDecay { name: String, error: RankingDecayError },
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -45,6 +51,11 @@ pub enum Criterion {
     Asc(String),
     /// Sorted by the decreasing value of the field specified.
     Desc(String),
+    /// Sorted by the decreasing value of the expression specified,
+    /// computed from the numeric fields of the documents.
+    Expression(String),
+    /// Sorted by the decreasing closeness of the value of the field specified to an origin.
+    Decay(String),
 }
 
 impl Criterion {
@@ -55,12 +66,30 @@ impl Criterion {
             _otherwise => None,
         }
     }
+
+    /// Returns the parsed expression of this criterion.
+    pub fn expression(&self) -> Option<Result<RankingExpression, RankingExpressionError>> {
+        match self {
+            Criterion::Expression(expression) => Some(expression.parse()),
+            _otherwise => None,
+        }
+    }
+
+    /// Returns the parsed decay of this criterion.
+    pub fn decay(&self) -> Option<Result<RankingDecay, RankingDecayError>> {
+        match self {
+            Criterion::Decay(decay) => Some(decay.parse()),
+            _otherwise => None,
+        }
+    }
 }
 
 impl FromStr for Criterion {
     type Err = CriterionError;
 
     fn from_str(text: &str) -> Result<Criterion, Self::Err> {
+        // a rule ending with an order sorts by a field, even when its name starts like an expression
+        let is_sort_rule = text.ends_with(":asc") || text.ends_with(":desc");
         match text {
             "words" => Ok(Criterion::Words),
             "typo" => Ok(Criterion::Typo),
@@ -68,6 +97,24 @@ impl FromStr for Criterion {
             "attribute" => Ok(Criterion::Attribute),
             "sort" => Ok(Criterion::Sort),
             "exactness" => Ok(Criterion::Exactness),
+            text if text.starts_with("expr:") && !is_sort_rule => {
+                let expression = &text["expr:".len()..];
+                match expression.parse::<RankingExpression>() {
+                    Ok(_) => Ok(Criterion::Expression(expression.to_string())),
+                    Err(error) => {
+                        Err(CriterionError::InvalidExpression { name: text.to_string(), error })
+                    }
+                }
+            }
+            text if text.starts_with("decay:") && !is_sort_rule => {
+                let decay = &text["decay:".len()..];
+                match decay.parse::<RankingDecay>() {
+                    Ok(_) => Ok(Criterion::Decay(decay.to_string())),
+                    Err(error) => {
+                        Err(CriterionError::InvalidDecay { name: text.to_string(), error })
+                    }
+                }
+            }
             text => match AscDesc::from_str(text)? {
                 AscDesc::Asc(Member::Field(field)) => Ok(Criterion::Asc(field)),
                 AscDesc::Desc(Member::Field(field)) => Ok(Criterion::Desc(field)),
@@ -103,6 +150,8 @@ impl fmt::Display for Criterion {
             Exactness => f.write_str("exactness"),
             Asc(attr) => write!(f, "{}:asc", attr),
             Desc(attr) => write!(f, "{}:desc", attr),
+            Expression(expression) => write!(f, "expr:{}", expression),
+            Decay(decay) => write!(f, "decay:{}", decay),
         }
     }
 }
@@ -130,6 +179,18 @@ mod tests {
             ("truc:machin:desc", Criterion::Desc(S("truc:machin"))),
             ("hello-world!:desc", Criterion::Desc(S("hello-world!"))),
             ("it's spacy over there:asc", Criterion::Asc(S("it's spacy over there"))),
+            ("expr:price * 2", Criterion::Expression(S("price * 2"))),
+            (
+                "expr: log(popularity + 1) * 0.3 + _rankingScore",
+                Criterion::Expression(S(" log(popularity + 1) * 0.3 + _rankingScore")),
+            ),
+            ("decay:gauss(price, 10, 5)", Criterion::Decay(S("gauss(price, 10, 5)"))),
+            (
+                "decay:exp(release_date, now, 30d)",
+                Criterion::Decay(S("exp(release_date, now, 30d)")),
+            ),
+            ("expr:weight:asc", Criterion::Asc(S("expr:weight"))),
+            ("decay:distance:desc", Criterion::Desc(S("decay:distance"))),
         ];
 
         for (input, expected) in valid_criteria {
@@ -165,6 +226,26 @@ mod tests {
                 "_geoBoundingBox([42, 75], [75, 59]):asc",
                 ReservedNameForFilter { name: S("_geoBoundingBox") },
             ),
+            ("_geoPolygon:asc", ReservedNameForFilter { name: S("_geoPolygon") }),
+            (
+                "_geoPolygon([42, 75], [75, 59], [12, 13]):asc",
+                ReservedNameForFilter { name: S("_geoPolygon") },
+            ),
+            ("expr:", InvalidExpression { name: S("expr:"), error: RankingExpressionError::Empty }),
+            (
+                "expr: log(price",
+                InvalidExpression {
+                    name: S("expr: log(price"),
+                    error: RankingExpressionError::UnexpectedEnd,
+                },
+            ),
+            (
+                "decay:gauss(price)",
+                InvalidDecay {
+                    name: S("decay:gauss(price)"),
+                    error: RankingDecayError::InvalidSyntax,
+                },
+            ),
         ];
 
         for (input, expected) in invalid_criteria {
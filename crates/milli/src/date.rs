@@ -0,0 +1,107 @@
+//! The dates of the fields declared as dates in the filterable attributes rules.
+//!
+//! The dates are stored as unix timestamps, in seconds, in the numeric facet databases
+//! and can be compared with RFC 3339 dates, full dates like `2024-01-01`, or dates
+//! relative to the time of the search like `NOW-7d`.
+
+use time::format_description::well_known::Rfc3339;
+use time::macros::format_description;
+use time::{Date, OffsetDateTime};
+
+/// Parses an RFC 3339 date, e.g. `2024-01-01T12:00:00Z`, or a full date, e.g. `2024-01-01`, into
+/// a unix timestamp in seconds. The full dates are considered to be at midnight UTC.
+///
+/// The parsing is case-insensitive, as the dates are lowercased in the string facet databases.
+pub fn parse_date(date: &str) -> Option<f64> {
+    let date = date.trim().to_uppercase();
+    let date = match OffsetDateTime::parse(&date, &Rfc3339) {
+        Ok(date) => date,
+        Err(_) => Date::parse(&date, format_description!("[year]-[month]-[day]"))
+            .ok()?
+            .midnight()
+            .assume_utc(),
+    };
+    Some(date.unix_timestamp_nanos() as f64 / 1_000_000_000.0)
+}
+
+/// Parses a duration like `30s`, `15m`, `12h`, `7d` or `2w` into a number of seconds.
+pub fn parse_duration(duration: &str) -> Option<f64> {
+    let unit = duration.chars().last()?;
+    let multiplier = match unit {
+        's' => 1.0,
+        'm' => 60.0,
+        'h' => 60.0 * 60.0,
+        'd' => 24.0 * 60.0 * 60.0,
+        'w' => 7.0 * 24.0 * 60.0 * 60.0,
+        _ => return None,
+    };
+    let number: f64 = duration[..duration.len() - unit.len_utf8()].parse().ok()?;
+    number.is_finite().then_some(number * multiplier)
+}
+
+/// Parses the date of a filter: either a date accepted by [`parse_date`] or the current date,
+/// written `NOW`, possibly shifted by a duration like in `NOW-7d` or `NOW+12h`.
+pub fn parse_filter_date(date: &str, now: OffsetDateTime) -> Option<f64> {
+    let date = date.trim();
+    let shift = match date.get(..3) {
+        Some(now) if now.eq_ignore_ascii_case("now") => date[3..].trim_start(),
+        _ => return parse_date(date),
+    };
+
+    let now = now.unix_timestamp_nanos() as f64 / 1_000_000_000.0;
+    if shift.is_empty() {
+        Some(now)
+    } else if let Some(duration) = shift.strip_prefix('+') {
+        Some(now + parse_duration(duration.trim_start())?)
+    } else if let Some(duration) = shift.strip_prefix('-') {
+        Some(now - parse_duration(duration.trim_start())?)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::*;
+
+    #[test]
+    fn dates() {
+        assert_eq!(parse_date("2024-01-01T00:00:00Z"), Some(1704067200.0));
+        assert_eq!(parse_date("2024-01-01t00:00:00z"), Some(1704067200.0));
+        assert_eq!(parse_date("2024-01-01T01:00:00+01:00"), Some(1704067200.0));
+        assert_eq!(parse_date("2024-01-01T00:00:00.5Z"), Some(1704067200.5));
+        assert_eq!(parse_date("2024-01-01"), Some(1704067200.0));
+        assert_eq!(parse_date("2024-13-01"), None);
+        assert_eq!(parse_date("yesterday"), None);
+        assert_eq!(parse_date("1704067200"), None);
+    }
+
+    #[test]
+    fn durations() {
+        assert_eq!(parse_duration("30s"), Some(30.0));
+        assert_eq!(parse_duration("1.5m"), Some(90.0));
+        assert_eq!(parse_duration("2h"), Some(7200.0));
+        assert_eq!(parse_duration("7d"), Some(604800.0));
+        assert_eq!(parse_duration("1w"), Some(604800.0));
+        assert_eq!(parse_duration("7"), None);
+        assert_eq!(parse_duration("d"), None);
+        assert_eq!(parse_duration(""), None);
+        assert_eq!(parse_duration("7y"), None);
+    }
+
+    #[test]
+    fn filter_dates() {
+        let now = datetime!(2024-01-08 00:00 UTC);
+        assert_eq!(parse_filter_date("NOW", now), Some(1704672000.0));
+        assert_eq!(parse_filter_date("now", now), Some(1704672000.0));
+        assert_eq!(parse_filter_date("NOW-7d", now), Some(1704067200.0));
+        assert_eq!(parse_filter_date("NOW - 7d", now), Some(1704067200.0));
+        assert_eq!(parse_filter_date("NOW+1h", now), Some(1704675600.0));
+        assert_eq!(parse_filter_date("2024-01-01", now), Some(1704067200.0));
+        assert_eq!(parse_filter_date("NOW*2", now), None);
+        assert_eq!(parse_filter_date("NOW-7", now), None);
+        assert_eq!(parse_filter_date("nowhere", now), None);
+    }
+}
@@ -15,11 +15,18 @@ use crate::constants::RESERVED_GEO_FIELD_NAME;
 use crate::documents::{self, DocumentsBatchCursorError};
 use crate::thread_pool_no_abort::PanicCatched;
 use crate::vector::settings::EmbeddingSettings;
-use crate::{CriterionError, DocumentId, FieldId, Object, SortError};
+use crate::{CriterionError, DocumentId, FieldId, Object, QueryRuleError, SortError};
 
 pub fn is_reserved_keyword(keyword: &str) -> bool {
-    [RESERVED_GEO_FIELD_NAME, "_geoDistance", "_geoPoint", "_geoRadius", "_geoBoundingBox"]
-        .contains(&keyword)
+    [
+        RESERVED_GEO_FIELD_NAME,
+        "_geoDistance",
+        "_geoPoint",
+        "_geoRadius",
+        "_geoBoundingBox",
+        "_geoPolygon",
+    ]
+    .contains(&keyword)
 }
 
 #[derive(Error, Debug)]
@@ -150,10 +157,22 @@ and can not be more than 511 bytes.", .document_id.to_string()
         valid_patterns: BTreeSet<String>,
         matching_rule_indices: HashMap<String, usize>,
     },
+    #[error("The interval `{interval}` of the `{field}` facet histogram is too small: the histogram would be made of more than {max_buckets} buckets.")]
+    InvalidFacetHistogramInterval { field: String, interval: f64, max_buckets: usize },
+    #[error("Invalid facet range `{facet}`: the ranges of a facet must be written `<attribute>[<from>..<to>, ...]`, e.g. `price[0..50, 50..100, 100..]`, with numeric bounds, the `from` bound being lower than the `to` bound.")]
+    InvalidFacetRange { facet: String },
+    #[error("A hierarchical facet must have at least one attribute and a non-empty separator.")]
+    InvalidFacetHierarchy,
+    #[error(transparent)]
+    InvalidQueryRules(#[from] QueryRuleError),
+    #[error(transparent)]
+    InvalidRerankers(#[from] crate::vector::rerank::RerankerError),
     #[error(transparent)]
     InvalidGeoField(#[from] Box<GeoError>),
     #[error("Invalid vector dimensions: expected: `{}`, found: `{}`.", .expected, .found)]
     InvalidVectorDimensions { expected: usize, found: usize },
+    #[error("Invalid vector for embedder `{embedder_name}`: {message}.")]
+    InvalidSearchVector { embedder_name: String, message: String },
     #[error("Invalid vector dimensions in document with id `{document_id}` in `._vectors.{embedder_name}`.\n  - note: embedding #{embedding_index} has dimensions {found}\n  - note: embedder `{embedder_name}` requires {expected}")]
     InvalidIndexingVectorDimensions {
         embedder_name: String,
@@ -174,6 +193,7 @@ and can not be more than 511 bytes.", .document_id.to_string()
         allowed_operators.join(", "),
         if operator == "=" || operator == "!=" || operator == "IN" {"equality"}
         else if operator == "<" || operator == ">" || operator == "<=" || operator == ">=" || operator == "TO" {"comparison"}
+        else if operator == "ENDS WITH" || operator == "MATCHES" {"patternMatching"}
         else {"the appropriate filter operators"}
     )]
     FilterOperatorNotAllowed {
@@ -251,6 +271,19 @@ and can not be more than 511 bytes.", .document_id.to_string()
         valid_fields: BTreeSet<String>,
         hidden_fields: bool,
     },
+    #[error("Attribute `{}` is not searchable and cannot be used as `{}:` in the query. Available searchable attributes are: `{}{}`.",
+        .field,
+        .field,
+        .valid_fields.iter().map(AsRef::as_ref).collect::<Vec<&str>>().join(", "),
+        .hidden_fields.then_some(", <..hidden-attributes>").unwrap_or(""),
+    )]
+    InvalidSearchableAttributeInQuery {
+        field: String,
+        valid_fields: BTreeSet<String>,
+        hidden_fields: bool,
+    },
+    #[error("The slop `{slop}` of a phrase or `NEAR` operator in the query is invalid. It must be an integer between 0 and 255.")]
+    InvalidQuerySlop { slop: String },
     #[error("An LMDB environment is already opened")]
     EnvAlreadyOpened,
     #[error("You must specify where `sort` is listed in the rankingRules setting to use the sort parameter at search time.")]
@@ -293,6 +326,8 @@ and can not be more than 511 bytes.", .document_id.to_string()
     InvalidSearchEmbedder(String),
     #[error("Cannot find embedder with name `{0}`.")]
     InvalidSimilarEmbedder(String),
+    #[error("Cannot find reranker with name `{0}`.")]
+    InvalidSearchReranker(String),
     #[error("Too many vectors for document with id {0}: found {1}, but limited to 256.")]
     TooManyVectors(String, usize),
     #[error("`.embedders.{embedder_name}`: Field `{field}` unavailable for source `{source_}`{for_context}.{available_sources}{available_fields}{available_contexts}",
@@ -377,6 +412,12 @@ and can not be more than 511 bytes.", .document_id.to_string()
     InvalidDisableBinaryQuantization { embedder_name: String },
     #[error("`.embedders.{embedder_name}.documentTemplateMaxBytes`: `documentTemplateMaxBytes` cannot be zero")]
     InvalidSettingsDocumentTemplateMaxBytes { embedder_name: String },
+    #[error("`.embedders.{embedder_name}.documentTemplateChunking`: `maxBytes` must be greater than zero and than `overlapBytes`, got `maxBytes: {max_bytes}` and `overlapBytes: {overlap_bytes}`")]
+    InvalidSettingsDocumentTemplateChunking {
+        embedder_name: String,
+        max_bytes: usize,
+        overlap_bytes: usize,
+    },
     #[error("`.embedders.{embedder_name}.url`: could not parse `{url}`: {inner_error}")]
     InvalidUrl { embedder_name: String, inner_error: url::ParseError, url: String },
     #[error("Document editions cannot modify a document's primary key")]
@@ -442,6 +483,19 @@ pub enum GeoError {
     BadLatitude { document_id: Value, value: Value },
     #[error("Could not parse longitude in the document with the id: `{document_id}`. Was expecting a finite number but instead got `{value}`.")]
     BadLongitude { document_id: Value, value: Value },
+    #[error("The `_geojson` field in the document with the id: `{document_id}` is not an object. Was expecting a GeoJSON geometry object with the `type` and `coordinates` fields but instead got `{value}`.")]
+    GeoJsonNotAnObject { document_id: Value, value: Value },
+    #[error("The `_geojson` field in the document with the id: `{document_id}` has an unsupported type. Was expecting one of `Point`, `MultiPoint`, `Polygon` or `MultiPolygon` but instead got `{value}`.")]
+    GeoJsonUnsupportedType { document_id: Value, value: Value },
+    #[error("Could not parse the coordinates of the `_geojson` {kind} in the document with the id: `{document_id}`. Was expecting {expected} but instead got `{value}`.")]
+    GeoJsonBadCoordinates {
+        document_id: Value,
+        kind: &'static str,
+        expected: &'static str,
+        value: Value,
+    },
+    #[error("The `_geojson` field in the document with the id: `{document_id}` contains an invalid position. Was expecting a `[longitude, latitude]` array with a longitude between -180 and 180 and a latitude between -90 and 90 but instead got `{value}`.")]
+    GeoJsonBadPosition { document_id: Value, value: Value },
 }
 
 #[allow(dead_code)]
@@ -4,7 +4,7 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
-use crate::constants::RESERVED_VECTORS_FIELD_NAME;
+use crate::constants::{RESERVED_GEOJSON_FIELD_NAME, RESERVED_VECTORS_FIELD_NAME};
 use crate::{FieldId, FieldsIdsMap, Weight};
 
 #[derive(Debug, Default, Serialize, Deserialize)]
@@ -27,7 +27,10 @@ impl FieldidsWeightsMap {
         FieldidsWeightsMap {
             map: fid_map
                 .iter()
-                .filter(|(_fid, name)| !crate::is_faceted_by(name, RESERVED_VECTORS_FIELD_NAME))
+                .filter(|(_fid, name)| {
+                    !crate::is_faceted_by(name, RESERVED_VECTORS_FIELD_NAME)
+                        && !crate::is_faceted_by(name, RESERVED_GEOJSON_FIELD_NAME)
+                })
                 .map(|(fid, _name)| (fid, 0))
                 .collect(),
         }
@@ -6,7 +6,9 @@ use heed::RoTxn;
 
 use super::FieldsIdsMap;
 use crate::attribute_patterns::{match_field_legacy, PatternMatch};
-use crate::constants::{RESERVED_GEO_FIELD_NAME, RESERVED_VECTORS_FIELD_NAME};
+use crate::constants::{
+    RESERVED_GEOJSON_FIELD_NAME, RESERVED_GEO_FIELD_NAME, RESERVED_VECTORS_FIELD_NAME,
+};
 use crate::{
     is_faceted_by, FieldId, FilterableAttributesFeatures, FilterableAttributesRule, Index,
     LocalizedAttributesRule, Result, Weight,
@@ -261,8 +263,10 @@ impl MetadataBuilder {
     }
 
     pub fn metadata_for_field(&self, field: &str) -> Metadata {
-        if is_faceted_by(field, RESERVED_VECTORS_FIELD_NAME) {
-            // Vectors fields are not searchable, filterable, distinct or asc_desc
+        if is_faceted_by(field, RESERVED_VECTORS_FIELD_NAME)
+            || is_faceted_by(field, RESERVED_GEOJSON_FIELD_NAME)
+        {
+            // Vectors and GeoJSON fields are not searchable, filterable, distinct or asc_desc
             return Metadata {
                 searchable: None,
                 sortable: false,
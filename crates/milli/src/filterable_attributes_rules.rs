@@ -77,6 +77,9 @@ pub struct FilterableAttributesFeatures {
     #[serde(default)]
     #[deserr(default)]
     filter: FilterFeatures,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    #[deserr(default)]
+    date: bool,
 }
 
 impl FilterableAttributesFeatures {
@@ -85,12 +88,12 @@ impl FilterableAttributesFeatures {
     /// This is the default behavior for `FilterableAttributesRule::Field`.
     /// This will set the facet search to true and activate all the filter operators.
     pub fn legacy_default() -> Self {
-        Self { facet_search: true, filter: FilterFeatures::legacy_default() }
+        Self { facet_search: true, filter: FilterFeatures::legacy_default(), date: false }
     }
 
     /// Create a new `FilterableAttributesFeatures` with no features.
     pub fn no_features() -> Self {
-        Self { facet_search: false, filter: FilterFeatures::no_features() }
+        Self { facet_search: false, filter: FilterFeatures::no_features(), date: false }
     }
 
     pub fn is_filterable(&self) -> bool {
@@ -122,11 +125,21 @@ impl FilterableAttributesFeatures {
         self.filter.is_filterable_comparison()
     }
 
+    /// Check if `ENDS WITH` or `MATCHES` are allowed
+    pub fn is_filterable_pattern_matching(&self) -> bool {
+        self.filter.is_filterable_pattern_matching()
+    }
+
     /// Check if the facet search is allowed
     pub fn is_facet_searchable(&self) -> bool {
         self.facet_search
     }
 
+    /// Check if the string values must be parsed as dates
+    pub fn is_date(&self) -> bool {
+        self.date
+    }
+
     pub fn allowed_filter_operators(&self) -> Vec<String> {
         self.filter.allowed_operators()
     }
@@ -157,6 +170,9 @@ pub struct FilterFeatures {
     #[serde(default)]
     #[deserr(default)]
     comparison: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    #[deserr(default)]
+    pattern_matching: bool,
 }
 
 fn default_true() -> bool {
@@ -177,6 +193,9 @@ impl FilterFeatures {
         if self.is_filterable_comparison() {
             operators.extend_from_slice(&["<", ">", "<=", ">=", "TO"]);
         }
+        if self.is_filterable_pattern_matching() {
+            operators.extend_from_slice(&["ENDS WITH", "MATCHES"]);
+        }
         if self.is_filterable_empty() {
             operators.push("IS EMPTY");
         }
@@ -191,7 +210,7 @@ impl FilterFeatures {
     }
 
     pub fn is_filterable(&self) -> bool {
-        self.equality || self.comparison
+        self.equality || self.comparison || self.pattern_matching
     }
 
     pub fn is_filterable_equality(&self) -> bool {
@@ -203,6 +222,11 @@ impl FilterFeatures {
         self.comparison
     }
 
+    /// Check if `ENDS WITH` or `MATCHES` are allowed
+    pub fn is_filterable_pattern_matching(&self) -> bool {
+        self.pattern_matching
+    }
+
     /// Check if `IS EMPTY` is allowed
     pub fn is_filterable_empty(&self) -> bool {
         self.is_filterable()
@@ -223,18 +247,18 @@ impl FilterFeatures {
     /// This is the default behavior for `FilterableAttributesRule::Field`.
     /// This will set the equality and comparison to true.
     pub fn legacy_default() -> Self {
-        Self { equality: true, comparison: true }
+        Self { equality: true, comparison: true, pattern_matching: false }
     }
 
     /// Create a new `FilterFeatures` with no features.
     pub fn no_features() -> Self {
-        Self { equality: false, comparison: false }
+        Self { equality: false, comparison: false, pattern_matching: false }
     }
 }
 
 impl Default for FilterFeatures {
     fn default() -> Self {
-        Self { equality: true, comparison: false }
+        Self { equality: true, comparison: false, pattern_matching: false }
     }
 }
 
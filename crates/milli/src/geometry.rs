@@ -0,0 +1,244 @@
+//! The geometric helpers used to index and filter the geographical data of the documents.
+//!
+//! The edges of the polygons are considered to be straight lines in the latitude/longitude plane.
+
+use std::f64::consts::{FRAC_PI_2, PI};
+
+use rstar::{RTreeObject, AABB};
+use serde::{Deserialize, Serialize};
+
+use crate::{distance_between_two_points, lat_lng_to_xyz, DocumentId};
+
+/// The smallest radius of the earth in meters. Using it to convert distances to angles
+/// gives envelopes that are slightly too big but never too small.
+const EARTH_POLAR_RADIUS: f64 = 6_356_752.0;
+
+/// A polygon of a `_geojson` geometry, stored in its own rtree along the id of its document.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GeoJsonPolygon {
+    pub docid: DocumentId,
+    /// The exterior ring of the polygon followed by its holes, every point is a `[lat, lng]`.
+    pub rings: Vec<Vec<[f64; 2]>>,
+    envelope: AABB<[f64; 3]>,
+}
+
+impl GeoJsonPolygon {
+    pub fn new(docid: DocumentId, rings: Vec<Vec<[f64; 2]>>) -> Self {
+        let envelope = lat_lng_box_envelope(&rings[0]);
+        GeoJsonPolygon { docid, rings, envelope }
+    }
+}
+
+impl RTreeObject for GeoJsonPolygon {
+    type Envelope = AABB<[f64; 3]>;
+
+    fn envelope(&self) -> Self::Envelope {
+        self.envelope
+    }
+}
+
+/// The geographical data of a document, extracted from its `_geo` and `_geojson` fields.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DocumentGeometry {
+    /// The `[lat, lng]` points stored in the geo rtree, the vertices of the polygons included.
+    pub points: Vec<[f64; 2]>,
+    /// The polygons stored in the geojson rtree, made of an exterior ring followed by its holes.
+    pub polygons: Vec<Vec<Vec<[f64; 2]>>>,
+}
+
+impl DocumentGeometry {
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty() && self.polygons.is_empty()
+    }
+
+    /// Returns the point of the geometry closest to the `[lat, lng]` point,
+    /// the point itself when it is inside one of the polygons.
+    pub fn closest_point(&self, point: &[f64; 2]) -> Option<[f64; 2]> {
+        let polygons =
+            self.polygons.iter().filter_map(|rings| closest_point_of_polygon(point, rings));
+        self.points.iter().copied().chain(polygons).min_by(|a, b| {
+            distance_between_two_points(point, a).total_cmp(&distance_between_two_points(point, b))
+        })
+    }
+
+    /// Adds the polygon along with the vertices of its exterior ring.
+    pub fn push_polygon(&mut self, rings: Vec<Vec<[f64; 2]>>) {
+        // the rings are closed, their last point being the same as their first one
+        let exterior = &rings[0];
+        self.points.extend_from_slice(&exterior[..exterior.len() - 1]);
+        self.polygons.push(rings);
+    }
+}
+
+/// Returns an iterator over the edges of the ring, the last point being linked to the first one.
+fn edges(ring: &[[f64; 2]]) -> impl Iterator<Item = (&[f64; 2], &[f64; 2])> {
+    ring.iter().zip(ring.iter().cycle().skip(1))
+}
+
+/// Returns `true` if the `[lat, lng]` point is inside the ring, using the even-odd rule.
+pub(crate) fn point_in_ring(&[lat, lng]: &[f64; 2], ring: &[[f64; 2]]) -> bool {
+    let mut inside = false;
+    for ([lat_a, lng_a], [lat_b, lng_b]) in edges(ring) {
+        if (*lat_a > lat) != (*lat_b > lat)
+            && lng < (lng_b - lng_a) * (lat - lat_a) / (lat_b - lat_a) + lng_a
+        {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+/// Returns `true` if the `[lat, lng]` point is inside the polygon, i.e. inside
+/// its exterior ring but not inside one of its holes.
+pub(crate) fn point_in_polygon(point: &[f64; 2], rings: &[Vec<[f64; 2]>]) -> bool {
+    rings.iter().filter(|ring| point_in_ring(point, ring)).count() % 2 == 1
+}
+
+/// Returns `true` if the two segments share at least one point.
+fn segments_intersect(a: &[f64; 2], b: &[f64; 2], c: &[f64; 2], d: &[f64; 2]) -> bool {
+    let orientation = |p: &[f64; 2], q: &[f64; 2], r: &[f64; 2]| {
+        let cross = (q[0] - p[0]) * (r[1] - p[1]) - (q[1] - p[1]) * (r[0] - p[0]);
+        if cross > 0.0 {
+            1
+        } else if cross < 0.0 {
+            -1
+        } else {
+            0
+        }
+    };
+    // when the three points are aligned we only have to check that `r` is between `p` and `q`
+    let on_segment = |p: &[f64; 2], q: &[f64; 2], r: &[f64; 2]| {
+        r[0] >= p[0].min(q[0])
+            && r[0] <= p[0].max(q[0])
+            && r[1] >= p[1].min(q[1])
+            && r[1] <= p[1].max(q[1])
+    };
+
+    let (o1, o2) = (orientation(a, b, c), orientation(a, b, d));
+    let (o3, o4) = (orientation(c, d, a), orientation(c, d, b));
+
+    (o1 != o2 && o3 != o4)
+        || (o1 == 0 && on_segment(a, b, c))
+        || (o2 == 0 && on_segment(a, b, d))
+        || (o3 == 0 && on_segment(c, d, a))
+        || (o4 == 0 && on_segment(c, d, b))
+}
+
+/// Returns `true` if the two polygons share at least one point.
+pub(crate) fn polygons_intersect(a: &[Vec<[f64; 2]>], b: &[Vec<[f64; 2]>]) -> bool {
+    let edges_cross = a.iter().flat_map(|ring| edges(ring)).any(|(a1, a2)| {
+        b.iter().flat_map(|ring| edges(ring)).any(|(b1, b2)| segments_intersect(a1, a2, b1, b2))
+    });
+
+    // If none of the edges cross, the polygons are either disjoint
+    // or one of them is entirely contained in the other one.
+    edges_cross
+        || a.first().and_then(|ring| ring.first()).is_some_and(|point| point_in_polygon(point, b))
+        || b.first().and_then(|ring| ring.first()).is_some_and(|point| point_in_polygon(point, a))
+}
+
+/// Returns `true` if at least one point of the polygon is at most `radius` meters away from the center.
+pub(crate) fn polygon_within_radius(
+    rings: &[Vec<[f64; 2]>],
+    center: &[f64; 2],
+    radius: f64,
+) -> bool {
+    point_in_polygon(center, rings)
+        || rings
+            .iter()
+            .flat_map(|ring| edges(ring))
+            .any(|(a, b)| distance_to_segment(center, a, b) <= radius + f64::EPSILON)
+}
+
+/// Returns the point of the polygon closest to the `[lat, lng]` point,
+/// the point itself when it is inside the polygon.
+fn closest_point_of_polygon(point: &[f64; 2], rings: &[Vec<[f64; 2]>]) -> Option<[f64; 2]> {
+    if point_in_polygon(point, rings) {
+        return Some(*point);
+    }
+
+    rings
+        .iter()
+        .flat_map(|ring| edges(ring))
+        .map(|(a, b)| closest_point_of_segment(point, a, b))
+        .min_by(|a, b| {
+            distance_between_two_points(point, a).total_cmp(&distance_between_two_points(point, b))
+        })
+}
+
+/// Returns the distance in meters between the point and the closest point of the segment.
+fn distance_to_segment(point: &[f64; 2], a: &[f64; 2], b: &[f64; 2]) -> f64 {
+    distance_between_two_points(point, &closest_point_of_segment(point, a, b))
+}
+
+/// Returns the point of the segment closest to the `[lat, lng]` point.
+fn closest_point_of_segment(point: &[f64; 2], a: &[f64; 2], b: &[f64; 2]) -> [f64; 2] {
+    // We look for the closest point in a plane where the degrees of longitude
+    // are scaled to have about the same length as the degrees of latitude.
+    let scale = point[0].to_radians().cos();
+    let project = |p: &[f64; 2]| [p[0] - point[0], (p[1] - point[1]) * scale];
+    let ([a_x, a_y], [b_x, b_y]) = (project(a), project(b));
+    let (ab_x, ab_y) = (b_x - a_x, b_y - a_y);
+    let length = ab_x * ab_x + ab_y * ab_y;
+    let t = if length == 0.0 { 0.0 } else { (-(a_x * ab_x + a_y * ab_y) / length).clamp(0.0, 1.0) };
+
+    [a[0] + t * (b[0] - a[0]), a[1] + t * (b[1] - a[1])]
+}
+
+/// Returns the cartesian envelope enclosing every point at most `radius` meters away from the center.
+pub(crate) fn circle_envelope(center: &[f64; 2], radius: f64) -> AABB<[f64; 3]> {
+    // the length of the chord between the center and the points of the circle on the unit sphere
+    let angle = (radius / EARTH_POLAR_RADIUS).min(PI);
+    let chord = 2.0 * (angle / 2.0).sin() + f64::EPSILON;
+    let [x, y, z] = lat_lng_to_xyz(center);
+    AABB::from_corners([x - chord, y - chord, z - chord], [x + chord, y + chord, z + chord])
+}
+
+/// Returns the cartesian envelope enclosing every point of the latitude/longitude box
+/// that contains the given points. This is the kind of envelope the geo rtrees are queried with.
+pub(crate) fn lat_lng_box_envelope(points: &[[f64; 2]]) -> AABB<[f64; 3]> {
+    let (mut min_lat, mut max_lat) = (f64::INFINITY, f64::NEG_INFINITY);
+    let (mut min_lng, mut max_lng) = (f64::INFINITY, f64::NEG_INFINITY);
+    for &[lat, lng] in points {
+        min_lat = min_lat.min(lat);
+        max_lat = max_lat.max(lat);
+        min_lng = min_lng.min(lng);
+        max_lng = max_lng.max(lng);
+    }
+
+    let (min_lat, max_lat) = (min_lat.to_radians(), max_lat.to_radians());
+    let (min_lng, max_lng) = (min_lng.to_radians(), max_lng.to_radians());
+    let contains = |min: f64, max: f64, angle: f64| (min..=max).contains(&angle);
+
+    // x = cos(lat) * cos(lng), y = cos(lat) * sin(lng) and z = sin(lat), we compute the
+    // range of each factor over the box and then the range of their products.
+    let cos_lat_max =
+        if contains(min_lat, max_lat, 0.0) { 1.0 } else { min_lat.cos().max(max_lat.cos()) };
+    let cos_lat = [min_lat.cos().min(max_lat.cos()), cos_lat_max];
+    let cos_lng_max =
+        if contains(min_lng, max_lng, 0.0) { 1.0 } else { min_lng.cos().max(max_lng.cos()) };
+    let cos_lng = [min_lng.cos().min(max_lng.cos()), cos_lng_max];
+    let sin_lng_min = if contains(min_lng, max_lng, -FRAC_PI_2) {
+        -1.0
+    } else {
+        min_lng.sin().min(max_lng.sin())
+    };
+    let sin_lng_max =
+        if contains(min_lng, max_lng, FRAC_PI_2) { 1.0 } else { min_lng.sin().max(max_lng.sin()) };
+    let sin_lng = [sin_lng_min, sin_lng_max];
+
+    let products_range = |a: [f64; 2], b: [f64; 2]| {
+        let products = [a[0] * b[0], a[0] * b[1], a[1] * b[0], a[1] * b[1]];
+        let min = products.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = products.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        [min, max]
+    };
+    let [min_x, max_x] = products_range(cos_lat, cos_lng);
+    let [min_y, max_y] = products_range(cos_lat, sin_lng);
+
+    // we extend the envelope a little bit to not lose the points lying on its borders
+    AABB::from_corners(
+        [min_x - f64::EPSILON, min_y - f64::EPSILON, min_lat.sin() - f64::EPSILON],
+        [max_x + f64::EPSILON, max_y + f64::EPSILON, max_lat.sin() + f64::EPSILON],
+    )
+}
@@ -0,0 +1,30 @@
+use std::borrow::Cow;
+
+use heed::BoxedError;
+
+use super::SliceTooShortError;
+use crate::try_split_array_at;
+
+/// Encodes a `u8` followed by a big-endian `u32`, e.g. an embedder id and a document id.
+pub struct U8BEU32Codec;
+
+impl<'a> heed::BytesDecode<'a> for U8BEU32Codec {
+    type DItem = (u8, u32);
+
+    fn bytes_decode(bytes: &'a [u8]) -> Result<Self::DItem, BoxedError> {
+        let ([prefix], bytes) = try_split_array_at(bytes).ok_or(SliceTooShortError)?;
+        let (value_bytes, _nothing) = try_split_array_at(bytes).ok_or(SliceTooShortError)?;
+        Ok((prefix, u32::from_be_bytes(value_bytes)))
+    }
+}
+
+impl<'a> heed::BytesEncode<'a> for U8BEU32Codec {
+    type EItem = (u8, u32);
+
+    fn bytes_encode((prefix, value): &Self::EItem) -> Result<Cow<'a, [u8]>, BoxedError> {
+        let mut bytes = Vec::with_capacity(1 + 4);
+        bytes.push(*prefix);
+        bytes.extend_from_slice(&value.to_be_bytes());
+        Ok(Cow::Owned(bytes))
+    }
+}
@@ -10,7 +10,9 @@ use roaring::RoaringBitmap;
 use rstar::RTree;
 use serde::{Deserialize, Serialize};
 
-use crate::constants::{self, RESERVED_GEO_FIELD_NAME, RESERVED_VECTORS_FIELD_NAME};
+use crate::constants::{
+    self, RESERVED_GEOJSON_FIELD_NAME, RESERVED_GEO_FIELD_NAME, RESERVED_VECTORS_FIELD_NAME,
+};
 use crate::database_stats::DatabaseStats;
 use crate::documents::PrimaryKey;
 use crate::error::{InternalError, UserError};
@@ -21,16 +23,21 @@ use crate::heed_codec::facet::{
     FieldIdCodec, OrderedF64Codec,
 };
 use crate::heed_codec::version::VersionCodec;
-use crate::heed_codec::{BEU16StrCodec, FstSetCodec, StrBEU16Codec, StrRefCodec};
+use crate::heed_codec::{BEU16StrCodec, FstSetCodec, StrBEU16Codec, StrRefCodec, U8BEU32Codec};
 use crate::order_by_map::OrderByMap;
 use crate::proximity::ProximityPrecision;
+use crate::vector::parsed_vectors::VectorOrArrayOfVectors;
+use crate::vector::rerank::RerankerSettings;
+use crate::vector::scalar::{ScalarQuantizedEmbedding, ScalarQuantizedStore};
+use crate::vector::sparse::{SparseEmbedding, SparsePostings, SparseVectorStore};
 use crate::vector::{ArroyStats, ArroyWrapper, Embedding, EmbeddingConfig};
 use crate::{
     default_criteria, CboRoaringBitmapCodec, Criterion, DocumentId, ExternalDocumentsIds,
     FacetDistribution, FieldDistribution, FieldId, FieldIdMapMissingEntry, FieldIdWordCountCodec,
-    FieldidsWeightsMap, FilterableAttributesRule, GeoPoint, LocalizedAttributesRule, ObkvCodec,
-    Result, RoaringBitmapCodec, RoaringBitmapLenCodec, Search, U8StrStrCodec, Weight, BEU16, BEU32,
-    BEU64,
+    FieldidsWeightsMap, FilterableAttributesRule, GeoJsonPolygon, GeoPoint,
+    LocalizedAttributesRule, ObkvCodec, ParsedQueryRuleConditions, QueryRule, RankingDecay,
+    RankingExpression, Result, RoaringBitmapCodec, RoaringBitmapLenCodec, Search, SynonymRule,
+    Synonyms, U8StrStrCodec, Weight, BEU16, BEU32, BEU64,
 };
 
 pub const DEFAULT_MIN_WORD_LEN_ONE_TYPO: u8 = 5;
@@ -50,6 +57,7 @@ pub mod main_key {
     pub const FIELDIDS_WEIGHTS_MAP_KEY: &str = "fieldids-weights-map";
     pub const GEO_FACETED_DOCUMENTS_IDS_KEY: &str = "geo-faceted-documents-ids";
     pub const GEO_RTREE_KEY: &str = "geo-rtree";
+    pub const GEOJSON_RTREE_KEY: &str = "geojson-rtree";
     pub const PRIMARY_KEY_KEY: &str = "primary-key";
     pub const SEARCHABLE_FIELDS_KEY: &str = "searchable-fields";
     pub const USER_DEFINED_SEARCHABLE_FIELDS_KEY: &str = "user-defined-searchable-fields";
@@ -59,6 +67,8 @@ pub mod main_key {
     pub const DICTIONARY_KEY: &str = "dictionary";
     pub const SYNONYMS_KEY: &str = "synonyms";
     pub const USER_DEFINED_SYNONYMS_KEY: &str = "user-defined-synonyms";
+    pub const USER_DEFINED_SYNONYM_RULES_KEY: &str = "user-defined-synonym-rules";
+    pub const TYPO_SYNONYMS_KEY: &str = "typo-synonyms";
     pub const WORDS_FST_KEY: &str = "words-fst";
     pub const WORDS_PREFIXES_FST_KEY: &str = "words-prefixes-fst";
     pub const CREATED_AT_KEY: &str = "created-at";
@@ -77,6 +87,9 @@ pub mod main_key {
     pub const LOCALIZED_ATTRIBUTES_RULES: &str = "localized_attributes_rules";
     pub const FACET_SEARCH: &str = "facet_search";
     pub const PREFIX_SEARCH: &str = "prefix_search";
+    pub const QUERY_RULES: &str = "query_rules";
+    pub const QUERY_RULES_CONDITIONS: &str = "query_rules_conditions";
+    pub const RERANKERS: &str = "rerankers";
     pub const DOCUMENTS_STATS: &str = "documents_stats";
     pub const DISABLED_TYPOS_TERMS: &str = "disabled_typos_terms";
 }
@@ -87,6 +100,7 @@ pub mod db_name {
     pub const EXACT_WORD_DOCIDS: &str = "exact-word-docids";
     pub const WORD_PREFIX_DOCIDS: &str = "word-prefix-docids";
     pub const EXACT_WORD_PREFIX_DOCIDS: &str = "exact-word-prefix-docids";
+    pub const STEM_WORDS: &str = "stem-words";
     pub const EXTERNAL_DOCUMENTS_IDS: &str = "external-documents-ids";
     pub const DOCID_WORD_POSITIONS: &str = "docid-word-positions";
     pub const WORD_PAIR_PROXIMITY_DOCIDS: &str = "word-pair-proximity-docids";
@@ -106,9 +120,13 @@ pub mod db_name {
     pub const FIELD_ID_DOCID_FACET_STRINGS: &str = "field-id-docid-facet-strings";
     pub const VECTOR_EMBEDDER_CATEGORY_ID: &str = "vector-embedder-category-id";
     pub const VECTOR_ARROY: &str = "vector-arroy";
+    pub const SPARSE_VECTOR_TOKEN_POSTINGS: &str = "sparse-vector-token-postings";
+    pub const SPARSE_VECTORS: &str = "sparse-vectors";
+    pub const SCALAR_QUANTIZED_VECTORS: &str = "scalar-quantized-vectors";
+    pub const FULL_PRECISION_VECTORS: &str = "full-precision-vectors";
     pub const DOCUMENTS: &str = "documents";
 }
-const NUMBER_OF_DBS: u32 = 25;
+const NUMBER_OF_DBS: u32 = 30;
 
 #[derive(Clone)]
 pub struct Index {
@@ -133,6 +151,9 @@ pub struct Index {
     /// A prefix of word and all the documents ids containing this prefix, from attributes for which typos are not allowed.
     pub exact_word_prefix_docids: Database<Str, CboRoaringBitmapCodec>,
 
+    /// A stem prefixed by the code of its locale and all the words sharing this stem, from attributes for which stemming is enabled.
+    pub stem_words: Database<Str, SerdeBincode<BTreeSet<String>>>,
+
     /// Maps the proximity between a pair of words with all the docids where this relation appears.
     pub word_pair_proximity_docids: Database<U8StrStrCodec, CboRoaringBitmapCodec>,
 
@@ -173,6 +194,17 @@ pub struct Index {
     pub embedder_category_id: Database<Str, U8>,
     /// Vector store based on arroy™.
     pub vector_arroy: arroy::Database<Unspecified>,
+    /// Maps the embedder id and a token of its vocabulary with the docids whose sparse vector contains it,
+    /// along with the normalized weight of the token in their sparse vector.
+    pub sparse_vector_token_postings: Database<U8BEU32Codec, SerdeBincode<SparsePostings>>,
+    /// Maps the embedder id and the document id with the sparse vector of the document.
+    pub sparse_vectors: Database<U8BEU32Codec, SerdeBincode<SparseEmbedding>>,
+    /// Maps the embedder id and the document id with the scalar quantized vectors of the document.
+    pub scalar_quantized_vectors:
+        Database<U8BEU32Codec, SerdeBincode<Vec<ScalarQuantizedEmbedding>>>,
+    /// Maps the embedder id and the document id with the original vectors of the document,
+    /// for the scalar quantized embedders.
+    pub full_precision_vectors: Database<U8BEU32Codec, SerdeBincode<Vec<Embedding>>>,
 
     /// Maps the document id to the document as an obkv store.
     pub(crate) documents: Database<BEU32, ObkvCodec>,
@@ -200,6 +232,7 @@ impl Index {
         let word_prefix_docids = env.create_database(&mut wtxn, Some(WORD_PREFIX_DOCIDS))?;
         let exact_word_prefix_docids =
             env.create_database(&mut wtxn, Some(EXACT_WORD_PREFIX_DOCIDS))?;
+        let stem_words = env.create_database(&mut wtxn, Some(STEM_WORDS))?;
         let word_pair_proximity_docids =
             env.create_database(&mut wtxn, Some(WORD_PAIR_PROXIMITY_DOCIDS))?;
         let word_position_docids = env.create_database(&mut wtxn, Some(WORD_POSITION_DOCIDS))?;
@@ -230,6 +263,13 @@ impl Index {
         let embedder_category_id =
             env.create_database(&mut wtxn, Some(VECTOR_EMBEDDER_CATEGORY_ID))?;
         let vector_arroy = env.create_database(&mut wtxn, Some(VECTOR_ARROY))?;
+        let sparse_vector_token_postings =
+            env.create_database(&mut wtxn, Some(SPARSE_VECTOR_TOKEN_POSTINGS))?;
+        let sparse_vectors = env.create_database(&mut wtxn, Some(SPARSE_VECTORS))?;
+        let scalar_quantized_vectors =
+            env.create_database(&mut wtxn, Some(SCALAR_QUANTIZED_VECTORS))?;
+        let full_precision_vectors =
+            env.create_database(&mut wtxn, Some(FULL_PRECISION_VECTORS))?;
 
         let documents = env.create_database(&mut wtxn, Some(DOCUMENTS))?;
 
@@ -241,6 +281,7 @@ impl Index {
             exact_word_docids,
             word_prefix_docids,
             exact_word_prefix_docids,
+            stem_words,
             word_pair_proximity_docids,
             word_position_docids,
             word_fid_docids,
@@ -257,6 +298,10 @@ impl Index {
             field_id_docid_facet_f64s,
             field_id_docid_facet_strings,
             vector_arroy,
+            sparse_vector_token_postings,
+            sparse_vectors,
+            scalar_quantized_vectors,
+            full_precision_vectors,
             embedder_category_id,
             documents,
         };
@@ -667,6 +712,38 @@ impl Index {
         }
     }
 
+    /* geojson rtree */
+
+    /// Writes the provided `rtree` which associates the `_geojson` polygons to documents ids.
+    pub(crate) fn put_geojson_rtree(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        rtree: &RTree<GeoJsonPolygon>,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeBincode<RTree<GeoJsonPolygon>>>().put(
+            wtxn,
+            main_key::GEOJSON_RTREE_KEY,
+            rtree,
+        )
+    }
+
+    /// Delete the `rtree` which associates the `_geojson` polygons to documents ids.
+    pub(crate) fn delete_geojson_rtree(&self, wtxn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(wtxn, main_key::GEOJSON_RTREE_KEY)
+    }
+
+    /// Returns the `rtree` which associates the `_geojson` polygons to documents ids.
+    pub fn geojson_rtree(&self, rtxn: &RoTxn<'_>) -> Result<Option<RTree<GeoJsonPolygon>>> {
+        match self
+            .main
+            .remap_types::<Str, SerdeBincode<RTree<GeoJsonPolygon>>>()
+            .get(rtxn, main_key::GEOJSON_RTREE_KEY)?
+        {
+            Some(rtree) => Ok(Some(rtree)),
+            None => Ok(None),
+        }
+    }
+
     /* geo faceted */
 
     /// Writes the documents ids that are faceted with a _geo field.
@@ -854,7 +931,10 @@ impl Index {
                 Ok(self
                     .fields_ids_map(rtxn)?
                     .names()
-                    .filter(|name| !crate::is_faceted_by(name, RESERVED_VECTORS_FIELD_NAME))
+                    .filter(|name| {
+                        !crate::is_faceted_by(name, RESERVED_VECTORS_FIELD_NAME)
+                            && !crate::is_faceted_by(name, RESERVED_GEOJSON_FIELD_NAME)
+                    })
                     .map(|field| Cow::Owned(field.to_string()))
                     .collect())
             })
@@ -1015,15 +1095,28 @@ impl Index {
         Ok(geo_filter)
     }
 
+    /// Returns the fields used by the custom ranking rules, the ones of the expressions and decays included.
     pub fn asc_desc_fields(&self, rtxn: &RoTxn<'_>) -> Result<HashSet<String>> {
-        let asc_desc_fields = self
-            .criteria(rtxn)?
-            .into_iter()
-            .filter_map(|criterion| match criterion {
-                Criterion::Asc(field) | Criterion::Desc(field) => Some(field),
-                _otherwise => None,
-            })
-            .collect();
+        let mut asc_desc_fields = HashSet::new();
+        for criterion in self.criteria(rtxn)? {
+            match criterion {
+                Criterion::Asc(field) | Criterion::Desc(field) => {
+                    asc_desc_fields.insert(field);
+                }
+                // the expressions were validated when the ranking rules were set
+                Criterion::Expression(expression) => {
+                    if let Ok(expression) = expression.parse::<RankingExpression>() {
+                        asc_desc_fields.extend(expression.fields().into_iter().map(String::from));
+                    }
+                }
+                Criterion::Decay(decay) => {
+                    if let Ok(decay) = decay.parse::<RankingDecay>() {
+                        asc_desc_fields.insert(decay.field);
+                    }
+                }
+                _otherwise => (),
+            }
+        }
 
         Ok(asc_desc_fields)
     }
@@ -1252,40 +1345,89 @@ impl Index {
             .get(rtxn, main_key::DICTIONARY_KEY)?)
     }
 
+    /// Returns the words of the dictionary along with the placeholders of the synonyms.
+    ///
+    /// The placeholders, e.g. `<street>`, must be matched by the tokenizer before the
+    /// normalization strips their brackets, both in the documents and in the queries.
+    pub fn tokenizer_dictionary(&self, rtxn: &RoTxn<'_>) -> Result<Option<BTreeSet<String>>> {
+        let mut dictionary = self.dictionary(rtxn)?;
+        let synonyms = self.user_defined_synonyms(rtxn)?;
+        let placeholders = synonyms.iter().filter(|(_, synonyms)| synonyms.is_placeholder());
+        for (placeholder, _) in placeholders {
+            dictionary.get_or_insert_with(BTreeSet::new).insert(placeholder.clone());
+        }
+        Ok(dictionary)
+    }
+
     /* synonyms */
 
+    /// Writes the normalized synonyms, the ones ranked as typos, and the synonyms defined by the user.
     pub(crate) fn put_synonyms(
         &self,
         wtxn: &mut RwTxn<'_>,
         synonyms: &HashMap<Vec<String>, Vec<Vec<String>>>,
-        user_defined_synonyms: &BTreeMap<String, Vec<String>>,
+        typo_synonyms: &HashMap<Vec<String>, Vec<Vec<String>>>,
+        user_defined_synonyms: &BTreeMap<String, Synonyms>,
     ) -> heed::Result<()> {
+        // the plain lists of synonyms are stored as before, the rules can't be stored with bincode
+        let mut words = BTreeMap::new();
+        let mut rules = BTreeMap::new();
+        for (word, synonyms) in user_defined_synonyms {
+            match synonyms {
+                Synonyms::Words(synonyms) => words.insert(word.clone(), synonyms.clone()),
+                Synonyms::Rule(rule) => rules.insert(word.clone(), rule.clone()),
+            };
+        }
+
         self.main.remap_types::<Str, SerdeBincode<_>>().put(
             wtxn,
             main_key::SYNONYMS_KEY,
             synonyms,
         )?;
+        self.main.remap_types::<Str, SerdeBincode<_>>().put(
+            wtxn,
+            main_key::TYPO_SYNONYMS_KEY,
+            typo_synonyms,
+        )?;
         self.main.remap_types::<Str, SerdeBincode<_>>().put(
             wtxn,
             main_key::USER_DEFINED_SYNONYMS_KEY,
-            user_defined_synonyms,
+            &words,
+        )?;
+        self.main.remap_types::<Str, SerdeJson<_>>().put(
+            wtxn,
+            main_key::USER_DEFINED_SYNONYM_RULES_KEY,
+            &rules,
         )
     }
 
     pub(crate) fn delete_synonyms(&self, wtxn: &mut RwTxn<'_>) -> heed::Result<bool> {
         self.main.remap_key_type::<Str>().delete(wtxn, main_key::SYNONYMS_KEY)?;
+        self.main.remap_key_type::<Str>().delete(wtxn, main_key::TYPO_SYNONYMS_KEY)?;
+        self.main.remap_key_type::<Str>().delete(wtxn, main_key::USER_DEFINED_SYNONYM_RULES_KEY)?;
         self.main.remap_key_type::<Str>().delete(wtxn, main_key::USER_DEFINED_SYNONYMS_KEY)
     }
 
     pub fn user_defined_synonyms(
         &self,
         rtxn: &RoTxn<'_>,
-    ) -> heed::Result<BTreeMap<String, Vec<String>>> {
-        Ok(self
+    ) -> heed::Result<BTreeMap<String, Synonyms>> {
+        let words: BTreeMap<String, Vec<String>> = self
             .main
             .remap_types::<Str, SerdeBincode<_>>()
             .get(rtxn, main_key::USER_DEFINED_SYNONYMS_KEY)?
-            .unwrap_or_default())
+            .unwrap_or_default();
+        let rules: BTreeMap<String, SynonymRule> = self
+            .main
+            .remap_types::<Str, SerdeJson<_>>()
+            .get(rtxn, main_key::USER_DEFINED_SYNONYM_RULES_KEY)?
+            .unwrap_or_default();
+
+        Ok(words
+            .into_iter()
+            .map(|(word, synonyms)| (word, Synonyms::Words(synonyms)))
+            .chain(rules.into_iter().map(|(word, rule)| (word, Synonyms::Rule(rule))))
+            .collect())
     }
 
     pub fn synonyms(
@@ -1299,6 +1441,18 @@ impl Index {
             .unwrap_or_default())
     }
 
+    /// Returns the synonyms whose matches are ranked like the words with one typo.
+    pub fn typo_synonyms(
+        &self,
+        rtxn: &RoTxn<'_>,
+    ) -> heed::Result<HashMap<Vec<String>, Vec<Vec<String>>>> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeBincode<_>>()
+            .get(rtxn, main_key::TYPO_SYNONYMS_KEY)?
+            .unwrap_or_default())
+    }
+
     pub fn words_synonyms<S: AsRef<str>>(
         &self,
         rtxn: &RoTxn<'_>,
@@ -1719,6 +1873,77 @@ impl Index {
         self.main.remap_key_type::<Str>().delete(txn, main_key::LOCALIZED_ATTRIBUTES_RULES)
     }
 
+    pub fn query_rules(&self, rtxn: &RoTxn<'_>) -> heed::Result<Option<Vec<QueryRule>>> {
+        self.main.remap_types::<Str, SerdeJson<Vec<QueryRule>>>().get(rtxn, main_key::QUERY_RULES)
+    }
+
+    pub(crate) fn put_query_rules(
+        &self,
+        txn: &mut RwTxn<'_>,
+        val: Vec<QueryRule>,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<Vec<QueryRule>>>().put(
+            txn,
+            main_key::QUERY_RULES,
+            &val,
+        )
+    }
+
+    pub(crate) fn delete_query_rules(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::QUERY_RULES)
+    }
+
+    /// The parsed conditions of the query rules, in the order of the rules.
+    pub fn query_rules_conditions(
+        &self,
+        rtxn: &RoTxn<'_>,
+    ) -> heed::Result<Option<Vec<ParsedQueryRuleConditions>>> {
+        self.main
+            .remap_types::<Str, SerdeJson<Vec<ParsedQueryRuleConditions>>>()
+            .get(rtxn, main_key::QUERY_RULES_CONDITIONS)
+    }
+
+    pub(crate) fn put_query_rules_conditions(
+        &self,
+        txn: &mut RwTxn<'_>,
+        val: Vec<ParsedQueryRuleConditions>,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<Vec<ParsedQueryRuleConditions>>>().put(
+            txn,
+            main_key::QUERY_RULES_CONDITIONS,
+            &val,
+        )
+    }
+
+    pub(crate) fn delete_query_rules_conditions(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::QUERY_RULES_CONDITIONS)
+    }
+
+    pub fn rerankers(
+        &self,
+        rtxn: &RoTxn<'_>,
+    ) -> heed::Result<Option<BTreeMap<String, RerankerSettings>>> {
+        self.main
+            .remap_types::<Str, SerdeJson<BTreeMap<String, RerankerSettings>>>()
+            .get(rtxn, main_key::RERANKERS)
+    }
+
+    pub(crate) fn put_rerankers(
+        &self,
+        txn: &mut RwTxn<'_>,
+        val: BTreeMap<String, RerankerSettings>,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<BTreeMap<String, RerankerSettings>>>().put(
+            txn,
+            main_key::RERANKERS,
+            &val,
+        )
+    }
+
+    pub(crate) fn delete_rerankers(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::RERANKERS)
+    }
+
     /// Put the embedding configs:
     /// 1. The name of the embedder
     /// 2. The configuration option for this embedder
@@ -1759,6 +1984,7 @@ impl Index {
         self.main.remap_key_type::<Str>().delete(wtxn, main_key::SEARCH_CUTOFF)
     }
 
+    /// The dense embeddings of the document, sparse embedders are ignored.
     pub fn embeddings(
         &self,
         rtxn: &RoTxn<'_>,
@@ -1767,15 +1993,54 @@ impl Index {
         let mut res = BTreeMap::new();
         let embedding_configs = self.embedding_configs(rtxn)?;
         for config in embedding_configs {
+            if config.config.is_sparse() {
+                continue;
+            }
             let embedder_id = self.embedder_category_id.get(rtxn, &config.name)?.unwrap();
-            let reader =
-                ArroyWrapper::new(self.vector_arroy, embedder_id, config.config.quantized());
+            let reader = ArroyWrapper::new(
+                self.vector_arroy,
+                embedder_id,
+                config.config.quantized(),
+                config.config.distance(),
+            )
+            .with_scalar_quantization(
+                config
+                    .config
+                    .scalar_quantized()
+                    .then(|| ScalarQuantizedStore::new(self, embedder_id)),
+            );
             let embeddings = reader.item_vectors(rtxn, docid)?;
             res.insert(config.name.to_owned(), embeddings);
         }
         Ok(res)
     }
 
+    /// The dense and sparse embeddings of the document, in the format of the `_vectors` field.
+    pub fn document_vectors(
+        &self,
+        rtxn: &RoTxn<'_>,
+        docid: DocumentId,
+    ) -> Result<BTreeMap<String, VectorOrArrayOfVectors>> {
+        let mut res: BTreeMap<_, _> = self
+            .embeddings(rtxn, docid)?
+            .into_iter()
+            .map(|(name, embeddings)| (name, embeddings.into()))
+            .collect();
+        for config in self.embedding_configs(rtxn)? {
+            if !config.config.is_sparse() {
+                continue;
+            }
+            let embedder_id = self.embedder_category_id.get(rtxn, &config.name)?.unwrap();
+            let embeddings =
+                match SparseVectorStore::new(self, embedder_id).item_vector(rtxn, docid)? {
+                    Some(embedding) => VectorOrArrayOfVectors::from_sparse_embedding(&embedding),
+                    None => VectorOrArrayOfVectors::from_array_of_vectors(Vec::new()),
+                };
+            res.insert(config.name, embeddings);
+        }
+        Ok(res)
+    }
+
     pub fn prefix_settings(&self, rtxn: &RoTxn<'_>) -> Result<PrefixSettings> {
         let compute_prefixes = self.prefix_search(rtxn)?.unwrap_or_default();
         Ok(PrefixSettings { compute_prefixes, max_prefix_length: 4, prefix_count_threshold: 100 })
@@ -1786,8 +2051,24 @@ impl Index {
         let embedding_configs = self.embedding_configs(rtxn)?;
         for config in embedding_configs {
             let embedder_id = self.embedder_category_id.get(rtxn, &config.name)?.unwrap();
-            let reader =
-                ArroyWrapper::new(self.vector_arroy, embedder_id, config.config.quantized());
+            if config.config.is_sparse() {
+                let documents = SparseVectorStore::new(self, embedder_id).documents(rtxn)?;
+                stats.number_of_embeddings += documents.len();
+                stats.documents |= documents;
+                continue;
+            }
+            let reader = ArroyWrapper::new(
+                self.vector_arroy,
+                embedder_id,
+                config.config.quantized(),
+                config.config.distance(),
+            )
+            .with_scalar_quantization(
+                config
+                    .config
+                    .scalar_quantized()
+                    .then(|| ScalarQuantizedStore::new(self, embedder_id)),
+            );
             reader.aggregate_stats(rtxn, &mut stats)?;
         }
         Ok(stats)
@@ -1816,6 +2097,7 @@ impl Index {
             exact_word_docids,
             word_prefix_docids,
             exact_word_prefix_docids,
+            stem_words,
             word_pair_proximity_docids,
             word_position_docids,
             word_fid_docids,
@@ -1832,6 +2114,10 @@ impl Index {
             field_id_docid_facet_f64s,
             field_id_docid_facet_strings,
             vector_arroy,
+            sparse_vector_token_postings,
+            sparse_vectors,
+            scalar_quantized_vectors,
+            full_precision_vectors,
             embedder_category_id,
             documents,
         } = self;
@@ -1860,6 +2146,7 @@ impl Index {
             "exact_word_prefix_docids",
             exact_word_prefix_docids.stat(rtxn).map(compute_size)?,
         );
+        sizes.insert("stem_words", stem_words.stat(rtxn).map(compute_size)?);
         sizes.insert(
             "word_pair_proximity_docids",
             word_pair_proximity_docids.stat(rtxn).map(compute_size)?,
@@ -1903,6 +2190,17 @@ impl Index {
             field_id_docid_facet_strings.stat(rtxn).map(compute_size)?,
         );
         sizes.insert("vector_arroy", vector_arroy.stat(rtxn).map(compute_size)?);
+        sizes.insert(
+            "sparse_vector_token_postings",
+            sparse_vector_token_postings.stat(rtxn).map(compute_size)?,
+        );
+        sizes.insert("sparse_vectors", sparse_vectors.stat(rtxn).map(compute_size)?);
+        sizes.insert(
+            "scalar_quantized_vectors",
+            scalar_quantized_vectors.stat(rtxn).map(compute_size)?,
+        );
+        sizes
+            .insert("full_precision_vectors", full_precision_vectors.stat(rtxn).map(compute_size)?);
         sizes.insert("embedder_category_id", embedder_category_id.stat(rtxn).map(compute_size)?);
         sizes.insert("documents", documents.stat(rtxn).map(compute_size)?);
 
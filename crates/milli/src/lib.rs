@@ -12,20 +12,26 @@ mod asc_desc;
 mod attribute_patterns;
 mod criterion;
 pub mod database_stats;
+mod date;
 pub mod disabled_typos_terms;
 mod error;
 mod external_documents_ids;
 pub mod facet;
 mod fields_ids_map;
 mod filterable_attributes_rules;
+mod geometry;
 pub mod heed_codec;
 pub mod index;
 mod localized_attributes_rules;
 pub mod order_by_map;
 pub mod prompt;
 pub mod proximity;
+mod query_rules;
+mod ranking_decay;
+mod ranking_expression;
 pub mod score_details;
 mod search;
+mod synonyms;
 mod thread_pool_no_abort;
 pub mod update;
 pub mod vector;
@@ -48,7 +54,7 @@ use fxhash::{FxHasher32, FxHasher64};
 pub use grenad::CompressionType;
 pub use search::new::{
     execute_search, filtered_universe, DefaultSearchLogger, GeoSortStrategy, SearchContext,
-    SearchLogger, VisualSearchLogger,
+    SearchLogger, SpellingCorrections, VisualSearchLogger,
 };
 use serde_json::Value;
 pub use thread_pool_no_abort::{PanicCatched, ThreadPoolNoAbort, ThreadPoolNoAbortBuilder};
@@ -68,6 +74,7 @@ pub use self::filterable_attributes_rules::{
     FilterFeatures, FilterableAttributesFeatures, FilterableAttributesPatterns,
     FilterableAttributesRule,
 };
+pub use self::geometry::{DocumentGeometry, GeoJsonPolygon};
 pub use self::heed_codec::{
     BEU16StrCodec, BEU32StrCodec, BoRoaringBitmapCodec, BoRoaringBitmapLenCodec,
     CboRoaringBitmapCodec, CboRoaringBitmapLenCodec, FieldIdWordCountCodec, ObkvCodec,
@@ -76,12 +83,23 @@ pub use self::heed_codec::{
 };
 pub use self::index::Index;
 pub use self::localized_attributes_rules::LocalizedAttributesRule;
+pub use self::query_rules::{
+    ParsedQueryRuleConditions, PinnedDocument, QueryRule, QueryRuleAnchoring, QueryRuleConditions,
+    QueryRuleError,
+};
+pub use self::ranking_decay::{DecayFunction, DecayOrigin, RankingDecay, RankingDecayError};
+pub use self::ranking_expression::{
+    RankingExpression, RankingExpressionError, RANKING_SCORE_VARIABLE,
+};
 pub use self::search::facet::{FacetValueHit, SearchForFacetValues};
 pub use self::search::similar::Similar;
+pub use self::search::suggest::{Suggest, Suggestion};
 pub use self::search::{
-    FacetDistribution, Filter, FormatOptions, MatchBounds, MatcherBuilder, MatchingWords, OrderBy,
-    Search, SearchResult, SemanticSearch, TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET,
+    FacetDistribution, FacetHierarchy, FacetRange, Filter, FormatOptions, HistogramBucket,
+    MatchBounds, MatcherBuilder, MatchingWords, OrderBy, Search, SearchResult, SemanticSearch,
+    TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET, MAX_HISTOGRAM_BUCKETS,
 };
+pub use self::synonyms::{SynonymKind, SynonymRule, Synonyms};
 pub use self::update::ChannelCongestion;
 
 pub use arroy;
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
 use charabia::Language;
+use rust_stemmers::{Algorithm, Stemmer};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
@@ -16,16 +17,25 @@ use crate::{AttributePatterns, FieldId};
 /// The pattern `attribute_name*` matches any attribute name that starts with `attribute_name`.
 /// The pattern `*attribute_name` matches any attribute name that ends with `attribute_name`.
 /// The pattern `*attribute_name*` matches any attribute name that contains `attribute_name`.
+///
+/// When stemming is enabled, the words of the attributes are also indexed by their stem in each of
+/// the locales of the rule, so that searching for `running` also matches `runs`.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 pub struct LocalizedAttributesRule {
     pub attribute_patterns: AttributePatterns,
     #[schema(value_type = Vec<String>)]
     pub locales: Vec<Language>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub stemming: bool,
 }
 
 impl LocalizedAttributesRule {
     pub fn new(attribute_patterns: Vec<String>, locales: Vec<Language>) -> Self {
-        Self { attribute_patterns: AttributePatterns::from(attribute_patterns), locales }
+        Self {
+            attribute_patterns: AttributePatterns::from(attribute_patterns),
+            locales,
+            stemming: false,
+        }
     }
 
     pub fn match_str(&self, str: &str) -> PatternMatch {
@@ -91,3 +101,84 @@ impl LocalizedFieldIds {
         locales
     }
 }
+
+/// Returns the stemmer of the language, if the language can be stemmed.
+pub fn stemmer(language: Language) -> Option<Stemmer> {
+    let algorithm = match language {
+        Language::Ara => Algorithm::Arabic,
+        Language::Dan => Algorithm::Danish,
+        Language::Deu => Algorithm::German,
+        Language::Ell => Algorithm::Greek,
+        Language::Eng => Algorithm::English,
+        Language::Fin => Algorithm::Finnish,
+        Language::Fra => Algorithm::French,
+        Language::Hun => Algorithm::Hungarian,
+        Language::Ita => Algorithm::Italian,
+        Language::Nld => Algorithm::Dutch,
+        Language::Nob => Algorithm::Norwegian,
+        Language::Por => Algorithm::Portuguese,
+        Language::Ron => Algorithm::Romanian,
+        Language::Rus => Algorithm::Russian,
+        Language::Spa => Algorithm::Spanish,
+        Language::Swe => Algorithm::Swedish,
+        Language::Tam => Algorithm::Tamil,
+        Language::Tur => Algorithm::Turkish,
+        _ => return None,
+    };
+    Some(Stemmer::create(algorithm))
+}
+
+/// Returns the key of a stem in the stem words database.
+///
+/// The stem is prefixed by the code of its language, so that the words of two languages
+/// sharing the same stem are not mixed together.
+pub fn stem_words_key(language: Language, stem: &str) -> String {
+    format!("{}:{stem}", language.code())
+}
+
+/// The languages in which the words of each field must be stemmed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StemmedFieldIds {
+    field_id_to_locales: HashMap<FieldId, Vec<Language>>,
+}
+
+impl StemmedFieldIds {
+    pub fn new(rules: &[LocalizedAttributesRule], fields_ids_map: &FieldsIdsMap) -> Self {
+        let mut field_id_to_locales = HashMap::new();
+
+        for (field_id, field_name) in fields_ids_map.iter() {
+            // Take the first rule that matches
+            let rule = rules.iter().find(|rule| rule.match_str(field_name) == PatternMatch::Match);
+            if let Some(rule) = rule.filter(|rule| rule.stemming) {
+                let mut locales: Vec<_> =
+                    rule.locales.iter().copied().filter(|l| stemmer(*l).is_some()).collect();
+                locales.sort();
+                locales.dedup();
+                if !locales.is_empty() {
+                    field_id_to_locales.insert(field_id, locales);
+                }
+            }
+        }
+
+        Self { field_id_to_locales }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.field_id_to_locales.is_empty()
+    }
+
+    pub fn locales(&self, field_id: FieldId) -> &[Language] {
+        self.field_id_to_locales.get(&field_id).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn field_ids(&self) -> impl Iterator<Item = FieldId> + '_ {
+        self.field_id_to_locales.keys().copied()
+    }
+
+    pub fn all_locales(&self) -> Vec<Language> {
+        let mut locales: Vec<_> = self.field_id_to_locales.values().flatten().copied().collect();
+        locales.sort();
+        locales.dedup();
+        locales
+    }
+}
@@ -10,9 +10,11 @@ use std::fmt::Debug;
 use std::num::NonZeroUsize;
 
 use bumpalo::Bump;
+use deserr::Deserr;
 use document::ParseableDocument;
 use error::{NewPromptError, RenderPromptError};
 use fields::{BorrowedFields, OwnedFields};
+use utoipa::ToSchema;
 
 use self::context::Context;
 use self::document::Document;
@@ -20,21 +22,43 @@ use crate::fields_ids_map::metadata::FieldIdMapWithMetadata;
 use crate::update::del_add::DelAdd;
 use crate::GlobalFieldsIdsMap;
 
+/// The maximum number of chunks a rendered document can be split into,
+/// as the embeddings of a document are limited to `u8::MAX`.
+pub const MAX_CHUNKS: usize = u8::MAX as usize;
+
 pub struct Prompt {
     template: liquid::Template,
     template_text: String,
     max_bytes: Option<NonZeroUsize>,
+    chunking: Option<Chunking>,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct PromptData {
     pub template: String,
     pub max_bytes: Option<NonZeroUsize>,
+    #[serde(default)]
+    pub chunking: Option<Chunking>,
+}
+
+/// Splits the rendered documents into overlapping chunks that are embedded separately.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, Deserr, ToSchema,
+)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct Chunking {
+    /// The maximum size of a chunk, in bytes.
+    pub max_bytes: usize,
+    /// The number of bytes at the end of a chunk that are repeated at the start of the next one.
+    #[serde(default)]
+    #[deserr(default)]
+    pub overlap_bytes: usize,
 }
 
 impl From<Prompt> for PromptData {
     fn from(value: Prompt) -> Self {
-        Self { template: value.template_text, max_bytes: value.max_bytes }
+        Self { template: value.template_text, max_bytes: value.max_bytes, chunking: value.chunking }
     }
 }
 
@@ -42,7 +66,7 @@ impl TryFrom<PromptData> for Prompt {
     type Error = NewPromptError;
 
     fn try_from(value: PromptData) -> Result<Self, Self::Error> {
-        Prompt::new(value.template, value.max_bytes)
+        Ok(Prompt::new(value.template, value.max_bytes)?.with_chunking(value.chunking))
     }
 }
 
@@ -53,6 +77,7 @@ impl Clone for Prompt {
             template: new_template(&template_text).unwrap(),
             template_text,
             max_bytes: self.max_bytes,
+            chunking: self.chunking,
         }
     }
 }
@@ -83,13 +108,18 @@ impl Default for Prompt {
             template: default_template(),
             template_text: default_template_text().into(),
             max_bytes: Some(default_max_bytes()),
+            chunking: None,
         }
     }
 }
 
 impl Default for PromptData {
     fn default() -> Self {
-        Self { template: default_template_text().into(), max_bytes: Some(default_max_bytes()) }
+        Self {
+            template: default_template_text().into(),
+            max_bytes: Some(default_max_bytes()),
+            chunking: None,
+        }
     }
 }
 
@@ -103,6 +133,7 @@ impl Prompt {
                 .map_err(NewPromptError::cannot_parse_template)?,
             template_text: template,
             max_bytes,
+            chunking: None,
         };
 
         // render template with special object that's OK with `doc.*` and `fields.*`
@@ -113,6 +144,30 @@ impl Prompt {
         Ok(this)
     }
 
+    pub fn with_chunking(self, chunking: Option<Chunking>) -> Self {
+        Self { chunking, ..self }
+    }
+
+    pub fn chunking(&self) -> Option<Chunking> {
+        self.chunking
+    }
+
+    /// Splits a rendered document into the texts to embed, a single one if chunking is disabled.
+    ///
+    /// Each text is truncated to the `max_bytes` of the prompt.
+    pub fn chunks<'t>(&self, rendered: &'t str) -> Vec<&'t str> {
+        let mut chunks = match self.chunking {
+            Some(chunking) => chunking.split(rendered),
+            None => vec![rendered],
+        };
+        if let Some(max_bytes) = self.max_bytes {
+            for chunk in &mut chunks {
+                *chunk = &chunk[..floor_char_boundary(chunk, max_bytes.get())];
+            }
+        }
+        chunks
+    }
+
     pub fn render_document<
         'a,       // lifetime of the borrow of the document
         'doc: 'a, // lifetime of the allocator, will live for an entire chunk of documents
@@ -152,13 +207,73 @@ impl Prompt {
 
         let mut rendered =
             self.template.render(&context).map_err(RenderPromptError::missing_context)?;
-        if let Some(max_bytes) = self.max_bytes {
+        // the chunks are truncated separately by `Self::chunks`
+        if let (Some(max_bytes), None) = (self.max_bytes, self.chunking) {
             truncate(&mut rendered, max_bytes.get());
         }
         Ok(rendered)
     }
 }
 
+impl Chunking {
+    /// Splits the text into chunks of at most `max_bytes`, each starting up to `overlap_bytes`
+    /// before the end of the previous one.
+    ///
+    /// The chunks are cut between words when possible,
+    /// and the text after the first [`MAX_CHUNKS`] chunks is ignored.
+    pub fn split<'t>(&self, text: &'t str) -> Vec<&'t str> {
+        let is_word_boundary = |index: usize| {
+            index == 0
+                || text[..index].ends_with(char::is_whitespace)
+                || text[index..].starts_with(char::is_whitespace)
+        };
+
+        let mut chunks = Vec::new();
+        let mut start = 0;
+        while chunks.len() < MAX_CHUNKS {
+            start = text.len() - text[start..].trim_start().len();
+            if start == text.len() {
+                break;
+            }
+
+            let mut end = floor_char_boundary(text, start.saturating_add(self.max_bytes));
+            if end == text.len() {
+                chunks.push(&text[start..]);
+                break;
+            }
+            if let Some(whitespace) = text[start..end].rfind(char::is_whitespace) {
+                end = start + whitespace;
+            }
+            if end == start {
+                // the chunk is smaller than the next character
+                end += text[start..].chars().next().map_or(0, char::len_utf8);
+            }
+            chunks.push(text[start..end].trim_end());
+
+            let mut next_start = floor_char_boundary(text, end.saturating_sub(self.overlap_bytes));
+            if !is_word_boundary(next_start) {
+                // extend the overlap to the start of the word
+                if let Some(whitespace) = text[start..next_start].rfind(char::is_whitespace) {
+                    next_start = start + whitespace;
+                }
+            }
+            start = if next_start > start { next_start } else { end };
+        }
+
+        if chunks.is_empty() {
+            chunks.push(text);
+        }
+        chunks
+    }
+}
+
+fn floor_char_boundary(s: &str, index: usize) -> usize {
+    if index >= s.len() {
+        return s.len();
+    }
+    (0..=index).rev().find(|&i| s.is_char_boundary(i)).unwrap_or(0)
+}
+
 fn truncate(s: &mut String, max_bytes: usize) {
     if max_bytes >= s.len() {
         return;
@@ -173,7 +288,9 @@ fn truncate(s: &mut String, max_bytes: usize) {
 
 #[cfg(test)]
 mod test {
-    use super::Prompt;
+    use std::num::NonZeroUsize;
+
+    use super::{Chunking, Prompt};
     use crate::error::FaultSource;
     use crate::prompt::error::{NewPromptError, NewPromptErrorKind};
     use crate::prompt::truncate;
@@ -276,4 +393,42 @@ mod test {
         truncate(&mut s, 2);
         assert_eq!(s, "");
     }
+
+    #[test]
+    fn template_chunking() {
+        let chunking = Chunking { max_bytes: 12, overlap_bytes: 0 };
+        assert_eq!(
+            chunking.split("the quick brown fox jumps"),
+            ["the quick", "brown fox", "jumps"]
+        );
+
+        // the overlap is extended to the start of the word
+        let chunking = Chunking { max_bytes: 12, overlap_bytes: 4 };
+        assert_eq!(
+            chunking.split("the quick brown fox jumps"),
+            ["the quick", "quick brown", "brown fox", "fox jumps"]
+        );
+
+        // chunks are cut on char boundaries
+        let chunking = Chunking { max_bytes: 7, overlap_bytes: 0 };
+        assert_eq!(chunking.split("インテル"), ["イン", "テル"]);
+
+        let chunking = Chunking { max_bytes: 2, overlap_bytes: 0 };
+        assert_eq!(chunking.split("イン"), ["イ", "ン"]);
+
+        assert_eq!(chunking.split(""), [""]);
+
+        // a huge maximum doesn't overflow after the first chunk
+        let chunking = Chunking { max_bytes: usize::MAX, overlap_bytes: 0 };
+        assert_eq!(chunking.split("  the quick brown fox"), ["the quick brown fox"]);
+    }
+
+    #[test]
+    fn template_chunks_are_truncated_separately() {
+        let prompt = Prompt::new("{{doc.text}}".into(), NonZeroUsize::new(6)).unwrap();
+        assert_eq!(prompt.chunks("the quick brown fox"), ["the qu"]);
+
+        let prompt = prompt.with_chunking(Some(Chunking { max_bytes: 12, overlap_bytes: 0 }));
+        assert_eq!(prompt.chunks("the quick brown fox"), ["the qu", "brown "]);
+    }
 }
@@ -0,0 +1,315 @@
+//! The query rules of an index, used to curate the results of some searches.
+//!
+//! A rule fires when all of its conditions are met by a search, i.e. when the query matches its
+//! pattern, the filter of the search contains its filter and the search is made within its time
+//! window. The documents of a rule are then pinned at fixed positions, hidden from the results,
+//! or boosted before the other documents.
+
+use deserr::Deserr;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::ToSchema;
+
+use crate::date::parse_date;
+use crate::Filter;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum QueryRuleError {
+    #[error("`.queryRules[{index}].id`: The id of a query rule must not be empty.")]
+    EmptyId { index: usize },
+    #[error("`.queryRules[{index}].id`: The id `{id}` is already used by another query rule.")]
+    DuplicateId { index: usize, id: String },
+    #[error("`.queryRules[{index}].conditions.pattern`: The pattern must not be empty.")]
+    EmptyPattern { index: usize },
+    #[error("`.queryRules[{index}].conditions.filter`: {error}")]
+    InvalidFilter { index: usize, error: String },
+    #[error("`.queryRules[{index}].conditions.{field}`: `{date}` is not a valid date, it must be written like `2024-01-01` or `2024-01-01T00:00:00Z`.")]
+    InvalidDate { index: usize, field: &'static str, date: String },
+    #[error("`.queryRules[{index}].conditions`: `validFrom` must be before `validUntil`.")]
+    InvalidTimeWindow { index: usize },
+    #[error("`.queryRules[{index}].boost[{boost}]`: {error}")]
+    InvalidBoost { index: usize, boost: usize, error: String },
+}
+
+/// A rule curating the results of the searches meeting its conditions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Deserr, ToSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct QueryRule {
+    /// The identifier of the rule, listed in the search response when the rule fires.
+    pub id: String,
+    #[serde(default)]
+    #[deserr(default)]
+    pub conditions: QueryRuleConditions,
+    /// The documents to display at a fixed position, whether they match the query or not.
+    #[serde(default)]
+    #[deserr(default)]
+    pub pin: Vec<PinnedDocument>,
+    /// The ids of the documents to remove from the results.
+    #[serde(default)]
+    #[deserr(default)]
+    pub hide: Vec<String>,
+    /// The filters whose matching documents are displayed before the other ones.
+    #[serde(default)]
+    #[deserr(default)]
+    pub boost: Vec<String>,
+}
+
+/// The conditions a search must meet for a rule to fire, a rule without conditions always fires.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Deserr, ToSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct QueryRuleConditions {
+    /// The text the query must match, case-insensitively.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub pattern: Option<String>,
+    /// How the query must match the pattern.
+    #[serde(default)]
+    #[deserr(default)]
+    pub anchoring: QueryRuleAnchoring,
+    /// The filter the filter of the search must contain, e.g. `brand = apple`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub filter: Option<String>,
+    /// The date from which the rule fires, e.g. `2024-01-01` or `2024-01-01T00:00:00Z`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub valid_from: Option<String>,
+    /// The date until which the rule fires, excluded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub valid_until: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Deserr, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[deserr(rename_all = camelCase)]
+pub enum QueryRuleAnchoring {
+    /// The query is the pattern.
+    #[default]
+    Is,
+    /// The query starts with the pattern.
+    StartsWith,
+    /// The query ends with the pattern.
+    EndsWith,
+    /// The query contains the pattern.
+    Contains,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Deserr, ToSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct PinnedDocument {
+    /// The id of the document.
+    pub id: String,
+    /// The position of the document in the results, starting at `0`.
+    pub position: usize,
+}
+
+/// Lowercases the query and collapses its whitespaces so that it can be compared to the patterns.
+fn normalize_query(query: &str) -> String {
+    query.split_whitespace().map(str::to_lowercase).collect::<Vec<_>>().join(" ")
+}
+
+/// The conditions of a rule, parsed when the settings are applied so that the searches
+/// don't have to parse the filters and the dates of the rules again.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ParsedQueryRuleConditions {
+    /// The normalized pattern.
+    pattern: Option<String>,
+    anchoring: QueryRuleAnchoring,
+    /// The top-level AND clauses of the filter, as returned by [`Filter::clauses`].
+    filter_clauses: Vec<String>,
+    valid_from: Option<f64>,
+    valid_until: Option<f64>,
+}
+
+impl QueryRule {
+    /// Checks the rules, returning an error if one of them can never be applied.
+    pub fn validate(rules: &[QueryRule]) -> Result<(), QueryRuleError> {
+        Self::parse_conditions(rules).map(drop)
+    }
+
+    /// Checks and parses the conditions of the rules, in the order of the rules.
+    pub fn parse_conditions(
+        rules: &[QueryRule],
+    ) -> Result<Vec<ParsedQueryRuleConditions>, QueryRuleError> {
+        let mut ids = std::collections::HashSet::new();
+        let mut parsed = Vec::with_capacity(rules.len());
+        for (index, rule) in rules.iter().enumerate() {
+            if rule.id.trim().is_empty() {
+                return Err(QueryRuleError::EmptyId { index });
+            }
+            if !ids.insert(rule.id.as_str()) {
+                return Err(QueryRuleError::DuplicateId { index, id: rule.id.clone() });
+            }
+
+            let QueryRuleConditions { pattern, anchoring, filter, valid_from, valid_until } =
+                &rule.conditions;
+            let pattern = pattern.as_deref().map(normalize_query);
+            if pattern.as_ref().is_some_and(String::is_empty) {
+                return Err(QueryRuleError::EmptyPattern { index });
+            }
+            let filter = match filter {
+                Some(filter) => Filter::from_str(filter).map_err(|error| {
+                    QueryRuleError::InvalidFilter { index, error: error.to_string() }
+                })?,
+                None => None,
+            };
+            for (boost, filter) in rule.boost.iter().enumerate() {
+                Filter::from_str(filter).map_err(|error| QueryRuleError::InvalidBoost {
+                    index,
+                    boost,
+                    error: error.to_string(),
+                })?;
+            }
+
+            let parse = |field, date: &Option<String>| match date {
+                Some(date) => parse_date(date).map(Some).ok_or_else(|| {
+                    QueryRuleError::InvalidDate { index, field, date: date.clone() }
+                }),
+                None => Ok(None),
+            };
+            let valid_from = parse("validFrom", valid_from)?;
+            let valid_until = parse("validUntil", valid_until)?;
+            if let (Some(from), Some(until)) = (valid_from, valid_until) {
+                if from >= until {
+                    return Err(QueryRuleError::InvalidTimeWindow { index });
+                }
+            }
+
+            parsed.push(ParsedQueryRuleConditions {
+                pattern,
+                anchoring: *anchoring,
+                filter_clauses: filter.map(|filter| filter.clauses()).unwrap_or_default(),
+                valid_from,
+                valid_until,
+            });
+        }
+
+        Ok(parsed)
+    }
+}
+
+impl ParsedQueryRuleConditions {
+    /// Returns `true` if the rule must be applied to a search made with this query, whose filter
+    /// has these top-level AND clauses, at this time, as a unix timestamp in seconds.
+    pub fn fires(&self, query: &str, filter_clauses: &[String], now: f64) -> bool {
+        let ParsedQueryRuleConditions {
+            pattern,
+            anchoring,
+            filter_clauses: rule_filter_clauses,
+            valid_from,
+            valid_until,
+        } = self;
+
+        if let Some(pattern) = pattern {
+            let query = normalize_query(query);
+            let matches = match anchoring {
+                QueryRuleAnchoring::Is => query == *pattern,
+                QueryRuleAnchoring::StartsWith => query.starts_with(pattern),
+                QueryRuleAnchoring::EndsWith => query.ends_with(pattern),
+                QueryRuleAnchoring::Contains => query.contains(pattern),
+            };
+            if !matches {
+                return false;
+            }
+        }
+
+        if !rule_filter_clauses.iter().all(|clause| filter_clauses.contains(clause)) {
+            return false;
+        }
+
+        valid_from.is_none_or(|from| from <= now) && valid_until.is_none_or(|until| now < until)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(conditions: QueryRuleConditions) -> QueryRule {
+        QueryRule {
+            id: "rule".to_string(),
+            conditions,
+            pin: Vec::new(),
+            hide: Vec::new(),
+            boost: Vec::new(),
+        }
+    }
+
+    fn rule_fires(rule: &QueryRule, query: &str, filter: Option<&str>, now: f64) -> bool {
+        let conditions = QueryRule::parse_conditions(std::slice::from_ref(rule)).unwrap();
+        let filter = filter.map(|filter| Filter::from_str(filter).unwrap().unwrap());
+        let clauses = filter.map(|filter| filter.clauses()).unwrap_or_default();
+        conditions[0].fires(query, &clauses, now)
+    }
+
+    #[test]
+    fn fires() {
+        // 2024-01-01T00:00:00Z
+        let now = 1704067200.0;
+
+        let iphone = |anchoring| {
+            rule(QueryRuleConditions {
+                pattern: Some("iPhone".to_string()),
+                anchoring,
+                ..Default::default()
+            })
+        };
+        assert!(rule_fires(&iphone(QueryRuleAnchoring::Is), "  IPHONE ", None, now));
+        assert!(!rule_fires(&iphone(QueryRuleAnchoring::Is), "iphone case", None, now));
+        assert!(rule_fires(&iphone(QueryRuleAnchoring::StartsWith), "iphone case", None, now));
+        assert!(rule_fires(&iphone(QueryRuleAnchoring::EndsWith), "red iphone", None, now));
+        assert!(!rule_fires(&iphone(QueryRuleAnchoring::EndsWith), "iphone case", None, now));
+        assert!(rule_fires(&iphone(QueryRuleAnchoring::Contains), "red iphone case", None, now));
+
+        let apple = rule(QueryRuleConditions {
+            filter: Some("brand = apple".to_string()),
+            ..Default::default()
+        });
+        assert!(rule_fires(&apple, "", Some("price < 100 AND brand = \"apple\""), now));
+        assert!(!rule_fires(&apple, "", Some("brand = apple OR price < 100"), now));
+        assert!(!rule_fires(&apple, "", None, now));
+
+        let window = |from: &str, until: &str| {
+            rule(QueryRuleConditions {
+                valid_from: Some(from.to_string()),
+                valid_until: Some(until.to_string()),
+                ..Default::default()
+            })
+        };
+        assert!(rule_fires(&window("2023-12-31", "2024-01-02"), "", None, now));
+        assert!(rule_fires(&window("2024-01-01T00:00:00Z", "2024-01-02"), "", None, now));
+        assert!(!rule_fires(&window("2023-12-30", "2024-01-01"), "", None, now));
+        assert!(!rule_fires(&window("2024-01-02", "2024-01-03"), "", None, now));
+    }
+
+    #[test]
+    fn invalid_rules() {
+        let error = |rules: Vec<QueryRule>| QueryRule::validate(&rules).unwrap_err().to_string();
+
+        let mut invalid = rule(Default::default());
+        invalid.id = " ".to_string();
+        insta::assert_snapshot!(error(vec![invalid]), @"`.queryRules[0].id`: The id of a query rule must not be empty.");
+        insta::assert_snapshot!(error(vec![rule(Default::default()), rule(Default::default())]), @"`.queryRules[1].id`: The id `rule` is already used by another query rule.");
+
+        let invalid = rule(QueryRuleConditions {
+            valid_from: Some("tomorrow".to_string()),
+            ..Default::default()
+        });
+        insta::assert_snapshot!(error(vec![invalid]), @"`.queryRules[0].conditions.validFrom`: `tomorrow` is not a valid date, it must be written like `2024-01-01` or `2024-01-01T00:00:00Z`.");
+
+        let invalid = rule(QueryRuleConditions {
+            valid_from: Some("2024-01-02".to_string()),
+            valid_until: Some("2024-01-01".to_string()),
+            ..Default::default()
+        });
+        insta::assert_snapshot!(error(vec![invalid]), @"`.queryRules[0].conditions`: `validFrom` must be before `validUntil`.");
+
+        let mut invalid = rule(Default::default());
+        invalid.boost.push("brand =".to_string());
+        assert!(error(vec![invalid]).starts_with("`.queryRules[0].boost[0]`: "));
+    }
+}
@@ -0,0 +1,258 @@
+//! The decay ranking rules, e.g. `decay:gauss(release_date, now, 30d)`.
+//!
+//! A decay rule scores the documents according to the distance between the value of a field
+//! and an origin, the score being `1` at the origin and `0.5` at a distance equal to the scale.
+//! The field can either contain numbers or dates, in which case the origin must be a
+//! date or `now` and the scale can be expressed as a duration, e.g. `12h` or `30d`.
+
+use std::fmt;
+use std::str::FromStr;
+
+use thiserror::Error;
+use time::OffsetDateTime;
+
+use crate::date::{parse_date, parse_duration};
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum RankingDecayError {
+    #[error("A decay must be written `decay:<function>(<field>, <origin>, <scale>)`.")]
+    InvalidSyntax,
+    #[error(
+        "Unknown decay function `{name}`. Available functions are `gauss`, `exp` and `linear`."
+    )]
+    UnknownFunction { name: String },
+    #[error("The decay field is missing.")]
+    MissingField,
+    #[error("The origin `{origin}` is invalid. It must be a number, a date or `now`.")]
+    InvalidOrigin { origin: String },
+    #[error("The scale `{scale}` is invalid. It must be a positive number or a duration like `12h` or `30d`.")]
+    InvalidScale { scale: String },
+    #[error("The scale `{scale}` is a duration and can only be used with a date origin.")]
+    DurationWithNumericOrigin { scale: String },
+}
+
+/// How the score decreases with the distance to the origin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecayFunction {
+    Gauss,
+    Exp,
+    Linear,
+}
+
+/// The value the documents are compared to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DecayOrigin {
+    Number(f64),
+    /// A date, as a unix timestamp in seconds.
+    Date(f64),
+    /// The date at which the search is made.
+    Now,
+}
+
+/// A parsed decay ranking rule.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RankingDecay {
+    pub function: DecayFunction,
+    pub field: String,
+    pub origin: DecayOrigin,
+    /// The distance at which the score is `0.5`, in seconds for the dates.
+    pub scale: f64,
+}
+
+impl RankingDecay {
+    /// Returns `true` if the values of the field must be interpreted as dates.
+    pub fn is_date(&self) -> bool {
+        !matches!(self.origin, DecayOrigin::Number(_))
+    }
+
+    /// Returns the origin as a number, a unix timestamp in seconds for the dates.
+    pub fn origin_value(&self) -> f64 {
+        match self.origin {
+            DecayOrigin::Number(number) | DecayOrigin::Date(number) => number,
+            DecayOrigin::Now => OffsetDateTime::now_utc().unix_timestamp() as f64,
+        }
+    }
+
+    /// Returns the score, between `0` and `1`, of a value for the given origin.
+    pub fn score(&self, origin: f64, value: f64) -> f64 {
+        let distance = (value - origin).abs() / self.scale;
+        let score = match self.function {
+            DecayFunction::Gauss => 0.5f64.powf(distance * distance),
+            DecayFunction::Exp => 0.5f64.powf(distance),
+            DecayFunction::Linear => (1.0 - distance / 2.0).max(0.0),
+        };
+        if score.is_finite() {
+            score
+        } else {
+            0.0
+        }
+    }
+
+    /// Returns the distance to the origin at which a value gets the given score,
+    /// between `0` and `1` excluded, the inverse of [`Self::score`].
+    pub fn distance(&self, score: f64) -> f64 {
+        let distance = match self.function {
+            DecayFunction::Gauss => (-score.log2()).sqrt(),
+            DecayFunction::Exp => -score.log2(),
+            DecayFunction::Linear => 2.0 * (1.0 - score),
+        };
+        distance * self.scale
+    }
+}
+
+fn parse_origin(origin: &str) -> Result<DecayOrigin, RankingDecayError> {
+    if origin == "now" {
+        Ok(DecayOrigin::Now)
+    } else if let Some(date) = parse_date(origin) {
+        Ok(DecayOrigin::Date(date))
+    } else {
+        match origin.parse::<f64>() {
+            Ok(number) if number.is_finite() => Ok(DecayOrigin::Number(number)),
+            _ => Err(RankingDecayError::InvalidOrigin { origin: origin.to_string() }),
+        }
+    }
+}
+
+/// Parses the scale, returning whether it was written as a duration.
+fn parse_scale(scale: &str) -> Result<(f64, bool), RankingDecayError> {
+    let (scale_value, is_duration) = match parse_duration(scale) {
+        Some(duration) => (Some(duration), true),
+        None => (scale.parse::<f64>().ok().filter(|number| number.is_finite()), false),
+    };
+    match scale_value {
+        Some(scale_value) if scale_value > 0.0 => Ok((scale_value, is_duration)),
+        _ => Err(RankingDecayError::InvalidScale { scale: scale.to_string() }),
+    }
+}
+
+impl FromStr for RankingDecay {
+    type Err = RankingDecayError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (function, arguments) = s
+            .trim()
+            .strip_suffix(')')
+            .and_then(|s| s.split_once('('))
+            .ok_or(RankingDecayError::InvalidSyntax)?;
+
+        let function = match function.trim() {
+            "gauss" => DecayFunction::Gauss,
+            "exp" => DecayFunction::Exp,
+            "linear" => DecayFunction::Linear,
+            name => return Err(RankingDecayError::UnknownFunction { name: name.to_string() }),
+        };
+
+        let arguments: Vec<_> = arguments.split(',').map(str::trim).collect();
+        let [field, origin, scale] = arguments.as_slice() else {
+            return Err(RankingDecayError::InvalidSyntax);
+        };
+        if field.is_empty() {
+            return Err(RankingDecayError::MissingField);
+        }
+
+        let origin = parse_origin(origin)?;
+        let (scale, is_duration) = parse_scale(scale)?;
+        if is_duration && matches!(origin, DecayOrigin::Number(_)) {
+            return Err(RankingDecayError::DurationWithNumericOrigin {
+                scale: arguments[2].to_string(),
+            });
+        }
+
+        Ok(RankingDecay { function, field: field.to_string(), origin, scale })
+    }
+}
+
+impl fmt::Display for DecayFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecayFunction::Gauss => f.write_str("gauss"),
+            DecayFunction::Exp => f.write_str("exp"),
+            DecayFunction::Linear => f.write_str("linear"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_decay() {
+        let decay: RankingDecay = "gauss(price, 10, 5)".parse().unwrap();
+        assert_eq!(
+            decay,
+            RankingDecay {
+                function: DecayFunction::Gauss,
+                field: "price".to_string(),
+                origin: DecayOrigin::Number(10.0),
+                scale: 5.0,
+            }
+        );
+        assert!(!decay.is_date());
+
+        let decay: RankingDecay = " exp( release_date , now , 2d )".parse().unwrap();
+        assert_eq!(decay.function, DecayFunction::Exp);
+        assert_eq!(decay.field, "release_date");
+        assert_eq!(decay.origin, DecayOrigin::Now);
+        assert_eq!(decay.scale, 2.0 * 24.0 * 60.0 * 60.0);
+        assert!(decay.is_date());
+
+        let decay: RankingDecay = "linear(date, 2024-01-01T00:00:00Z, 3600)".parse().unwrap();
+        assert_eq!(decay.origin, DecayOrigin::Date(1704067200.0));
+        assert_eq!(decay.scale, 3600.0);
+    }
+
+    #[test]
+    fn invalid_decay() {
+        let error = |s: &str| s.parse::<RankingDecay>().unwrap_err().to_string();
+        insta::assert_snapshot!(error("gauss"), @"A decay must be written `decay:<function>(<field>, <origin>, <scale>)`.");
+        insta::assert_snapshot!(error("gauss(price, 10)"), @"A decay must be written `decay:<function>(<field>, <origin>, <scale>)`.");
+        insta::assert_snapshot!(error("sigmoid(price, 10, 5)"), @"Unknown decay function `sigmoid`. Available functions are `gauss`, `exp` and `linear`.");
+        insta::assert_snapshot!(error("gauss(, 10, 5)"), @"The decay field is missing.");
+        insta::assert_snapshot!(error("gauss(price, tomorrow, 5)"), @"The origin `tomorrow` is invalid. It must be a number, a date or `now`.");
+        insta::assert_snapshot!(error("gauss(price, 10, -5)"), @"The scale `-5` is invalid. It must be a positive number or a duration like `12h` or `30d`.");
+        insta::assert_snapshot!(error("gauss(price, 10, 5d)"), @"The scale `5d` is a duration and can only be used with a date origin.");
+    }
+
+    #[test]
+    fn score() {
+        let decay = |function| RankingDecay {
+            function,
+            field: "price".to_string(),
+            origin: DecayOrigin::Number(0.0),
+            scale: 10.0,
+        };
+
+        for function in [DecayFunction::Gauss, DecayFunction::Exp, DecayFunction::Linear] {
+            let decay = decay(function);
+            assert_eq!(decay.score(0.0, 0.0), 1.0);
+            assert_eq!(decay.score(0.0, 10.0), 0.5);
+            assert_eq!(decay.score(0.0, -10.0), 0.5);
+            assert!(decay.score(0.0, 15.0) < 0.5);
+        }
+
+        assert_eq!(decay(DecayFunction::Gauss).score(0.0, 20.0), 0.0625);
+        assert_eq!(decay(DecayFunction::Exp).score(0.0, 20.0), 0.25);
+        assert_eq!(decay(DecayFunction::Linear).score(0.0, 20.0), 0.0);
+        assert_eq!(decay(DecayFunction::Linear).score(0.0, 30.0), 0.0);
+    }
+
+    #[test]
+    fn distance() {
+        let decay = |function| RankingDecay {
+            function,
+            field: "price".to_string(),
+            origin: DecayOrigin::Number(0.0),
+            scale: 10.0,
+        };
+
+        for function in [DecayFunction::Gauss, DecayFunction::Exp, DecayFunction::Linear] {
+            let decay = decay(function);
+            assert_eq!(decay.distance(0.5), 10.0);
+            for score in [0.01, 0.25, 0.75, 0.99] {
+                let distance = decay.distance(score);
+                assert!((decay.score(0.0, distance) - score).abs() < 1e-9);
+            }
+        }
+    }
+}
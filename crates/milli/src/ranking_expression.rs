@@ -0,0 +1,493 @@
+//! The expressions of the `expr:` ranking rules, e.g. `expr: log(popularity + 1) * 0.3 + _rankingScore`.
+//!
+//! An expression is made of numbers, of numeric fields of the documents, of the
+//! `_rankingScore` variable, of the `+`, `-`, `*` and `/` operators, of parentheses
+//! and of the following functions: `log`, `log10`, `sqrt`, `abs`, `exp`, `min`, `max` and `pow`.
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+/// The variable holding the ranking score given by the ranking rules preceding the expression.
+pub const RANKING_SCORE_VARIABLE: &str = "_rankingScore";
+
+/// The maximum depth of the tree of an expression, bounding the recursion of the parser and of the evaluation.
+const MAX_EXPRESSION_DEPTH: usize = 200;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum RankingExpressionError {
+    #[error("The expression is empty.")]
+    Empty,
+    #[error("Unexpected character `{character}` at position {position}.")]
+    UnexpectedCharacter { character: char, position: usize },
+    #[error("Unexpected `{token}` at position {position}.")]
+    UnexpectedToken { token: String, position: usize },
+    #[error("Unexpected end of the expression.")]
+    UnexpectedEnd,
+    #[error("Unknown function `{name}`. Available functions are `log`, `log10`, `sqrt`, `abs`, `exp`, `min`, `max` and `pow`.")]
+    UnknownFunction { name: String },
+    #[error("The `{name}` function expects {expected} argument(s) but received {found}.")]
+    InvalidArgumentCount { name: &'static str, expected: usize, found: usize },
+    #[error("The expression exceeded the maximum depth limit. Try rewriting the expression so that it contains fewer nested operations.")]
+    DepthLimitReached,
+}
+
+/// A parsed ranking expression.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RankingExpression {
+    root: Node,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Number(f64),
+    Variable(String),
+    Neg(Box<Node>),
+    Binary(BinaryOp, Box<Node>, Box<Node>),
+    Function(Function, Vec<Node>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Function {
+    Log,
+    Log10,
+    Sqrt,
+    Abs,
+    Exp,
+    Min,
+    Max,
+    Pow,
+}
+
+impl Function {
+    fn from_name(name: &str) -> Option<Function> {
+        match name {
+            "log" => Some(Function::Log),
+            "log10" => Some(Function::Log10),
+            "sqrt" => Some(Function::Sqrt),
+            "abs" => Some(Function::Abs),
+            "exp" => Some(Function::Exp),
+            "min" => Some(Function::Min),
+            "max" => Some(Function::Max),
+            "pow" => Some(Function::Pow),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Function::Log => "log",
+            Function::Log10 => "log10",
+            Function::Sqrt => "sqrt",
+            Function::Abs => "abs",
+            Function::Exp => "exp",
+            Function::Min => "min",
+            Function::Max => "max",
+            Function::Pow => "pow",
+        }
+    }
+
+    fn arity(&self) -> usize {
+        match self {
+            Function::Log | Function::Log10 | Function::Sqrt | Function::Abs | Function::Exp => 1,
+            Function::Min | Function::Max | Function::Pow => 2,
+        }
+    }
+
+    fn apply(&self, args: &[f64]) -> f64 {
+        match self {
+            Function::Log => args[0].ln(),
+            Function::Log10 => args[0].log10(),
+            Function::Sqrt => args[0].sqrt(),
+            Function::Abs => args[0].abs(),
+            Function::Exp => args[0].exp(),
+            Function::Min => args[0].min(args[1]),
+            Function::Max => args[0].max(args[1]),
+            Function::Pow => args[0].powf(args[1]),
+        }
+    }
+}
+
+impl RankingExpression {
+    /// Returns the names of the fields used by the expression, the `_rankingScore` variable excluded.
+    pub fn fields(&self) -> BTreeSet<&str> {
+        fn collect<'a>(node: &'a Node, fields: &mut BTreeSet<&'a str>) {
+            match node {
+                Node::Number(_) => (),
+                Node::Variable(name) => {
+                    if name != RANKING_SCORE_VARIABLE {
+                        fields.insert(name);
+                    }
+                }
+                Node::Neg(node) => collect(node, fields),
+                Node::Binary(_, left, right) => {
+                    collect(left, fields);
+                    collect(right, fields);
+                }
+                Node::Function(_, args) => args.iter().for_each(|arg| collect(arg, fields)),
+            }
+        }
+
+        let mut fields = BTreeSet::new();
+        collect(&self.root, &mut fields);
+        fields
+    }
+
+    /// Returns `true` if the expression uses the `_rankingScore` variable.
+    pub fn uses_ranking_score(&self) -> bool {
+        fn uses(node: &Node) -> bool {
+            match node {
+                Node::Number(_) => false,
+                Node::Variable(name) => name == RANKING_SCORE_VARIABLE,
+                Node::Neg(node) => uses(node),
+                Node::Binary(_, left, right) => uses(left) || uses(right),
+                Node::Function(_, args) => args.iter().any(uses),
+            }
+        }
+        uses(&self.root)
+    }
+
+    /// Evaluates the expression with the values of the variables returned by the given function.
+    ///
+    /// Returns `None` if one of the variables has no value or if the result is not a finite number.
+    pub fn evaluate(&self, variable: &impl Fn(&str) -> Option<f64>) -> Option<f64> {
+        fn evaluate(node: &Node, variable: &impl Fn(&str) -> Option<f64>) -> Option<f64> {
+            match node {
+                Node::Number(number) => Some(*number),
+                Node::Variable(name) => variable(name),
+                Node::Neg(node) => evaluate(node, variable).map(|value| -value),
+                Node::Binary(op, left, right) => {
+                    let (left, right) = (evaluate(left, variable)?, evaluate(right, variable)?);
+                    Some(match op {
+                        BinaryOp::Add => left + right,
+                        BinaryOp::Sub => left - right,
+                        BinaryOp::Mul => left * right,
+                        BinaryOp::Div => left / right,
+                    })
+                }
+                Node::Function(function, args) => {
+                    let args: Option<Vec<f64>> =
+                        args.iter().map(|arg| evaluate(arg, variable)).collect();
+                    Some(function.apply(&args?))
+                }
+            }
+        }
+
+        evaluate(&self.root, variable).filter(|value| value.is_finite())
+    }
+}
+
+impl FromStr for RankingExpression {
+    type Err = RankingExpressionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens = tokenize(s)?;
+        if tokens.is_empty() {
+            return Err(RankingExpressionError::Empty);
+        }
+        let mut parser = Parser { tokens, position: 0 };
+        let root = parser.parse_sum(0)?;
+        match parser.tokens.get(parser.position) {
+            Some((token, position)) => Err(RankingExpressionError::UnexpectedToken {
+                token: token.to_string(),
+                position: *position,
+            }),
+            None => Ok(RankingExpression { root }),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Identifier(String),
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    LeftParen,
+    RightParen,
+    Comma,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Number(number) => write!(f, "{number}"),
+            Token::Identifier(identifier) => f.write_str(identifier),
+            Token::Plus => f.write_str("+"),
+            Token::Minus => f.write_str("-"),
+            Token::Star => f.write_str("*"),
+            Token::Slash => f.write_str("/"),
+            Token::LeftParen => f.write_str("("),
+            Token::RightParen => f.write_str(")"),
+            Token::Comma => f.write_str(","),
+        }
+    }
+}
+
+/// Splits the expression in tokens, each of them associated with its position in the expression.
+fn tokenize(s: &str) -> Result<Vec<(Token, usize)>, RankingExpressionError> {
+    let mut tokens = Vec::new();
+    let mut chars = s.char_indices().peekable();
+
+    while let Some((position, c)) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '+' => Token::Plus,
+            '-' => Token::Minus,
+            '*' => Token::Star,
+            '/' => Token::Slash,
+            '(' => Token::LeftParen,
+            ')' => Token::RightParen,
+            ',' => Token::Comma,
+            c if c.is_ascii_digit() || c == '.' => {
+                let mut end = position + c.len_utf8();
+                while let Some(&(i, c)) = chars.peek() {
+                    // the exponent of a number can be signed, e.g. `1e-3`
+                    let is_exponent_sign = matches!(c, '+' | '-') && s[..i].ends_with(['e', 'E']);
+                    if c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E') || is_exponent_sign {
+                        end = i + c.len_utf8();
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                let number = &s[position..end];
+                match number.parse() {
+                    Ok(number) => Token::Number(number),
+                    Err(_) => {
+                        return Err(RankingExpressionError::UnexpectedToken {
+                            token: number.to_string(),
+                            position,
+                        })
+                    }
+                }
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut end = position + c.len_utf8();
+                while let Some(&(i, c)) = chars.peek() {
+                    if c.is_alphanumeric() || c == '_' || c == '.' {
+                        end = i + c.len_utf8();
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                Token::Identifier(s[position..end].to_string())
+            }
+            character => {
+                return Err(RankingExpressionError::UnexpectedCharacter { character, position })
+            }
+        };
+        tokens.push((token, position));
+    }
+
+    Ok(tokens)
+}
+
+/// A recursive descent parser over the tokens of an expression.
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(token, _)| token)
+    }
+
+    fn next(&mut self) -> Result<(Token, usize), RankingExpressionError> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token.ok_or(RankingExpressionError::UnexpectedEnd)
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), RankingExpressionError> {
+        match self.next()? {
+            (token, _) if token == expected => Ok(()),
+            (token, position) => {
+                Err(RankingExpressionError::UnexpectedToken { token: token.to_string(), position })
+            }
+        }
+    }
+
+    /// Every operation nests the tree of the expression deeper, the left operand included.
+    fn check_depth(depth: usize) -> Result<(), RankingExpressionError> {
+        if depth > MAX_EXPRESSION_DEPTH {
+            return Err(RankingExpressionError::DepthLimitReached);
+        }
+        Ok(())
+    }
+
+    /// sum = product (('+' | '-') product)*
+    fn parse_sum(&mut self, mut depth: usize) -> Result<Node, RankingExpressionError> {
+        Self::check_depth(depth)?;
+        let mut node = self.parse_product(depth + 1)?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Plus) => BinaryOp::Add,
+                Some(Token::Minus) => BinaryOp::Sub,
+                _ => return Ok(node),
+            };
+            self.position += 1;
+            depth += 1;
+            Self::check_depth(depth)?;
+            node = Node::Binary(op, Box::new(node), Box::new(self.parse_product(depth + 1)?));
+        }
+    }
+
+    /// product = unary (('*' | '/') unary)*
+    fn parse_product(&mut self, mut depth: usize) -> Result<Node, RankingExpressionError> {
+        Self::check_depth(depth)?;
+        let mut node = self.parse_unary(depth + 1)?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Star) => BinaryOp::Mul,
+                Some(Token::Slash) => BinaryOp::Div,
+                _ => return Ok(node),
+            };
+            self.position += 1;
+            depth += 1;
+            Self::check_depth(depth)?;
+            node = Node::Binary(op, Box::new(node), Box::new(self.parse_unary(depth + 1)?));
+        }
+    }
+
+    /// unary = '-' unary | atom
+    fn parse_unary(&mut self, depth: usize) -> Result<Node, RankingExpressionError> {
+        Self::check_depth(depth)?;
+        if self.peek() == Some(&Token::Minus) {
+            self.position += 1;
+            return Ok(Node::Neg(Box::new(self.parse_unary(depth + 1)?)));
+        }
+        self.parse_atom(depth + 1)
+    }
+
+    /// atom = number | identifier | identifier '(' arguments ')' | '(' sum ')'
+    fn parse_atom(&mut self, depth: usize) -> Result<Node, RankingExpressionError> {
+        Self::check_depth(depth)?;
+        match self.next()? {
+            (Token::Number(number), _) => Ok(Node::Number(number)),
+            (Token::LeftParen, _) => {
+                let node = self.parse_sum(depth + 1)?;
+                self.expect(Token::RightParen)?;
+                Ok(node)
+            }
+            (Token::Identifier(name), _) if self.peek() == Some(&Token::LeftParen) => {
+                self.position += 1;
+                let function = Function::from_name(&name)
+                    .ok_or(RankingExpressionError::UnknownFunction { name })?;
+                let mut args = Vec::new();
+                if self.peek() != Some(&Token::RightParen) {
+                    args.push(self.parse_sum(depth + 1)?);
+                    while self.peek() == Some(&Token::Comma) {
+                        self.position += 1;
+                        args.push(self.parse_sum(depth + 1)?);
+                    }
+                }
+                self.expect(Token::RightParen)?;
+                if args.len() != function.arity() {
+                    return Err(RankingExpressionError::InvalidArgumentCount {
+                        name: function.name(),
+                        expected: function.arity(),
+                        found: args.len(),
+                    });
+                }
+                Ok(Node::Function(function, args))
+            }
+            (Token::Identifier(name), _) => Ok(Node::Variable(name)),
+            (token, position) => {
+                Err(RankingExpressionError::UnexpectedToken { token: token.to_string(), position })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evaluate(expression: &str, variables: &[(&str, f64)]) -> Option<f64> {
+        let expression: RankingExpression = expression.parse().unwrap();
+        expression.evaluate(&|name| {
+            variables.iter().find(|(variable, _)| *variable == name).map(|(_, value)| *value)
+        })
+    }
+
+    #[test]
+    fn parse_and_evaluate() {
+        assert_eq!(evaluate("1 + 2 * 3", &[]), Some(7.0));
+        assert_eq!(evaluate("(1 + 2) * 3", &[]), Some(9.0));
+        assert_eq!(evaluate("10 - 4 - 3", &[]), Some(3.0));
+        assert_eq!(evaluate("8 / 4 / 2", &[]), Some(1.0));
+        assert_eq!(evaluate("-2 * -3", &[]), Some(6.0));
+        assert_eq!(evaluate("1.5e2 + 2E-1", &[]), Some(150.2));
+        assert_eq!(evaluate("pow(2, 10)", &[]), Some(1024.0));
+        assert_eq!(evaluate("max(min(3, 4), abs(-2))", &[]), Some(3.0));
+        assert_eq!(evaluate("log10(1000)", &[]), Some(3.0));
+        assert_eq!(evaluate("sqrt(16) + log(exp(2))", &[]), Some(6.0));
+        assert_eq!(
+            evaluate(
+                "log(popularity + 1) * 0.3 + _rankingScore",
+                &[("popularity", 0.0), ("_rankingScore", 0.5)]
+            ),
+            Some(0.5)
+        );
+        assert_eq!(evaluate("stats.views / 2", &[("stats.views", 42.0)]), Some(21.0));
+        assert_eq!(evaluate(&format!("{}1{}", "(".repeat(20), ")".repeat(20)), &[]), Some(1.0));
+    }
+
+    #[test]
+    fn evaluate_without_a_value() {
+        // a missing variable
+        assert_eq!(evaluate("popularity + 1", &[]), None);
+        // an infinite result
+        assert_eq!(evaluate("1 / popularity", &[("popularity", 0.0)]), None);
+        assert_eq!(evaluate("log(0)", &[]), None);
+        // not a number
+        assert_eq!(evaluate("sqrt(-1)", &[]), None);
+    }
+
+    #[test]
+    fn fields() {
+        let expression: RankingExpression =
+            "log(popularity + 1) * 0.3 + _rankingScore - stats.views / popularity".parse().unwrap();
+        assert_eq!(expression.fields(), BTreeSet::from(["popularity", "stats.views"]));
+        assert!(expression.uses_ranking_score());
+
+        let expression: RankingExpression = "price * 2".parse().unwrap();
+        assert!(!expression.uses_ranking_score());
+    }
+
+    #[test]
+    fn invalid_expressions() {
+        let error =
+            |expression: &str| expression.parse::<RankingExpression>().unwrap_err().to_string();
+
+        insta::assert_snapshot!(error(""), @"The expression is empty.");
+        insta::assert_snapshot!(error("   "), @"The expression is empty.");
+        insta::assert_snapshot!(error("1 +"), @"Unexpected end of the expression.");
+        insta::assert_snapshot!(error("(1 + 2"), @"Unexpected end of the expression.");
+        insta::assert_snapshot!(error("1 + 2)"), @"Unexpected `)` at position 5.");
+        insta::assert_snapshot!(error("1 2"), @"Unexpected `2` at position 2.");
+        insta::assert_snapshot!(error("price % 2"), @"Unexpected character `%` at position 6.");
+        insta::assert_snapshot!(error("1..2"), @"Unexpected `1..2` at position 0.");
+        insta::assert_snapshot!(error("ln(price)"), @"Unknown function `ln`. Available functions are `log`, `log10`, `sqrt`, `abs`, `exp`, `min`, `max` and `pow`.");
+        insta::assert_snapshot!(error("pow(price)"), @"The `pow` function expects 2 argument(s) but received 1.");
+        insta::assert_snapshot!(error("log(price, 2)"), @"The `log` function expects 1 argument(s) but received 2.");
+        insta::assert_snapshot!(error(&format!("{}1{}", "(".repeat(100), ")".repeat(100))), @"The expression exceeded the maximum depth limit. Try rewriting the expression so that it contains fewer nested operations.");
+        insta::assert_snapshot!(error(&"-".repeat(1000)), @"The expression exceeded the maximum depth limit. Try rewriting the expression so that it contains fewer nested operations.");
+        insta::assert_snapshot!(error(&vec!["1"; 1000].join(" + ")), @"The expression exceeded the maximum depth limit. Try rewriting the expression so that it contains fewer nested operations.");
+    }
+}
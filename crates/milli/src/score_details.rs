@@ -17,6 +17,10 @@ pub enum ScoreDetails {
     Sort(Sort),
     Vector(Vector),
     GeoSort(GeoSort),
+    Expression(Expression),
+    Decay(Decay),
+    Boost(Boost),
+    Rerank(Rerank),
 
     /// Returned when we don't have the time to finish applying all the subsequent ranking-rules
     Skipped,
@@ -59,9 +63,13 @@ impl ScoreDetails {
             ScoreDetails::Position(details) => Some(*details),
             ScoreDetails::ExactAttribute(details) => Some(details.rank()),
             ScoreDetails::ExactWords(details) => Some(details.rank()),
+            ScoreDetails::Decay(details) => Some(details.rank),
+            ScoreDetails::Boost(details) => Some(details.rank()),
             ScoreDetails::Sort(_) => None,
             ScoreDetails::GeoSort(_) => None,
+            ScoreDetails::Expression(details) => Some(details.rank()),
             ScoreDetails::Vector(_) => None,
+            ScoreDetails::Rerank(_) => None,
             ScoreDetails::Skipped => Some(Rank { rank: 0, max_rank: 1 }),
         }
     }
@@ -77,6 +85,15 @@ impl ScoreDetails {
             .unwrap_or(1.0f64)
     }
 
+    /// The chunk of the document that matched best, when it was ranked by an embedder splitting
+    /// the documents into chunks.
+    pub fn matched_chunk<'a>(mut details: impl Iterator<Item = &'a Self>) -> Option<usize> {
+        details.find_map(|details| match details {
+            ScoreDetails::Vector(vector) => vector.matched_chunk,
+            _ => None,
+        })
+    }
+
     pub fn score_values<'a>(
         details: impl Iterator<Item = &'a Self> + 'a,
     ) -> impl Iterator<Item = ScoreValue<'a>> + 'a {
@@ -116,6 +133,7 @@ impl ScoreDetails {
                 RankOrValue::GeoSort(g) => {
                     WeightedScoreValue::GeoSort { asc: g.ascending, distance: g.distance() }
                 }
+                // an expression sorts the documents by decreasing values, just like a descending sort
                 RankOrValue::Score(s) => WeightedScoreValue::VectorSort(s * weight),
             })
     }
@@ -129,11 +147,15 @@ impl ScoreDetails {
             ScoreDetails::Position(p) => RankOrValue::Rank(*p),
             ScoreDetails::ExactAttribute(e) => RankOrValue::Rank(e.rank()),
             ScoreDetails::ExactWords(e) => RankOrValue::Rank(e.rank()),
+            ScoreDetails::Decay(d) => RankOrValue::Rank(d.rank),
+            ScoreDetails::Boost(b) => RankOrValue::Rank(b.rank()),
             ScoreDetails::Sort(sort) => RankOrValue::Sort(sort),
             ScoreDetails::GeoSort(geosort) => RankOrValue::GeoSort(geosort),
+            ScoreDetails::Expression(e) => RankOrValue::Rank(e.rank()),
             ScoreDetails::Vector(vector) => {
                 RankOrValue::Score(vector.similarity.as_ref().map(|s| *s as f64).unwrap_or(0.0f64))
             }
+            ScoreDetails::Rerank(rerank) => RankOrValue::Score(rerank.normalized_score),
             ScoreDetails::Skipped => RankOrValue::Rank(Rank { rank: 0, max_rank: 1 }),
         }
     }
@@ -281,16 +303,70 @@ impl ScoreDetails {
                     details_map.insert(sort, sort_details);
                     order += 1;
                 }
+                ScoreDetails::Expression(details) => {
+                    let expression = if details.redacted {
+                        format!("<hidden-rule-{order}>")
+                    } else {
+                        format!("expr:{}", details.expression)
+                    };
+                    let value: serde_json::Value =
+                        if details.redacted { "<hidden>".into() } else { details.value.into() };
+                    let expression_details = serde_json::json!({
+                        "order": order,
+                        "value": value,
+                        "score": details.rank().local_score(),
+                    });
+                    details_map.insert(expression, expression_details);
+                    order += 1;
+                }
+                ScoreDetails::Decay(details) => {
+                    let decay = if details.redacted {
+                        format!("<hidden-rule-{order}>")
+                    } else {
+                        format!("decay:{}", details.decay)
+                    };
+                    let decay_details = serde_json::json!({
+                        "order": order,
+                        "score": details.rank.local_score(),
+                    });
+                    details_map.insert(decay, decay_details);
+                    order += 1;
+                }
+                ScoreDetails::Boost(details) => {
+                    let boost_details = serde_json::json!({
+                        "order": order,
+                        "weight": details.weight,
+                        "maxWeight": details.max_weight,
+                        "score": details.rank().local_score(),
+                    });
+                    details_map.insert("boost".into(), boost_details);
+                    order += 1;
+                }
                 ScoreDetails::Vector(s) => {
                     let similarity = s.similarity.as_ref();
 
-                    let details = serde_json::json!({
+                    let mut details = serde_json::json!({
                         "order": order,
                         "similarity": similarity,
                     });
+                    if let Some(matched_chunk) = s.matched_chunk {
+                        details["matchedChunk"] = matched_chunk.into();
+                    }
                     details_map.insert("vectorSort".into(), details);
                     order += 1;
                 }
+                ScoreDetails::Rerank(rerank) => {
+                    let mut details = serde_json::json!({
+                        "order": order,
+                        "reranker": rerank.reranker,
+                        "score": rerank.normalized_score,
+                    });
+                    if let Some(score) = rerank.score {
+                        details["rerankerScore"] = serde_json::json!(score);
+                    }
+                    details_map.insert("rerank".into(), details);
+                    order += 1;
+                }
                 ScoreDetails::Skipped => {
                     details_map
                         .insert("skipped".to_string(), serde_json::json!({ "order": order }));
@@ -540,9 +616,94 @@ impl PartialOrd for GeoSort {
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expression {
+    pub expression: String,
+    pub redacted: bool,
+    /// The value of the expression, `None` when it could not be computed for the documents.
+    pub value: Option<f64>,
+    /// The value of the expression normalized between the smallest and the largest values
+    /// of the sorted documents, `None` when it could not be computed for the documents.
+    pub score: Option<f64>,
+}
+
+impl Expression {
+    /// Ranks the documents like a decay, by their normalized value.
+    pub fn rank(&self) -> Rank {
+        Decay::rank_from_score(self.score)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Decay {
+    pub decay: String,
+    pub redacted: bool,
+    pub rank: Rank,
+}
+
+impl Decay {
+    /// The number of distinct ranks a decay can give, documents with close scores being ranked together.
+    pub const MAX_RANK: u32 = 101;
+
+    /// Converts a score between `0` and `1` into a rank, the documents without a score having the last rank.
+    pub fn rank_from_score(score: Option<f64>) -> Rank {
+        let rank = match score {
+            Some(score) => 1 + (score.clamp(0.0, 1.0) * (Self::MAX_RANK - 1) as f64).round() as u32,
+            None => 1,
+        };
+        Rank { rank, max_rank: Self::MAX_RANK }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Boost {
+    /// The sum of the weights of the boosts matched by the document.
+    pub weight: f64,
+    /// The sum of the weights of all the boosts of the search.
+    pub max_weight: f64,
+}
+
+impl Boost {
+    /// Ranks the documents like a decay, by the share of the total weight they match.
+    pub fn rank(&self) -> Rank {
+        let score = if self.max_weight > 0.0 { self.weight / self.max_weight } else { 0.0 };
+        Decay::rank_from_score(Some(score))
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
 pub struct Vector {
     pub similarity: Option<f32>,
+    /// The index of the chunk of the document that matched best,
+    /// only set when the embedder splits the documents into chunks.
+    pub matched_chunk: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rerank {
+    /// The name of the reranker that scored the best documents of the search.
+    pub reranker: String,
+    /// The relevance score returned by the reranker, `None` if the document wasn't reranked.
+    pub score: Option<f32>,
+    /// The score of the document, above `0.5` for the reranked documents, and up to `0.5` for the
+    /// following ones, so that they are always ranked after the reranked ones.
+    pub normalized_score: f64,
+}
+
+impl Rerank {
+    /// Scores a reranked document, normalizing the relevance score returned by the reranker
+    /// with a sigmoid, as some rerankers return raw logits instead of probabilities.
+    pub fn reranked(reranker: String, score: f32) -> Self {
+        let sigmoid = 1.0 / (1.0 + (-score as f64).exp());
+        // the sigmoid of very low scores rounds to zero, but must remain above the other documents
+        let normalized_score = (0.5 + sigmoid / 2.0).max(0.5 + f64::EPSILON);
+        Self { reranker, score: Some(score), normalized_score }
+    }
+
+    /// Scores a document following the reranked documents, given the global score it had before.
+    pub fn not_reranked(reranker: String, global_score: f64) -> Self {
+        Self { reranker, score: None, normalized_score: global_score / 2.0 }
+    }
 }
 
 impl GeoSort {
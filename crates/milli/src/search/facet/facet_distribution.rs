@@ -1,6 +1,6 @@
 use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fmt::Display;
-use std::ops::ControlFlow;
+use std::ops::{Bound, ControlFlow};
 use std::{fmt, mem};
 
 use heed::types::Bytes;
@@ -10,15 +10,19 @@ use roaring::RoaringBitmap;
 use serde::{Deserialize, Serialize};
 
 use crate::attribute_patterns::match_field_legacy;
+use crate::date::parse_date;
 use crate::facet::FacetType;
 use crate::filterable_attributes_rules::{filtered_matching_patterns, matching_features};
 use crate::heed_codec::facet::{
-    FacetGroupKeyCodec, FieldDocIdFacetF64Codec, FieldDocIdFacetStringCodec, OrderedF64Codec,
+    FacetGroupKey, FacetGroupKeyCodec, FacetGroupValue, FieldDocIdFacetF64Codec,
+    FieldDocIdFacetStringCodec, OrderedF64Codec,
 };
 use crate::heed_codec::{BytesRefCodec, StrRefCodec};
 use crate::search::facet::facet_distribution_iter::{
     count_iterate_over_facet_distribution, lexicographically_iterate_over_facet_distribution,
 };
+use crate::search::facet::facet_range_search::find_docids_of_facet_within_bounds;
+use crate::search::facet::{facet_max_value, facet_min_value};
 use crate::{Error, FieldId, FilterableAttributesRule, Index, PatternMatch, Result, UserError};
 
 /// The default number of values by facets that will
@@ -29,6 +33,92 @@ pub const DEFAULT_VALUES_PER_FACET: usize = 100;
 /// the system to choose between one algorithm or another.
 const CANDIDATES_THRESHOLD: u64 = 3000;
 
+/// The maximum number of buckets a facet histogram can be made of.
+pub const MAX_HISTOGRAM_BUCKETS: usize = 1000;
+
+/// A bucket of a facet histogram: the number of documents
+/// with a value greater than or equal to `from` and lower than `to`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HistogramBucket {
+    pub from: f64,
+    pub to: f64,
+    pub count: u64,
+}
+
+/// A bucket of a range facet, e.g. `50..100`: the values greater than or equal
+/// to `from` and lower than `to`, a missing bound meaning the range is unbounded.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FacetRange {
+    pub from: Option<f64>,
+    pub to: Option<f64>,
+}
+
+impl FacetRange {
+    /// Parses a facet written with the range-bucket syntax, e.g. `price[0..50, 50..100, 100..]`,
+    /// into the name of the facet and its ranges.
+    ///
+    /// Returns `None` if the facet is a plain facet name.
+    pub fn parse_facet(facet: &str) -> Result<Option<(&str, Vec<FacetRange>)>> {
+        let Some(facet_ranges) = facet.trim_end().strip_suffix(']') else { return Ok(None) };
+        let invalid = || UserError::InvalidFacetRange { facet: facet.to_string() };
+
+        let (name, ranges) = facet_ranges.rsplit_once('[').ok_or_else(invalid)?;
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(invalid().into());
+        }
+        let ranges = ranges.split(',').map(Self::parse_range).collect::<Option<_>>();
+        match ranges {
+            Some(ranges) => Ok(Some((name, ranges))),
+            None => Err(invalid().into()),
+        }
+    }
+
+    fn parse_range(range: &str) -> Option<FacetRange> {
+        let (from, to) = range.trim().split_once("..")?;
+        let parse_bound = |bound: &str| match bound.trim() {
+            "" => Some(None),
+            bound => bound.parse::<f64>().ok().filter(|n| n.is_finite()).map(Some),
+        };
+        let range = FacetRange { from: parse_bound(from)?, to: parse_bound(to)? };
+        match range {
+            FacetRange { from: Some(from), to: Some(to) } if from >= to => None,
+            range => Some(range),
+        }
+    }
+
+    fn bounds(&self) -> (Bound<f64>, Bound<f64>) {
+        let from = self.from.map_or(Bound::Unbounded, Bound::Included);
+        let to = self.to.map_or(Bound::Unbounded, Bound::Excluded);
+        (from, to)
+    }
+}
+
+impl Display for FacetRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(from) = self.from {
+            write!(f, "{from}")?;
+        }
+        f.write_str("..")?;
+        if let Some(to) = self.to {
+            write!(f, "{to}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A category tree whose levels are stored in different fields, e.g. `categories.lvl0`
+/// containing `Books` and `categories.lvl1` containing `Books > Science`, the value
+/// of a category being its path: its ancestors and itself joined by a separator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FacetHierarchy {
+    /// The fields of the levels of the tree, from the root to the leaves.
+    pub levels: Vec<String>,
+    pub separator: String,
+    /// The path of the selected category, e.g. `Books > Science`, if any.
+    pub path: Option<String>,
+}
+
 /// How should we fetch the facets?
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum OrderBy {
@@ -50,6 +140,8 @@ impl Display for OrderBy {
 
 pub struct FacetDistribution<'a> {
     facets: Option<HashMap<String, OrderBy>>,
+    ranges: BTreeMap<String, Vec<FacetRange>>,
+    hierarchies: Vec<FacetHierarchy>,
     candidates: Option<RoaringBitmap>,
     max_values_per_facet: usize,
     default_order_by: OrderBy,
@@ -61,6 +153,8 @@ impl<'a> FacetDistribution<'a> {
     pub fn new(rtxn: &'a heed::RoTxn<'a>, index: &'a Index) -> FacetDistribution<'a> {
         FacetDistribution {
             facets: None,
+            ranges: BTreeMap::new(),
+            hierarchies: Vec::new(),
             candidates: None,
             max_values_per_facet: DEFAULT_VALUES_PER_FACET,
             default_order_by: OrderBy::default(),
@@ -82,6 +176,27 @@ impl<'a> FacetDistribution<'a> {
         self
     }
 
+    /// Counts the documents in the given ranges of values of these facets,
+    /// instead of counting the documents of each one of their values.
+    pub fn ranges<I: IntoIterator<Item = (A, Vec<FacetRange>)>, A: AsRef<str>>(
+        &mut self,
+        names_ranges: I,
+    ) -> &mut Self {
+        self.ranges = names_ranges
+            .into_iter()
+            .map(|(name, ranges)| (name.as_ref().to_string(), ranges))
+            .collect();
+        self
+    }
+
+    /// Returns the distribution of the levels of these category trees, from the root down to
+    /// the children of the selected category, the values of each level being restricted to
+    /// the children of the selected category's ancestor of the previous level.
+    pub fn hierarchies(&mut self, hierarchies: Vec<FacetHierarchy>) -> &mut Self {
+        self.hierarchies = hierarchies;
+        self
+    }
+
     pub fn max_values_per_facet(&mut self, max: usize) -> &mut Self {
         self.max_values_per_facet = max;
         self
@@ -244,10 +359,60 @@ impl<'a> FacetDistribution<'a> {
         )
     }
 
+    /// Returns the distribution of the numbers of a date field, without the timestamps
+    /// derived from its dates as they are also stored, as written in the documents,
+    /// in the string facets.
+    fn facet_numbers_distribution_without_dates(
+        &self,
+        field_id: FieldId,
+        candidates: &RoaringBitmap,
+        order_by: OrderBy,
+        distribution: &mut IndexMap<String, u64>,
+    ) -> heed::Result<()> {
+        // the documents of the timestamps of the dates, by the bits of the timestamps
+        let mut dates_docids: HashMap<u64, RoaringBitmap> = HashMap::new();
+        let base = FacetGroupKey { field_id, level: 0, left_bound: "" };
+        let strings_db = self.index.facet_id_string_docids;
+        for result in strings_db.prefix_iter(self.rtxn, &base)? {
+            let (FacetGroupKey { left_bound, .. }, FacetGroupValue { bitmap, .. }) = result?;
+            if let Some(timestamp) = parse_date(left_bound) {
+                *dates_docids.entry(timestamp.to_bits()).or_default() |= bitmap & candidates;
+            }
+        }
+
+        let mut numbers = Vec::new();
+        let base = FacetGroupKey { field_id, level: 0, left_bound: &[][..] };
+        let numbers_db =
+            self.index.facet_id_f64_docids.remap_key_type::<FacetGroupKeyCodec<BytesRefCodec>>();
+        for result in numbers_db.prefix_iter(self.rtxn, &base)? {
+            let (FacetGroupKey { left_bound, .. }, FacetGroupValue { bitmap, .. }) = result?;
+            let number = OrderedF64Codec::bytes_decode(left_bound).unwrap();
+            let mut docids = bitmap & candidates;
+            if let Some(dates_docids) = dates_docids.get(&number.to_bits()) {
+                docids -= dates_docids;
+            }
+            if !docids.is_empty() {
+                numbers.push((number, docids.len()));
+            }
+        }
+
+        if order_by == OrderBy::Count {
+            numbers.sort_by(|(_, left), (_, right)| right.cmp(left));
+        }
+        let limit = self.max_values_per_facet.saturating_sub(distribution.len());
+        distribution.extend(
+            numbers.into_iter().take(limit).map(|(number, count)| (number.to_string(), count)),
+        );
+
+        Ok(())
+    }
+
+    /// Returns the distribution of the values of a field.
     fn facet_values(
         &self,
         field_id: FieldId,
         order_by: OrderBy,
+        is_date: bool,
     ) -> heed::Result<IndexMap<String, u64>> {
         use FacetType::{Number, String};
 
@@ -256,7 +421,21 @@ impl<'a> FacetDistribution<'a> {
             (OrderBy::Lexicographic, Some(cnd)) if cnd.len() <= CANDIDATES_THRESHOLD => {
                 // Classic search, candidates were specified, we must return facet values only related
                 // to those candidates. We also enter here for facet strings for performance reasons.
-                self.facet_distribution_from_documents(field_id, Number, cnd, &mut distribution)?;
+                if is_date {
+                    self.facet_numbers_distribution_without_dates(
+                        field_id,
+                        cnd,
+                        order_by,
+                        &mut distribution,
+                    )?;
+                } else {
+                    self.facet_distribution_from_documents(
+                        field_id,
+                        Number,
+                        cnd,
+                        &mut distribution,
+                    )?;
+                }
                 self.facet_distribution_from_documents(field_id, String, cnd, &mut distribution)?;
             }
             _ => {
@@ -269,12 +448,21 @@ impl<'a> FacetDistribution<'a> {
                     }
                 };
 
-                self.facet_numbers_distribution_from_facet_levels(
-                    field_id,
-                    candidates,
-                    order_by,
-                    &mut distribution,
-                )?;
+                if is_date {
+                    self.facet_numbers_distribution_without_dates(
+                        field_id,
+                        candidates,
+                        order_by,
+                        &mut distribution,
+                    )?;
+                } else {
+                    self.facet_numbers_distribution_from_facet_levels(
+                        field_id,
+                        candidates,
+                        order_by,
+                        &mut distribution,
+                    )?;
+                }
                 self.facet_strings_distribution_from_facet_levels(
                     field_id,
                     candidates,
@@ -329,6 +517,130 @@ impl<'a> FacetDistribution<'a> {
         Ok(distribution)
     }
 
+    /// Computes the histograms of the numeric values of the candidates,
+    /// the buckets of each field having the associated width.
+    ///
+    /// The buckets are aligned on the multiples of their width and go from the smallest
+    /// to the biggest value of the candidates, the empty buckets included.
+    pub fn compute_histograms(
+        &self,
+        intervals: &BTreeMap<String, f64>,
+    ) -> Result<BTreeMap<String, Vec<HistogramBucket>>> {
+        let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
+        let filterable_attributes_rules = self.index.filterable_attributes_rules(self.rtxn)?;
+        check_filterable_fields(intervals.keys(), &filterable_attributes_rules)?;
+
+        let candidates = self.candidates_or_all_documents()?;
+        let db = self.index.facet_id_f64_docids;
+        let mut histograms = BTreeMap::new();
+        for (name, &interval) in intervals {
+            let mut buckets = Vec::new();
+            if let Some(fid) = fields_ids_map.id(name) {
+                let min = facet_min_value(self.index, self.rtxn, fid, candidates.clone())?;
+                let max = facet_max_value(self.index, self.rtxn, fid, candidates.clone())?;
+                if let Some((min, max)) = min.zip(max) {
+                    let first = (min / interval).floor();
+                    let last = (max / interval).floor();
+                    if last - first >= MAX_HISTOGRAM_BUCKETS as f64 {
+                        return Err(UserError::InvalidFacetHistogramInterval {
+                            field: name.to_string(),
+                            interval,
+                            max_buckets: MAX_HISTOGRAM_BUCKETS,
+                        }
+                        .into());
+                    }
+
+                    for i in 0..=(last - first) as usize {
+                        let from = (first + i as f64) * interval;
+                        let to = from + interval;
+                        let mut docids = RoaringBitmap::new();
+                        find_docids_of_facet_within_bounds::<OrderedF64Codec>(
+                            self.rtxn,
+                            db,
+                            fid,
+                            &Bound::Included(from),
+                            &Bound::Excluded(to),
+                            Some(&candidates),
+                            &mut docids,
+                        )?;
+                        buckets.push(HistogramBucket { from, to, count: docids.len() });
+                    }
+                }
+            }
+            histograms.insert(name.to_string(), buckets);
+        }
+
+        Ok(histograms)
+    }
+
+    /// Computes the percentiles, between `0` and `100`, of the numeric values of the candidates.
+    ///
+    /// The percentiles are computed with the nearest-rank method over all the values,
+    /// a document with many values being counted once by value.
+    /// The fields without any value are not returned.
+    pub fn compute_percentiles(
+        &self,
+        percentiles: &BTreeMap<String, Vec<f64>>,
+    ) -> Result<BTreeMap<String, Vec<f64>>> {
+        let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
+        let filterable_attributes_rules = self.index.filterable_attributes_rules(self.rtxn)?;
+        check_filterable_fields(percentiles.keys(), &filterable_attributes_rules)?;
+
+        let candidates = self.candidates_or_all_documents()?;
+        let db =
+            self.index.facet_id_f64_docids.remap_key_type::<FacetGroupKeyCodec<BytesRefCodec>>();
+        let mut output = BTreeMap::new();
+        for (name, percentiles) in percentiles {
+            let Some(fid) = fields_ids_map.id(name) else { continue };
+
+            // the level tree allows to skip the groups of values without any candidate
+            let mut values = Vec::new();
+            let mut total = 0;
+            lexicographically_iterate_over_facet_distribution(
+                self.rtxn,
+                db,
+                fid,
+                &candidates,
+                |facet_key, nbr_docids, _| {
+                    let value =
+                        OrderedF64Codec::bytes_decode(facet_key).map_err(heed::Error::Decoding)?;
+                    values.push((value, nbr_docids));
+                    total += nbr_docids;
+                    Ok(ControlFlow::Continue(()))
+                },
+            )?;
+
+            if total == 0 {
+                continue;
+            }
+
+            let results = percentiles
+                .iter()
+                .map(|percentile| {
+                    let rank = ((percentile / 100.0) * total as f64).ceil().max(1.0) as u64;
+                    let mut seen = 0;
+                    values
+                        .iter()
+                        .find_map(|&(value, count)| {
+                            seen += count;
+                            (seen >= rank).then_some(value)
+                        })
+                        .unwrap_or_else(|| values[values.len() - 1].0)
+                })
+                .collect();
+            output.insert(name.to_string(), results);
+        }
+
+        Ok(output)
+    }
+
+    fn candidates_or_all_documents(&self) -> Result<RoaringBitmap> {
+        match &self.candidates {
+            Some(candidates) => Ok(candidates.clone()),
+            None => Ok(self.index.documents_ids(self.rtxn)?),
+        }
+    }
+
     pub fn execute(&self) -> Result<BTreeMap<String, IndexMap<String, u64>>> {
         let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
         let filterable_attributes_rules = self.index.filterable_attributes_rules(self.rtxn)?;
@@ -336,20 +648,127 @@ impl<'a> FacetDistribution<'a> {
 
         let mut distribution = BTreeMap::new();
         for (fid, name) in fields_ids_map.iter() {
+            if self.ranges.contains_key(name) || self.is_hierarchy_level(name) {
+                continue;
+            }
             if self.select_field(name, &filterable_attributes_rules) {
                 let order_by = self
                     .facets
                     .as_ref()
                     .and_then(|facets| facets.get(name).copied())
                     .unwrap_or(self.default_order_by);
-                let values = self.facet_values(fid, order_by)?;
+                let is_date = matching_features(name, &filterable_attributes_rules)
+                    .is_some_and(|(_, features)| features.is_date());
+                let values = self.facet_values(fid, order_by, is_date)?;
                 distribution.insert(name.to_string(), values);
             }
         }
 
+        if !self.ranges.is_empty() || !self.hierarchies.is_empty() {
+            let candidates = self.candidates_or_all_documents()?;
+            for (name, ranges) in &self.ranges {
+                let Some(fid) = fields_ids_map.id(name) else { continue };
+                let values = self.facet_ranges(fid, ranges, &candidates)?;
+                distribution.insert(name.to_string(), values);
+            }
+
+            for hierarchy in &self.hierarchies {
+                let FacetHierarchy { levels, separator, path } = hierarchy;
+                if levels.is_empty() || separator.is_empty() {
+                    return Err(UserError::InvalidFacetHierarchy.into());
+                }
+
+                let path: Vec<_> = match path {
+                    Some(path) => path.split(separator.as_str()).collect(),
+                    None => Vec::new(),
+                };
+                for (depth, name) in levels.iter().enumerate().take(path.len() + 1) {
+                    let Some(fid) = fields_ids_map.id(name) else { continue };
+                    let prefix = match depth {
+                        0 => String::new(),
+                        _ => {
+                            let mut prefix = crate::normalize_facet(&path[..depth].join(separator));
+                            prefix.push_str(&separator.to_lowercase());
+                            prefix
+                        }
+                    };
+                    let order_by = self
+                        .facets
+                        .as_ref()
+                        .and_then(|facets| facets.get(name).copied())
+                        .unwrap_or(self.default_order_by);
+                    let values =
+                        self.facet_strings_with_prefix(fid, &prefix, &candidates, order_by)?;
+                    distribution.insert(name.to_string(), values);
+                }
+            }
+        }
+
         Ok(distribution)
     }
 
+    fn is_hierarchy_level(&self, name: &str) -> bool {
+        self.hierarchies.iter().any(|hierarchy| hierarchy.levels.iter().any(|level| level == name))
+    }
+
+    /// Counts the candidates of the string values of a field starting with a normalized prefix.
+    fn facet_strings_with_prefix(
+        &self,
+        field_id: FieldId,
+        prefix: &str,
+        candidates: &RoaringBitmap,
+        order_by: OrderBy,
+    ) -> Result<IndexMap<String, u64>> {
+        let mut values = Vec::new();
+        let prefix = FacetGroupKey { field_id, level: 0, left_bound: prefix };
+        for result in self.index.facet_id_string_docids.prefix_iter(self.rtxn, &prefix)? {
+            let (FacetGroupKey { left_bound, .. }, FacetGroupValue { bitmap, .. }) = result?;
+            let docids = bitmap & candidates;
+            let Some(any_docid) = docids.min() else { continue };
+
+            let key: (FieldId, _, &str) = (field_id, any_docid, left_bound);
+            let original_string =
+                match self.index.field_id_docid_facet_strings.get(self.rtxn, &key)? {
+                    Some(original_string) => original_string.to_owned(),
+                    None => left_bound.to_string(),
+                };
+            values.push((original_string, docids.len()));
+        }
+
+        if order_by == OrderBy::Count {
+            // biggest first
+            values.sort_by(|(_, left), (_, right)| right.cmp(left));
+        }
+        values.truncate(self.max_values_per_facet);
+
+        Ok(values.into_iter().collect())
+    }
+
+    /// Counts the candidates in each range of values of a field, in the order of the ranges.
+    fn facet_ranges(
+        &self,
+        field_id: FieldId,
+        ranges: &[FacetRange],
+        candidates: &RoaringBitmap,
+    ) -> Result<IndexMap<String, u64>> {
+        let mut values = IndexMap::new();
+        for range in ranges {
+            let (from, to) = range.bounds();
+            let mut docids = RoaringBitmap::new();
+            find_docids_of_facet_within_bounds::<OrderedF64Codec>(
+                self.rtxn,
+                self.index.facet_id_f64_docids,
+                field_id,
+                &from,
+                &to,
+                Some(candidates),
+                &mut docids,
+            )?;
+            values.insert(range.to_string(), docids.len());
+        }
+        Ok(values)
+    }
+
     /// Select a field if it is filterable and in the facets.
     fn select_field(
         &self,
@@ -367,6 +786,7 @@ impl<'a> FacetDistribution<'a> {
             Some(facets) => {
                 // The list of facets provided by the user is a legacy pattern ("dog.age" must be selected with "dog").
                 facets.keys().any(|key| match_field_legacy(key, name) == PatternMatch::Match)
+                    || self.ranges.contains_key(name)
             }
             None => true,
         }
@@ -377,49 +797,61 @@ impl<'a> FacetDistribution<'a> {
         &self,
         filterable_attributes_rules: &[FilterableAttributesRule],
     ) -> Result<()> {
-        let mut invalid_facets = BTreeSet::new();
-        let mut matching_rule_indices = HashMap::new();
-
-        if let Some(facets) = &self.facets {
-            for field in facets.keys() {
-                let matched_rule = matching_features(field, filterable_attributes_rules);
-                let is_filterable = matched_rule.is_some_and(|(_, f)| f.is_filterable());
-
-                if !is_filterable {
-                    invalid_facets.insert(field.to_string());
+        let facets = self.facets.iter().flat_map(|facets| facets.keys());
+        let levels = self.hierarchies.iter().flat_map(|hierarchy| &hierarchy.levels);
+        check_filterable_fields(
+            facets.chain(self.ranges.keys()).chain(levels),
+            filterable_attributes_rules,
+        )
+    }
+}
 
-                    // If the field matched a rule but that rule doesn't enable filtering,
-                    // store the rule index for better error messages
-                    if let Some((rule_index, _)) = matched_rule {
-                        matching_rule_indices.insert(field.to_string(), rule_index);
-                    }
-                }
+/// Check if the fields are valid filterable fields.
+fn check_filterable_fields<'a>(
+    fields: impl IntoIterator<Item = &'a String>,
+    filterable_attributes_rules: &[FilterableAttributesRule],
+) -> Result<()> {
+    let mut invalid_facets = BTreeSet::new();
+    let mut matching_rule_indices = HashMap::new();
+
+    for field in fields {
+        let matched_rule = matching_features(field, filterable_attributes_rules);
+        let is_filterable = matched_rule.is_some_and(|(_, f)| f.is_filterable());
+
+        if !is_filterable {
+            invalid_facets.insert(field.to_string());
+
+            // If the field matched a rule but that rule doesn't enable filtering,
+            // store the rule index for better error messages
+            if let Some((rule_index, _)) = matched_rule {
+                matching_rule_indices.insert(field.to_string(), rule_index);
             }
         }
+    }
 
-        if !invalid_facets.is_empty() {
-            let valid_patterns =
-                filtered_matching_patterns(filterable_attributes_rules, &|features| {
-                    features.is_filterable()
-                })
-                .into_iter()
-                .map(String::from)
-                .collect();
-            return Err(Error::UserError(UserError::InvalidFacetsDistribution {
-                invalid_facets_name: invalid_facets,
-                valid_patterns,
-                matching_rule_indices,
-            }));
-        }
-
-        Ok(())
+    if !invalid_facets.is_empty() {
+        let valid_patterns = filtered_matching_patterns(filterable_attributes_rules, &|features| {
+            features.is_filterable()
+        })
+        .into_iter()
+        .map(String::from)
+        .collect();
+        return Err(Error::UserError(UserError::InvalidFacetsDistribution {
+            invalid_facets_name: invalid_facets,
+            valid_patterns,
+            matching_rule_indices,
+        }));
     }
+
+    Ok(())
 }
 
 impl fmt::Debug for FacetDistribution<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let FacetDistribution {
             facets,
+            ranges,
+            hierarchies,
             candidates,
             max_values_per_facet,
             default_order_by,
@@ -429,6 +861,8 @@ impl fmt::Debug for FacetDistribution<'_> {
 
         f.debug_struct("FacetDistribution")
             .field("facets", facets)
+            .field("ranges", ranges)
+            .field("hierarchies", hierarchies)
             .field("candidates", candidates)
             .field("max_values_per_facet", max_values_per_facet)
             .field("default_order_by", default_order_by)
@@ -438,13 +872,17 @@ impl fmt::Debug for FacetDistribution<'_> {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
     use std::iter;
 
     use big_s::S;
 
     use crate::documents::mmap_from_objects;
     use crate::index::tests::TempIndex;
-    use crate::{milli_snap, FacetDistribution, FilterableAttributesRule, OrderBy};
+    use crate::{
+        milli_snap, FacetDistribution, FacetHierarchy, FacetRange, FilterableAttributesRule,
+        OrderBy,
+    };
 
     #[test]
     fn few_candidates_few_facet_values() {
@@ -723,6 +1161,172 @@ mod tests {
         milli_snap!(format!("{map:?}"), "candidates_217_777", @r###"{"colour": (217.0, 776.0)}"###);
     }
 
+    #[test]
+    fn facet_histograms_and_percentiles() {
+        let index = TempIndex::new_with_map_size(4096 * 10_000);
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(vec![FilterableAttributesRule::Field(S("colour"))])
+            })
+            .unwrap();
+
+        let mut documents = vec![];
+        for i in 0..1000 {
+            let document = serde_json::json!({ "id": i, "colour": i }).as_object().unwrap().clone();
+            documents.push(document);
+        }
+
+        let documents = mmap_from_objects(documents);
+        index.add_documents(documents).unwrap();
+
+        let txn = index.read_txn().unwrap();
+
+        let intervals = BTreeMap::from([(S("colour"), 500.0)]);
+        let map = FacetDistribution::new(&txn, &index)
+            .candidates((217..777).collect())
+            .compute_histograms(&intervals)
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"colour": [HistogramBucket { from: 0.0, to: 500.0, count: 283 }, HistogramBucket { from: 500.0, to: 1000.0, count: 277 }]}"###);
+
+        let intervals = BTreeMap::from([(S("colour"), 0.1)]);
+        let error = FacetDistribution::new(&txn, &index)
+            .candidates((0..1000).collect())
+            .compute_histograms(&intervals)
+            .unwrap_err();
+
+        milli_snap!(error.to_string(), @"The interval `0.1` of the `colour` facet histogram is too small: the histogram would be made of more than 1000 buckets.");
+
+        let percentiles = BTreeMap::from([(S("colour"), vec![0.0, 25.0, 50.0, 90.0, 100.0])]);
+        let map = FacetDistribution::new(&txn, &index)
+            .candidates((0..1000).collect())
+            .compute_percentiles(&percentiles)
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"colour": [0.0, 249.0, 499.0, 899.0, 999.0]}"###);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .candidates((217..777).collect())
+            .compute_percentiles(&percentiles)
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"colour": [217.0, 356.0, 496.0, 720.0, 776.0]}"###);
+
+        let percentiles = BTreeMap::from([(S("size"), vec![50.0])]);
+        let error = FacetDistribution::new(&txn, &index)
+            .candidates((0..1000).collect())
+            .compute_percentiles(&percentiles)
+            .unwrap_err();
+
+        milli_snap!(error.to_string(), @"Invalid facet distribution: Attribute `size` is not filterable. Available filterable attributes patterns are: `colour`.");
+    }
+
+    #[test]
+    fn facet_ranges() {
+        let index = TempIndex::new_with_map_size(4096 * 10_000);
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(vec![FilterableAttributesRule::Field(S("colour"))])
+            })
+            .unwrap();
+
+        let mut documents = vec![];
+        for i in 0..1000 {
+            let document = serde_json::json!({ "id": i, "colour": i }).as_object().unwrap().clone();
+            documents.push(document);
+        }
+
+        let documents = mmap_from_objects(documents);
+        index.add_documents(documents).unwrap();
+
+        let txn = index.read_txn().unwrap();
+
+        let (name, ranges) =
+            FacetRange::parse_facet("colour[..100, 100..250.5, 500..]").unwrap().unwrap();
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::empty::<(&str, OrderBy)>())
+            .ranges([(name, ranges.clone())])
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"colour": {"..100": 100, "100..250.5": 151, "500..": 500}}"###);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .candidates((217..777).collect())
+            .ranges([(name, ranges)])
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"colour": {"..100": 0, "100..250.5": 34, "500..": 277}}"###);
+
+        assert_eq!(FacetRange::parse_facet("colour").unwrap(), None);
+        for facet in
+            ["colour[]", "colour[10]", "colour[10..5]", "colour[a..b]", "[0..10]", "colour]"]
+        {
+            let error = FacetRange::parse_facet(facet).unwrap_err();
+            assert!(error.to_string().starts_with(&format!("Invalid facet range `{facet}`")));
+        }
+    }
+
+    #[test]
+    fn facet_hierarchy() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(vec![
+                    FilterableAttributesRule::Field(S("lvl0")),
+                    FilterableAttributesRule::Field(S("lvl1")),
+                    FilterableAttributesRule::Field(S("lvl2")),
+                ])
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "lvl0": "Books", "lvl1": "Books > Science", "lvl2": "Books > Science > Physics" },
+                { "id": 1, "lvl0": "Books", "lvl1": "Books > Science", "lvl2": "Books > Science > Biology" },
+                { "id": 2, "lvl0": "Books", "lvl1": "Books > Fiction" },
+                { "id": 3, "lvl0": "Movies", "lvl1": "Movies > Action" },
+            ]))
+            .unwrap();
+
+        let txn = index.read_txn().unwrap();
+
+        let hierarchy = |path: Option<&str>| FacetHierarchy {
+            levels: vec![S("lvl0"), S("lvl1"), S("lvl2")],
+            separator: S(" > "),
+            path: path.map(S),
+        };
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::empty::<(&str, OrderBy)>())
+            .hierarchies(vec![hierarchy(None)])
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"lvl0": {"Books": 3, "Movies": 1}}"###);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::empty::<(&str, OrderBy)>())
+            .hierarchies(vec![hierarchy(Some("books > Science"))])
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"lvl0": {"Books": 3, "Movies": 1}, "lvl1": {"Books > Fiction": 1, "Books > Science": 2}, "lvl2": {"Books > Science > Biology": 1, "Books > Science > Physics": 1}}"###);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .candidates([0, 2, 3].into_iter().collect())
+            .default_order_by(OrderBy::Count)
+            .hierarchies(vec![hierarchy(Some("Books"))])
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"lvl0": {"Books": 2, "Movies": 1}, "lvl1": {"Books > Fiction": 1, "Books > Science": 1}}"###);
+    }
+
     #[test]
     fn facet_stats_array() {
         let index = TempIndex::new_with_map_size(4096 * 10_000);
@@ -886,4 +1490,43 @@ mod tests {
 
         milli_snap!(format!("{map:?}"), "candidates_217_777", @r###"{"colour": (218.0, 1776.0)}"###);
     }
+
+    #[test]
+    fn facet_date_values() {
+        let index = TempIndex::new();
+
+        let date_rule: FilterableAttributesRule = serde_json::from_value(serde_json::json!({
+            "attributePatterns": ["date"],
+            "features": { "date": true }
+        }))
+        .unwrap();
+        index.update_settings(|settings| settings.set_filterable_fields(vec![date_rule])).unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "date": "2024-01-01" },
+                { "id": 1, "date": "2024-01-02" },
+                { "id": 2, "date": 1704067200 },
+                { "id": 3, "date": 5 },
+            ]))
+            .unwrap();
+
+        let txn = index.read_txn().unwrap();
+
+        // the timestamps of the dates are not returned, but the numbers are
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::once(("date", OrderBy::default())))
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"date": {"5": 1, "1704067200": 1, "2024-01-01": 1, "2024-01-02": 1}}"###);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::once(("date", OrderBy::default())))
+            .candidates([0, 1].into_iter().collect())
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"date": {"2024-01-01": 1, "2024-01-02": 1}}"###);
+    }
 }
@@ -7,13 +7,19 @@ pub use filter_parser::{Condition, Error as FPError, FilterCondition, Token};
 use heed::types::LazyDecode;
 use heed::BytesEncode;
 use memchr::memmem::Finder;
+use regex::RegexBuilder;
 use roaring::{MultiOps, RoaringBitmap};
 use serde_json::Value;
+use time::OffsetDateTime;
 
 use super::facet_range_search;
 use crate::constants::RESERVED_GEO_FIELD_NAME;
+use crate::date::parse_filter_date;
 use crate::error::{Error, UserError};
 use crate::filterable_attributes_rules::{filtered_matching_patterns, matching_features};
+use crate::geometry::{
+    circle_envelope, lat_lng_box_envelope, point_in_ring, polygon_within_radius, polygons_intersect,
+};
 use crate::heed_codec::facet::{
     FacetGroupKey, FacetGroupKeyCodec, FacetGroupValue, FacetGroupValueCodec,
 };
@@ -21,7 +27,7 @@ use crate::index::db_name::FACET_ID_STRING_DOCIDS;
 use crate::{
     distance_between_two_points, lat_lng_to_xyz, FieldId, FieldsIdsMap,
     FilterableAttributesFeatures, FilterableAttributesRule, Index, InternalError, Result,
-    SerializationError,
+    SerializationError, MAX_FACET_VALUE_LENGTH,
 };
 
 /// The maximum number of filters the filter AST can process.
@@ -37,6 +43,8 @@ pub enum BadGeoError {
     Lat(f64),
     Lng(f64),
     BoundingBoxTopIsBelowBottom(f64, f64),
+    PolygonNotEnoughPoints(usize),
+    PolygonWithoutArea,
 }
 
 impl std::error::Error for BadGeoError {}
@@ -47,6 +55,13 @@ impl Display for BadGeoError {
             Self::BoundingBoxTopIsBelowBottom(top, bottom) => {
                 write!(f, "The top latitude `{top}` is below the bottom latitude `{bottom}`.")
             }
+            Self::PolygonNotEnoughPoints(count) => write!(
+                f,
+                "A polygon must be made of at least three distinct points but only `{count}` were provided."
+            ),
+            Self::PolygonWithoutArea => {
+                write!(f, "The points of the polygon are all aligned and don't enclose any area.")
+            }
             Self::Lat(lat) => write!(
                 f,
                 "Bad latitude `{}`. Latitude must be contained between -90 and 90 degrees. ",
@@ -65,6 +80,7 @@ impl Display for BadGeoError {
 enum FilterError<'a> {
     AttributeNotFilterable { attribute: &'a str, filterable_patterns: BTreeSet<&'a str> },
     ParseGeoError(BadGeoError),
+    InvalidRegex(regex::Error),
     TooDeep,
 }
 impl std::error::Error for FilterError<'_> {}
@@ -102,6 +118,7 @@ impl Display for FilterError<'_> {
                 MAX_FILTER_DEPTH
             ),
             Self::ParseGeoError(error) => write!(f, "{}", error),
+            Self::InvalidRegex(error) => write!(f, "Invalid regular expression: {}", error),
         }
     }
 }
@@ -228,6 +245,48 @@ impl<'a> Filter<'a> {
     pub fn use_contains_operator(&self) -> Option<&Token> {
         self.condition.use_contains_operator()
     }
+
+    /// Returns the filter without the top-level AND clauses that only apply to the given facet,
+    /// or `None` if nothing remains.
+    ///
+    /// This is what a disjunctive facet is computed with: its distribution ignores its own
+    /// clauses while still being restricted by the clauses of the other facets.
+    pub fn without_facet(&self, facet: &str) -> Option<Filter<'a>> {
+        let only_on_facet = |condition: &FilterCondition| {
+            let mut fids = condition.fids(MAX_FILTER_DEPTH).peekable();
+            fids.peek().is_some() && fids.all(|fid| crate::is_faceted_by(fid.value(), facet))
+        };
+
+        let condition = match &self.condition {
+            FilterCondition::And(conditions) => {
+                let mut conditions: Vec<_> = conditions
+                    .iter()
+                    .filter(|condition| !only_on_facet(condition))
+                    .cloned()
+                    .collect();
+                match conditions.len() {
+                    0 => return None,
+                    1 => conditions.pop().unwrap(),
+                    _ => FilterCondition::And(conditions),
+                }
+            }
+            condition if only_on_facet(condition) => return None,
+            condition => condition.clone(),
+        };
+
+        Some(Self { condition })
+    }
+
+    /// Returns the top-level AND clauses of the filter, written in a canonical form
+    /// so that they can be compared to the clauses of another filter.
+    pub fn clauses(&self) -> Vec<String> {
+        match &self.condition {
+            FilterCondition::And(conditions) => {
+                conditions.iter().map(|condition| condition.to_string()).collect()
+            }
+            condition => vec![condition.to_string()],
+        }
+    }
 }
 
 impl<'a> Filter<'a> {
@@ -272,7 +331,7 @@ impl<'a> Filter<'a> {
         // as the facets values are all in the same database and prefixed by the
         // field id and the level.
 
-        let (number_bounds, (left_str, right_str)) = match operator {
+        let (number_bounds, str_bounds) = match operator {
             // return an error if the filter is not allowed for this field
             Condition::GreaterThan(_)
             | Condition::GreaterThanOrEqual(_)
@@ -305,37 +364,45 @@ impl<'a> Filter<'a> {
                     rtxn, index, field_id, operator, features, rule_index,
                 ));
             }
+            Condition::EndsWith { .. } | Condition::Matches { .. }
+                if !features.is_filterable_pattern_matching() =>
+            {
+                return Err(generate_filter_error(
+                    rtxn, index, field_id, operator, features, rule_index,
+                ));
+            }
             Condition::GreaterThan(val) => {
-                let number = val.parse_finite_float().ok();
+                let (number, is_date) = Self::parse_number(val, features);
                 let number_bounds = number.map(|number| (Excluded(number), Included(f64::MAX)));
-                let str_bounds = (Excluded(val.value()), Unbounded);
+                let str_bounds = (!is_date).then(|| (Excluded(val.value()), Unbounded));
                 (number_bounds, str_bounds)
             }
             Condition::GreaterThanOrEqual(val) => {
-                let number = val.parse_finite_float().ok();
+                let (number, is_date) = Self::parse_number(val, features);
                 let number_bounds = number.map(|number| (Included(number), Included(f64::MAX)));
-                let str_bounds = (Included(val.value()), Unbounded);
+                let str_bounds = (!is_date).then(|| (Included(val.value()), Unbounded));
                 (number_bounds, str_bounds)
             }
             Condition::LowerThan(val) => {
-                let number = val.parse_finite_float().ok();
+                let (number, is_date) = Self::parse_number(val, features);
                 let number_bounds = number.map(|number| (Included(f64::MIN), Excluded(number)));
-                let str_bounds = (Unbounded, Excluded(val.value()));
+                let str_bounds = (!is_date).then(|| (Unbounded, Excluded(val.value())));
                 (number_bounds, str_bounds)
             }
             Condition::LowerThanOrEqual(val) => {
-                let number = val.parse_finite_float().ok();
+                let (number, is_date) = Self::parse_number(val, features);
                 let number_bounds = number.map(|number| (Included(f64::MIN), Included(number)));
-                let str_bounds = (Unbounded, Included(val.value()));
+                let str_bounds = (!is_date).then(|| (Unbounded, Included(val.value())));
                 (number_bounds, str_bounds)
             }
             Condition::Between { from, to } => {
-                let from_number = from.parse_finite_float().ok();
-                let to_number = to.parse_finite_float().ok();
+                let (from_number, from_is_date) = Self::parse_number(from, features);
+                let (to_number, to_is_date) = Self::parse_number(to, features);
 
                 let number_bounds =
                     from_number.zip(to_number).map(|(from, to)| (Included(from), Included(to)));
-                let str_bounds = (Included(from.value()), Included(to.value()));
+                let str_bounds = (!from_is_date && !to_is_date)
+                    .then(|| (Included(from.value()), Included(to.value())));
                 (number_bounds, str_bounds)
             }
             Condition::Null => {
@@ -362,7 +429,7 @@ impl<'a> Filter<'a> {
                     )?
                     .map(|v| v.bitmap)
                     .unwrap_or_default();
-                let number = val.parse_finite_float().ok();
+                let (number, _) = Self::parse_number(val, features);
                 let number_docids = match number {
                     Some(n) => numbers_db
                         .get(rtxn, &FacetGroupKey { field_id, level: 0, left_bound: n })?
@@ -433,6 +500,22 @@ impl<'a> Filter<'a> {
 
                 return Ok(docids);
             }
+            Condition::EndsWith { keyword: _, word } => {
+                let value = crate::normalize_facet(word.value());
+                return Self::facet_strings_docids_matching(rtxn, index, field_id, |facet| {
+                    facet.ends_with(value.as_str())
+                });
+            }
+            Condition::Matches { keyword: _, regex } => {
+                // The facet strings are lowercased, hence the case insensitive matching.
+                let compiled = RegexBuilder::new(regex.value())
+                    .case_insensitive(true)
+                    .build()
+                    .map_err(|error| regex.as_external_error(FilterError::InvalidRegex(error)))?;
+                return Self::facet_strings_docids_matching(rtxn, index, field_id, |facet| {
+                    compiled.is_match(facet)
+                });
+            }
         };
 
         let mut output = RoaringBitmap::new();
@@ -449,19 +532,86 @@ impl<'a> Filter<'a> {
             )?;
         }
 
-        Self::explore_facet_levels(
-            rtxn,
-            strings_db,
-            field_id,
-            &left_str,
-            &right_str,
-            universe,
-            &mut output,
-        )?;
+        if let Some((left_str, right_str)) = str_bounds {
+            Self::explore_facet_levels(
+                rtxn,
+                strings_db,
+                field_id,
+                &left_str,
+                &right_str,
+                universe,
+                &mut output,
+            )?;
+        }
 
         Ok(output)
     }
 
+    /// Returns the documents ids having a facet string of the field that satisfies the predicate.
+    ///
+    /// The facet strings are truncated in the keys of the database, the ones that may have
+    /// been truncated are checked against the original value of each one of their documents.
+    fn facet_strings_docids_matching(
+        rtxn: &heed::RoTxn<'_>,
+        index: &Index,
+        field_id: FieldId,
+        predicate: impl Fn(&str) -> bool,
+    ) -> Result<RoaringBitmap> {
+        let base = FacetGroupKey { field_id, level: 0, left_bound: "" };
+        let mut docids = RoaringBitmap::new();
+        for result in index
+            .facet_id_string_docids
+            .remap_data_type::<LazyDecode<FacetGroupValueCodec>>()
+            .prefix_iter(rtxn, &base)?
+        {
+            let (FacetGroupKey { left_bound, .. }, lazy_group_value) = result?;
+            // a char is at most 4 bytes long, a shorter key can't have been truncated
+            let may_be_truncated = left_bound.len() + 4 > MAX_FACET_VALUE_LENGTH;
+            if !may_be_truncated && !predicate(left_bound) {
+                continue;
+            }
+
+            let group_value = lazy_group_value.decode().map_err(|_| {
+                InternalError::from(SerializationError::Decoding {
+                    db_name: Some(FACET_ID_STRING_DOCIDS),
+                })
+            })?;
+            if may_be_truncated {
+                for docid in group_value.bitmap {
+                    let key = (field_id, docid, left_bound);
+                    if let Some(original) = index.field_id_docid_facet_strings.get(rtxn, &key)? {
+                        if predicate(&crate::normalize_facet(original)) {
+                            docids.insert(docid);
+                        }
+                    }
+                }
+            } else {
+                docids |= group_value.bitmap;
+            }
+        }
+        Ok(docids)
+    }
+
+    /// Parses the number a facet value must be compared with.
+    ///
+    /// The values compared with the date fields can also be dates, e.g. `2024-01-01` or `NOW-7d`,
+    /// which are converted into timestamps. In this case `true` is returned as the dates must not
+    /// be compared with the strings.
+    fn parse_number(
+        val: &Token<'_>,
+        features: &FilterableAttributesFeatures,
+    ) -> (Option<f64>, bool) {
+        if let Ok(number) = val.parse_finite_float() {
+            return (Some(number), false);
+        }
+        if features.is_date() {
+            if let Some(timestamp) = parse_filter_date(val.value(), OffsetDateTime::now_utc()) {
+                return (Some(timestamp), true);
+            }
+        }
+        (None, false)
+    }
+
     /// Aggregates the documents ids that are part of the specified range automatically
     /// going deeper through the levels.
     fn explore_facet_levels<'data, BoundCodec>(
@@ -617,7 +767,7 @@ impl<'a> Filter<'a> {
 
                     let xyz_base_point = lat_lng_to_xyz(&base_point);
 
-                    let result = rtree
+                    let mut result: RoaringBitmap = rtree
                         .nearest_neighbor_iter(&xyz_base_point)
                         .take_while(|point| {
                             distance_between_two_points(&base_point, &point.data.1)
@@ -626,6 +776,16 @@ impl<'a> Filter<'a> {
                         .map(|point| point.data.0)
                         .collect();
 
+                    if let Some(geojson_rtree) = index.geojson_rtree(rtxn)? {
+                        result |= geojson_rtree
+                            .locate_in_envelope_intersecting(&circle_envelope(&base_point, radius))
+                            .filter(|polygon| {
+                                polygon_within_radius(&polygon.rings, &base_point, radius)
+                            })
+                            .map(|polygon| polygon.docid)
+                            .collect::<RoaringBitmap>();
+                    }
+
                     Ok(result)
                 } else {
                     Err(point[0].as_external_error(FilterError::AttributeNotFilterable {
@@ -671,101 +831,10 @@ impl<'a> Filter<'a> {
                         ))?;
                     }
 
-                    // Instead of writing a custom `GeoBoundingBox` filter we're simply going to re-use the range
-                    // filter to create the following filter;
-                    // `_geo.lat {top_right[0]} TO {bottom_left[0]} AND _geo.lng {top_right[1]} TO {bottom_left[1]}`
-                    // As we can see, we need to use a bunch of tokens that don't exist in the original filter,
-                    // thus we're going to create tokens that point to a random span but contain our text.
-
-                    let geo_lat_token = Token::new(
-                        top_right_point[0].original_span(),
-                        Some("_geo.lat".to_string()),
-                    );
-
-                    let condition_lat = FilterCondition::Condition {
-                        fid: geo_lat_token,
-                        op: Condition::Between {
-                            from: bottom_left_point[0].clone(),
-                            to: top_right_point[0].clone(),
-                        },
-                    };
-
-                    let selected_lat = Filter { condition: condition_lat }.inner_evaluate(
-                        rtxn,
-                        index,
-                        field_ids_map,
-                        filterable_attribute_rules,
-                        universe,
-                    )?;
-
-                    let geo_lng_token = Token::new(
-                        top_right_point[1].original_span(),
-                        Some("_geo.lng".to_string()),
-                    );
-                    let selected_lng = if top_right[1] < bottom_left[1] {
-                        // In this case the bounding box is wrapping around the earth (going from 180 to -180).
-                        // We need to update the lng part of the filter from;
-                        // `_geo.lng {top_right[1]} TO {bottom_left[1]}` to
-                        // `_geo.lng {bottom_left[1]} TO 180 AND _geo.lng -180 TO {top_right[1]}`
-
-                        let min_lng_token = Token::new(
-                            top_right_point[1].original_span(),
-                            Some("-180.0".to_string()),
-                        );
-                        let max_lng_token = Token::new(
-                            top_right_point[1].original_span(),
-                            Some("180.0".to_string()),
-                        );
-
-                        let condition_left = FilterCondition::Condition {
-                            fid: geo_lng_token.clone(),
-                            op: Condition::Between {
-                                from: bottom_left_point[1].clone(),
-                                to: max_lng_token,
-                            },
-                        };
-                        let left = Filter { condition: condition_left }.inner_evaluate(
-                            rtxn,
-                            index,
-                            field_ids_map,
-                            filterable_attribute_rules,
-                            universe,
-                        )?;
-
-                        let condition_right = FilterCondition::Condition {
-                            fid: geo_lng_token,
-                            op: Condition::Between {
-                                from: min_lng_token,
-                                to: top_right_point[1].clone(),
-                            },
-                        };
-                        let right = Filter { condition: condition_right }.inner_evaluate(
-                            rtxn,
-                            index,
-                            field_ids_map,
-                            filterable_attribute_rules,
-                            universe,
-                        )?;
-
-                        left | right
-                    } else {
-                        let condition_lng = FilterCondition::Condition {
-                            fid: geo_lng_token,
-                            op: Condition::Between {
-                                from: bottom_left_point[1].clone(),
-                                to: top_right_point[1].clone(),
-                            },
-                        };
-                        Filter { condition: condition_lng }.inner_evaluate(
-                            rtxn,
-                            index,
-                            field_ids_map,
-                            filterable_attribute_rules,
-                            universe,
-                        )?
-                    };
-
-                    Ok(selected_lat & selected_lng)
+                    // A document can have many points, filtering the `_geo.lat` and `_geo.lng` facets
+                    // independently could match the latitude of a point with the longitude of another
+                    // one, we therefore retrieve the points and polygons from the rtrees.
+                    bounding_box_docids(rtxn, index, bottom_left, top_right)
                 } else {
                     Err(top_right_point[0].as_external_error(
                         FilterError::AttributeNotFilterable {
@@ -778,8 +847,128 @@ impl<'a> Filter<'a> {
                     ))?
                 }
             }
+            FilterCondition::GeoPolygon { points } => {
+                if index.is_geo_filtering_enabled(rtxn)? {
+                    let mut polygon = Vec::with_capacity(points.len());
+                    for [lat_token, lng_token] in points {
+                        let lat = lat_token.parse_finite_float()?;
+                        let lng = lng_token.parse_finite_float()?;
+                        if !(-90.0..=90.0).contains(&lat) {
+                            return Err(lat_token.as_external_error(BadGeoError::Lat(lat)))?;
+                        }
+                        if !(-180.0..=180.0).contains(&lng) {
+                            return Err(lng_token.as_external_error(BadGeoError::Lng(lng)))?;
+                        }
+                        // consecutive duplicated points don't add any edge to the polygon
+                        if polygon.last() != Some(&[lat, lng]) {
+                            polygon.push([lat, lng]);
+                        }
+                    }
+                    // the polygon can be explicitly closed by repeating the first point
+                    if polygon.len() > 1 && polygon.first() == polygon.last() {
+                        polygon.pop();
+                    }
+
+                    if polygon.len() < 3 {
+                        return Err(points[0][0].as_external_error(
+                            BadGeoError::PolygonNotEnoughPoints(polygon.len()),
+                        ))?;
+                    }
+                    if polygon_area(&polygon) == 0.0 {
+                        return Err(
+                            points[0][0].as_external_error(BadGeoError::PolygonWithoutArea)
+                        )?;
+                    }
+
+                    let rtree = match index.geo_rtree(rtxn)? {
+                        Some(rtree) => rtree,
+                        None => return Ok(RoaringBitmap::new()),
+                    };
+
+                    // We first retrieve the points contained in the box enclosing the polygon
+                    // and then only keep the ones that are really contained in the polygon.
+                    let envelope = lat_lng_box_envelope(&polygon);
+                    let mut result: RoaringBitmap = rtree
+                        .locate_in_envelope(&envelope)
+                        .filter(|point| point_in_ring(&point.data.1, &polygon))
+                        .map(|point| point.data.0)
+                        .collect();
+
+                    if let Some(geojson_rtree) = index.geojson_rtree(rtxn)? {
+                        let query = [polygon];
+                        result |= geojson_rtree
+                            .locate_in_envelope_intersecting(&envelope)
+                            .filter(|geojson| polygons_intersect(&geojson.rings, &query))
+                            .map(|geojson| geojson.docid)
+                            .collect::<RoaringBitmap>();
+                    }
+
+                    Ok(result)
+                } else {
+                    Err(points[0][0].as_external_error(FilterError::AttributeNotFilterable {
+                        attribute: RESERVED_GEO_FIELD_NAME,
+                        filterable_patterns: filtered_matching_patterns(
+                            filterable_attribute_rules,
+                            &|features| features.is_filterable(),
+                        ),
+                    }))?
+                }
+            }
+        }
+    }
+}
+
+/// Returns the area of the polygon, in squared degrees, computed with the shoelace formula.
+///
+/// The edges of the polygon are considered to be straight lines in the latitude/longitude plane.
+fn polygon_area(polygon: &[[f64; 2]]) -> f64 {
+    let doubled_area: f64 = polygon
+        .iter()
+        .zip(polygon.iter().cycle().skip(1))
+        .map(|([lat_a, lng_a], [lat_b, lng_b])| lng_a * lat_b - lng_b * lat_a)
+        .sum();
+    doubled_area.abs() / 2.0
+}
+
+/// Returns the documents with a point or a polygon in the bounding box.
+fn bounding_box_docids(
+    rtxn: &heed::RoTxn<'_>,
+    index: &Index,
+    [bottom, left]: [f64; 2],
+    [top, right]: [f64; 2],
+) -> Result<RoaringBitmap> {
+    // a bounding box wrapping around the earth is split in two boxes
+    let lng_ranges =
+        if right < left { vec![(left, 180.0), (-180.0, right)] } else { vec![(left, right)] };
+
+    let rtree = index.geo_rtree(rtxn)?;
+    let geojson_rtree = index.geojson_rtree(rtxn)?;
+
+    let mut result = RoaringBitmap::new();
+    for (left, right) in lng_ranges {
+        let ring = vec![[bottom, left], [bottom, right], [top, right], [top, left]];
+        let envelope = lat_lng_box_envelope(&ring);
+        if let Some(rtree) = &rtree {
+            result |= rtree
+                .locate_in_envelope(&envelope)
+                .filter(|point| {
+                    let [lat, lng] = point.data.1;
+                    (bottom..=top).contains(&lat) && (left..=right).contains(&lng)
+                })
+                .map(|point| point.data.0)
+                .collect::<RoaringBitmap>();
+        }
+        if let Some(geojson_rtree) = &geojson_rtree {
+            let query = [ring];
+            result |= geojson_rtree
+                .locate_in_envelope_intersecting(&envelope)
+                .filter(|polygon| polygons_intersect(&polygon.rings, &query))
+                .map(|polygon| polygon.docid)
+                .collect::<RoaringBitmap>();
         }
     }
+
+    Ok(result)
 }
 
 fn generate_filter_error(
@@ -1230,6 +1419,58 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn geo_polygon_error() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_searchable_fields(vec![S(RESERVED_GEO_FIELD_NAME), S("price")]); // to keep the fields order
+                settings.set_filterable_fields(vec![
+                    FilterableAttributesRule::Field(S(RESERVED_GEO_FIELD_NAME)),
+                    FilterableAttributesRule::Field("price".to_string()),
+                ]);
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+
+        // geopolygon have a bad latitude
+        let filter =
+            Filter::from_str("_geoPolygon([0, 0], [90.0000001, 5], [10, 10])").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(
+            error.to_string().starts_with(
+                "Bad latitude `90.0000001`. Latitude must be contained between -90 and 90 degrees."
+            ),
+            "{}",
+            error.to_string()
+        );
+
+        // geopolygon have a bad longitude
+        let filter =
+            Filter::from_str("_geoPolygon([0, 0], [10, 10], [5, -180.000001])").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(
+            error.to_string().starts_with(
+                "Bad longitude `-180.000001`. Longitude must be contained between -180 and 180 degrees."
+            ),
+            "{}",
+            error.to_string()
+        );
+
+        // geopolygon doesn't have enough points
+        let filter = Filter::from_str("_geoPolygon([0, 0], [10, 10], [0, 0])").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(
+            error.to_string().starts_with(
+                "A polygon must be made of at least three distinct points but only `2` were provided."
+            ),
+            "{}",
+            error.to_string()
+        );
+    }
+
     #[test]
     fn filter_depth() {
         // generates a big (2 MiB) filter with too much of ORs.
@@ -1248,6 +1489,22 @@ mod tests {
         assert!(filter.is_some());
     }
 
+    #[test]
+    fn without_facet() {
+        let without = |filter: &str, facet: &str| {
+            let filter = Filter::from_str(filter).unwrap().unwrap();
+            filter.without_facet(facet).map(|filter| filter.condition.to_string())
+        };
+
+        snapshot!(format!("{:?}", without("color = red", "color")), @"None");
+        snapshot!(format!("{:?}", without("color = red", "size")), @r###"Some("{color} = {red}")"###);
+        snapshot!(format!("{:?}", without("color = red AND size = 1", "color")), @r###"Some("{size} = {1}")"###);
+        snapshot!(format!("{:?}", without("(color = red OR color = blue) AND size > 1 AND brand = nike", "color")), @r###"Some("AND[{size} > {1}, {brand} = {nike}, ]")"###);
+        snapshot!(format!("{:?}", without("color.dark = red AND colors = blue", "color")), @r###"Some("{colors} = {blue}")"###);
+        snapshot!(format!("{:?}", without("(color = red OR size = 1) AND brand = nike", "color")), @r###"Some("AND[OR[{color} = {red}, {size} = {1}, ], {brand} = {nike}, ]")"###);
+        snapshot!(format!("{:?}", without("NOT color = red AND _geoRadius(12, 13, 14)", "color")), @r###"Some("_geoRadius({12}, {13}, {14})")"###);
+    }
+
     #[test]
     fn empty_filter() {
         let option = Filter::from_str("     ").unwrap();
@@ -1392,4 +1649,116 @@ mod tests {
         let result = filter.evaluate(&rtxn, &index).unwrap();
         assert_eq!(result, RoaringBitmap::from_iter((0..100).filter(|x| x % 10 != 0)));
     }
+
+    #[test]
+    fn filter_date() {
+        let index = TempIndex::new();
+
+        let date_rule: FilterableAttributesRule = serde_json::from_value(serde_json::json!({
+            "attributePatterns": ["date"],
+            "features": { "date": true, "filter": { "equality": true, "comparison": true } }
+        }))
+        .unwrap();
+        index
+            .update_settings(|settings| {
+                settings.set_primary_key("id".to_owned());
+                settings.set_filterable_fields(vec![date_rule]);
+            })
+            .unwrap();
+
+        let now = time::OffsetDateTime::now_utc();
+        let days_ago = |days: i64| {
+            (now - time::Duration::days(days))
+                .format(&time::format_description::well_known::Rfc3339)
+                .unwrap()
+        };
+        index
+            .add_documents(documents!([
+                { "id": 0, "date": "2023-12-31" },
+                { "id": 1, "date": "2024-01-01T12:00:00Z" },
+                { "id": 2, "date": "2024-01-02T00:00:00+02:00" },
+                { "id": 3, "date": days_ago(3) },
+                { "id": 4, "date": [days_ago(30), "2020-01-01"] },
+                { "id": 5, "date": "not a date" },
+                { "id": 6, "date": 1704067200 },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let evaluate = |filter: &str| {
+            let filter = Filter::from_str(filter).unwrap().unwrap();
+            filter.evaluate(&rtxn, &index).unwrap()
+        };
+
+        snapshot!(format!("{:?}", evaluate("date > 2024-01-01")), @"RoaringBitmap<[1, 2, 3, 4]>");
+        snapshot!(format!("{:?}", evaluate("date >= 2024-01-01")), @"RoaringBitmap<[1, 2, 3, 4, 6]>");
+        snapshot!(format!("{:?}", evaluate("date < '2024-01-01T12:00:00Z'")), @"RoaringBitmap<[0, 4, 6]>");
+        snapshot!(format!("{:?}", evaluate("date 2023-12-31 TO 2024-01-01")), @"RoaringBitmap<[0, 6]>");
+        snapshot!(format!("{:?}", evaluate("date > NOW-7d")), @"RoaringBitmap<[3]>");
+        snapshot!(format!("{:?}", evaluate("date > 'NOW - 60d'")), @"RoaringBitmap<[3, 4]>");
+        snapshot!(format!("{:?}", evaluate("date = '2024-01-01T12:00:00Z'")), @"RoaringBitmap<[1]>");
+        snapshot!(format!("{:?}", evaluate("date = 'not a date'")), @"RoaringBitmap<[5]>");
+    }
+
+    #[test]
+    fn filter_pattern_matching() {
+        let index = TempIndex::new();
+
+        let title_rule: FilterableAttributesRule = serde_json::from_value(serde_json::json!({
+            "attributePatterns": ["title"],
+            "features": { "filter": { "equality": true, "patternMatching": true } }
+        }))
+        .unwrap();
+        index
+            .update_settings(|settings| {
+                settings.set_primary_key("id".to_owned());
+                settings.set_filterable_fields(vec![
+                    title_rule,
+                    FilterableAttributesRule::Field("genre".to_string()),
+                ]);
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "title": "The Lord of the Rings", "genre": "fantasy" },
+                { "id": 1, "title": "The Hobbit", "genre": "fantasy" },
+                { "id": 2, "title": "Rings of Saturn", "genre": "science fiction" },
+                { "id": 3, "title": ["Dune", "Dune Messiah"], "genre": "science fiction" },
+                { "id": 4, "title": 2001, "genre": "science fiction" },
+                { "id": 5, "title": format!("{} the end", "long ".repeat(200)), "genre": "poetry" },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let evaluate = |filter: &str| {
+            let filter = Filter::from_str(filter).unwrap().unwrap();
+            filter.evaluate(&rtxn, &index).map(|docids| format!("{docids:?}"))
+        };
+
+        snapshot!(evaluate("title ENDS WITH rings").unwrap(), @"RoaringBitmap<[0]>");
+        snapshot!(evaluate("title ENDS WITH 'MESSIAH'").unwrap(), @"RoaringBitmap<[3]>");
+        snapshot!(evaluate("title NOT ENDS WITH rings").unwrap(), @"RoaringBitmap<[1, 2, 3, 4, 5]>");
+        snapshot!(evaluate("title MATCHES /rings/").unwrap(), @"RoaringBitmap<[0, 2]>");
+        snapshot!(evaluate("title MATCHES /^the \\w+$/").unwrap(), @"RoaringBitmap<[1]>");
+        snapshot!(evaluate("title MATCHES /^dune$/").unwrap(), @"RoaringBitmap<[3]>");
+        snapshot!(evaluate("title MATCHES /^(the|rings) /").unwrap(), @"RoaringBitmap<[0, 1, 2]>");
+        snapshot!(evaluate("title NOT MATCHES /^the/").unwrap(), @"RoaringBitmap<[2, 3, 4, 5]>");
+
+        // the values longer than the facet keys are matched on their whole value
+        snapshot!(evaluate("title ENDS WITH 'the end'").unwrap(), @"RoaringBitmap<[5]>");
+        snapshot!(evaluate("title MATCHES /long$/").unwrap(), @"RoaringBitmap<[]>");
+        snapshot!(evaluate("title MATCHES /long the end$/").unwrap(), @"RoaringBitmap<[5]>");
+
+        let error = evaluate("title MATCHES /(the/").unwrap_err().to_string();
+        assert!(error.starts_with("Invalid regular expression: regex parse error:"), "{error}");
+        assert!(error.contains("unclosed group"), "{error}");
+        snapshot!(evaluate("genre MATCHES /fiction$/").unwrap_err(), @r###"
+        Filter operator `MATCHES` is not allowed for the attribute `genre`.
+          - Note: allowed operators: OR, AND, NOT, =, !=, IN, <, >, <=, >=, TO, IS EMPTY, IS NULL, EXISTS.
+          - Note: field `genre` matched rule #1 in `filterableAttributes`
+          - Hint: enable patternMatching in rule #1 by modifying the features.filter object
+          - Hint: prepend another rule matching `genre` with appropriate filter features before rule #1
+        "###);
+    }
 }
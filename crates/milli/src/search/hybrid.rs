@@ -13,6 +13,7 @@ struct ScoreWithRatioResult {
     document_scores: Vec<(u32, ScoreWithRatio)>,
     degraded: bool,
     used_negative_operator: bool,
+    suggested_query: Option<String>,
 }
 
 type ScoreWithRatio = (Vec<ScoreDetails>, f32);
@@ -82,6 +83,7 @@ impl ScoreWithRatioResult {
             document_scores,
             degraded: results.degraded,
             used_negative_operator: results.used_negative_operator,
+            suggested_query: results.suggested_query,
         }
     }
 
@@ -145,6 +147,7 @@ impl ScoreWithRatioResult {
                 degraded: vector_results.degraded | keyword_results.degraded,
                 used_negative_operator: vector_results.used_negative_operator
                     | keyword_results.used_negative_operator,
+                suggested_query: keyword_results.suggested_query,
             },
             semantic_hit_count,
         )
@@ -154,6 +157,10 @@ impl ScoreWithRatioResult {
 impl Search<'_> {
     #[tracing::instrument(level = "trace", skip_all, target = "search::hybrid")]
     pub fn execute_hybrid(&self, semantic_ratio: f32) -> Result<(SearchResult, Option<u32>)> {
+        if let Some(reranker) = &self.reranker {
+            return self.execute_reranked(reranker, |search| search.execute_hybrid(semantic_ratio));
+        }
+
         // TODO: find classier way to achieve that than to reset vector and query params
         // create separate keyword and semantic searches
         let mut search = Search {
@@ -162,6 +169,7 @@ impl Search<'_> {
             offset: 0,
             limit: self.limit + self.offset,
             sort_criteria: self.sort_criteria.clone(),
+            boosts: self.boosts.clone(),
             distinct: self.distinct.clone(),
             searchable_attributes: self.searchable_attributes,
             geo_param: self.geo_param,
@@ -175,6 +183,8 @@ impl Search<'_> {
             time_budget: self.time_budget.clone(),
             ranking_score_threshold: self.ranking_score_threshold,
             locales: self.locales.clone(),
+            reranker: None,
+            spelling_corrections: self.spelling_corrections,
         };
 
         let semantic = search.semantic.take();
@@ -263,6 +273,7 @@ fn return_keyword_results(
         mut document_scores,
         degraded,
         used_negative_operator,
+        suggested_query,
     }: SearchResult,
 ) -> (SearchResult, Option<u32>) {
     let (documents_ids, document_scores) = if offset >= documents_ids.len() ||
@@ -289,6 +300,7 @@ fn return_keyword_results(
             document_scores,
             degraded,
             used_negative_operator,
+            suggested_query,
         },
         Some(0),
     )
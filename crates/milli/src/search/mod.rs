@@ -6,9 +6,14 @@ use levenshtein_automata::{LevenshteinAutomatonBuilder as LevBuilder, DFA};
 use once_cell::sync::Lazy;
 use roaring::bitmap::RoaringBitmap;
 
-pub use self::facet::{FacetDistribution, Filter, OrderBy, DEFAULT_VALUES_PER_FACET};
+pub use self::facet::{
+    FacetDistribution, FacetHierarchy, FacetRange, Filter, HistogramBucket, OrderBy,
+    DEFAULT_VALUES_PER_FACET, MAX_HISTOGRAM_BUCKETS,
+};
 pub use self::new::matches::{FormatOptions, MatchBounds, MatcherBuilder, MatchingWords};
-use self::new::{execute_vector_search, PartialSearchResult, VectorStoreStats};
+use self::new::{
+    execute_vector_search, PartialSearchResult, SpellingCorrections, VectorStoreStats,
+};
 use crate::filterable_attributes_rules::{filtered_matching_patterns, matching_features};
 use crate::score_details::{ScoreDetails, ScoringStrategy};
 use crate::vector::Embedder;
@@ -26,10 +31,13 @@ pub mod facet;
 mod fst_utils;
 pub mod hybrid;
 pub mod new;
+mod rerank;
 pub mod similar;
+pub mod suggest;
 
 #[derive(Debug, Clone)]
 pub struct SemanticSearch {
+    /// Packed with [`crate::vector::sparse::SparseEmbedding::to_packed`] for the sparse embedders.
     vector: Option<Vec<f32>>,
     embedder_name: String,
     embedder: Arc<Embedder>,
@@ -43,6 +51,7 @@ pub struct Search<'a> {
     offset: usize,
     limit: usize,
     sort_criteria: Option<Vec<AscDesc>>,
+    boosts: Vec<(Filter<'a>, f64)>,
     distinct: Option<String>,
     searchable_attributes: Option<&'a [String]>,
     geo_param: new::GeoSortParameter,
@@ -56,6 +65,8 @@ pub struct Search<'a> {
     time_budget: TimeBudget,
     ranking_score_threshold: Option<f64>,
     locales: Option<Vec<Language>>,
+    reranker: Option<String>,
+    spelling_corrections: Option<&'a SpellingCorrections>,
 }
 
 impl<'a> Search<'a> {
@@ -66,6 +77,7 @@ impl<'a> Search<'a> {
             offset: 0,
             limit: 20,
             sort_criteria: None,
+            boosts: Vec::new(),
             distinct: None,
             searchable_attributes: None,
             geo_param: new::GeoSortParameter::default(),
@@ -79,6 +91,8 @@ impl<'a> Search<'a> {
             locales: None,
             time_budget: TimeBudget::max(),
             ranking_score_threshold: None,
+            reranker: None,
+            spelling_corrections: None,
         }
     }
 
@@ -87,6 +101,7 @@ impl<'a> Search<'a> {
         self
     }
 
+    /// The `vector` of a sparse embedder must already be packed.
     pub fn semantic(
         &mut self,
         embedder_name: String,
@@ -113,6 +128,13 @@ impl<'a> Search<'a> {
         self
     }
 
+    /// Ranks the documents matching the filters before the other ones, by the sum
+    /// of their weights, when the ranking rules can't tell the documents apart.
+    pub fn boosts(&mut self, boosts: Vec<(Filter<'a>, f64)>) -> &mut Search<'a> {
+        self.boosts = boosts;
+        self
+    }
+
     pub fn distinct(&mut self, distinct: String) -> &mut Search<'a> {
         self.distinct = Some(distinct);
         self
@@ -177,6 +199,22 @@ impl<'a> Search<'a> {
         self
     }
 
+    /// Reorders the best results of the search with the reranker of the index with this name.
+    pub fn reranker(&mut self, reranker: String) -> &mut Search<'a> {
+        self.reranker = Some(reranker);
+        self
+    }
+
+    /// Shares the corrections of the words of the suggested queries with the other searches
+    /// made on the index with the same read transaction.
+    pub fn spelling_corrections(
+        &mut self,
+        spelling_corrections: &'a SpellingCorrections,
+    ) -> &mut Search<'a> {
+        self.spelling_corrections = Some(spelling_corrections);
+        self
+    }
+
     pub fn execute_for_candidates(&self, has_vector_search: bool) -> Result<RoaringBitmap> {
         if has_vector_search {
             let ctx = SearchContext::new(self.index, self.rtxn)?;
@@ -187,7 +225,14 @@ impl<'a> Search<'a> {
     }
 
     pub fn execute(&self) -> Result<SearchResult> {
+        if let Some(reranker) = &self.reranker {
+            return self
+                .execute_reranked(reranker, |search| Ok((search.execute()?, ())))
+                .map(|(result, ())| result);
+        }
+
         let mut ctx = SearchContext::new(self.index, self.rtxn)?;
+        ctx.spelling_corrections = self.spelling_corrections;
 
         if let Some(searchable_attributes) = self.searchable_attributes {
             ctx.attributes_to_search_on(searchable_attributes)?;
@@ -222,6 +267,11 @@ impl<'a> Search<'a> {
         }
 
         let universe = filtered_universe(ctx.index, ctx.txn, &self.filter)?;
+        let boosts = self
+            .boosts
+            .iter()
+            .map(|(filter, weight)| Ok((filter.evaluate(ctx.txn, ctx.index)? & &universe, *weight)))
+            .collect::<Result<Vec<_>>>()?;
         let PartialSearchResult {
             located_query_terms,
             candidates,
@@ -229,6 +279,7 @@ impl<'a> Search<'a> {
             document_scores,
             degraded,
             used_negative_operator,
+            suggested_query,
         } = match self.semantic.as_ref() {
             Some(SemanticSearch { vector: Some(vector), embedder_name, embedder, quantized }) => {
                 execute_vector_search(
@@ -237,6 +288,7 @@ impl<'a> Search<'a> {
                     self.scoring_strategy,
                     universe,
                     &self.sort_criteria,
+                    boosts,
                     &self.distinct,
                     self.geo_param,
                     self.offset,
@@ -256,6 +308,7 @@ impl<'a> Search<'a> {
                 self.exhaustive_number_hits,
                 universe,
                 &self.sort_criteria,
+                boosts,
                 &self.distinct,
                 self.geo_param,
                 self.offset,
@@ -288,6 +341,7 @@ impl<'a> Search<'a> {
             documents_ids,
             degraded,
             used_negative_operator,
+            suggested_query,
         })
     }
 }
@@ -300,6 +354,7 @@ impl fmt::Debug for Search<'_> {
             offset,
             limit,
             sort_criteria,
+            boosts,
             distinct,
             searchable_attributes,
             geo_param: _,
@@ -313,6 +368,8 @@ impl fmt::Debug for Search<'_> {
             time_budget,
             ranking_score_threshold,
             locales,
+            reranker,
+            spelling_corrections: _,
         } = self;
         f.debug_struct("Search")
             .field("query", query)
@@ -321,6 +378,7 @@ impl fmt::Debug for Search<'_> {
             .field("offset", offset)
             .field("limit", limit)
             .field("sort_criteria", sort_criteria)
+            .field("boosts", boosts)
             .field("distinct", distinct)
             .field("searchable_attributes", searchable_attributes)
             .field("terms_matching_strategy", terms_matching_strategy)
@@ -334,6 +392,7 @@ impl fmt::Debug for Search<'_> {
             .field("time_budget", time_budget)
             .field("ranking_score_threshold", ranking_score_threshold)
             .field("locales", locales)
+            .field("reranker", reranker)
             .finish()
     }
 }
@@ -346,6 +405,8 @@ pub struct SearchResult {
     pub document_scores: Vec<Vec<ScoreDetails>>,
     pub degraded: bool,
     pub used_negative_operator: bool,
+    /// The query with its words missing from the index corrected, when the search has few hits.
+    pub suggested_query: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -0,0 +1,106 @@
+use roaring::RoaringBitmap;
+
+use super::logger::SearchLogger;
+use super::{RankingRule, RankingRuleOutput, RankingRuleQueryTrait, SearchContext};
+use crate::score_details::{self, ScoreDetails};
+use crate::Result;
+
+/// Ranks the documents by the sum of the weights of the boosts they match.
+///
+/// This rule is always the last one, so that the boosts only change the order of
+/// the documents that the ranking rules of the index could not tell apart.
+pub struct BoostSort<Query> {
+    /// The documents matching each boost filter, with the weight of the boost.
+    boosts: Vec<(RoaringBitmap, f64)>,
+    max_weight: f64,
+    original_query: Option<Query>,
+    /// The documents of each total weight, the best weight being the last one.
+    buckets: Vec<(f64, RoaringBitmap)>,
+}
+
+impl<Query> BoostSort<Query> {
+    pub fn new(boosts: Vec<(RoaringBitmap, f64)>) -> Self {
+        let max_weight = boosts.iter().map(|(_, weight)| weight).sum();
+        Self { boosts, max_weight, original_query: None, buckets: Vec::new() }
+    }
+
+    fn score(&self, weight: f64) -> ScoreDetails {
+        ScoreDetails::Boost(score_details::Boost { weight, max_weight: self.max_weight })
+    }
+}
+
+impl<'ctx, Query: RankingRuleQueryTrait> RankingRule<'ctx, Query> for BoostSort<Query> {
+    fn id(&self) -> String {
+        "boost".to_string()
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::boost_sort")]
+    fn start_iteration(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        parent_candidates: &RoaringBitmap,
+        parent_query: &Query,
+    ) -> Result<()> {
+        // split the candidates by the boosts they match, one boost at a time
+        let mut groups = vec![(0.0, parent_candidates.clone())];
+        for (docids, weight) in &self.boosts {
+            groups = groups
+                .into_iter()
+                .flat_map(|(total, candidates)| {
+                    let matching = &candidates & docids;
+                    let others = candidates - &matching;
+                    [(total + weight, matching), (total, others)]
+                })
+                .filter(|(_, candidates)| !candidates.is_empty())
+                .collect();
+        }
+
+        // the groups with the same total weight are merged
+        groups.sort_by(|(left, _), (right, _)| left.total_cmp(right));
+        let mut buckets: Vec<(f64, RoaringBitmap)> = Vec::new();
+        for (total, candidates) in groups {
+            match buckets.last_mut() {
+                Some((last, docids)) if *last == total => *docids |= candidates,
+                _ => buckets.push((total, candidates)),
+            }
+        }
+
+        self.buckets = buckets;
+        self.original_query = Some(parent_query.clone());
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::boost_sort")]
+    fn next_bucket(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        universe: &RoaringBitmap,
+    ) -> Result<Option<RankingRuleOutput<Query>>> {
+        let query = self.original_query.as_ref().unwrap().clone();
+        while let Some((weight, mut candidates)) = self.buckets.pop() {
+            candidates &= universe;
+            if !candidates.is_empty() {
+                return Ok(Some(RankingRuleOutput {
+                    query,
+                    candidates,
+                    score: self.score(weight),
+                }));
+            }
+        }
+
+        // the remaining documents were not part of the parent candidates
+        Ok(Some(RankingRuleOutput { query, candidates: universe.clone(), score: self.score(0.0) }))
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::boost_sort")]
+    fn end_iteration(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+    ) {
+        self.original_query = None;
+        self.buckets.clear();
+    }
+}
@@ -263,6 +263,8 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
 
         cur_ranking_rule_index += 1;
         ranking_rule_universes[cur_ranking_rule_index].clone_from(&next_bucket.candidates);
+        ranking_rules[cur_ranking_rule_index]
+            .set_parent_ranking_score(ScoreDetails::global_score(ranking_rule_scores.iter()));
         logger.start_iteration_ranking_rule(
             cur_ranking_rule_index,
             ranking_rules[cur_ranking_rule_index].as_ref(),
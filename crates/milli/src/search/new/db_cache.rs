@@ -31,6 +31,7 @@ pub struct DatabaseCache<'ctx> {
     pub prefix_word_pair_proximity_docids:
         FxHashMap<(u8, Interned<String>, Interned<String>), Option<Cow<'ctx, [u8]>>>,
     pub word_docids: FxHashMap<Interned<String>, Option<Cow<'ctx, [u8]>>>,
+    pub stem_word_docids: FxHashMap<Interned<String>, Option<Cow<'ctx, [u8]>>>,
     pub exact_word_docids: FxHashMap<Interned<String>, Option<Cow<'ctx, [u8]>>>,
     pub word_prefix_docids: FxHashMap<Interned<String>, Option<Cow<'ctx, [u8]>>>,
     pub exact_word_prefix_docids: FxHashMap<Interned<String>, Option<Cow<'ctx, [u8]>>>,
@@ -201,9 +202,43 @@ impl<'ctx> SearchContext<'ctx> {
                 })
             }
             Word::Derived(word) => self.get_db_word_docids(universe, word),
+            Word::Stem(word) => self.get_db_stem_word_docids(universe, word),
         }
     }
 
+    /// Retrieve or insert the documents containing the word in the stemmed fields,
+    /// from the `word_fid_docids` database.
+    fn get_db_stem_word_docids(
+        &mut self,
+        universe: Option<&RoaringBitmap>,
+        word: Interned<String>,
+    ) -> Result<Option<RoaringBitmap>> {
+        let interned = self.word_interner.get(word).as_str();
+        let keys: Vec<_> = self
+            .stemmed_fids
+            .iter()
+            .filter(|fid| {
+                self.restricted_fids.as_ref().is_none_or(|restricted_fids| {
+                    restricted_fids
+                        .tolerant
+                        .iter()
+                        .any(|(restricted_fid, _)| restricted_fid == *fid)
+                })
+            })
+            .map(|fid| (interned, *fid))
+            .collect();
+
+        DatabaseCache::get_value_from_keys(
+            self.txn,
+            word,
+            &keys[..],
+            &mut self.db_cache.stem_word_docids,
+            self.index.word_fid_docids.remap_data_type::<Bytes>(),
+            universe,
+            MergeCboRoaringBitmaps,
+        )
+    }
+
     /// Retrieve or insert the given value in the `word_docids` database.
     fn get_db_word_docids(
         &mut self,
@@ -289,7 +324,9 @@ impl<'ctx> SearchContext<'ctx> {
                     }
                 })
             }
-            Word::Derived(prefix) => self.get_db_word_prefix_docids(universe, prefix),
+            Word::Derived(prefix) | Word::Stem(prefix) => {
+                self.get_db_word_prefix_docids(universe, prefix)
+            }
         }
     }
 
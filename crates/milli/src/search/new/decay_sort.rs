@@ -0,0 +1,203 @@
+use std::ops::Bound;
+
+use roaring::RoaringBitmap;
+
+use super::logger::SearchLogger;
+use super::{RankingRule, RankingRuleOutput, RankingRuleQueryTrait, SearchContext};
+use crate::date::parse_date;
+use crate::filterable_attributes_rules::matching_features;
+use crate::heed_codec::facet::{FacetGroupKey, FacetGroupValue, OrderedF64Codec};
+use crate::score_details::{self, Rank, ScoreDetails};
+use crate::search::facet::find_docids_of_facet_within_bounds;
+use crate::{CriterionError, FieldId, Index, RankingDecay, Result};
+
+/// Ranks the documents according to the closeness of the values of a field to an origin,
+/// as described by a `decay:` ranking rule.
+///
+/// The scores of the documents are rounded to a hundred of ranks so that the documents with
+/// close values are returned together and can be sorted by the subsequent ranking rules.
+/// A document with many values is ranked with the closest one, and the documents
+/// without a value are returned last.
+///
+/// Each rank corresponds to a range of distances to the origin, the documents of a rank are
+/// found with a range search in the numeric facet database, one rank after the other.
+pub struct DecaySort<Query> {
+    source: String,
+    decay: RankingDecay,
+    field_id: Option<FieldId>,
+    /// Whether the values of the field are stored as timestamps in the numeric facet database,
+    /// or if the dates must be parsed from the string facet database.
+    dates_as_numbers: bool,
+    must_redact: bool,
+    original_query: Option<Query>,
+    origin: f64,
+    /// The next rank to return, the best rank being returned first.
+    next_rank: u32,
+    /// The documents of each rank whose dates are only stored as strings.
+    string_buckets: Vec<RoaringBitmap>,
+}
+
+impl<Query> DecaySort<Query> {
+    pub fn new(index: &Index, rtxn: &heed::RoTxn<'_>, source: String) -> Result<Self> {
+        let decay: RankingDecay = source.parse().map_err(|error| CriterionError::InvalidDecay {
+            name: format!("decay:{source}"),
+            error,
+        })?;
+
+        let fields_ids_map = index.fields_ids_map(rtxn)?;
+        let field_id = fields_ids_map.id(&decay.field);
+
+        let filterable_attributes_rules = index.filterable_attributes_rules(rtxn)?;
+        let dates_as_numbers = !decay.is_date()
+            || matching_features(&decay.field, &filterable_attributes_rules)
+                .is_some_and(|(_, features)| features.is_date());
+
+        let must_redact = match index.displayed_fields(rtxn)? {
+            Some(displayed_fields) => !displayed_fields.contains(&decay.field.as_str()),
+            None => false,
+        };
+
+        Ok(Self {
+            source,
+            decay,
+            field_id,
+            dates_as_numbers,
+            must_redact,
+            original_query: None,
+            origin: 0.0,
+            next_rank: 0,
+            string_buckets: Vec::new(),
+        })
+    }
+
+    fn score(&self, rank: u32) -> ScoreDetails {
+        ScoreDetails::Decay(score_details::Decay {
+            decay: self.source.clone(),
+            redacted: self.must_redact,
+            rank: Rank { rank, max_rank: score_details::Decay::MAX_RANK },
+        })
+    }
+
+    /// Returns the documents of the universe having a value in the range of distances of the rank.
+    fn rank_docids(
+        &self,
+        ctx: &SearchContext<'_>,
+        field_id: FieldId,
+        rank: u32,
+        universe: &RoaringBitmap,
+    ) -> Result<RoaringBitmap> {
+        // a score is rounded to the rank `r` when it is in `[(r - 1.5) / 100, (r - 0.5) / 100)`
+        let steps = (score_details::Decay::MAX_RANK - 1) as f64;
+        let outer = self.decay.distance((rank as f64 - 1.5) / steps);
+        let inner = (rank < score_details::Decay::MAX_RANK)
+            .then(|| self.decay.distance((rank as f64 - 0.5) / steps));
+
+        let ranges = match inner {
+            Some(inner) => vec![
+                (Bound::Included(self.origin - outer), Bound::Excluded(self.origin - inner)),
+                (Bound::Excluded(self.origin + inner), Bound::Included(self.origin + outer)),
+            ],
+            None => {
+                vec![(Bound::Included(self.origin - outer), Bound::Included(self.origin + outer))]
+            }
+        };
+
+        let mut docids = RoaringBitmap::new();
+        for (left, right) in &ranges {
+            find_docids_of_facet_within_bounds::<OrderedF64Codec>(
+                ctx.txn,
+                ctx.index.facet_id_f64_docids,
+                field_id,
+                left,
+                right,
+                Some(universe),
+                &mut docids,
+            )?;
+        }
+        if let Some(string_docids) = self.string_buckets.get(rank as usize - 1) {
+            docids |= string_docids & universe;
+        }
+
+        Ok(docids)
+    }
+}
+
+impl<'ctx, Query: RankingRuleQueryTrait> RankingRule<'ctx, Query> for DecaySort<Query> {
+    fn id(&self) -> String {
+        format!("decay:{}", self.source)
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::decay_sort")]
+    fn start_iteration(
+        &mut self,
+        ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        parent_candidates: &RoaringBitmap,
+        parent_query: &Query,
+    ) -> Result<()> {
+        self.origin = self.decay.origin_value();
+        self.next_rank = score_details::Decay::MAX_RANK;
+        self.string_buckets.clear();
+
+        // The dates that are not declared as such in the filterable attributes are only stored
+        // as strings in the documents, they can't be found by range and must all be parsed.
+        if let Some(field_id) = self.field_id.filter(|_| !self.dates_as_numbers) {
+            let mut buckets = vec![RoaringBitmap::new(); score_details::Decay::MAX_RANK as usize];
+            let prefix = FacetGroupKey { field_id, level: 0, left_bound: "" };
+            for result in ctx.index.facet_id_string_docids.prefix_iter(ctx.txn, &prefix)? {
+                let (FacetGroupKey { left_bound, .. }, FacetGroupValue { bitmap, .. }) = result?;
+                let Some(value) = parse_date(left_bound) else { continue };
+                let docids = bitmap & parent_candidates;
+                if !docids.is_empty() {
+                    let score = self.decay.score(self.origin, value);
+                    let Rank { rank, .. } = score_details::Decay::rank_from_score(Some(score));
+                    buckets[rank as usize - 1] |= docids;
+                }
+            }
+            self.string_buckets = buckets;
+        }
+
+        self.original_query = Some(parent_query.clone());
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::decay_sort")]
+    fn next_bucket(
+        &mut self,
+        ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        universe: &RoaringBitmap,
+    ) -> Result<Option<RankingRuleOutput<Query>>> {
+        let query = self.original_query.as_ref().unwrap().clone();
+
+        // The documents of the best ranks are removed from the universe once returned,
+        // a document with many values is thus only returned with its closest value.
+        if let Some(field_id) = self.field_id {
+            while self.next_rank > 1 && !universe.is_empty() {
+                let rank = self.next_rank;
+                self.next_rank -= 1;
+                let candidates = self.rank_docids(ctx, field_id, rank, universe)?;
+                if !candidates.is_empty() {
+                    return Ok(Some(RankingRuleOutput {
+                        query,
+                        candidates,
+                        score: self.score(rank),
+                    }));
+                }
+            }
+        }
+
+        // the remaining documents are too far from the origin or don't have a value
+        Ok(Some(RankingRuleOutput { query, candidates: universe.clone(), score: self.score(1) }))
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::decay_sort")]
+    fn end_iteration(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+    ) {
+        self.original_query = None;
+        self.string_buckets.clear();
+    }
+}
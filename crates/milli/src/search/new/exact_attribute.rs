@@ -98,8 +98,10 @@ impl State {
         struct ExactTermInfo {
             exact_term: ExactTerm,
             start_position: u16,
-            start_term_id: u8,
             position_count: usize,
+            start_term_id: u8,
+            end_term_id: u8,
+            word_count: usize,
         }
 
         let mut exact_terms: Vec<ExactTermInfo> =
@@ -115,121 +117,142 @@ impl State {
                     exact_terms.push(ExactTermInfo {
                         exact_term,
                         start_position: *term.positions.start(),
-                        start_term_id: *term.term_ids.start(),
                         position_count: term.positions.len(),
+                        start_term_id: *term.term_ids.start(),
+                        end_term_id: *term.term_ids.end(),
+                        // an alternative of an `OR` operator can be located at the positions
+                        // of an operand made of more words
+                        word_count: term
+                            .positions
+                            .len()
+                            .min(exact_term.interned_words(ctx).count()),
                     });
                 }
                 QueryNodeData::Deleted | QueryNodeData::Start | QueryNodeData::End => continue,
             }
         }
 
-        exact_terms.sort_by_key(|x| x.start_term_id);
-        exact_terms.dedup_by_key(|x| x.start_term_id);
-        let count_all_positions = exact_terms.iter().fold(0, |acc, x| acc + x.position_count);
-
-        // bail if there is a "hole" (missing word) in remaining query graph
-        if let Some(e) = exact_terms.first() {
-            if e.start_term_id != 0 {
-                return Ok(State::Empty(query_graph.clone()));
-            }
-        } else {
+        // The alternatives of the `OR` operators make several sequences of terms, each of them
+        // being a way to write the query, e.g. `gaming laptop` and `notebook` for the query
+        // `(gaming laptop) OR notebook`.
+        // We bail if there is a "hole" (missing word) in all of them.
+        let Some(last_term_id) = exact_terms.iter().map(|e| e.end_term_id).max() else {
             return Ok(State::Empty(query_graph.clone()));
-        }
-        let mut previous_id = 0;
-        for e in exact_terms.iter() {
-            if e.start_term_id < previous_id || e.start_term_id - previous_id > 1 {
-                return Ok(State::Empty(query_graph.clone()));
-            } else {
-                previous_id = e.start_term_id;
+        };
+        let mut sequences: Vec<Vec<&ExactTermInfo>> = Vec::new();
+        let mut stack: Vec<Vec<&ExactTermInfo>> = vec![vec![]];
+        while let Some(sequence) = stack.pop() {
+            let next_term_id = sequence.last().map_or(0, |e| e.end_term_id + 1);
+            if next_term_id > last_term_id {
+                sequences.push(sequence);
+                continue;
+            }
+            for e in exact_terms.iter().filter(|e| e.start_term_id == next_term_id) {
+                let mut sequence = sequence.clone();
+                sequence.push(e);
+                stack.push(sequence);
             }
         }
+        if sequences.is_empty() {
+            return Ok(State::Empty(query_graph.clone()));
+        }
 
-        // sample query: "sunflower are pretty"
-        // sunflower at pos 0 in attr A
-        // are at pos 1 in attr B
-        // pretty at pos 2 in attr C
-        // We want to eliminate such document
+        let searchable_fields_ids = ctx.index.searchable_fields_ids(ctx.txn)?;
 
-        // first check that for each term, there exists some attribute that has this term at the correct position
-        //"word-position-docids";
-        let mut candidates = universe.clone();
-        let words_positions: Vec<(Vec<_>, _)> = exact_terms
-            .iter()
-            .map(|e| (e.exact_term.interned_words(ctx).collect(), e.start_position))
-            .collect();
-        for (words, position) in &words_positions {
-            if candidates.is_empty() {
-                return Ok(State::Empty(query_graph.clone()));
+        let mut any_candidates = false;
+        let mut candidates_per_attribute = Vec::new();
+        for sequence in sequences {
+            // The terms following an alternative located at the positions of a longer operand
+            // are shifted back to the positions they have in this sequence.
+            let mut shift = 0;
+            let mut words_positions: Vec<(Vec<_>, u16)> = Vec::with_capacity(sequence.len());
+            for e in &sequence {
+                words_positions
+                    .push((e.exact_term.interned_words(ctx).collect(), e.start_position - shift));
+                shift += (e.position_count - e.word_count) as u16;
             }
+            let count_all_positions = sequence.iter().fold(0, |acc, x| acc + x.word_count);
 
-            'words: for (offset, word) in words.iter().enumerate() {
-                let offset = offset as u16;
-                let word = if let Some(word) = word {
-                    word
-                } else {
-                    continue 'words;
-                };
-                // Note: Since the position is stored bucketed in word_position_docids, for queries with a lot of
-                // longer phrases we'll be losing on precision here.
-                let bucketed_position = crate::bucketed_position(position + offset);
-                let word_position_docids = ctx
-                    .get_db_word_position_docids(Some(universe), *word, bucketed_position)?
-                    .unwrap_or_default();
-                candidates &= word_position_docids;
-                if candidates.is_empty() {
-                    return Ok(State::Empty(query_graph.clone()));
+            // sample query: "sunflower are pretty"
+            // sunflower at pos 0 in attr A
+            // are at pos 1 in attr B
+            // pretty at pos 2 in attr C
+            // We want to eliminate such document
+
+            // first check that for each term, there exists some attribute that has this term at the correct position
+            //"word-position-docids";
+            let mut candidates = universe.clone();
+            'terms: for (words, position) in &words_positions {
+                'words: for (offset, word) in words.iter().enumerate() {
+                    let offset = offset as u16;
+                    let word = if let Some(word) = word {
+                        word
+                    } else {
+                        continue 'words;
+                    };
+                    // Note: Since the position is stored bucketed in word_position_docids, for queries with a lot of
+                    // longer phrases we'll be losing on precision here.
+                    let bucketed_position = crate::bucketed_position(position + offset);
+                    let word_position_docids = ctx
+                        .get_db_word_position_docids(Some(universe), *word, bucketed_position)?
+                        .unwrap_or_default();
+                    candidates &= word_position_docids;
+                    if candidates.is_empty() {
+                        break 'terms;
+                    }
                 }
             }
-        }
-
-        let candidates = candidates;
-
-        if candidates.is_empty() {
-            return Ok(State::Empty(query_graph.clone()));
-        }
 
-        let searchable_fields_ids = ctx.index.searchable_fields_ids(ctx.txn)?;
+            if candidates.is_empty() {
+                continue;
+            }
+            any_candidates = true;
 
-        let mut candidates_per_attribute = Vec::with_capacity(searchable_fields_ids.len());
-        // then check that there exists at least one attribute that has all of the terms
-        for fid in searchable_fields_ids {
-            let intersection = MultiOps::intersection(
-                words_positions
-                    .iter()
-                    .flat_map(|(words, ..)| words.iter())
-                    // ignore stop words words in phrases
-                    .flatten()
-                    .map(|word| -> Result<_> {
-                        Ok(ctx
-                            .get_db_word_fid_docids(Some(&candidates), *word, fid)?
-                            .unwrap_or_default())
-                    }),
-            )?;
-            if !intersection.is_empty() {
-                // Although not really worth it in terms of performance,
-                // if would be good to put this in cache for the sake of consistency
-                let candidates_with_exact_word_count = if count_all_positions < u8::MAX as usize {
-                    let bitmap_bytes = ctx
-                        .index
-                        .field_id_word_count_docids
-                        .remap_data_type::<Bytes>()
-                        .get(ctx.txn, &(fid, count_all_positions as u8))?;
+            // then check that there exists at least one attribute that has all of the terms
+            for &fid in &searchable_fields_ids {
+                let intersection = MultiOps::intersection(
+                    words_positions
+                        .iter()
+                        .flat_map(|(words, ..)| words.iter())
+                        // ignore stop words words in phrases
+                        .flatten()
+                        .map(|word| -> Result<_> {
+                            Ok(ctx
+                                .get_db_word_fid_docids(Some(&candidates), *word, fid)?
+                                .unwrap_or_default())
+                        }),
+                )?;
+                if !intersection.is_empty() {
+                    // Although not really worth it in terms of performance,
+                    // if would be good to put this in cache for the sake of consistency
+                    let candidates_with_exact_word_count = if count_all_positions < u8::MAX as usize
+                    {
+                        let bitmap_bytes = ctx
+                            .index
+                            .field_id_word_count_docids
+                            .remap_data_type::<Bytes>()
+                            .get(ctx.txn, &(fid, count_all_positions as u8))?;
 
-                    match bitmap_bytes {
-                        Some(bytes) => {
-                            CboRoaringBitmapCodec::intersection_with_serialized(bytes, universe)?
+                        match bitmap_bytes {
+                            Some(bytes) => CboRoaringBitmapCodec::intersection_with_serialized(
+                                bytes, universe,
+                            )?,
+                            None => RoaringBitmap::default(),
                         }
-                        None => RoaringBitmap::default(),
-                    }
-                } else {
-                    RoaringBitmap::default()
-                };
-                candidates_per_attribute.push(FieldCandidates {
-                    start_with_exact: intersection,
-                    exact_word_count: candidates_with_exact_word_count,
-                });
+                    } else {
+                        RoaringBitmap::default()
+                    };
+                    candidates_per_attribute.push(FieldCandidates {
+                        start_with_exact: intersection,
+                        exact_word_count: candidates_with_exact_word_count,
+                    });
+                }
             }
         }
+
+        if !any_candidates {
+            return Ok(State::Empty(query_graph.clone()));
+        }
         // note we could have "false positives" where there both exist different attributes that collectively
         // have the terms in the correct order and a single attribute that have all the terms, but in the incorrect order.
 
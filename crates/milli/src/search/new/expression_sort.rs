@@ -0,0 +1,188 @@
+use std::collections::{HashMap, VecDeque};
+
+use heed::BytesDecode;
+use roaring::RoaringBitmap;
+
+use super::logger::SearchLogger;
+use super::{RankingRule, RankingRuleOutput, RankingRuleQueryTrait, SearchContext};
+use crate::heed_codec::facet::{FacetGroupKeyCodec, OrderedF64Codec};
+use crate::heed_codec::BytesRefCodec;
+use crate::score_details::{self, ScoreDetails};
+use crate::search::facet::ascending_facet_sort;
+use crate::{
+    CriterionError, DocumentId, FieldId, Index, RankingExpression, Result, RANKING_SCORE_VARIABLE,
+};
+
+/// Sorts the documents by the decreasing value of an `expr:` ranking rule.
+///
+/// The fields of the expression are read from the numeric facet databases, the smallest
+/// number being used when a field contains many of them. The documents for which the
+/// expression cannot be computed are returned last.
+///
+/// The score of a document is its value normalized between the smallest and the largest
+/// values of the sorted candidates.
+pub struct ExpressionSort<Query> {
+    source: String,
+    expression: RankingExpression,
+    field_ids: Vec<(String, Option<FieldId>)>,
+    must_redact: bool,
+    parent_ranking_score: f64,
+    original_query: Option<Query>,
+    /// The buckets of documents sharing the same value, sorted by decreasing values.
+    buckets: VecDeque<(Option<f64>, RoaringBitmap)>,
+    /// The smallest and largest values of the sorted candidates.
+    bounds: (f64, f64),
+}
+
+impl<Query> ExpressionSort<Query> {
+    pub fn new(index: &Index, rtxn: &heed::RoTxn<'_>, source: String) -> Result<Self> {
+        let expression: RankingExpression = source.parse().map_err(|error| {
+            CriterionError::InvalidExpression { name: format!("expr:{source}"), error }
+        })?;
+
+        let fields_ids_map = index.fields_ids_map(rtxn)?;
+        let field_ids: Vec<_> = expression
+            .fields()
+            .into_iter()
+            .map(|field| (field.to_string(), fields_ids_map.id(field)))
+            .collect();
+
+        let must_redact = match index.displayed_fields(rtxn)? {
+            Some(displayed_fields) => {
+                field_ids.iter().any(|(field, _)| !displayed_fields.contains(&field.as_str()))
+            }
+            None => false,
+        };
+
+        Ok(Self {
+            source,
+            expression,
+            field_ids,
+            must_redact,
+            parent_ranking_score: 1.0,
+            original_query: None,
+            buckets: VecDeque::new(),
+            bounds: (0.0, 0.0),
+        })
+    }
+
+    fn score(&self, value: Option<f64>) -> ScoreDetails {
+        let (min, max) = self.bounds;
+        // when all the documents share the same value, they all get the best score
+        let score = value.map(|value| if max > min { (value - min) / (max - min) } else { 1.0 });
+        ScoreDetails::Expression(score_details::Expression {
+            expression: self.source.clone(),
+            redacted: self.must_redact,
+            value,
+            score,
+        })
+    }
+}
+
+impl<'ctx, Query: RankingRuleQueryTrait> RankingRule<'ctx, Query> for ExpressionSort<Query> {
+    fn id(&self) -> String {
+        format!("expr:{}", self.source)
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::expression_sort")]
+    fn start_iteration(
+        &mut self,
+        ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        parent_candidates: &RoaringBitmap,
+        parent_query: &Query,
+    ) -> Result<()> {
+        let number_db =
+            ctx.index.facet_id_f64_docids.remap_key_type::<FacetGroupKeyCodec<BytesRefCodec>>();
+
+        // We retrieve the values of every field for all the candidates at once,
+        // iterating in ascending order to only keep the smallest value of each document.
+        let mut field_values: HashMap<&str, HashMap<DocumentId, f64>> = HashMap::new();
+        for (field, field_id) in &self.field_ids {
+            let values = field_values.entry(field.as_str()).or_default();
+            let Some(field_id) = *field_id else { continue };
+            for result in
+                ascending_facet_sort(ctx.txn, number_db, field_id, parent_candidates.clone())?
+            {
+                let (docids, bytes) = result?;
+                let value = OrderedF64Codec::bytes_decode(bytes).expect("some number");
+                for docid in docids {
+                    values.entry(docid).or_insert(value);
+                }
+            }
+        }
+
+        let mut values = Vec::with_capacity(parent_candidates.len() as usize);
+        let mut without_value = RoaringBitmap::new();
+        for docid in parent_candidates {
+            let value = self.expression.evaluate(&|variable| {
+                if variable == RANKING_SCORE_VARIABLE {
+                    Some(self.parent_ranking_score)
+                } else {
+                    field_values.get(variable)?.get(&docid).copied()
+                }
+            });
+            match value {
+                Some(value) => values.push((value, docid)),
+                None => {
+                    without_value.insert(docid);
+                }
+            }
+        }
+
+        // the documents with the highest values come first
+        values.sort_unstable_by(|(left, _), (right, _)| right.total_cmp(left));
+        self.bounds = match (values.last(), values.first()) {
+            (Some((min, _)), Some((max, _))) => (*min, *max),
+            _ => (0.0, 0.0),
+        };
+        self.buckets.clear();
+        for (value, docid) in values {
+            match self.buckets.back_mut() {
+                Some((Some(bucket_value), docids)) if *bucket_value == value => {
+                    docids.insert(docid);
+                }
+                _ => self.buckets.push_back((Some(value), RoaringBitmap::from_iter([docid]))),
+            }
+        }
+        if !without_value.is_empty() {
+            self.buckets.push_back((None, without_value));
+        }
+
+        self.original_query = Some(parent_query.clone());
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::expression_sort")]
+    fn next_bucket(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        universe: &RoaringBitmap,
+    ) -> Result<Option<RankingRuleOutput<Query>>> {
+        let query = self.original_query.as_ref().unwrap().clone();
+        while let Some((value, mut candidates)) = self.buckets.pop_front() {
+            candidates &= universe;
+            if !candidates.is_empty() {
+                return Ok(Some(RankingRuleOutput { query, candidates, score: self.score(value) }));
+            }
+        }
+
+        // the remaining documents were not part of the parent candidates
+        Ok(Some(RankingRuleOutput { query, candidates: universe.clone(), score: self.score(None) }))
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::expression_sort")]
+    fn end_iteration(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+    ) {
+        self.original_query = None;
+        self.buckets.clear();
+    }
+
+    fn set_parent_ranking_score(&mut self, ranking_score: f64) {
+        self.parent_ranking_score = ranking_score;
+    }
+}
@@ -1,44 +1,20 @@
-use heed::types::{Bytes, Unit};
-use heed::{RoPrefix, RoTxn};
+use heed::RoTxn;
+use ordered_float::OrderedFloat;
 use roaring::RoaringBitmap;
 use rstar::RTree;
-use std::collections::VecDeque;
+use serde_json::Value;
+use std::collections::{BinaryHeap, VecDeque};
 
-use super::facet_string_values;
 use super::ranking_rules::{RankingRule, RankingRuleOutput, RankingRuleQueryTrait};
-use crate::heed_codec::facet::{FieldDocIdFacetCodec, OrderedF64Codec};
+use crate::constants::{RESERVED_GEOJSON_FIELD_NAME, RESERVED_GEO_FIELD_NAME};
+use crate::geometry::DocumentGeometry;
 use crate::score_details::{self, ScoreDetails};
+use crate::update::new::{extract_geo_coordinates_from_value, extract_geojson_geometry_from_value};
 use crate::{
-    distance_between_two_points, lat_lng_to_xyz, GeoPoint, Index, Result, SearchContext,
-    SearchLogger,
+    distance_between_two_points, lat_lng_to_xyz, GeoJsonPolygon, GeoPoint, Index, InternalError,
+    Result, SearchContext, SearchLogger,
 };
 
-const FID_SIZE: usize = 2;
-const DOCID_SIZE: usize = 4;
-
-#[allow(clippy::drop_non_drop)]
-fn facet_values_prefix_key(distinct: u16, id: u32) -> [u8; FID_SIZE + DOCID_SIZE] {
-    concat_arrays::concat_arrays!(distinct.to_be_bytes(), id.to_be_bytes())
-}
-
-/// Return an iterator over each number value in the given field of the given document.
-fn facet_number_values<'a>(
-    docid: u32,
-    field_id: u16,
-    index: &Index,
-    txn: &'a RoTxn<'a>,
-) -> Result<RoPrefix<'a, FieldDocIdFacetCodec<OrderedF64Codec>, Unit>> {
-    let key = facet_values_prefix_key(field_id, docid);
-
-    let iter = index
-        .field_id_docid_facet_f64s
-        .remap_key_type::<Bytes>()
-        .prefix_iter(txn, &key)?
-        .remap_key_type();
-
-    Ok(iter)
-}
-
 #[derive(Debug, Clone, Copy)]
 pub struct Parameter {
     // Define the strategy used by the geo sort
@@ -86,14 +62,22 @@ impl Strategy {
     }
 }
 
+/// The fields ids used to retrieve the points of a document in the iterative strategy.
+#[derive(Debug, Clone, Copy)]
+struct GeoFieldIds {
+    geo: Option<u16>,
+    geojson: Option<u16>,
+}
+
 pub struct GeoSort<Q: RankingRuleQueryTrait> {
     query: Option<Q>,
 
     strategy: Strategy,
     ascending: bool,
     point: [f64; 2],
-    field_ids: Option<[u16; 2]>,
+    field_ids: Option<GeoFieldIds>,
     rtree: Option<RTree<GeoPoint>>,
+    geojson_rtree: Option<RTree<GeoJsonPolygon>>,
 
     cached_sorted_docids: VecDeque<(u32, [f64; 2])>,
     geo_candidates: RoaringBitmap,
@@ -120,6 +104,7 @@ impl<Q: RankingRuleQueryTrait> GeoSort<Q> {
             geo_candidates: geo_faceted_docids,
             field_ids: None,
             rtree: None,
+            geojson_rtree: None,
             cached_sorted_docids: VecDeque::new(),
             max_bucket_size,
             distance_error_margin,
@@ -154,36 +139,108 @@ impl<Q: RankingRuleQueryTrait> GeoSort<Q> {
         let cache_size = self.strategy.cache_size();
         if let Some(rtree) = rtree {
             if self.ascending {
+                // The closest point of a polygon isn't one of its vertices, it is on one of its edges
+                // or the queried point itself when it is inside. The documents with polygons are
+                // ranked by their closest point and the rtree points are only used for the others.
+                let geojson_rtree = match self.geojson_rtree.as_ref() {
+                    Some(geojson_rtree) => Some(geojson_rtree),
+                    None => ctx
+                        .index
+                        .geojson_rtree(ctx.txn)?
+                        .map(|rtree| &*self.geojson_rtree.insert(rtree)),
+                };
+                let polygons_docids: RoaringBitmap = geojson_rtree
+                    .into_iter()
+                    .flat_map(|geojson_rtree| geojson_rtree.iter())
+                    .map(|polygon| polygon.docid)
+                    .filter(|docid| geo_candidates.contains(*docid))
+                    .collect();
+                let field_ids = self.field_ids.unwrap();
+                let mut polygons_documents = Vec::new();
+                for docid in &polygons_docids {
+                    let geometry = geo_geometry(docid, field_ids, ctx.index, ctx.txn)?;
+                    if let Some(point) = geometry.closest_point(&self.point) {
+                        let distance = distance_between_two_points(&self.point, &point);
+                        polygons_documents.push((distance, docid, point));
+                    }
+                }
+                polygons_documents.sort_by(|(a, _, _), (b, _, _)| a.total_cmp(b));
+                let mut polygons_documents = polygons_documents.into_iter().peekable();
+
                 let point = lat_lng_to_xyz(&self.point);
                 for point in rtree.nearest_neighbor_iter(&point) {
-                    if geo_candidates.contains(point.data.0) {
-                        self.cached_sorted_docids.push_back(point.data);
-                        if self.cached_sorted_docids.len() >= cache_size {
-                            break;
+                    let (docid, coords) = point.data;
+                    if !geo_candidates.contains(docid) || polygons_docids.contains(docid) {
+                        continue;
+                    }
+                    if polygons_documents.peek().is_some() {
+                        let distance = distance_between_two_points(&self.point, &coords);
+                        while let Some((_, docid, point)) =
+                            polygons_documents.next_if(|(d, _, _)| *d <= distance)
+                        {
+                            self.cached_sorted_docids.push_back((docid, point));
                         }
                     }
+                    self.cached_sorted_docids.push_back(point.data);
+                    if self.cached_sorted_docids.len() >= cache_size {
+                        break;
+                    }
+                }
+                // the rtree is exhausted, the remaining documents all have polygons
+                if self.cached_sorted_docids.len() < cache_size {
+                    self.cached_sorted_docids
+                        .extend(polygons_documents.map(|(_, docid, point)| (docid, point)));
                 }
             } else {
                 // in the case of the desc geo sort we look for the closest point to the opposite of the queried point
-                // and we insert the points in reverse order they get reversed when emptying the cache later on
+                // and we insert the points in reverse order they get reversed when emptying the cache later on.
+                // A document is first met through its furthest point but it is ranked by its closest one, it can
+                // only be inserted once the remaining points of the rtree are all closer to the queried point.
+                let field_ids = self.field_ids.unwrap();
+                let mut seen = RoaringBitmap::new();
+                let mut documents = BinaryHeap::new();
+
                 let point = lat_lng_to_xyz(&opposite_of(self.point));
                 for point in rtree.nearest_neighbor_iter(&point) {
-                    if geo_candidates.contains(point.data.0) {
-                        self.cached_sorted_docids.push_front(point.data);
-                        if self.cached_sorted_docids.len() >= cache_size {
-                            break;
-                        }
+                    let (docid, coords) = point.data;
+                    if !geo_candidates.contains(docid) || !seen.insert(docid) {
+                        continue;
+                    }
+                    let distance = distance_between_two_points(&self.point, &coords);
+                    while documents.peek().is_some_and(|(d, _, _)| d.0 >= distance) {
+                        let (_, docid, point) = documents.pop().unwrap();
+                        self.cached_sorted_docids.push_front((docid, point.map(|c| c.0)));
+                    }
+                    if self.cached_sorted_docids.len() >= cache_size {
+                        break;
+                    }
+
+                    let geometry = geo_geometry(docid, field_ids, ctx.index, ctx.txn)?;
+                    if let Some(point) = geometry.closest_point(&self.point) {
+                        let distance = distance_between_two_points(&self.point, &point);
+                        documents.push((OrderedFloat(distance), docid, point.map(OrderedFloat)));
+                    }
+                }
+                // the rtree is exhausted, the remaining documents can be inserted
+                if self.cached_sorted_docids.len() < cache_size {
+                    while let Some((_, docid, point)) = documents.pop() {
+                        self.cached_sorted_docids.push_front((docid, point.map(|c| c.0)));
                     }
                 }
             }
         } else {
             // the iterative version
-            let [lat, lng] = self.field_ids.unwrap();
-
-            let mut documents = geo_candidates
-                .iter()
-                .map(|id| -> Result<_> { Ok((id, geo_value(id, lat, lng, ctx.index, ctx.txn)?)) })
-                .collect::<Result<Vec<(u32, [f64; 2])>>>()?;
+            let field_ids = self.field_ids.unwrap();
+
+            // A document with several points is ranked by its closest point in both orders,
+            // just like with the rtree and the `_geoDistance` of the hits.
+            let mut documents = Vec::new();
+            for id in geo_candidates {
+                let geometry = geo_geometry(id, field_ids, ctx.index, ctx.txn)?;
+                if let Some(point) = geometry.closest_point(&self.point) {
+                    documents.push((id, point));
+                }
+            }
             // computing the distance between two points is expensive thus we cache the result
             documents
                 .sort_by_cached_key(|(_, p)| distance_between_two_points(&self.point, p) as usize);
@@ -194,35 +251,33 @@ impl<Q: RankingRuleQueryTrait> GeoSort<Q> {
     }
 }
 
-/// Extracts the lat and long values from a single document.
-///
-/// If it is not able to find it in the facet number index it will extract it
-/// from the facet string index and parse it as f64 (as the geo extraction behaves).
-fn geo_value(
+/// Extracts the geometry of a single document, its `_geo` points and its `_geojson` geometry.
+fn geo_geometry(
     docid: u32,
-    field_lat: u16,
-    field_lng: u16,
+    field_ids: GeoFieldIds,
     index: &Index,
     rtxn: &RoTxn<'_>,
-) -> Result<[f64; 2]> {
-    let extract_geo = |geo_field: u16| -> Result<f64> {
-        match facet_number_values(docid, geo_field, index, rtxn)?.next() {
-            Some(Ok(((_, _, geo), ()))) => Ok(geo),
-            Some(Err(e)) => Err(e.into()),
-            None => match facet_string_values(docid, geo_field, index, rtxn)?.next() {
-                Some(Ok((_, geo))) => {
-                    Ok(geo.parse::<f64>().expect("cannot parse geo field as f64"))
-                }
-                Some(Err(e)) => Err(e.into()),
-                None => panic!("A geo faceted document doesn't contain any lat or lng"),
-            },
-        }
-    };
+) -> Result<DocumentGeometry> {
+    let document = index.document(rtxn, docid)?;
+    let document_id = || Value::from(docid);
+    let mut geometry = DocumentGeometry::default();
+
+    if let Some(geo) = field_ids.geo.and_then(|fid| document.get(fid)) {
+        let geo = serde_json::from_slice(geo).map_err(InternalError::SerdeJson)?;
+        geometry.points.extend(extract_geo_coordinates_from_value(document_id, geo)?);
+    }
 
-    let lat = extract_geo(field_lat)?;
-    let lng = extract_geo(field_lng)?;
+    if let Some(geojson) = field_ids.geojson.and_then(|fid| document.get(fid)) {
+        let geojson = serde_json::from_slice(geojson).map_err(InternalError::SerdeJson)?;
+        if let Some(DocumentGeometry { points, polygons }) =
+            extract_geojson_geometry_from_value(document_id, geojson)?
+        {
+            geometry.points.extend(points);
+            geometry.polygons.extend(polygons);
+        }
+    }
 
-    Ok([lat, lng])
+    Ok(geometry)
 }
 
 impl<'ctx, Q: RankingRuleQueryTrait> RankingRule<'ctx, Q> for GeoSort<Q> {
@@ -249,9 +304,9 @@ impl<'ctx, Q: RankingRuleQueryTrait> RankingRule<'ctx, Q> for GeoSort<Q> {
         }
 
         let fid_map = ctx.index.fields_ids_map(ctx.txn)?;
-        let lat = fid_map.id("_geo.lat").expect("geo candidates but no fid for lat");
-        let lng = fid_map.id("_geo.lng").expect("geo candidates but no fid for lng");
-        self.field_ids = Some([lat, lng]);
+        let geo = fid_map.id(RESERVED_GEO_FIELD_NAME);
+        let geojson = fid_map.id(RESERVED_GEOJSON_FIELD_NAME);
+        self.field_ids = Some(GeoFieldIds { geo, geojson });
         self.fill_buffer(ctx, &geo_candidates)?;
         Ok(())
     }
@@ -1,12 +1,17 @@
 /// Maximum number of tokens we consider in a single search.
 pub const MAX_TOKEN_COUNT: usize = 1_000;
 
+/// Maximum number of hits of a search for which a corrected query is suggested.
+pub const MAX_HITS_FOR_SUGGESTED_QUERY: u64 = 10;
+
 /// Maximum number of prefixes that can be derived from a single word.
 pub const MAX_PREFIX_COUNT: usize = 1_000;
 /// Maximum number of words that can be derived from a single word with a distance of one to that word.
 pub const MAX_ONE_TYPO_COUNT: usize = 150;
 /// Maximum number of words that can be derived from a single word with a distance of two to that word.
 pub const MAX_TWO_TYPOS_COUNT: usize = 50;
+/// Maximum number of words sharing the stem of a single word that can be derived from that word.
+pub const MAX_STEM_COUNT: usize = 50;
 
 /// Maximum amount of synonym phrases that can be derived from a single word.
 pub const MAX_SYNONYM_PHRASE_COUNT: usize = 50;
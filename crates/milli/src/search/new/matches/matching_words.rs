@@ -32,7 +32,7 @@ pub struct MatchingWords {
 }
 
 impl MatchingWords {
-    pub fn new(ctx: SearchContext<'_>, located_terms: Vec<LocatedQueryTerm>) -> Self {
+    pub fn new(mut ctx: SearchContext<'_>, located_terms: Vec<LocatedQueryTerm>) -> Self {
         let mut phrases = Vec::new();
         let mut words = Vec::new();
 
@@ -47,6 +47,17 @@ impl MatchingWords {
                     value: matching_phrase,
                     positions: located_term.positions.clone(),
                 });
+
+                // the words of an unordered phrase are also matched in the reverse order
+                let phrase = ctx.phrase_interner.get(matching_phrase);
+                if phrase.unordered {
+                    let mut reversed = phrase.clone();
+                    reversed.words.reverse();
+                    phrases.push(LocatedMatchingPhrase {
+                        value: ctx.phrase_interner.insert(reversed),
+                        positions: located_term.positions.clone(),
+                    });
+                }
             }
 
             words.push(LocatedMatchingWords {
@@ -133,7 +144,12 @@ impl<'a> Iterator for MatchesIter<'a, '_> {
                         word.map(|word| self.matching_words.word_interner.get(word).as_str())
                     })
                     .collect();
-                let partial = PartialMatch { matching_words: words, ids };
+                let partial = PartialMatch {
+                    matching_words: words,
+                    ids,
+                    slop: phrase.slop,
+                    remaining_slop: 0,
+                };
 
                 partial.match_token(self.token).or_else(|| self.next())
             }
@@ -149,6 +165,7 @@ pub type WordId = u16;
 /// A given token can partially match a query word for several reasons:
 /// - split words
 /// - multi-word synonyms
+/// - phrases, possibly allowing other words between their words
 ///   In these cases we need to match consecutively several tokens to consider that the match is full.
 #[derive(Debug, PartialEq)]
 pub enum MatchType<'a> {
@@ -161,15 +178,25 @@ pub enum MatchType<'a> {
 pub struct PartialMatch<'a> {
     matching_words: Vec<Option<&'a str>>,
     ids: &'a RangeInclusive<WordId>,
+    /// The number of other words allowed between two consecutive words of the phrase.
+    slop: u8,
+    /// The number of other words that can still be skipped before matching the next word.
+    remaining_slop: u8,
 }
 
 impl<'a> PartialMatch<'a> {
+    /// Returns the number of words that remain to be matched to complete the match.
+    pub fn remaining_words(&self) -> usize {
+        self.matching_words.len()
+    }
+
     /// Returns:
     /// - None if the given token breaks the partial match
-    /// - Partial if the given token matches the partial match but doesn't complete it
+    /// - Partial if the given token matches the partial match but doesn't complete it,
+    ///   or if it is skipped thanks to the slop of the phrase
     /// - Full if the given token completes the partial match
     pub fn match_token(self, token: &Token<'_>) -> Option<MatchType<'a>> {
-        let Self { mut matching_words, ids, .. } = self;
+        let Self { mut matching_words, ids, slop, remaining_slop } = self;
 
         let is_matching = match matching_words.first()? {
             Some(word) => &token.lemma() == word,
@@ -182,7 +209,7 @@ impl<'a> PartialMatch<'a> {
         // return a new Partial match allowing the highlighter to continue.
         if is_matching && matching_words.len() > 1 {
             matching_words.remove(0);
-            Some(MatchType::Partial(Self { matching_words, ids }))
+            Some(MatchType::Partial(Self { matching_words, ids, slop, remaining_slop: slop }))
         // if there is no remaining word to match in the phrase and the current token is matching,
         // return a Full match.
         } else if is_matching {
@@ -191,6 +218,11 @@ impl<'a> PartialMatch<'a> {
                 byte_len: token.byte_end - token.byte_start,
                 ids,
             })
+        // if the current token doesn't match but the phrase allows other words in between,
+        // skip the token and keep waiting for the next word of the phrase.
+        } else if remaining_slop > 0 {
+            let remaining_slop = remaining_slop - 1;
+            Some(MatchType::Partial(Self { matching_words, ids, slop, remaining_slop }))
         // if the current token doesn't match, return None to break the match sequence.
         } else {
             None
@@ -264,7 +296,7 @@ pub(crate) mod tests {
         let tokenizer = builder.build();
         let tokens = tokenizer.tokenize("split this world");
         let ExtractedTokens { query_terms, .. } =
-            located_query_terms_from_tokens(&mut ctx, tokens, None).unwrap();
+            located_query_terms_from_tokens(&mut ctx, "split this world", tokens, None).unwrap();
         let matching_words = MatchingWords::new(ctx, query_terms);
 
         assert_eq!(
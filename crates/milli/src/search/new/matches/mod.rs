@@ -132,26 +132,48 @@ impl<'t> Matcher<'t, '_, '_, '_> {
             mut partial: PartialMatch<'a>,
             first_token_position: usize,
             first_word_position: usize,
-            first_word_char_start: &usize,
+            first_word: &'a Token<'a>,
             words_positions: &mut impl Iterator<Item = (usize, usize, &'a Token<'a>)>,
             matches: &mut Vec<Match>,
         ) -> bool {
+            // the tokens matching the words of the phrase, a phrase with a slop
+            // can have other words between them that must not be highlighted.
+            let mut matched_words = vec![(first_token_position, first_word_position, first_word)];
             for (token_position, word_position, word) in words_positions {
+                let remaining_words = partial.remaining_words();
                 partial = match partial.match_token(word) {
                     // token matches the partial match, but the match is not full,
                     // we temporarily save the current token then we try to match the next one.
-                    Some(MatchType::Partial(partial)) => partial,
+                    Some(MatchType::Partial(partial)) => {
+                        if partial.remaining_words() < remaining_words {
+                            matched_words.push((token_position, word_position, word));
+                        }
+                        partial
+                    }
                     // partial match is now full, we keep this matches and we advance positions
                     Some(MatchType::Full { ids, .. }) => {
-                        // save the token that closes the partial match as a match.
-                        matches.push(Match {
-                            char_count: word.char_end - *first_word_char_start,
-                            ids: ids.clone().collect(),
-                            position: MatchPosition::Phrase {
-                                word_positions: [first_word_position, word_position],
-                                token_positions: [first_token_position, token_position],
-                            },
-                        });
+                        let ids: Vec<_> = ids.clone().collect();
+                        if word_position - first_word_position == matched_words.len() {
+                            // save the token that closes the partial match as a match.
+                            matches.push(Match {
+                                char_count: word.char_end - first_word.char_start,
+                                ids,
+                                position: MatchPosition::Phrase {
+                                    word_positions: [first_word_position, word_position],
+                                    token_positions: [first_token_position, token_position],
+                                },
+                            });
+                        } else {
+                            // other words were skipped, each matching word is saved as a match.
+                            matched_words.push((token_position, word_position, word));
+                            for (token_position, word_position, word) in matched_words {
+                                matches.push(Match {
+                                    char_count: word.char_end - word.char_start,
+                                    ids: ids.clone(),
+                                    position: MatchPosition::Word { word_position, token_position },
+                                });
+                            }
+                        }
 
                         // the match is complete, we return true.
                         return true;
@@ -206,7 +228,7 @@ impl<'t> Matcher<'t, '_, '_, '_> {
                             partial,
                             token_position,
                             word_position,
-                            &word.char_start,
+                            word,
                             &mut wp,
                             &mut matches,
                         ) {
@@ -512,6 +534,7 @@ mod tests {
                 false,
                 universe,
                 &None,
+                Vec::new(),
                 &None,
                 crate::search::new::GeoSortParameter::default(),
                 0,
@@ -890,6 +913,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn format_highlight_phrase_slop_and_near() {
+        let temp_index = TempIndex::new();
+
+        let text = "The groundbreaking invention had the power to split the world between those who embraced progress and those who resisted change!";
+        temp_index
+            .add_documents(documents!([
+                { "id": 1, "text": text }
+            ]))
+            .unwrap();
+
+        let rtxn = temp_index.read_txn().unwrap();
+
+        let format_options = FormatOptions { highlight: true, crop: None };
+
+        let builder = MatcherBuilder::new_test(&rtxn, &temp_index, "\"power split\"");
+        let mut matcher = builder.build(text, None);
+        // the words of the phrase are not next to each other, nothing is highlighted.
+        insta::assert_snapshot!(
+            matcher.format(format_options),
+            @"The groundbreaking invention had the power to split the world between those who embraced progress and those who resisted change!"
+        );
+
+        let builder = MatcherBuilder::new_test(&rtxn, &temp_index, "\"power split\"~1");
+        let mut matcher = builder.build(text, None);
+        // should highlight the words of the phrase but not the word between them.
+        insta::assert_snapshot!(
+            matcher.format(format_options),
+            @"The groundbreaking invention had the <em>power</em> to <em>split</em> the world between those who embraced progress and those who resisted change!"
+        );
+
+        let builder = MatcherBuilder::new_test(&rtxn, &temp_index, "world NEAR/1 split");
+        let mut matcher = builder.build(text, None);
+        // should highlight the words in the reverse order.
+        insta::assert_snapshot!(
+            matcher.format(format_options),
+            @"The groundbreaking invention had the power to <em>split</em> the <em>world</em> between those who embraced progress and those who resisted change!"
+        );
+    }
+
     #[test]
     fn smaller_crop_size() {
         //! testing: https://github.com/meilisearch/specifications/pull/120#discussion_r836536295
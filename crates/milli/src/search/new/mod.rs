@@ -14,14 +14,18 @@ mod ranking_rules;
 mod resolve_query_graph;
 mod small_bitmap;
 
+mod boost_sort;
+mod decay_sort;
 mod exact_attribute;
+mod expression_sort;
 mod sort;
 mod vector_sort;
 
 #[cfg(test)]
 mod tests;
 
-use std::collections::HashSet;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 use std::ops::AddAssign;
 use std::time::Duration;
 
@@ -36,16 +40,20 @@ pub use logger::visual::VisualSearchLogger;
 pub use logger::{DefaultSearchLogger, SearchLogger};
 use query_graph::{QueryGraph, QueryNode};
 use query_term::{
-    located_query_terms_from_tokens, ExtractedTokens, LocatedQueryTerm, Phrase, QueryTerm,
+    located_query_terms_from_tokens, suggested_query_from_tokens, ExtractedTokens,
+    LocatedQueryTerm, Phrase, QueryTerm,
 };
 use ranking_rules::{
     BoxRankingRule, PlaceholderQuery, RankingRule, RankingRuleOutput, RankingRuleQueryTrait,
 };
 use resolve_query_graph::{compute_query_graph_docids, PhraseDocIdsCache};
 use roaring::RoaringBitmap;
+use rust_stemmers::Stemmer;
 use sort::Sort;
 
-use self::distinct::facet_string_values;
+use self::boost_sort::BoostSort;
+use self::decay_sort::DecaySort;
+use self::expression_sort::ExpressionSort;
 use self::geo_sort::GeoSort;
 pub use self::geo_sort::Parameter as GeoSortParameter;
 pub use self::geo_sort::Strategy as GeoSortStrategy;
@@ -54,7 +62,7 @@ use self::interner::Interned;
 use self::vector_sort::VectorSort;
 use crate::constants::RESERVED_GEO_FIELD_NAME;
 use crate::index::PrefixSearch;
-use crate::localized_attributes_rules::LocalizedFieldIds;
+use crate::localized_attributes_rules::{stemmer, LocalizedFieldIds, StemmedFieldIds};
 use crate::score_details::{ScoreDetails, ScoringStrategy};
 use crate::search::new::distinct::apply_distinct_rule;
 use crate::vector::Embedder;
@@ -75,6 +83,31 @@ pub struct SearchContext<'ctx> {
     pub restricted_fids: Option<RestrictedFids>,
     pub prefix_search: PrefixSearch,
     pub vector_store_stats: Option<VectorStoreStats>,
+    /// The stemmers of the locales of the localized attributes rules for which stemming is enabled.
+    pub stemmers: Vec<(Language, Stemmer)>,
+    /// The searchable fields in which the words sharing the stem of a query word are matched.
+    pub stemmed_fids: Vec<FieldId>,
+    /// The corrections of the words of the queries shared with the other searches on the index.
+    pub spelling_corrections: Option<&'ctx SpellingCorrections>,
+}
+
+/// The corrections of the misspelled words of the queries made on the same index with the
+/// same read transaction, to suggest corrected queries without deriving the words again.
+#[derive(Debug, Default)]
+pub struct SpellingCorrections {
+    corrections: RefCell<HashMap<(String, bool), Option<String>>>,
+}
+
+impl SpellingCorrections {
+    /// Returns the correction of the word, `Some(None)` if the word doesn't need one,
+    /// or `None` if the word was never corrected.
+    pub fn get(&self, word: &str, is_prefix: bool) -> Option<Option<String>> {
+        self.corrections.borrow().get(&(word.to_string(), is_prefix)).cloned()
+    }
+
+    pub fn insert(&self, word: &str, is_prefix: bool, correction: Option<String>) {
+        self.corrections.borrow_mut().insert((word.to_string(), is_prefix), correction);
+    }
 }
 
 impl<'ctx> SearchContext<'ctx> {
@@ -94,6 +127,19 @@ impl<'ctx> SearchContext<'ctx> {
 
         let prefix_search = index.prefix_search(txn)?.unwrap_or_default();
 
+        let rules = index.localized_attributes_rules(txn)?.unwrap_or_default();
+        let stemmed_fields = StemmedFieldIds::new(&rules, &index.fields_ids_map(txn)?);
+        let stemmers = stemmed_fields
+            .all_locales()
+            .into_iter()
+            .filter_map(|locale| stemmer(locale).map(|stemmer| (locale, stemmer)))
+            .collect();
+        // the stems are derivations, they are not matched in the exact attributes
+        let stemmed_fids = stemmed_fields
+            .field_ids()
+            .filter(|fid| tolerant.iter().any(|(tolerant_fid, _)| tolerant_fid == fid))
+            .collect();
+
         Ok(Self {
             index,
             txn,
@@ -105,6 +151,9 @@ impl<'ctx> SearchContext<'ctx> {
             restricted_fids: None,
             prefix_search,
             vector_store_stats: None,
+            stemmers,
+            stemmed_fids,
+            spelling_corrections: None,
         })
     }
 
@@ -193,6 +242,8 @@ impl AddAssign for VectorStoreStats {
 pub enum Word {
     Original(Interned<String>),
     Derived(Interned<String>),
+    /// A word sharing the stem of the original word, only matched in the stemmed fields.
+    Stem(Interned<String>),
 }
 
 impl Word {
@@ -200,6 +251,7 @@ impl Word {
         match self {
             Word::Original(word) => *word,
             Word::Derived(word) => *word,
+            Word::Stem(word) => *word,
         }
     }
 }
@@ -341,6 +393,12 @@ fn get_ranking_rules_for_placeholder_search<'ctx>(
                 sorted_fields.insert(field_name.clone());
                 ranking_rules.push(Box::new(Sort::new(ctx.index, ctx.txn, field_name, false)?));
             }
+            crate::Criterion::Expression(expression) => {
+                ranking_rules.push(Box::new(ExpressionSort::new(ctx.index, ctx.txn, expression)?));
+            }
+            crate::Criterion::Decay(decay) => {
+                ranking_rules.push(Box::new(DecaySort::new(ctx.index, ctx.txn, decay)?));
+            }
         }
     }
     Ok(ranking_rules)
@@ -417,6 +475,12 @@ fn get_ranking_rules_for_vector<'ctx>(
                 sorted_fields.insert(field_name.clone());
                 ranking_rules.push(Box::new(Sort::new(ctx.index, ctx.txn, field_name, false)?));
             }
+            crate::Criterion::Expression(expression) => {
+                ranking_rules.push(Box::new(ExpressionSort::new(ctx.index, ctx.txn, expression)?));
+            }
+            crate::Criterion::Decay(decay) => {
+                ranking_rules.push(Box::new(DecaySort::new(ctx.index, ctx.txn, decay)?));
+            }
         }
     }
 
@@ -527,6 +591,12 @@ fn get_ranking_rules_for_query_graph_search<'ctx>(
                 sorted_fields.insert(field_name.clone());
                 ranking_rules.push(Box::new(Sort::new(ctx.index, ctx.txn, field_name, false)?));
             }
+            crate::Criterion::Expression(expression) => {
+                ranking_rules.push(Box::new(ExpressionSort::new(ctx.index, ctx.txn, expression)?));
+            }
+            crate::Criterion::Decay(decay) => {
+                ranking_rules.push(Box::new(DecaySort::new(ctx.index, ctx.txn, decay)?));
+            }
         }
     }
     Ok(ranking_rules)
@@ -607,6 +677,7 @@ pub fn execute_vector_search(
     scoring_strategy: ScoringStrategy,
     universe: RoaringBitmap,
     sort_criteria: &Option<Vec<AscDesc>>,
+    boosts: Vec<(RoaringBitmap, f64)>,
     distinct: &Option<String>,
     geo_param: geo_sort::Parameter,
     from: usize,
@@ -621,7 +692,7 @@ pub fn execute_vector_search(
 
     // FIXME: input universe = universe & documents_with_vectors
     // for now if we're computing embeddings for ALL documents, we can assume that this is just universe
-    let ranking_rules = get_ranking_rules_for_vector(
+    let mut ranking_rules = get_ranking_rules_for_vector(
         ctx,
         sort_criteria,
         geo_param,
@@ -631,6 +702,9 @@ pub fn execute_vector_search(
         embedder,
         quantized,
     )?;
+    if !boosts.is_empty() {
+        ranking_rules.push(Box::new(BoostSort::new(boosts)));
+    }
 
     let mut placeholder_search_logger = logger::DefaultSearchLogger;
     let placeholder_search_logger: &mut dyn SearchLogger<PlaceholderQuery> =
@@ -657,6 +731,7 @@ pub fn execute_vector_search(
         located_query_terms: None,
         degraded,
         used_negative_operator: false,
+        suggested_query: None,
     })
 }
 
@@ -670,6 +745,7 @@ pub fn execute_search(
     exhaustive_number_hits: bool,
     mut universe: RoaringBitmap,
     sort_criteria: &Option<Vec<AscDesc>>,
+    boosts: Vec<(RoaringBitmap, f64)>,
     distinct: &Option<String>,
     geo_param: geo_sort::Parameter,
     from: usize,
@@ -685,6 +761,7 @@ pub fn execute_search(
 
     let mut used_negative_operator = false;
     let mut located_query_terms = None;
+    let mut query_tokens = None;
     let query_terms = if let Some(query) = query {
         let span = tracing::trace_span!(target: "search::tokens", "tokenizer_builder");
         let entered = span.enter();
@@ -704,7 +781,7 @@ pub fn execute_search(
             tokbuilder.separators(separators);
         }
 
-        let dictionary = ctx.index.dictionary(ctx.txn)?;
+        let dictionary = ctx.index.tokenizer_dictionary(ctx.txn)?;
         let dictionary: Option<Vec<_>> =
             dictionary.as_ref().map(|x| x.iter().map(String::as_str).collect());
         if let Some(ref dictionary) = dictionary {
@@ -755,11 +832,12 @@ pub fn execute_search(
 
         let span = tracing::trace_span!(target: "search::tokens", "tokenize");
         let entered = span.enter();
-        let tokens = tokenizer.tokenize(query);
+        let tokens: Vec<_> = tokenizer.tokenize(query).collect();
         drop(entered);
 
         let ExtractedTokens { query_terms, negative_words, negative_phrases } =
-            located_query_terms_from_tokens(ctx, tokens, words_limit)?;
+            located_query_terms_from_tokens(ctx, query, tokens.iter().cloned(), words_limit)?;
+        query_tokens = Some(tokens);
         used_negative_operator = !negative_words.is_empty() || !negative_phrases.is_empty();
 
         let ignored_documents = resolve_negative_words(ctx, Some(&universe), &negative_words)?;
@@ -782,12 +860,15 @@ pub fn execute_search(
         let (graph, new_located_query_terms) = QueryGraph::from_query(ctx, &query_terms)?;
         located_query_terms = Some(new_located_query_terms);
 
-        let ranking_rules = get_ranking_rules_for_query_graph_search(
+        let mut ranking_rules = get_ranking_rules_for_query_graph_search(
             ctx,
             sort_criteria,
             geo_param,
             terms_matching_strategy,
         )?;
+        if !boosts.is_empty() {
+            ranking_rules.push(Box::new(BoostSort::new(boosts)));
+        }
 
         universe &=
             resolve_universe(ctx, &universe, &graph, terms_matching_strategy, query_graph_logger)?;
@@ -806,8 +887,11 @@ pub fn execute_search(
             ranking_score_threshold,
         )?
     } else {
-        let ranking_rules =
+        let mut ranking_rules =
             get_ranking_rules_for_placeholder_search(ctx, sort_criteria, geo_param)?;
+        if !boosts.is_empty() {
+            ranking_rules.push(Box::new(BoostSort::new(boosts)));
+        }
         bucket_sort(
             ctx,
             ranking_rules,
@@ -826,6 +910,15 @@ pub fn execute_search(
     let BucketSortOutput { docids, scores, mut all_candidates, degraded } = bucket_sort_output;
     let fields_ids_map = ctx.index.fields_ids_map(ctx.txn)?;
 
+    let suggested_query = match (query, query_tokens) {
+        (Some(query), Some(tokens))
+            if all_candidates.len() <= limits::MAX_HITS_FOR_SUGGESTED_QUERY =>
+        {
+            suggested_query_from_tokens(ctx, query, tokens)?
+        }
+        _ => None,
+    };
+
     // The candidates is the universe unless the exhaustive number of hits
     // is requested and a distinct attribute is set.
     if exhaustive_number_hits {
@@ -848,6 +941,7 @@ pub fn execute_search(
         located_query_terms,
         degraded,
         used_negative_operator,
+        suggested_query,
     })
 }
 
@@ -914,4 +1008,5 @@ pub struct PartialSearchResult {
 
     pub degraded: bool,
     pub used_negative_operator: bool,
+    pub suggested_query: Option<String>,
 }
@@ -92,6 +92,12 @@ pub struct QueryGraph {
 impl QueryGraph {
     /// Build the query graph from the parsed user search query, return an updated list of the located query terms
     /// which contains ngrams.
+    ///
+    /// A term id is given to each position where a term starts, and each term spans the ids of the
+    /// positions it covers. The alternatives of an `OR` operator start and end at the same positions,
+    /// e.g. `notebook` spans the ids of `gaming` and `laptop` in `(gaming laptop) OR notebook 16gb`,
+    /// so that they are connected to the same predecessors and successors, like an ngram, and removed
+    /// together by the terms matching strategy.
     pub fn from_query(
         ctx: &mut SearchContext<'_>,
         // The terms here must be consecutive
@@ -105,57 +111,70 @@ impl QueryGraph {
         let root_node = 0;
         let end_node = 1;
 
-        // Ee could consider generalizing to 4,5,6,7,etc. ngrams
-        let (mut prev2, mut prev1, mut prev0): (Vec<u16>, Vec<u16>, Vec<u16>) =
-            (vec![], vec![], vec![root_node]);
-
-        let original_terms_len = terms.len();
-        for term_idx in 0..original_terms_len {
-            let mut new_nodes = vec![];
+        let mut starts: Vec<u16> = terms.iter().map(|term| *term.positions.start()).collect();
+        starts.sort_unstable();
+        starts.dedup();
+        let term_id =
+            |position: u16| (starts.partition_point(|&start| start <= position) - 1) as u8;
+        let terms_ids: Vec<_> = terms
+            .iter()
+            .map(|term| term_id(*term.positions.start())..=term_id(*term.positions.end()))
+            .collect();
+
+        // The term that is alone at each term id, which can be merged into an ngram.
+        // The alternatives are never merged with the neighbouring terms.
+        let mut single_terms: Vec<Option<&LocatedQueryTerm>> = vec![None; starts.len()];
+        let mut terms_count = vec![0usize; starts.len()];
+        for (term, term_ids) in terms.iter().zip(&terms_ids) {
+            for term_idx in term_ids.clone() {
+                terms_count[term_idx as usize] += 1;
+                single_terms[term_idx as usize] = Some(term);
+            }
+        }
+        for (single_term, count) in single_terms.iter_mut().zip(terms_count) {
+            if count != 1 {
+                *single_term = None;
+            }
+        }
+        // The terms of the `len` term ids ending at `term_idx`, which can be merged into an ngram.
+        let ngram_terms = |term_idx: usize, len: usize| -> Option<Vec<LocatedQueryTerm>> {
+            let window = single_terms.get((term_idx + 1).checked_sub(len)?..=term_idx)?;
+            window.iter().map(|term| term.cloned()).collect()
+        };
 
-            let new_node_idx = add_node(
+        // Ee could consider generalizing to 4,5,6,7,etc. ngrams
+        for (term, term_ids) in terms.iter().zip(&terms_ids) {
+            let mut term_subset = QueryTermSubset::full(term.value);
+            if term.mandatory {
+                term_subset.make_mandatory();
+            }
+            add_node(
                 &mut nodes_data,
                 QueryNodeData::Term(LocatedQueryTermSubset {
-                    term_subset: QueryTermSubset::full(terms[term_idx].value),
-                    positions: terms[term_idx].positions.clone(),
-                    term_ids: term_idx as u8..=term_idx as u8,
+                    term_subset,
+                    positions: term.positions.clone(),
+                    term_ids: term_ids.clone(),
                 }),
             );
-            new_nodes.push(new_node_idx);
 
-            if !prev1.is_empty() {
-                if let Some(ngram) =
-                    query_term::make_ngram(ctx, &terms[term_idx - 1..=term_idx], &nbr_typos)?
-                {
-                    new_located_query_terms.push(ngram.clone());
-                    let ngram_idx = add_node(
-                        &mut nodes_data,
-                        QueryNodeData::Term(LocatedQueryTermSubset {
-                            term_subset: QueryTermSubset::full(ngram.value),
-                            positions: ngram.positions,
-                            term_ids: term_idx as u8 - 1..=term_idx as u8,
-                        }),
-                    );
-                    new_nodes.push(ngram_idx);
-                }
+            let term_idx = *term_ids.end() as usize;
+            if single_terms[term_idx].is_none() {
+                continue;
             }
-            if !prev2.is_empty() {
-                if let Some(ngram) =
-                    query_term::make_ngram(ctx, &terms[term_idx - 2..=term_idx], &nbr_typos)?
-                {
+            for len in [2, 3] {
+                let Some(window) = ngram_terms(term_idx, len) else { continue };
+                if let Some(ngram) = query_term::make_ngram(ctx, &window, &nbr_typos)? {
                     new_located_query_terms.push(ngram.clone());
-                    let ngram_idx = add_node(
+                    add_node(
                         &mut nodes_data,
                         QueryNodeData::Term(LocatedQueryTermSubset {
                             term_subset: QueryTermSubset::full(ngram.value),
                             positions: ngram.positions,
-                            term_ids: term_idx as u8 - 2..=term_idx as u8,
+                            term_ids: (term_idx + 1 - len) as u8..=term_idx as u8,
                         }),
                     );
-                    new_nodes.push(ngram_idx);
                 }
             }
-            (prev0, prev1, prev2) = (new_nodes, prev0, prev1);
         }
 
         let root_node = Interned::from_raw(root_node);
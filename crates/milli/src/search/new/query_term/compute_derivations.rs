@@ -9,10 +9,11 @@ use heed::types::DecodeIgnore;
 use itertools::{merge_join_by, EitherOrBoth};
 
 use super::{OneTypoTerm, Phrase, QueryTerm, ZeroTypoTerm};
+use crate::localized_attributes_rules::stem_words_key;
 use crate::search::fst_utils::{Complement, Intersection, StartsWith, Union};
 use crate::search::new::interner::{DedupInterner, Interned};
 use crate::search::new::query_term::{Lazy, TwoTypoTerm};
-use crate::search::new::{limits, SearchContext};
+use crate::search::new::{limits, SearchContext, Word};
 use crate::search::{build_dfa, get_first};
 use crate::{Result, MAX_WORD_LENGTH};
 
@@ -40,6 +41,50 @@ impl Interned<QueryTerm> {
         }
         Ok(())
     }
+
+    /// Returns the most frequent word of the index with the fewest typos to replace the term
+    /// with, or `None` if the term already matches words of the index without typos.
+    pub fn spelling_correction(
+        self,
+        ctx: &mut SearchContext<'_>,
+    ) -> Result<Option<Interned<String>>> {
+        let QueryTerm { zero_typo, ngram_words, .. } = ctx.term_interner.get(self);
+        let ZeroTypoTerm { phrase, exact, prefix_of, use_prefix_db, .. } = zero_typo;
+        if ngram_words.is_some()
+            || phrase.is_some()
+            || exact.is_some()
+            || use_prefix_db.is_some()
+            || !prefix_of.is_empty()
+        {
+            return Ok(None);
+        }
+
+        self.compute_fully_if_needed(ctx)?;
+        let QueryTerm { one_typo, two_typo, .. } = ctx.term_interner.get(self);
+        let one_typo = match one_typo {
+            Lazy::Init(OneTypoTerm { one_typo, .. }) => one_typo.clone(),
+            Lazy::Uninit => BTreeSet::new(),
+        };
+        let two_typos = match two_typo {
+            Lazy::Init(TwoTypoTerm { two_typos }) => two_typos.clone(),
+            Lazy::Uninit => BTreeSet::new(),
+        };
+
+        for words in [one_typo, two_typos] {
+            let mut best = None;
+            for word in words {
+                let count = ctx.word_docids(None, Word::Derived(word))?.map_or(0, |d| d.len());
+                if best.is_none_or(|(_, best_count)| count > best_count) {
+                    best = Some((word, count));
+                }
+            }
+            if let Some((word, _)) = best {
+                return Ok(Some(word));
+            }
+        }
+
+        Ok(None)
+    }
 }
 
 fn find_zero_typo_prefix_derivations(
@@ -183,6 +228,7 @@ pub fn partially_initialized_term_from_word(
                 zero_typo: <_>::default(),
                 one_typo: Lazy::Init(<_>::default()),
                 two_typo: Lazy::Init(<_>::default()),
+                field_ids: None,
             }
         });
     }
@@ -221,9 +267,51 @@ pub fn partially_initialized_term_from_word(
         })?;
     }
     let synonyms = ctx.index.synonyms(ctx.txn)?;
+    let synonyms = synonym_phrases(ctx, synonyms.get(&vec![word.to_owned()]));
+    let stems = if is_ngram { BTreeSet::new() } else { find_stem_derivations(ctx, word)? };
+    let zero_typo =
+        ZeroTypoTerm { phrase: None, exact: zero_typo, prefix_of, synonyms, stems, use_prefix_db };
+
+    Ok(QueryTerm {
+        original: word_interned,
+        ngram_words: None,
+        max_levenshtein_distance: max_typo,
+        is_prefix,
+        zero_typo,
+        one_typo: Lazy::Uninit,
+        two_typo: Lazy::Uninit,
+        field_ids: None,
+    })
+}
+
+/// Returns the indexed words sharing the stem of the word, in the languages of the
+/// localized attributes for which stemming is enabled.
+fn find_stem_derivations(
+    ctx: &mut SearchContext<'_>,
+    word: &str,
+) -> Result<BTreeSet<Interned<String>>> {
+    let mut stems = BTreeSet::new();
+    for (locale, stemmer) in &ctx.stemmers {
+        let key = stem_words_key(*locale, &stemmer.stem(word));
+        let Some(words) = ctx.index.stem_words.get(ctx.txn, &key)? else { continue };
+        for derived_word in words.into_iter().filter(|derived_word| derived_word != word) {
+            if stems.len() >= limits::MAX_STEM_COUNT {
+                return Ok(stems);
+            }
+            stems.insert(ctx.word_interner.insert(derived_word));
+        }
+    }
+
+    Ok(stems)
+}
+
+/// Interns the synonyms as phrases, within the limits of the number of synonyms of a term.
+fn synonym_phrases(
+    ctx: &mut SearchContext<'_>,
+    synonyms: Option<&Vec<Vec<String>>>,
+) -> BTreeSet<Interned<Phrase>> {
     let mut synonym_word_count = 0;
-    let synonyms = synonyms
-        .get(&vec![word.to_owned()])
+    synonyms
         .cloned()
         .unwrap_or_default()
         .into_iter()
@@ -234,26 +322,37 @@ pub fn partially_initialized_term_from_word(
             }
             synonym_word_count += words.len();
             let words = words.into_iter().map(|w| Some(ctx.word_interner.insert(w))).collect();
-            Some(ctx.phrase_interner.insert(Phrase { words }))
+            Some(ctx.phrase_interner.insert(Phrase { words, ..Default::default() }))
         })
-        .collect();
-    let zero_typo =
-        ZeroTypoTerm { phrase: None, exact: zero_typo, prefix_of, synonyms, use_prefix_db };
+        .collect()
+}
 
-    Ok(QueryTerm {
-        original: word_interned,
-        ngram_words: None,
-        max_levenshtein_distance: max_typo,
-        is_prefix,
-        zero_typo,
-        one_typo: Lazy::Uninit,
-        two_typo: Lazy::Uninit,
-    })
+/// Returns the synonyms of the term that must be ranked like words with one typo.
+fn find_typo_synonyms(
+    ctx: &mut SearchContext<'_>,
+    term: Interned<QueryTerm>,
+) -> Result<BTreeSet<Interned<Phrase>>> {
+    let QueryTerm { original, ngram_words, zero_typo, .. } = ctx.term_interner.get(term);
+    if zero_typo.phrase.is_some() {
+        return Ok(BTreeSet::new());
+    }
+    let words: Vec<_> = match ngram_words {
+        Some(ngram_words) => {
+            ngram_words.iter().map(|w| ctx.word_interner.get(*w).clone()).collect()
+        }
+        None => vec![ctx.word_interner.get(*original).clone()],
+    };
+
+    let typo_synonyms = ctx.index.typo_synonyms(ctx.txn)?;
+    Ok(synonym_phrases(ctx, typo_synonyms.get(&words)))
 }
 
 fn find_split_words(ctx: &mut SearchContext<'_>, word: &str) -> Result<Option<Interned<Phrase>>> {
     if let Some((l, r)) = split_best_frequency(ctx, word)? {
-        Ok(Some(ctx.phrase_interner.insert(Phrase { words: vec![Some(l), Some(r)] })))
+        Ok(Some(
+            ctx.phrase_interner
+                .insert(Phrase { words: vec![Some(l), Some(r)], ..Default::default() }),
+        ))
     } else {
         Ok(None)
     }
@@ -306,7 +405,7 @@ impl Interned<QueryTerm> {
         let split_words = if let Some((ngram_words, split_words)) =
             self_mut.ngram_words.as_ref().zip(split_words.as_ref())
         {
-            let Phrase { words } = ctx.phrase_interner.get(*split_words);
+            let Phrase { words, .. } = ctx.phrase_interner.get(*split_words);
             if ngram_words.iter().ne(words.iter().flatten()) {
                 Some(*split_words)
             } else {
@@ -315,7 +414,9 @@ impl Interned<QueryTerm> {
         } else {
             split_words
         };
-        let one_typo = OneTypoTerm { split_words, one_typo: one_typo_words };
+        let synonyms = find_typo_synonyms(ctx, self)?;
+        let self_mut = ctx.term_interner.get_mut(self);
+        let one_typo = OneTypoTerm { split_words, one_typo: one_typo_words, synonyms };
 
         self_mut.one_typo = Lazy::Init(one_typo);
 
@@ -368,9 +469,10 @@ impl Interned<QueryTerm> {
         }
 
         let split_words = find_split_words(ctx, original_str.as_str())?;
+        let synonyms = find_typo_synonyms(ctx, self)?;
         let self_mut = ctx.term_interner.get_mut(self);
 
-        let one_typo = OneTypoTerm { one_typo: one_typo_words, split_words };
+        let one_typo = OneTypoTerm { one_typo: one_typo_words, split_words, synonyms };
 
         let two_typo = TwoTypoTerm { two_typos: two_typo_words };
 
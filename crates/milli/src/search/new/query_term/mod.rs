@@ -10,13 +10,14 @@ use std::ops::RangeInclusive;
 use either::Either;
 pub use ntypo_subset::NTypoTermSubset;
 pub use parse_query::{
-    located_query_terms_from_tokens, make_ngram, number_of_typos_allowed, ExtractedTokens,
+    located_query_terms_from_tokens, make_ngram, number_of_typos_allowed,
+    suggested_query_from_tokens, ExtractedTokens,
 };
 pub use phrase::Phrase;
 
 use super::interner::{DedupInterner, Interned};
 use super::{limits, SearchContext, Word};
-use crate::Result;
+use crate::{FieldId, Result};
 
 /// A set of word derivations attached to a location in the search query.
 #[derive(Clone, PartialEq, Eq, Hash)]
@@ -51,6 +52,8 @@ pub struct QueryTerm {
     one_typo: Lazy<OneTypoTerm>,
     // May not be computed yet
     two_typo: Lazy<TwoTypoTerm>,
+    /// The fields the term must be found in, e.g. the `title` field for `title:harry`
+    field_ids: Option<Vec<FieldId>>,
 }
 
 // SubTerms will be in a dedup interner
@@ -64,6 +67,8 @@ struct ZeroTypoTerm {
     prefix_of: BTreeSet<Interned<String>>,
     /// All the synonyms of the original word or phrase
     synonyms: BTreeSet<Interned<Phrase>>,
+    /// All the words sharing the stem of the original word, e.g. `runs` for `running`
+    stems: BTreeSet<Interned<String>>,
     /// A prefix in the prefix databases matching the original word
     use_prefix_db: Option<Interned<String>>,
 }
@@ -73,6 +78,8 @@ struct OneTypoTerm {
     split_words: Option<Interned<Phrase>>,
     /// Words that are 1 typo away from the original word
     one_typo: BTreeSet<Interned<String>>,
+    /// The synonyms of the original word or phrase ranked like words with 1 typo
+    synonyms: BTreeSet<Interned<Phrase>>,
 }
 #[derive(Default, Clone, PartialEq, Eq, Hash)]
 struct TwoTypoTerm {
@@ -128,6 +135,18 @@ impl QueryTermSubset {
     pub fn make_mandatory(&mut self) {
         self.mandatory = true;
     }
+    /// Returns the fields the term must be found in, if it was scoped with `field:word`.
+    pub fn field_ids(&self, ctx: &SearchContext<'_>) -> Option<Vec<FieldId>> {
+        ctx.term_interner.get(self.original).field_ids.clone()
+    }
+    /// Returns the number of words of the query merged into the term, e.g. 2 for the ngram
+    /// `sunflower` made of `sun flower`.
+    ///
+    /// An alternative of an `OR` operator spans the term ids of all the words it replaces, e.g.
+    /// `notebook` in `(gaming laptop) OR notebook`, but it is a single word.
+    pub fn ngram_len(&self, ctx: &SearchContext<'_>) -> usize {
+        ctx.term_interner.get(self.original).ngram_words.as_ref().map_or(1, Vec::len)
+    }
     pub fn exact_term(&self, ctx: &SearchContext<'_>) -> Option<ExactTerm> {
         let full_query_term = ctx.term_interner.get(self.original);
         if full_query_term.ngram_words.is_some() {
@@ -213,6 +232,7 @@ impl QueryTermSubset {
                     exact: zero_typo,
                     prefix_of,
                     synonyms: _,
+                    stems,
                     use_prefix_db: _,
                 } = &original.zero_typo;
                 result.extend(zero_typo.iter().copied().map(|w| {
@@ -229,6 +249,7 @@ impl QueryTermSubset {
                         Word::Original(w)
                     }
                 }));
+                result.extend(stems.iter().copied().map(Word::Stem));
             }
             NTypoTermSubset::Subset { words, phrases: _ } => {
                 let ZeroTypoTerm {
@@ -236,6 +257,7 @@ impl QueryTermSubset {
                     exact: zero_typo,
                     prefix_of,
                     synonyms: _,
+                    stems,
                     use_prefix_db: _,
                 } = &original.zero_typo;
                 if let Some(zero_typo) = zero_typo {
@@ -254,20 +276,23 @@ impl QueryTermSubset {
                         Word::Original(w)
                     }
                 }));
+                result.extend(stems.intersection(words).copied().map(Word::Stem));
             }
             NTypoTermSubset::Nothing => {}
         }
 
         match &self.one_typo_subset {
             NTypoTermSubset::All => {
-                let Lazy::Init(OneTypoTerm { split_words: _, one_typo }) = &original.one_typo
+                let Lazy::Init(OneTypoTerm { split_words: _, one_typo, synonyms: _ }) =
+                    &original.one_typo
                 else {
                     panic!()
                 };
                 result.extend(one_typo.iter().copied().map(Word::Derived))
             }
             NTypoTermSubset::Subset { words, phrases: _ } => {
-                let Lazy::Init(OneTypoTerm { split_words: _, one_typo }) = &original.one_typo
+                let Lazy::Init(OneTypoTerm { split_words: _, one_typo, synonyms: _ }) =
+                    &original.one_typo
                 else {
                     panic!()
                 };
@@ -298,21 +323,24 @@ impl QueryTermSubset {
         }
         let original = ctx.term_interner.get_mut(self.original);
 
-        let ZeroTypoTerm { phrase, exact: _, prefix_of: _, synonyms, use_prefix_db: _ } =
+        let ZeroTypoTerm { phrase, exact: _, prefix_of: _, synonyms, stems: _, use_prefix_db: _ } =
             &original.zero_typo;
         result.extend(phrase.iter().copied());
         result.extend(synonyms.iter().copied());
 
         match &self.one_typo_subset {
             NTypoTermSubset::All => {
-                let Lazy::Init(OneTypoTerm { split_words, one_typo: _ }) = &original.one_typo
+                let Lazy::Init(OneTypoTerm { split_words, one_typo: _, synonyms }) =
+                    &original.one_typo
                 else {
                     panic!();
                 };
                 result.extend(split_words.iter().copied());
+                result.extend(synonyms.iter().copied());
             }
             NTypoTermSubset::Subset { phrases, .. } => {
-                let Lazy::Init(OneTypoTerm { split_words, one_typo: _ }) = &original.one_typo
+                let Lazy::Init(OneTypoTerm { split_words, one_typo: _, synonyms }) =
+                    &original.one_typo
                 else {
                     panic!();
                 };
@@ -321,6 +349,7 @@ impl QueryTermSubset {
                         result.insert(*split_words);
                     }
                 }
+                result.extend(synonyms.intersection(phrases).copied());
             }
             NTypoTermSubset::Nothing => {}
         }
@@ -407,18 +436,20 @@ impl QueryTermSubset {
 
 impl ZeroTypoTerm {
     fn is_empty(&self) -> bool {
-        let ZeroTypoTerm { phrase, exact: zero_typo, prefix_of, synonyms, use_prefix_db } = self;
+        let ZeroTypoTerm { phrase, exact: zero_typo, prefix_of, synonyms, stems, use_prefix_db } =
+            self;
         phrase.is_none()
             && zero_typo.is_none()
             && prefix_of.is_empty()
             && synonyms.is_empty()
+            && stems.is_empty()
             && use_prefix_db.is_none()
     }
 }
 impl OneTypoTerm {
     fn is_empty(&self) -> bool {
-        let OneTypoTerm { split_words, one_typo } = self;
-        one_typo.is_empty() && split_words.is_none()
+        let OneTypoTerm { split_words, one_typo, synonyms } = self;
+        one_typo.is_empty() && split_words.is_none() && synonyms.is_empty()
     }
 }
 impl TwoTypoTerm {
@@ -460,7 +491,12 @@ impl Interned<QueryTerm> {
 #[derive(Clone)]
 pub struct LocatedQueryTerm {
     pub value: Interned<QueryTerm>,
+    /// The operands of an `OR` operator start and end at the same positions,
+    /// e.g. `notebook` is located at the positions of `gaming laptop` in `(gaming laptop) OR notebook`.
     pub positions: RangeInclusive<u16>,
+    /// Whether the documents must contain the term, e.g. for `+word`,
+    /// regardless of the terms matching strategy.
+    pub mandatory: bool,
 }
 
 impl LocatedQueryTerm {
@@ -489,16 +525,18 @@ impl QueryTerm {
         let mut words = BTreeSet::new();
         let mut phrases = BTreeSet::new();
 
-        let ZeroTypoTerm { phrase, exact: zero_typo, prefix_of, synonyms, use_prefix_db: _ } =
+        let ZeroTypoTerm { phrase, exact: zero_typo, prefix_of, synonyms, stems, use_prefix_db: _ } =
             &self.zero_typo;
         words.extend(zero_typo.iter().copied());
         words.extend(prefix_of.iter().copied());
+        words.extend(stems.iter().copied());
         phrases.extend(phrase.iter().copied());
         phrases.extend(synonyms.iter().copied());
 
-        if let Lazy::Init(OneTypoTerm { split_words, one_typo }) = &self.one_typo {
+        if let Lazy::Init(OneTypoTerm { split_words, one_typo, synonyms }) = &self.one_typo {
             words.extend(one_typo.iter().copied());
             phrases.extend(split_words.iter().copied());
+            phrases.extend(synonyms.iter().copied());
         };
 
         if let Lazy::Init(TwoTypoTerm { two_typos }) = &self.two_typo {
@@ -1,13 +1,15 @@
 use std::collections::BTreeSet;
+use std::iter::{once, Peekable};
+use std::ops::Range;
 
-use charabia::normalizer::NormalizedTokenIter;
-use charabia::{SeparatorKind, TokenKind};
+use charabia::{SeparatorKind, Token, TokenKind};
 
 use super::compute_derivations::partially_initialized_term_from_word;
 use super::{LocatedQueryTerm, ZeroTypoTerm};
+use crate::error::UserError;
 use crate::search::new::query_term::{Lazy, Phrase, QueryTerm};
 use crate::search::new::Word;
-use crate::{Result, SearchContext, MAX_WORD_LENGTH};
+use crate::{is_faceted_by, FieldId, Result, SearchContext, MAX_WORD_LENGTH};
 
 #[derive(Clone)]
 /// Extraction of the content of a query.
@@ -22,13 +24,15 @@ pub struct ExtractedTokens {
 
 /// Convert the tokenised search query into a list of located query terms.
 #[tracing::instrument(level = "trace", skip_all, target = "search::query")]
-pub fn located_query_terms_from_tokens(
+pub fn located_query_terms_from_tokens<'o>(
     ctx: &mut SearchContext<'_>,
-    query: NormalizedTokenIter<'_, '_, '_, '_>,
+    query: &str,
+    tokens: impl Iterator<Item = Token<'o>>,
     words_limit: Option<usize>,
 ) -> Result<ExtractedTokens> {
     let nbr_typos = number_of_typos_allowed(ctx)?;
     let allow_prefix_search = ctx.is_prefix_search_allowed();
+    let field_scopes = query_field_scopes(ctx, query)?;
 
     let mut query_terms = Vec::new();
 
@@ -36,6 +40,11 @@ pub fn located_query_terms_from_tokens(
     let mut phrase: Option<PhraseBuilder> = None;
     let mut encountered_whitespace = true;
     let mut negative_next_token = false;
+    let mut required_next_token = false;
+    let mut near_slop = None;
+    let mut alternative_next_token = false;
+    let mut alternatives = Alternatives::default();
+    let mut field_scope: Option<Vec<FieldId>> = None;
     let mut negative_words = Vec::new();
     let mut negative_phrases = Vec::new();
 
@@ -44,7 +53,7 @@ pub fn located_query_terms_from_tokens(
     // start with the last position as we will wrap around to position 0 at the beginning of the loop below.
     let mut position = u16::MAX;
 
-    let mut peekable = query.take(super::limits::MAX_TOKEN_COUNT).peekable();
+    let mut peekable = tokens.take(super::limits::MAX_TOKEN_COUNT).peekable();
     while let Some(token) = peekable.next() {
         if token.lemma().is_empty() {
             continue;
@@ -52,17 +61,72 @@ pub fn located_query_terms_from_tokens(
 
         // early return if word limit is exceeded
         if query_terms.len() >= parts_limit {
+            alternatives.end(&mut query_terms, &mut position);
             return Ok(ExtractedTokens { query_terms, negative_words, negative_phrases });
         }
 
+        // the tokens of a `field:` prefix are not part of the query,
+        // they scope the next word or phrase to the field.
+        if phrase.is_none() {
+            if let Some(scope) = field_scopes.iter().find(|s| s.prefix.contains(&token.byte_start))
+            {
+                field_scope = Some(scope.field_ids.clone());
+                // the last token of the prefix can also open a phrase, e.g. `title:"harry potter"`
+                if token.byte_end <= scope.prefix.end {
+                    encountered_whitespace = false;
+                    continue;
+                }
+            }
+        }
+
         match token.kind {
             TokenKind::Word | TokenKind::StopWord => {
+                // `OR` makes the next word, phrase or group an alternative of the previous one, not a word.
+                if phrase.is_none()
+                    && alternatives.last_operand.is_some()
+                    && !negative_next_token
+                    && !required_next_token
+                    && !alternative_next_token
+                    && near_slop.is_none()
+                    && field_scope.is_none()
+                    && &query[token.byte_start..token.byte_end] == "OR"
+                    && query_terms.last().is_some_and(|term| *term.positions.end() == position)
+                {
+                    alternative_next_token = true;
+                    encountered_whitespace = false;
+                    continue;
+                }
+
+                // `NEAR/n` is an operator between the previous word and the next one, not a word.
+                if phrase.is_none()
+                    && !negative_next_token
+                    && !required_next_token
+                    && near_slop.is_none()
+                    && field_scope.is_none()
+                    && !alternative_next_token
+                    && &query[token.byte_start..token.byte_end] == "NEAR"
+                    && is_near_operand(ctx, &query_terms, position)
+                {
+                    if let Some(slop) = near_operator_slop(&mut peekable)? {
+                        near_slop = Some(slop);
+                        encountered_whitespace = false;
+                        continue;
+                    }
+                }
+
+                // The right operand of an `OR` operator starts at the position of the left one.
+                if alternative_next_token {
+                    alternatives.start_right_operand(&query_terms, &mut position);
+                }
+
                 // On first loop, goes from u16::MAX to 0, then normal increment.
                 position = position.wrapping_add(1);
 
                 // 1. if the word is quoted we push it in a phrase-buffer waiting for the ending quote,
-                // 2. if the word is not the last token of the query and is not a stop_word we push it as a non-prefix word,
-                // 3. if the word is the last token of the query we push it as a prefix word.
+                // 2. if the word follows a `NEAR/n` operator we replace the previous word by an unordered phrase,
+                // 3. if the word is not the last token of the query and is not a stop_word we push it as a non-prefix word,
+                // 4. if the word is the last token of the query we push it as a prefix word,
+                // 5. if the word follows an `OR` operator it ends the right operand of the operator.
                 if let Some(phrase) = &mut phrase {
                     phrase.push_word(ctx, &token, position)
                 } else if negative_next_token {
@@ -70,40 +134,65 @@ pub fn located_query_terms_from_tokens(
                     let word = Word::Original(ctx.word_interner.insert(word));
                     negative_words.push(word);
                     negative_next_token = false;
+                } else if let Some(slop) =
+                    near_slop.take().filter(|_| token.kind == TokenKind::Word)
+                {
+                    // the previous word was checked to be a single word by `is_near_operand`
+                    let left = query_terms.pop().unwrap();
+                    let left_word = left.value.original_single_word(ctx).unwrap();
+                    let mut near = PhraseBuilder {
+                        words: vec![Some(left_word)],
+                        start: *left.positions.start(),
+                        end: *left.positions.end(),
+                        slop,
+                        unordered: true,
+                        field_ids: None,
+                    };
+                    near.push_word(ctx, &token, position);
+                    if let Some(near) = near.build(ctx) {
+                        alternatives.push(&mut query_terms, near, &mut position);
+                    }
                 } else if peekable.peek().is_some() {
                     match token.kind {
                         TokenKind::Word => {
                             let word = token.lemma();
-                            let term = partially_initialized_term_from_word(
+                            let mut term = partially_initialized_term_from_word(
                                 ctx,
                                 word,
                                 nbr_typos(word),
                                 false,
                                 false,
                             )?;
+                            term.field_ids = field_scope.take();
                             let located_term = LocatedQueryTerm {
                                 value: ctx.term_interner.push(term),
                                 positions: position..=position,
+                                mandatory: required_next_token,
                             };
-                            query_terms.push(located_term);
+                            alternatives.push(&mut query_terms, located_term, &mut position);
                         }
                         TokenKind::StopWord | TokenKind::Separator(_) | TokenKind::Unknown => (),
                     }
                 } else {
                     let word = token.lemma();
-                    let term = partially_initialized_term_from_word(
+                    let mut term = partially_initialized_term_from_word(
                         ctx,
                         word,
                         nbr_typos(word),
                         allow_prefix_search,
                         false,
                     )?;
+                    term.field_ids = field_scope.take();
                     let located_term = LocatedQueryTerm {
                         value: ctx.term_interner.push(term),
                         positions: position..=position,
+                        mandatory: required_next_token,
                     };
-                    query_terms.push(located_term);
+                    alternatives.push(&mut query_terms, located_term, &mut position);
                 }
+                required_next_token = false;
+                alternative_next_token = false;
+                field_scope = None;
             }
             TokenKind::Separator(separator_kind) => {
                 // add penalty for hard separators
@@ -124,7 +213,11 @@ pub fn located_query_terms_from_tokens(
                                 if negative_phrase {
                                     negative_phrases.push(located_query_term);
                                 } else {
-                                    query_terms.push(located_query_term);
+                                    alternatives.push(
+                                        &mut query_terms,
+                                        located_query_term,
+                                        &mut position,
+                                    );
                                 }
                             }
                             Some(PhraseBuilder::empty())
@@ -141,10 +234,16 @@ pub fn located_query_terms_from_tokens(
                         break 'phrase phrase;
                     }
 
+                    // a `NEAR/n` operator can't be followed by a phrase
+                    near_slop = None;
+
                     // Consume the closing quote and the phrase
-                    if let Some(phrase) = phrase {
+                    if let Some(mut phrase) = phrase {
                         // Per the check above, quote_count > 0
                         quote_count -= 1;
+                        if let Some(slop) = phrase_slop(&token, &mut peekable)? {
+                            phrase.slop = slop;
+                        }
                         if let Some(located_query_term) = phrase.build(ctx) {
                             // we were evaluating a negative operator so we
                             // put the phrase in the negative phrases
@@ -152,7 +251,11 @@ pub fn located_query_terms_from_tokens(
                                 negative_phrases.push(located_query_term);
                                 negative_phrase = false;
                             } else {
-                                query_terms.push(located_query_term);
+                                alternatives.push(
+                                    &mut query_terms,
+                                    located_query_term,
+                                    &mut position,
+                                );
                             }
                         }
                     }
@@ -160,14 +263,45 @@ pub fn located_query_terms_from_tokens(
                     // Start new phrase if the token ends with an opening quote
                     if quote_count % 2 == 1 {
                         negative_phrase = negative_next_token;
-                        Some(PhraseBuilder::empty())
+                        if alternative_next_token {
+                            alternatives.start_right_operand(&query_terms, &mut position);
+                        }
+                        Some(PhraseBuilder {
+                            field_ids: field_scope.take(),
+                            ..PhraseBuilder::empty()
+                        })
                     } else {
                         None
                     }
                 };
+                // a `field:` prefix only scopes the word or phrase directly following it
+                field_scope = None;
 
                 negative_next_token =
                     phrase.is_none() && token.lemma() == "-" && encountered_whitespace;
+                required_next_token =
+                    phrase.is_none() && token.lemma() == "+" && encountered_whitespace;
+                // the operands of a `NEAR/n` operator must only be separated by whitespaces
+                near_slop = near_slop.filter(|_| token.lemma().chars().all(char::is_whitespace));
+                // the operands of an `OR` operator can be grouped in parentheses,
+                // e.g. `(gaming laptop) OR notebook`
+                if phrase.is_none() {
+                    for c in token.lemma().chars() {
+                        match c {
+                            '(' if alternative_next_token => {
+                                alternatives.start_right_operand(&query_terms, &mut position);
+                                alternatives.open_group(&query_terms);
+                                alternative_next_token = false;
+                            }
+                            '(' => alternatives.open_group(&query_terms),
+                            ')' => alternatives.close_group(&mut query_terms, &mut position),
+                            _ => (),
+                        }
+                    }
+                }
+                alternative_next_token = alternative_next_token
+                    && phrase.is_none()
+                    && token.lemma().chars().all(char::is_whitespace);
             }
             _ => (),
         }
@@ -183,14 +317,337 @@ pub fn located_query_terms_from_tokens(
             if negative_phrase {
                 negative_phrases.push(located_query_term);
             } else {
-                query_terms.push(located_query_term);
+                alternatives.push(&mut query_terms, located_query_term, &mut position);
             }
         }
     }
+    alternatives.end(&mut query_terms, &mut position);
 
     Ok(ExtractedTokens { query_terms, negative_words, negative_phrases })
 }
 
+/// Rewrites the tokenised search query with each of its words missing from the index replaced by
+/// its best typo derivation, returning `None` if none of its words had to be replaced.
+///
+/// The words of the phrases, the negative words, the `field:` prefixes and the `OR` operators
+/// are left as is.
+#[tracing::instrument(level = "trace", skip_all, target = "search::query")]
+pub fn suggested_query_from_tokens(
+    ctx: &mut SearchContext<'_>,
+    query: &str,
+    tokens: impl IntoIterator<Item = Token<'_>>,
+) -> Result<Option<String>> {
+    let nbr_typos = number_of_typos_allowed(ctx)?;
+    let allow_prefix_search = ctx.is_prefix_search_allowed();
+    let field_scopes = query_field_scopes(ctx, query)?;
+
+    let mut suggested_query = String::with_capacity(query.len());
+    let mut corrected = false;
+    let mut in_phrase = false;
+    let mut negative_next_token = false;
+    let mut encountered_whitespace = true;
+
+    let mut peekable = tokens.into_iter().peekable();
+    while let Some(token) = peekable.next() {
+        let original = &query[token.byte_start..token.byte_end];
+        let mut correction = None;
+        let in_field_prefix = field_scopes.iter().any(|s| s.prefix.contains(&token.byte_start));
+
+        match token.kind {
+            TokenKind::Word
+                if !in_phrase
+                    && !negative_next_token
+                    && !in_field_prefix
+                    && original != "OR"
+                    && !token.lemma().is_empty() =>
+            {
+                let word = token.lemma();
+                let is_prefix = allow_prefix_search && peekable.peek().is_none();
+                let cached = ctx.spelling_corrections.and_then(|c| c.get(word, is_prefix));
+                correction = match cached {
+                    Some(correction) => correction,
+                    None => {
+                        let term = partially_initialized_term_from_word(
+                            ctx,
+                            word,
+                            nbr_typos(word),
+                            is_prefix,
+                            false,
+                        )?;
+                        let correction = ctx
+                            .term_interner
+                            .push(term)
+                            .spelling_correction(ctx)?
+                            .map(|correction| ctx.word_interner.get(correction).clone());
+                        if let Some(spelling_corrections) = ctx.spelling_corrections {
+                            spelling_corrections.insert(word, is_prefix, correction.clone());
+                        }
+                        correction
+                    }
+                };
+            }
+            TokenKind::Separator(_) => {
+                let quote_count = token.lemma().chars().filter(|&s| s == '"').count();
+                in_phrase ^= quote_count % 2 == 1;
+            }
+            _ => (),
+        }
+
+        negative_next_token = matches!(token.kind, TokenKind::Separator(_))
+            && !in_phrase
+            && token.lemma() == "-"
+            && encountered_whitespace;
+        encountered_whitespace =
+            token.lemma().chars().last().filter(|c| c.is_whitespace()).is_some();
+
+        match correction {
+            Some(word) => {
+                suggested_query.push_str(&word);
+                corrected = true;
+            }
+            None => suggested_query.push_str(original),
+        }
+    }
+
+    Ok(corrected.then_some(suggested_query))
+}
+
+/// A `field:` prefix in the query, restricting the next word or phrase to a searchable field.
+struct FieldScope {
+    /// The bytes of the prefix in the query, including the colon.
+    prefix: Range<usize>,
+    /// The searchable fields matching the name, the field and its nested fields.
+    field_ids: Vec<FieldId>,
+}
+
+/// Returns the `field:` prefixes of the query naming a searchable field,
+/// e.g. `title:` and `author:` in `title:harry author:rowling`.
+///
+/// The words prefixed by a name that isn't a field of the index are left as is,
+/// but naming a field that isn't searchable is an error.
+fn query_field_scopes(ctx: &SearchContext<'_>, query: &str) -> Result<Vec<FieldScope>> {
+    let mut scopes = Vec::new();
+    if !query.contains(':') {
+        return Ok(scopes);
+    }
+
+    let searchable_fields = ctx.index.searchable_fields_and_weights(ctx.txn)?;
+    let fields_ids_map = ctx.index.fields_ids_map(ctx.txn)?;
+
+    let mut chunk_start = 0;
+    for (index, c) in query.char_indices().chain(once((query.len(), ' '))) {
+        if !c.is_whitespace() {
+            continue;
+        }
+        let chunk = &query[chunk_start..index];
+        let chunk_offset = chunk_start;
+        chunk_start = index + c.len_utf8();
+
+        // the `+` of a required term can precede the name of the field
+        let name_start = chunk.len() - chunk.trim_start_matches('+').len();
+        let Some((name, _)) = chunk[name_start..].split_once(':') else { continue };
+        if name.is_empty() || name.contains('"') {
+            continue;
+        }
+
+        let field_ids: Vec<_> = searchable_fields
+            .iter()
+            .filter(|(field, _, _)| is_faceted_by(field, name))
+            .map(|(_, fid, _)| *fid)
+            .collect();
+        if field_ids.is_empty() {
+            if fields_ids_map.names().any(|field| is_faceted_by(field, name)) {
+                let (valid_fields, hidden_fields) = ctx.index.remove_hidden_fields(
+                    ctx.txn,
+                    searchable_fields.iter().map(|(name, _, _)| name),
+                )?;
+                return Err(UserError::InvalidSearchableAttributeInQuery {
+                    field: name.to_string(),
+                    valid_fields,
+                    hidden_fields,
+                }
+                .into());
+            }
+            continue;
+        }
+
+        let prefix_start = chunk_offset + name_start;
+        scopes.push(FieldScope { prefix: prefix_start..prefix_start + name.len() + 1, field_ids });
+    }
+
+    Ok(scopes)
+}
+
+/// The `OR` operators of the query and the parentheses grouping their operands.
+///
+/// The operands of an `OR` operator start at the same position, and the last terms of the shorter
+/// operand are extended to end at the same position as the longer one, e.g. `notebook` is located
+/// at the positions `0..=1` of `gaming laptop` in `(gaming laptop) OR notebook`.
+#[derive(Default)]
+struct Alternatives {
+    /// The index of the first query term of each parenthesis that is not closed yet.
+    groups: Vec<usize>,
+    /// The `OR` operators whose right operand is not complete yet.
+    pending: Vec<PendingAlternative>,
+    /// The index of the first query term of the last word, phrase, group or `OR` operator,
+    /// which is the left operand of an `OR` operator directly following it.
+    last_operand: Option<usize>,
+}
+
+/// An `OR` operator whose right operand is not complete yet.
+struct PendingAlternative {
+    /// The index of the first query term of the left operand.
+    left: usize,
+    /// The index of the first query term of the right operand.
+    right: usize,
+    /// The number of parentheses opened before the right operand.
+    depth: usize,
+}
+
+impl Alternatives {
+    /// Pushes the word or phrase, which may complete the right operand of an `OR` operator.
+    fn push(
+        &mut self,
+        query_terms: &mut Vec<LocatedQueryTerm>,
+        term: LocatedQueryTerm,
+        position: &mut u16,
+    ) {
+        self.last_operand = Some(query_terms.len());
+        query_terms.push(term);
+        self.end_operands(query_terms, position);
+    }
+
+    /// Starts the right operand of an `OR` operator at the position of its left operand.
+    fn start_right_operand(&mut self, query_terms: &[LocatedQueryTerm], position: &mut u16) {
+        // an `OR` operator is only recognized after an operand
+        let left = self.last_operand.unwrap();
+        *position = query_terms[left].positions.start().wrapping_sub(1);
+        self.pending.push(PendingAlternative {
+            left,
+            right: query_terms.len(),
+            depth: self.groups.len(),
+        });
+    }
+
+    fn open_group(&mut self, query_terms: &[LocatedQueryTerm]) {
+        self.groups.push(query_terms.len());
+    }
+
+    /// Closes the last group, which may complete the right operand of an `OR` operator.
+    fn close_group(&mut self, query_terms: &mut [LocatedQueryTerm], position: &mut u16) {
+        // an unbalanced closing parenthesis is ignored
+        let Some(start) = self.groups.pop() else { return };
+        if start < query_terms.len() {
+            self.last_operand = Some(start);
+        }
+        self.end_operands(query_terms, position);
+    }
+
+    /// Ends the `OR` operators whose right operand started outside of the groups still open.
+    fn end_operands(&mut self, query_terms: &mut [LocatedQueryTerm], position: &mut u16) {
+        while self.pending.last().is_some_and(|pending| pending.depth == self.groups.len()) {
+            let pending = self.pending.pop().unwrap();
+            self.end_alternative(query_terms, pending, position);
+        }
+    }
+
+    /// Ends all the `OR` operators, at the end of the query.
+    fn end(&mut self, query_terms: &mut [LocatedQueryTerm], position: &mut u16) {
+        while let Some(pending) = self.pending.pop() {
+            self.end_alternative(query_terms, pending, position);
+        }
+    }
+
+    fn end_alternative(
+        &mut self,
+        query_terms: &mut [LocatedQueryTerm],
+        PendingAlternative { left, right, depth: _ }: PendingAlternative,
+        position: &mut u16,
+    ) {
+        let operand_end =
+            |terms: &[LocatedQueryTerm]| terms.iter().map(|t| *t.positions.end()).max();
+        let (left_terms, right_terms) = query_terms[left..].split_at_mut(right - left);
+        let (Some(left_end), Some(right_end)) = (operand_end(left_terms), operand_end(right_terms))
+        else {
+            return;
+        };
+
+        let end = left_end.max(right_end);
+        for (terms, terms_end) in [(left_terms, left_end), (right_terms, right_end)] {
+            for term in terms.iter_mut().filter(|term| *term.positions.end() == terms_end) {
+                term.positions = *term.positions.start()..=end;
+            }
+        }
+        *position = (*position).max(end);
+        self.last_operand = Some(left);
+    }
+}
+
+/// Returns `true` if the last query term is the single word right before the current position,
+/// and can therefore be the left operand of a `NEAR/n` operator.
+///
+/// The operands of an `OR` operator can't be an operand of a `NEAR/n` operator.
+fn is_near_operand(
+    ctx: &SearchContext<'_>,
+    query_terms: &[LocatedQueryTerm],
+    position: u16,
+) -> bool {
+    if let Some((last, previous)) = query_terms.split_last() {
+        if previous.iter().any(|term| term.positions.end() >= last.positions.start()) {
+            return false;
+        }
+    }
+    query_terms.last().is_some_and(|term| {
+        let query_term = ctx.term_interner.get(term.value);
+        *term.positions.start() == position
+            && query_term.zero_typo.phrase.is_none()
+            && query_term.field_ids.is_none()
+            && term.value.original_single_word(ctx).is_some()
+    })
+}
+
+/// Returns the `n` of a `NEAR/n` operator whose `near` word was just read,
+/// consuming the `/n` tokens that follow it.
+fn near_operator_slop<'o>(
+    tokens: &mut Peekable<impl Iterator<Item = Token<'o>>>,
+) -> Result<Option<u8>> {
+    if tokens.next_if(|token| token.lemma() == "/").is_none() {
+        return Ok(None);
+    }
+    operator_argument(tokens)
+}
+
+/// Returns the `n` of the `~n` slop following the closing quote of a phrase, consuming its tokens.
+fn phrase_slop<'o>(
+    closing_quote: &Token<'_>,
+    tokens: &mut Peekable<impl Iterator<Item = Token<'o>>>,
+) -> Result<Option<u8>> {
+    if !closing_quote.lemma().ends_with('~')
+        && tokens.next_if(|token| token.lemma() == "~").is_none()
+    {
+        return Ok(None);
+    }
+    operator_argument(tokens)
+}
+
+/// Consumes the number given as argument to a query operator, e.g. the `3` of `NEAR/3`.
+///
+/// Returns an error when the number is greater than the maximum slop of 255.
+fn operator_argument<'o>(
+    tokens: &mut Peekable<impl Iterator<Item = Token<'o>>>,
+) -> Result<Option<u8>> {
+    let is_number = |token: &Token<'_>| {
+        token.is_word()
+            && !token.lemma().is_empty()
+            && token.lemma().bytes().all(|b| b.is_ascii_digit())
+    };
+    let Some(token) = tokens.next_if(is_number) else { return Ok(None) };
+    match token.lemma().parse() {
+        Ok(slop) => Ok(Some(slop)),
+        Err(_) => Err(UserError::InvalidQuerySlop { slop: token.lemma().to_string() }.into()),
+    }
+}
+
 pub fn number_of_typos_allowed<'ctx>(
     ctx: &SearchContext<'ctx>,
 ) -> Result<impl Fn(&str) -> u8 + 'ctx> {
@@ -221,7 +678,8 @@ pub fn make_ngram(
 ) -> Result<Option<LocatedQueryTerm>> {
     assert!(!terms.is_empty());
     for t in terms {
-        if ctx.term_interner.get(t.value).zero_typo.phrase.is_some() {
+        let term = ctx.term_interner.get(t.value);
+        if term.zero_typo.phrase.is_some() || term.field_ids.is_some() {
             return Ok(None);
         }
     }
@@ -263,7 +721,7 @@ pub fn make_ngram(
     term.zero_typo.synonyms.extend(
         index_synonyms.get(&words).cloned().unwrap_or_default().into_iter().map(|words| {
             let words = words.into_iter().map(|w| Some(ctx.word_interner.insert(w))).collect();
-            ctx.phrase_interner.insert(Phrase { words })
+            ctx.phrase_interner.insert(Phrase { words, ..Default::default() })
         }),
     );
 
@@ -275,9 +733,14 @@ pub fn make_ngram(
         zero_typo: term.zero_typo,
         one_typo: Lazy::Uninit,
         two_typo: Lazy::Uninit,
+        field_ids: None,
     };
 
-    let term = LocatedQueryTerm { value: ctx.term_interner.push(term), positions: start..=end };
+    let term = LocatedQueryTerm {
+        value: ctx.term_interner.push(term),
+        positions: start..=end,
+        mandatory: false,
+    };
 
     Ok(Some(term))
 }
@@ -286,11 +749,21 @@ struct PhraseBuilder {
     words: Vec<Option<crate::search::new::Interned<String>>>,
     start: u16,
     end: u16,
+    slop: u8,
+    unordered: bool,
+    field_ids: Option<Vec<FieldId>>,
 }
 
 impl PhraseBuilder {
     fn empty() -> Self {
-        Self { words: Default::default(), start: u16::MAX, end: u16::MAX }
+        Self {
+            words: Default::default(),
+            start: u16::MAX,
+            end: u16::MAX,
+            slop: 0,
+            unordered: false,
+            field_ids: None,
+        }
     }
 
     fn is_empty(&self) -> bool {
@@ -323,7 +796,11 @@ impl PhraseBuilder {
         }
         Some(LocatedQueryTerm {
             value: ctx.term_interner.push({
-                let phrase = ctx.phrase_interner.insert(Phrase { words: self.words });
+                let phrase = ctx.phrase_interner.insert(Phrase {
+                    words: self.words,
+                    slop: self.slop,
+                    unordered: self.unordered,
+                });
                 let phrase_desc = phrase.description(ctx);
                 QueryTerm {
                     original: ctx.word_interner.insert(phrase_desc),
@@ -335,13 +812,16 @@ impl PhraseBuilder {
                         exact: None,
                         prefix_of: BTreeSet::default(),
                         synonyms: BTreeSet::default(),
+                        stems: BTreeSet::default(),
                         use_prefix_db: None,
                     },
                     one_typo: Lazy::Uninit,
                     two_typo: Lazy::Uninit,
+                    field_ids: self.field_ids,
                 }
             }),
             positions: self.start..=self.end,
+            mandatory: false,
         })
     }
 }
@@ -375,7 +855,7 @@ mod tests {
         let mut ctx = SearchContext::new(&index, &rtxn)?;
         // panics with `attempt to add with overflow` before <https://github.com/meilisearch/meilisearch/issues/3785>
         let ExtractedTokens { query_terms, .. } =
-            located_query_terms_from_tokens(&mut ctx, tokens, None)?;
+            located_query_terms_from_tokens(&mut ctx, ".", tokens, None)?;
         assert!(query_terms.is_empty());
 
         Ok(())
@@ -8,6 +8,11 @@ use crate::SearchContext;
 #[derive(Default, Clone, PartialEq, Eq, Hash)]
 pub struct Phrase {
     pub words: Vec<Option<Interned<String>>>,
+    /// The number of other words allowed between two consecutive words of the phrase,
+    /// e.g. `3` for `"quick fox"~3`.
+    pub slop: u8,
+    /// Whether the words of the phrase can appear in any order, e.g. for `quick NEAR/3 fox`.
+    pub unordered: bool,
 }
 impl Interned<Phrase> {
     pub fn description(self, ctx: &SearchContext<'_>) -> String {
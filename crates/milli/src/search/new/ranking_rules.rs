@@ -57,6 +57,12 @@ pub trait RankingRule<'ctx, Query: RankingRuleQueryTrait> {
         ctx: &mut SearchContext<'ctx>,
         logger: &mut dyn SearchLogger<Query>,
     );
+
+    /// Receive the ranking score given by the previous ranking rules to the universe
+    /// of the next call to [`start_iteration`](RankingRule::start_iteration).
+    ///
+    /// It is ignored by default, only the ranking rules relying on this score implement it.
+    fn set_parent_ranking_score(&mut self, _ranking_score: f64) {}
 }
 
 /// Output of a ranking rule, consisting of the query to be used
@@ -1,5 +1,6 @@
 #![allow(clippy::too_many_arguments)]
 
+use std::cmp::Ordering;
 use std::collections::VecDeque;
 
 use fxhash::FxHashMap;
@@ -10,6 +11,7 @@ use super::query_graph::QueryNodeData;
 use super::query_term::{Phrase, QueryTermSubset};
 use super::small_bitmap::SmallBitmap;
 use super::{QueryGraph, SearchContext, Word};
+use crate::proximity::MAX_DISTANCE;
 use crate::search::new::query_term::LocatedQueryTermSubset;
 use crate::Result;
 
@@ -52,6 +54,16 @@ pub fn compute_query_term_subset_docids(
         }
     }
 
+    // A term scoped with `field:word` must be found in one of its fields.
+    if let Some(field_ids) = term.field_ids(ctx) {
+        let mut field_docids = RoaringBitmap::new();
+        for fid in field_ids {
+            field_docids |=
+                compute_query_term_subset_docids_within_field_id(ctx, universe, term, fid)?;
+        }
+        docids &= field_docids;
+    }
+
     match universe {
         Some(universe) => Ok(docids & universe),
         None => Ok(docids),
@@ -64,8 +76,16 @@ pub fn compute_query_term_subset_docids_within_field_id(
     term: &QueryTermSubset,
     fid: u16,
 ) -> Result<RoaringBitmap> {
+    if term.field_ids(ctx).is_some_and(|field_ids| !field_ids.contains(&fid)) {
+        return Ok(RoaringBitmap::new());
+    }
+
     let mut docids = RoaringBitmap::new();
     for word in term.all_single_words_except_prefix_db(ctx)? {
+        // the stems only match in the stemmed fields
+        if matches!(word, Word::Stem(_)) && !ctx.stemmed_fids.contains(&fid) {
+            continue;
+        }
         if let Some(word_fid_docids) = ctx.get_db_word_fid_docids(universe, word.interned(), fid)? {
             docids |= word_fid_docids;
         }
@@ -188,7 +208,7 @@ pub fn compute_phrase_docids(
     ctx: &mut SearchContext<'_>,
     phrase: Interned<Phrase>,
 ) -> Result<RoaringBitmap> {
-    let Phrase { words } = ctx.phrase_interner.get(phrase).clone();
+    let Phrase { words, slop, unordered } = ctx.phrase_interner.get(phrase).clone();
 
     if words.is_empty() {
         return Ok(RoaringBitmap::new());
@@ -226,27 +246,41 @@ pub fn compute_phrase_docids(
                 .enumerate()
                 .filter_map(|(index, word)| word.as_ref().map(|word| (index, word)))
             {
-                if dist == 0 {
-                    match ctx.get_db_word_pair_proximity_docids(None, s1, s2, 1)? {
-                        Some(m) => bitmaps.push(m),
-                        // If there are no documents for this pair, there will be no
-                        // results for the phrase query.
-                        None => return Ok(RoaringBitmap::new()),
+                // Each gap between the words of the phrase can contain up to `slop` other words.
+                let max_proximity = (dist + 1) * (slop as usize + 1);
+                let mut bitmap = RoaringBitmap::new();
+                // The database doesn't store the proximities from `MAX_DISTANCE`,
+                // the larger ones are checked with the positions of the words.
+                if max_proximity >= MAX_DISTANCE as usize {
+                    bitmap |= word_pair_within_proximity(
+                        ctx,
+                        &candidates,
+                        s1,
+                        s2,
+                        max_proximity,
+                        unordered,
+                    )?;
+                }
+                for proximity in 1..=max_proximity.min(MAX_DISTANCE as usize - 1) as u8 {
+                    if let Some(m) =
+                        ctx.get_db_word_pair_proximity_docids(None, s1, s2, proximity)?
+                    {
+                        bitmap |= m;
                     }
-                } else {
-                    let mut bitmap = RoaringBitmap::new();
-                    for dist in 0..=dist {
+                    if unordered {
                         if let Some(m) =
-                            ctx.get_db_word_pair_proximity_docids(None, s1, s2, dist as u8 + 1)?
+                            ctx.get_db_word_pair_proximity_docids(None, s2, s1, proximity)?
                         {
                             bitmap |= m;
                         }
                     }
-                    if bitmap.is_empty() {
-                        return Ok(bitmap);
-                    } else {
-                        bitmaps.push(bitmap);
-                    }
+                }
+                // If there are no documents for this pair, there will be no
+                // results for the phrase query.
+                if bitmap.is_empty() {
+                    return Ok(bitmap);
+                } else {
+                    bitmaps.push(bitmap);
                 }
             }
         }
@@ -266,3 +300,66 @@ pub fn compute_phrase_docids(
     }
     Ok(candidates)
 }
+
+/// Returns the documents of the `universe` in which the `right` word follows the `left` one with at most
+/// the given proximity, or precedes it if the pair is `unordered`, according to the positions of the words.
+///
+/// The positions of the `word_position_docids` database are relative to their attribute and bucketed
+/// from 16, so the proximity between words far from the start of their attribute is approximated.
+/// Only the documents containing both words in the same attribute are considered.
+fn word_pair_within_proximity(
+    ctx: &mut SearchContext<'_>,
+    universe: &RoaringBitmap,
+    left: Interned<String>,
+    right: Interned<String>,
+    max_proximity: usize,
+    unordered: bool,
+) -> Result<RoaringBitmap> {
+    // the relative positions of words in different attributes can't be compared
+    let mut universe_in_same_attribute = RoaringBitmap::new();
+    let right_fids = ctx.get_db_word_fids(right)?;
+    for fid in ctx.get_db_word_fids(left)? {
+        if !right_fids.contains(&fid) {
+            continue;
+        }
+        let Some(left_docids) = ctx.get_db_word_fid_docids(Some(universe), left, fid)? else {
+            continue;
+        };
+        if let Some(right_docids) = ctx.get_db_word_fid_docids(Some(universe), right, fid)? {
+            universe_in_same_attribute |= left_docids & right_docids;
+        }
+    }
+    if universe_in_same_attribute.is_empty() {
+        return Ok(universe_in_same_attribute);
+    }
+    let universe = &universe_in_same_attribute;
+
+    let left_positions = ctx.get_db_word_positions(left)?;
+    let right_positions = ctx.get_db_word_positions(right)?;
+
+    let mut docids = RoaringBitmap::new();
+    for left_position in left_positions {
+        let Some(left_docids) =
+            ctx.get_db_word_position_docids(Some(universe), left, left_position)?
+        else {
+            continue;
+        };
+        for &right_position in &right_positions {
+            // the proximity of words in the reverse order is one more than their distance
+            let proximity = match right_position.cmp(&left_position) {
+                Ordering::Greater => (right_position - left_position) as usize,
+                Ordering::Less if unordered => (left_position - right_position) as usize + 1,
+                _ => continue,
+            };
+            if proximity > max_proximity {
+                continue;
+            }
+            if let Some(right_docids) =
+                ctx.get_db_word_position_docids(Some(universe), right, right_position)?
+            {
+                docids |= &left_docids & right_docids;
+            }
+        }
+    }
+    Ok(docids)
+}
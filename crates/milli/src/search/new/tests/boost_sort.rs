@@ -0,0 +1,76 @@
+/*!
+This module tests the boosts of a search, applied after the ranking rules of the index
+*/
+
+use crate::index::tests::TempIndex;
+use crate::score_details::{self, ScoreDetails, ScoringStrategy};
+use crate::search::new::tests::collect_field_values;
+use crate::{
+    Criterion, Filter, FilterableAttributesRule, Search, SearchResult, TermsMatchingStrategy,
+};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_criteria(vec![Criterion::Words]);
+            s.set_filterable_fields(vec![
+                FilterableAttributesRule::Field("brand".to_owned()),
+                FilterableAttributesRule::Field("color".to_owned()),
+            ]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "title": "the quick fox", "brand": "samsung", "color": "red" },
+            { "id": 1, "title": "the quick fox", "brand": "apple", "color": "blue" },
+            { "id": 2, "title": "the fox", "brand": "apple", "color": "red" },
+            { "id": 3, "title": "the quick fox", "brand": "apple", "color": "red" },
+            { "id": 4, "title": "the quick fox", "brand": "nokia", "color": "blue" },
+            { "id": 5, "title": "the quick fox", "brand": "samsung", "color": "green" },
+        ]))
+        .unwrap();
+    index
+}
+
+fn boosts() -> Vec<(Filter<'static>, f64)> {
+    vec![
+        (Filter::from_str("brand = apple").unwrap().unwrap(), 2.0),
+        (Filter::from_str("color = red").unwrap().unwrap(), 1.0),
+    ]
+}
+
+#[test]
+fn boost_sort() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.scoring_strategy(ScoringStrategy::Detailed);
+    s.boosts(boosts());
+    let SearchResult { documents_ids, document_scores, .. } = s.execute().unwrap();
+    let ids = collect_field_values(&index, &txn, "id", &documents_ids);
+    // the documents matching several boosts are ranked by the sum of their weights
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["2", "3", "1", "0", "4", "5"]"###);
+
+    let scores: Vec<_> =
+        document_scores.iter().map(|s| ScoreDetails::global_score(s.iter())).collect();
+    insta::assert_snapshot!(format!("{scores:.2?}"), @"[1.00, 1.00, 0.67, 0.34, 0.01, 0.01]");
+    assert_eq!(
+        document_scores[2],
+        vec![ScoreDetails::Boost(score_details::Boost { weight: 2.0, max_weight: 3.0 })]
+    );
+
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.query("quick fox");
+    s.boosts(boosts());
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    let ids = collect_field_values(&index, &txn, "id", &documents_ids);
+    // the boosts only sort the documents within the buckets of the ranking rules
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["3", "1", "0", "4", "5", "2"]"###);
+}
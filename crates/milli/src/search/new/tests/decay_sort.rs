@@ -0,0 +1,100 @@
+/*!
+This module tests the `decay:` ranking rule
+*/
+
+use crate::index::tests::TempIndex;
+use crate::score_details::{ScoreDetails, ScoringStrategy};
+use crate::search::new::tests::collect_field_values;
+use crate::{Criterion, FilterableAttributesRule, Search, SearchResult};
+
+fn create_index(decay: &str) -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_criteria(vec![Criterion::Decay(decay.to_owned())]);
+        })
+        .unwrap();
+    index
+}
+
+#[test]
+fn numeric_decay() {
+    let index = create_index("gauss(price, 100, 10)");
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "price": 100 },
+            { "id": 1, "price": 110 },
+            { "id": 2, "price": 90 },
+            { "id": 3, "price": 130 },
+            { "id": 4, "price": [200, 105] },
+            { "id": 5 },
+        ]))
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.scoring_strategy(ScoringStrategy::Detailed);
+    let SearchResult { documents_ids, document_scores, .. } = s.execute().unwrap();
+    let ids = collect_field_values(&index, &txn, "id", &documents_ids);
+    // the closest value of a document is used, and the documents without a value are returned last
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["0", "4", "1", "2", "3", "5"]"###);
+
+    let scores: Vec<_> =
+        document_scores.iter().map(|s| ScoreDetails::global_score(s.iter())).collect();
+    insta::assert_snapshot!(format!("{scores:.2?}"), @"[1.00, 0.84, 0.50, 0.50, 0.01, 0.01]");
+}
+
+#[test]
+fn date_decay() {
+    let index = create_index("exp(date, 2024-01-01T00:00:00Z, 1d)");
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "date": "2024-01-01T00:00:00Z" },
+            { "id": 1, "date": "2023-12-31T00:00:00Z" },
+            { "id": 2, "date": "2024-01-03T00:00:00Z" },
+            { "id": 3, "date": 1704070800 },
+            { "id": 4, "date": "not a date" },
+            { "id": 5, "date": "2024-01-01T12:00:00+00:00" },
+        ]))
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+    let s = Search::new(&txn, &index);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    let ids = collect_field_values(&index, &txn, "id", &documents_ids);
+    // the numbers are considered to be unix timestamps
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["0", "3", "5", "1", "2", "4"]"###);
+}
+
+#[test]
+fn date_decay_on_declared_dates() {
+    let index = create_index("gauss(date, 2024-01-01T00:00:00Z, 1d)");
+
+    let date_rule: FilterableAttributesRule = serde_json::from_value(serde_json::json!({
+        "attributePatterns": ["date"],
+        "features": { "date": true }
+    }))
+    .unwrap();
+    index.update_settings(|s| s.set_filterable_fields(vec![date_rule])).unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "date": "2023-12-25T00:00:00Z" },
+            { "id": 1, "date": "2024-01-01T06:00:00Z" },
+            { "id": 2, "date": ["2024-03-01", "2023-12-31"] },
+            { "id": 3, "date": "2024-01-01" },
+            { "id": 4, "date": "not a date" },
+        ]))
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+    let s = Search::new(&txn, &index);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    let ids = collect_field_values(&index, &txn, "id", &documents_ids);
+    // the dates are found by range in the numeric facets
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["3", "1", "2", "0", "4"]"###);
+}
@@ -0,0 +1,71 @@
+/*!
+This module tests the `expr:` ranking rule
+*/
+
+use crate::index::tests::TempIndex;
+use crate::score_details::{ScoreDetails, ScoringStrategy};
+use crate::search::new::tests::collect_field_values;
+use crate::{Criterion, Search, SearchResult, TermsMatchingStrategy};
+
+fn create_index(criterion: &str) -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_criteria(vec![Criterion::Words, criterion.parse().unwrap()]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "title": "the quick fox", "rating": 4.5, "views": 10 },
+            { "id": 1, "title": "the quick fox", "rating": 3, "views": 10000 },
+            { "id": 2, "title": "the fox", "rating": 5, "views": 1000 },
+            { "id": 3, "title": "the quick fox", "rating": 2 },
+            { "id": 4, "title": "the quick fox", "rating": [1, 5], "views": 100 },
+            { "id": 5, "title": "the quick fox", "rating": 4, "views": 1 },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn expression_sort() {
+    let index = create_index("expr:rating * log10(views + 10)");
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.scoring_strategy(ScoringStrategy::Detailed);
+    let SearchResult { documents_ids, document_scores, .. } = s.execute().unwrap();
+    let ids = collect_field_values(&index, &txn, "id", &documents_ids);
+    // the document without views is returned last, and the smallest rating is used
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["2", "1", "0", "5", "4", "3"]"###);
+
+    // the values are normalized between the smallest and the largest ones
+    let scores: Vec<_> =
+        document_scores.iter().map(|s| ScoreDetails::global_score(s.iter())).collect();
+    insta::assert_snapshot!(format!("{scores:.2?}"), @"[1.00, 0.77, 0.30, 0.17, 0.01, 0.01]");
+
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.query("quick fox");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    let ids = collect_field_values(&index, &txn, "id", &documents_ids);
+    // the expression only sorts the documents within the buckets of the previous rules
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["1", "0", "5", "4", "3", "2"]"###);
+}
+
+#[test]
+fn expression_sort_with_ranking_score() {
+    let index = create_index("expr:_rankingScore * rating");
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.query("quick fox");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    let ids = collect_field_values(&index, &txn, "id", &documents_ids);
+    insta::assert_snapshot!(format!("{ids:?}"), @r###"["0", "5", "1", "3", "4", "2"]"###);
+}
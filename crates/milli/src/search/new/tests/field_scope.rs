@@ -0,0 +1,105 @@
+/*!
+This module tests the field-scoped terms of the query, e.g. `title:harry`:
+
+1. A scoped word or phrase only matches the documents containing it in the field
+2. The scope only applies to the word or phrase directly following the prefix
+3. A name that isn't a field of the index is searched as a regular word
+4. Scoping a term to a field that isn't searchable is an error
+*/
+
+use crate::index::tests::TempIndex;
+use crate::{Criterion, Search, SearchResult, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["title".to_owned(), "author".to_owned()]);
+            s.set_criteria(vec![Criterion::Words]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "title": "Harry Potter", "author": "J. K. Rowling", "genre": "fantasy" },
+            { "id": 1, "title": "Rowling biography", "author": "Sean Smith", "genre": "biography" },
+            { "id": 2, "title": "Harry Bosch", "author": "Michael Connelly", "genre": "crime" },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn field_scoped_terms() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.query("rowling");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1]");
+
+    let mut s = Search::new(&txn, &index);
+    s.query("author:rowling");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0]");
+
+    let mut s = Search::new(&txn, &index);
+    s.query("title:rowling");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1]");
+
+    let mut s = Search::new(&txn, &index);
+    s.query("title:harry author:rowling");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0]");
+
+    // the scope doesn't apply to `connelly`
+    let mut s = Search::new(&txn, &index);
+    s.query("title:harry connelly");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[2]");
+
+    let mut s = Search::new(&txn, &index);
+    s.query("title:\"harry potter\"");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0]");
+
+    let mut s = Search::new(&txn, &index);
+    s.query("author:\"harry potter\"");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[]");
+}
+
+#[test]
+fn unknown_field_scope() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    // `harry` isn't a field, the query is searched as the words `harry bosch`
+    let mut s = Search::new(&txn, &index);
+    s.query("harry:bosch");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[2]");
+}
+
+#[test]
+fn non_searchable_field_scope() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.query("genre:fantasy");
+    let error = s.execute().unwrap_err();
+    insta::assert_snapshot!(error.to_string(), @"Attribute `genre` is not searchable and cannot be used as `genre:` in the query. Available searchable attributes are: `author, title`.");
+}
@@ -7,7 +7,7 @@ use heed::RoTxn;
 use itertools::Itertools;
 use maplit::hashset;
 
-use crate::constants::RESERVED_GEO_FIELD_NAME;
+use crate::constants::{RESERVED_GEOJSON_FIELD_NAME, RESERVED_GEO_FIELD_NAME};
 use crate::index::tests::TempIndex;
 use crate::score_details::ScoreDetails;
 use crate::search::new::tests::collect_field_values;
@@ -415,3 +415,90 @@ fn geo_sort_without_any_geo_faceted_documents() {
     insta::assert_snapshot!(format!("{ids:?}"), @"[0, 2, 3]");
     insta::assert_snapshot!(format!("{scores:#?}"));
 }
+
+#[test]
+fn geo_sort_with_geojson() {
+    let index = create_index();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, RESERVED_GEO_FIELD_NAME: { "lat": 0, "lng": 0 } },
+            { "id": 1, RESERVED_GEOJSON_FIELD_NAME: { "type": "Point", "coordinates": [1, 1] } },
+            { "id": 2, RESERVED_GEOJSON_FIELD_NAME: { "type": "MultiPoint", "coordinates": [[-10, -10], [2, 2]] } },
+            { "id": 3, RESERVED_GEOJSON_FIELD_NAME: { "type": "Polygon", "coordinates": [[[3, 3], [4, 3], [4, 4], [3, 4], [3, 3]]] } },
+            { "id": 4, RESERVED_GEO_FIELD_NAME: { "lat": 5, "lng": 5 }, RESERVED_GEOJSON_FIELD_NAME: { "type": "Point", "coordinates": [-0.5, -0.5] } },
+            { "id": 5 },
+        ]))
+        .unwrap();
+
+    let rtxn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&rtxn, &index);
+    s.scoring_strategy(crate::score_details::ScoringStrategy::Detailed);
+
+    // the documents are ranked by their closest point
+    s.sort_criteria(vec![AscDesc::Asc(Member::Geo([0., 0.]))]);
+    let (ids, _scores) = execute_iterative_and_rtree_returns_the_same(&rtxn, &index, &mut s);
+    insta::assert_snapshot!(format!("{ids:?}"), @"[0, 4, 1, 2, 3, 5]");
+
+    // and also by their closest point in the descending order
+    s.sort_criteria(vec![AscDesc::Desc(Member::Geo([0., 0.]))]);
+    let (ids, _scores) = execute_iterative_and_rtree_returns_the_same(&rtxn, &index, &mut s);
+    insta::assert_snapshot!(format!("{ids:?}"), @"[3, 2, 1, 4, 0, 5]");
+}
+
+#[test]
+fn geo_sort_with_geojson_polygons() {
+    let index = create_index();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, RESERVED_GEO_FIELD_NAME: { "lat": 1, "lng": 1 } },
+            { "id": 1, RESERVED_GEOJSON_FIELD_NAME: { "type": "Polygon", "coordinates": [[[-10, -10], [10, -10], [10, 10], [-10, 10], [-10, -10]]] } },
+            { "id": 2, RESERVED_GEOJSON_FIELD_NAME: { "type": "Polygon", "coordinates": [[[-20, 0.5], [20, 0.5], [20, 0.6], [-20, 0.6], [-20, 0.5]]] } },
+        ]))
+        .unwrap();
+
+    let rtxn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&rtxn, &index);
+    s.scoring_strategy(crate::score_details::ScoringStrategy::Detailed);
+
+    // a polygon containing the point comes first and the others are ranked by their closest edge
+    s.sort_criteria(vec![AscDesc::Asc(Member::Geo([0., 0.]))]);
+    let (ids, _scores) = execute_iterative_and_rtree_returns_the_same(&rtxn, &index, &mut s);
+    insta::assert_snapshot!(format!("{ids:?}"), @"[1, 2, 0]");
+
+    s.sort_criteria(vec![AscDesc::Desc(Member::Geo([0., 0.]))]);
+    let (ids, _scores) = execute_iterative_and_rtree_returns_the_same(&rtxn, &index, &mut s);
+    insta::assert_snapshot!(format!("{ids:?}"), @"[0, 2, 1]");
+}
+
+#[test]
+fn geo_sort_with_many_geo_points() {
+    let index = create_index();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, RESERVED_GEO_FIELD_NAME: [{ "lat": 10, "lng": 10 }, { "lat": 1, "lng": 1 }] },
+            { "id": 1, RESERVED_GEO_FIELD_NAME: { "lat": 2, "lng": 2 } },
+            { "id": 2, RESERVED_GEO_FIELD_NAME: [{ "lat": 3, "lng": 3 }, { "lat": -20, "lng": -20 }] },
+            { "id": 3, RESERVED_GEO_FIELD_NAME: [] },
+        ]))
+        .unwrap();
+
+    let rtxn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&rtxn, &index);
+    s.scoring_strategy(crate::score_details::ScoringStrategy::Detailed);
+
+    // the documents are ranked by their nearest point
+    s.sort_criteria(vec![AscDesc::Asc(Member::Geo([0., 0.]))]);
+    let (ids, _scores) = execute_iterative_and_rtree_returns_the_same(&rtxn, &index, &mut s);
+    insta::assert_snapshot!(format!("{ids:?}"), @"[0, 1, 2, 3]");
+
+    // and also by their nearest point in the descending order
+    s.sort_criteria(vec![AscDesc::Desc(Member::Geo([0., 0.]))]);
+    let (ids, _scores) = execute_iterative_and_rtree_returns_the_same(&rtxn, &index, &mut s);
+    insta::assert_snapshot!(format!("{ids:?}"), @"[2, 1, 0, 3]");
+}
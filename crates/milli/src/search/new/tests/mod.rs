@@ -1,18 +1,25 @@
 pub mod attribute_fid;
 pub mod attribute_position;
+pub mod boost_sort;
 pub mod cutoff;
+pub mod decay_sort;
 pub mod distinct;
 pub mod exactness;
+pub mod expression_sort;
+pub mod field_scope;
 pub mod geo_sort;
 pub mod integration;
 #[cfg(feature = "all-tokenizations")]
 #[cfg(not(feature = "chinese-pinyin"))]
 pub mod language;
 pub mod ngram_split_words;
+pub mod or_operator;
+pub mod phrase_operators;
 pub mod proximity;
 pub mod proximity_typo;
 pub mod sort;
 pub mod stop_words;
+pub mod suggested_query;
 pub mod typo;
 pub mod typo_proximity;
 pub mod words_tms;
@@ -0,0 +1,204 @@
+/*!
+This module tests the `OR` operator of the query syntax:
+
+1. `a OR b` matches the documents containing either `a` or `b`
+2. The operands can be quoted phrases and groups of words in parentheses
+3. The alternatives are removed together by the terms matching strategy
+4. A lowercase `or` is a regular word of the query
+5. The `exactness`, `proximity` and `typo` ranking rules rank the alternatives of a group alike
+*/
+
+use crate::index::tests::TempIndex;
+use crate::search::new::tests::collect_field_values;
+use crate::{Criterion, Search, SearchResult, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_criteria(vec![Criterion::Words]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "a silver laptop" },
+            { "id": 1, "text": "a silver notebook" },
+            { "id": 2, "text": "a silver tablet" },
+            { "id": 3, "text": "a black laptop" },
+            { "id": 4, "text": "a gaming notebook" },
+        ]))
+        .unwrap();
+    index
+}
+
+fn create_index_with_groups(criteria: Vec<Criterion>) -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_criteria(criteria);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "gaming laptop" },
+            { "id": 1, "text": "notebook" },
+            { "id": 2, "text": "a laptop for gaming" },
+            { "id": 3, "text": "a gaming notebook" },
+            { "id": 4, "text": "notebok" },
+            { "id": 5, "text": "gaming laptop bag" },
+            { "id": 6, "text": "a gaming mouse" },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn or_operator() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.query("laptop OR notebook");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 3, 4]");
+
+    let mut s = Search::new(&txn, &index);
+    s.query("(laptop OR notebook) silver");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1]");
+
+    // without parentheses, `OR` only applies to the words around it
+    let mut s = Search::new(&txn, &index);
+    s.query("tablet OR laptop silver");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 2]");
+
+    let mut s = Search::new(&txn, &index);
+    s.query("\"gaming notebook\" OR laptop");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 3, 4]");
+
+    // `or` is a word of the query
+    let mut s = Search::new(&txn, &index);
+    s.query("laptop or notebook");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[]");
+}
+
+#[test]
+fn or_operator_terms_matching_strategy() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    // `laptop` and `notebook` are removed together, leaving only `silver`
+    let mut s = Search::new(&txn, &index);
+    s.query("silver (laptop OR notebook)");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 2]");
+}
+
+#[test]
+fn or_operator_groups() {
+    let index = create_index_with_groups(vec![Criterion::Words]);
+    let txn = index.read_txn().unwrap();
+
+    // the parentheses group both words as the left operand
+    let mut s = Search::new(&txn, &index);
+    s.query("(gaming laptop) OR notebook");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 2, 3, 4, 5]");
+
+    let mut s = Search::new(&txn, &index);
+    s.query("notebook OR (gaming laptop)");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 2, 3, 4, 5]");
+
+    let mut s = Search::new(&txn, &index);
+    s.query("gaming (laptop OR notebook)");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 2, 3, 5]");
+}
+
+#[test]
+fn or_operator_exactness() {
+    let index = create_index_with_groups(vec![Criterion::Words, Criterion::Exactness]);
+    let txn = index.read_txn().unwrap();
+
+    // both alternatives can be the exact attribute
+    let mut s = Search::new(&txn, &index);
+    s.query("(gaming laptop) OR notebook");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    let texts = collect_field_values(&index, &txn, "text", &documents_ids);
+    insta::assert_debug_snapshot!(texts, @r###"
+    [
+        "\"gaming laptop\"",
+        "\"notebook\"",
+        "\"gaming laptop bag\"",
+        "\"a laptop for gaming\"",
+        "\"a gaming notebook\"",
+        "\"notebok\"",
+    ]
+    "###);
+}
+
+#[test]
+fn or_operator_proximity() {
+    let index = create_index_with_groups(vec![Criterion::Words, Criterion::Proximity]);
+    let txn = index.read_txn().unwrap();
+
+    // `notebook` is as close as the words of `gaming laptop` next to each other
+    let mut s = Search::new(&txn, &index);
+    s.query("(gaming laptop) OR notebook");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    let texts = collect_field_values(&index, &txn, "text", &documents_ids);
+    insta::assert_debug_snapshot!(texts, @r###"
+    [
+        "\"gaming laptop\"",
+        "\"notebook\"",
+        "\"a gaming notebook\"",
+        "\"notebok\"",
+        "\"gaming laptop bag\"",
+        "\"a laptop for gaming\"",
+    ]
+    "###);
+}
+
+#[test]
+fn or_operator_typo() {
+    let index = create_index_with_groups(vec![Criterion::Words, Criterion::Typo]);
+    let txn = index.read_txn().unwrap();
+
+    // `notebook` costs no more typos than the words of `gaming laptop`
+    let mut s = Search::new(&txn, &index);
+    s.query("(gaming laptop) OR notebook");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    let texts = collect_field_values(&index, &txn, "text", &documents_ids);
+    insta::assert_debug_snapshot!(texts, @r###"
+    [
+        "\"gaming laptop\"",
+        "\"notebook\"",
+        "\"a laptop for gaming\"",
+        "\"a gaming notebook\"",
+        "\"gaming laptop bag\"",
+        "\"notebok\"",
+    ]
+    "###);
+}
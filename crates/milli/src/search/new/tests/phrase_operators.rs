@@ -0,0 +1,158 @@
+/*!
+This module tests the proximity operators of the query syntax:
+
+1. A phrase followed by `~n` allows up to `n` other words between its consecutive words
+2. `NEAR/n` matches the two words around it with up to `n` words between them, in any order
+3. The words prefixed with `+` are never removed by the terms matching strategy
+*/
+
+use crate::index::tests::TempIndex;
+use crate::{Criterion, Search, SearchResult, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_criteria(vec![Criterion::Words]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "the quick brown fox jumps" },
+            { "id": 1, "text": "the quick fox" },
+            { "id": 2, "text": "the fox is quick" },
+            { "id": 3, "text": "a quick and very brown fox" },
+            { "id": 4, "text": "the brown dog" },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn phrase_slop() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.query("\"quick fox\"");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1]");
+
+    let mut s = Search::new(&txn, &index);
+    s.query("\"quick fox\"~1");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1]");
+
+    // the words must still be in the order of the phrase
+    let mut s = Search::new(&txn, &index);
+    s.query("\"fox quick\"~2");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[2]");
+
+    // the proximities from `MAX_DISTANCE` are checked with the positions of the words
+    let mut s = Search::new(&txn, &index);
+    s.query("\"quick fox\"~2");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1]");
+
+    let mut s = Search::new(&txn, &index);
+    s.query("\"quick fox\"~3");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 3]");
+
+    let mut s = Search::new(&txn, &index);
+    s.query("\"the fox\"~10");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 2]");
+
+    let mut s = Search::new(&txn, &index);
+    s.query("\"quick fox\"~256");
+    insta::assert_snapshot!(s.execute().unwrap_err(), @"The slop `256` of a phrase or `NEAR` operator in the query is invalid. It must be an integer between 0 and 255.");
+}
+
+#[test]
+fn phrase_slop_within_one_attribute() {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_criteria(vec![Criterion::Words]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "title": "quick", "text": "the brown fox" },
+            { "id": 1, "title": "quick brown fox", "text": "" },
+        ]))
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+
+    // the positions of the words in different attributes are not compared
+    let mut s = Search::new(&txn, &index);
+    s.query("\"quick fox\"~10");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1]");
+}
+
+#[test]
+fn near_operator() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.query("quick NEAR/2 fox");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 2]");
+
+    let mut s = Search::new(&txn, &index);
+    s.query("quick NEAR/0 fox");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1]");
+
+    let mut s = Search::new(&txn, &index);
+    s.query("quick NEAR/3 fox");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 2, 3]");
+
+    // without an argument, `near` is a word of the query
+    let mut s = Search::new(&txn, &index);
+    s.query("quick near fox");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[]");
+
+    // like `OR`, the operator must be written in uppercase
+    let mut s = Search::new(&txn, &index);
+    s.query("quick near/2 fox");
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[]");
+
+    let mut s = Search::new(&txn, &index);
+    s.query("quick NEAR/1000 fox");
+    insta::assert_snapshot!(s.execute().unwrap_err(), @"The slop `1000` of a phrase or `NEAR` operator in the query is invalid. It must be an integer between 0 and 255.");
+}
+
+#[test]
+fn required_term() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.query("fox dog");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 2, 3]");
+
+    let mut s = Search::new(&txn, &index);
+    s.query("fox +dog");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[4]");
+}
@@ -0,0 +1,72 @@
+/*!
+This module tests the query suggested when a search returns few hits:
+
+1. The words missing from the index are replaced by their most frequent typo derivation
+2. The separators and the words of the index are kept as written in the query
+3. The words of the phrases are not corrected
+4. No query is suggested when all the words of the query are in the index
+*/
+
+use crate::index::tests::TempIndex;
+use crate::{Criterion, Search, SearchResult};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_criteria(vec![Criterion::Words, Criterion::Typo]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "title": "The Lord of the Rings" },
+            { "id": 1, "title": "Lord of the Flies" },
+            { "id": 2, "title": "Lords of Dogtown" },
+            { "id": 3, "title": "The Hobbit" },
+            { "id": 4, "title": "The Rings of Power" },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn suggested_query() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.query("Lorrd of the rimgs");
+    let SearchResult { suggested_query, .. } = s.execute().unwrap();
+    insta::assert_debug_snapshot!(suggested_query, @r###"
+    Some(
+        "lord of the rings",
+    )
+    "###);
+
+    // the last word of the query is corrected as a prefix
+    let mut s = Search::new(&txn, &index);
+    s.query("the hobbot");
+    let SearchResult { suggested_query, .. } = s.execute().unwrap();
+    insta::assert_debug_snapshot!(suggested_query, @r###"
+    Some(
+        "the hobbit",
+    )
+    "###);
+
+    let mut s = Search::new(&txn, &index);
+    s.query("\"lorrd of\" the rimgs");
+    let SearchResult { suggested_query, .. } = s.execute().unwrap();
+    insta::assert_debug_snapshot!(suggested_query, @r###"
+    Some(
+        "\"lorrd of\" the rings",
+    )
+    "###);
+
+    let mut s = Search::new(&txn, &index);
+    s.query("the lord of the rin");
+    let SearchResult { suggested_query, .. } = s.execute().unwrap();
+    insta::assert_debug_snapshot!(suggested_query, @"None");
+}
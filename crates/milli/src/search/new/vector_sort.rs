@@ -6,18 +6,27 @@ use roaring::RoaringBitmap;
 use super::ranking_rules::{RankingRule, RankingRuleOutput, RankingRuleQueryTrait};
 use super::VectorStoreStats;
 use crate::score_details::{self, ScoreDetails};
-use crate::vector::{ArroyWrapper, DistributionShift, Embedder};
+use crate::vector::scalar::ScalarQuantizedStore;
+use crate::vector::sparse::{SparseEmbedding, SparseVectorStore};
+use crate::vector::{ArroyWrapper, Distance, DistributionShift, Embedder};
 use crate::{DocumentId, Result, SearchContext, SearchLogger};
 
 pub struct VectorSort<Q: RankingRuleQueryTrait> {
     query: Option<Q>,
     target: Vec<f32>,
     vector_candidates: RoaringBitmap,
-    cached_sorted_docids: std::vec::IntoIter<(DocumentId, f32)>,
+    cached_sorted_docids: std::vec::IntoIter<(DocumentId, usize, f32)>,
     limit: usize,
     distribution_shift: Option<DistributionShift>,
     embedder_index: u8,
     quantized: bool,
+    distance: Distance,
+    /// Whether the documents are split into several chunks, each with its own vector.
+    chunked: bool,
+    /// Whether the target and the documents are sparse embeddings, searched in their inverted index.
+    sparse: bool,
+    scalar_quantized: bool,
+    full_precision_rescoring: bool,
 }
 
 impl<Q: RankingRuleQueryTrait> VectorSort<Q> {
@@ -35,6 +44,18 @@ impl<Q: RankingRuleQueryTrait> VectorSort<Q> {
             .embedder_category_id
             .get(ctx.txn, embedder_name)?
             .ok_or_else(|| crate::UserError::InvalidSearchEmbedder(embedder_name.to_owned()))?;
+        let config = ctx
+            .index
+            .embedding_configs(ctx.txn)?
+            .into_iter()
+            .find(|config| config.name == embedder_name);
+        let chunked = config.as_ref().is_some_and(|config| config.config.prompt.chunking.is_some())
+            && !embedder.is_sparse();
+        let scalar_quantized =
+            config.as_ref().is_some_and(|config| config.config.scalar_quantized());
+        let full_precision_rescoring =
+            config.as_ref().is_some_and(|config| config.config.full_precision_rescoring());
+        let distance = config.map(|config| config.config.distance()).unwrap_or_default();
 
         Ok(Self {
             query: None,
@@ -45,6 +66,11 @@ impl<Q: RankingRuleQueryTrait> VectorSort<Q> {
             distribution_shift: embedder.distribution(),
             embedder_index,
             quantized,
+            distance,
+            chunked,
+            sparse: embedder.is_sparse(),
+            scalar_quantized,
+            full_precision_rescoring,
         })
     }
 
@@ -56,8 +82,31 @@ impl<Q: RankingRuleQueryTrait> VectorSort<Q> {
         let target = &self.target;
 
         let before = Instant::now();
-        let reader = ArroyWrapper::new(ctx.index.vector_arroy, self.embedder_index, self.quantized);
-        let results = reader.nns_by_vector(ctx.txn, target, self.limit, Some(vector_candidates))?;
+        let results = if self.sparse {
+            // the chunks of a document are merged into a single sparse embedding
+            let store = SparseVectorStore::new(ctx.index, self.embedder_index);
+            let target = SparseEmbedding::from_packed(target);
+            store
+                .nns_by_vector(ctx.txn, &target, self.limit, Some(vector_candidates))?
+                .into_iter()
+                .map(|(docid, distance)| (docid, 0, distance))
+                .collect()
+        } else {
+            let reader = ArroyWrapper::new(
+                ctx.index.vector_arroy,
+                self.embedder_index,
+                self.quantized,
+                self.distance,
+            )
+            .with_scalar_quantization(
+                self.scalar_quantized
+                    .then(|| ScalarQuantizedStore::new(ctx.index, self.embedder_index)),
+            )
+            .with_full_precision_rescoring(self.full_precision_rescoring);
+            // a document is returned at the position of its best matching chunk,
+            // its other chunks are skipped as the document is then no longer in the universe
+            reader.nns_by_vector_with_index(ctx.txn, target, self.limit, Some(vector_candidates))?
+        };
         self.cached_sorted_docids = results.into_iter();
         *ctx.vector_store_stats.get_or_insert_default() += VectorStoreStats {
             total_time: before.elapsed(),
@@ -105,13 +154,21 @@ impl<'ctx, Q: RankingRuleQueryTrait> RankingRule<'ctx, Q> for VectorSort<Q> {
             return Ok(Some(RankingRuleOutput {
                 query,
                 candidates: universe.clone(),
-                score: ScoreDetails::Vector(score_details::Vector { similarity: None }),
+                score: ScoreDetails::Vector(score_details::Vector {
+                    similarity: None,
+                    matched_chunk: None,
+                }),
             }));
         }
 
-        for (docid, distance) in self.cached_sorted_docids.by_ref() {
+        for (docid, chunk, distance) in self.cached_sorted_docids.by_ref() {
             if vector_candidates.contains(docid) {
-                let score = 1.0 - distance;
+                // the sparse embeddings are always compared by their cosine distance
+                let score = if self.sparse {
+                    Distance::Cosine.similarity(distance)
+                } else {
+                    self.distance.similarity(distance)
+                };
                 let score = self
                     .distribution_shift
                     .map(|distribution| distribution.shift(score))
@@ -119,7 +176,10 @@ impl<'ctx, Q: RankingRuleQueryTrait> RankingRule<'ctx, Q> for VectorSort<Q> {
                 return Ok(Some(RankingRuleOutput {
                     query,
                     candidates: RoaringBitmap::from_iter([docid]),
-                    score: ScoreDetails::Vector(score_details::Vector { similarity: Some(score) }),
+                    score: ScoreDetails::Vector(score_details::Vector {
+                        similarity: Some(score),
+                        matched_chunk: self.chunked.then_some(chunk),
+                    }),
                 }));
             }
         }
@@ -135,7 +195,10 @@ impl<'ctx, Q: RankingRuleQueryTrait> RankingRule<'ctx, Q> for VectorSort<Q> {
             return Ok(Some(RankingRuleOutput {
                 query,
                 candidates: universe.clone(),
-                score: ScoreDetails::Vector(score_details::Vector { similarity: None }),
+                score: ScoreDetails::Vector(score_details::Vector {
+                    similarity: None,
+                    matched_chunk: None,
+                }),
             }));
         }
 
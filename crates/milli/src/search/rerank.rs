@@ -0,0 +1,147 @@
+use std::cell::RefCell;
+use std::sync::RwLock;
+
+use bumpalo::Bump;
+
+use crate::score_details::{self, ScoreDetails};
+use crate::update::new::document::DocumentFromDb;
+use crate::vector::rerank::Reranker;
+use crate::{GlobalFieldsIdsMap, Result, Search, SearchResult, UserError};
+
+impl<'a> Search<'a> {
+    /// Executes the search without its reranker, then reorders its `topK` best results with
+    /// the scores returned by the reranker.
+    ///
+    /// At least `topK` results are retrieved whatever the requested page, so that the pages
+    /// display the best of the reranked results.
+    #[tracing::instrument(level = "trace", skip_all, target = "search::rerank")]
+    pub(super) fn execute_reranked<T>(
+        &self,
+        reranker_name: &str,
+        execute: impl FnOnce(&Search<'a>) -> Result<(SearchResult, T)>,
+    ) -> Result<(SearchResult, T)> {
+        let rerankers = self.index.rerankers(self.rtxn)?.unwrap_or_default();
+        let settings = rerankers
+            .get(reranker_name)
+            .ok_or_else(|| UserError::InvalidSearchReranker(reranker_name.to_owned()))?;
+        let reranker = Reranker::new(reranker_name, settings).map_err(UserError::from)?;
+
+        let search = Search {
+            query: self.query.clone(),
+            filter: self.filter.clone(),
+            offset: 0,
+            limit: (self.offset + self.limit).max(reranker.top_k()),
+            sort_criteria: self.sort_criteria.clone(),
+            boosts: self.boosts.clone(),
+            distinct: self.distinct.clone(),
+            searchable_attributes: self.searchable_attributes,
+            geo_param: self.geo_param,
+            terms_matching_strategy: self.terms_matching_strategy,
+            scoring_strategy: self.scoring_strategy,
+            words_limit: self.words_limit,
+            exhaustive_number_hits: self.exhaustive_number_hits,
+            rtxn: self.rtxn,
+            index: self.index,
+            semantic: self.semantic.clone(),
+            time_budget: self.time_budget.clone(),
+            ranking_score_threshold: self.ranking_score_threshold,
+            locales: self.locales.clone(),
+            reranker: None,
+            spelling_corrections: self.spelling_corrections,
+        };
+        let (mut result, extra) = execute(&search)?;
+
+        // there is nothing to rerank the documents against for a placeholder search
+        if let Some(query) = self.query.as_deref().filter(|query| !query.trim().is_empty()) {
+            let count = result.documents_ids.len().min(reranker.top_k());
+            self.rerank(&reranker, query, &mut result, count)?;
+        }
+
+        result.documents_ids =
+            result.documents_ids.into_iter().skip(self.offset).take(self.limit).collect();
+        result.document_scores =
+            result.document_scores.into_iter().skip(self.offset).take(self.limit).collect();
+
+        Ok((result, extra))
+    }
+
+    /// Reorders the first `count` documents of the results by decreasing reranker score.
+    ///
+    /// The following documents keep their order, but their scores are lowered below the scores
+    /// of the reranked documents.
+    ///
+    /// The results are left untouched if the reranker fails, as when the query can't be embedded
+    /// in a hybrid search.
+    fn rerank(
+        &self,
+        reranker: &Reranker,
+        query: &str,
+        result: &mut SearchResult,
+        count: usize,
+    ) -> Result<()> {
+        if count == 0 {
+            return Ok(());
+        }
+
+        let docids = &result.documents_ids[..count];
+        let db_fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
+        let fields_ids_map = RwLock::new(self.index.fields_ids_map_with_metadata(self.rtxn)?);
+        let global_fields_ids_map = RefCell::new(GlobalFieldsIdsMap::new(&fields_ids_map));
+        let external_ids = self.index.external_id_of(self.rtxn, docids.iter().copied())?;
+        let doc_alloc = Bump::new();
+
+        let mut documents = Vec::with_capacity(count);
+        for (&docid, external_id) in docids.iter().zip(external_ids) {
+            let external_id = external_id?;
+            let document = DocumentFromDb::new(docid, self.rtxn, self.index, &db_fields_ids_map)?
+                .ok_or(UserError::UnknownInternalDocumentId { document_id: docid })?;
+            let rendered = reranker
+                .document_template()
+                .render_document(&external_id, document, &global_fields_ids_map, &doc_alloc)
+                .map_err(UserError::from)?;
+            documents.push(rendered);
+        }
+
+        let span = tracing::trace_span!(target: "search::rerank", "rerank");
+        let _entered = span.enter();
+
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(3);
+        let scores = match reranker.rerank(query, &documents, Some(deadline)) {
+            Ok(scores) => scores,
+            Err(error) => {
+                tracing::error!(error=%error, "Reranking failed");
+                return Ok(());
+            }
+        };
+
+        let other_docids = result.documents_ids.split_off(count);
+        let other_scores =
+            result.document_scores.split_off(count.min(result.document_scores.len()));
+
+        let mut reranked: Vec<_> = std::mem::take(&mut result.documents_ids)
+            .into_iter()
+            .zip(std::mem::take(&mut result.document_scores))
+            .zip(scores)
+            .collect();
+        // the stable sort keeps the order of the search for the documents with the same score
+        reranked.sort_by(|(_, left), (_, right)| right.total_cmp(left));
+
+        for ((docid, mut details), score) in reranked {
+            let rerank = score_details::Rerank::reranked(reranker.name().to_owned(), score);
+            details.insert(0, ScoreDetails::Rerank(rerank));
+            result.documents_ids.push(docid);
+            result.document_scores.push(details);
+        }
+        // the documents that weren't reranked are scored below the reranked ones
+        for mut details in other_scores {
+            let global_score = ScoreDetails::global_score(details.iter());
+            let rerank =
+                score_details::Rerank::not_reranked(reranker.name().to_owned(), global_score);
+            details.insert(0, ScoreDetails::Rerank(rerank));
+            result.document_scores.push(details);
+        }
+        result.documents_ids.extend(other_docids);
+
+        Ok(())
+    }
+}
@@ -3,7 +3,9 @@ use std::sync::Arc;
 use roaring::RoaringBitmap;
 
 use crate::score_details::{self, ScoreDetails};
-use crate::vector::{ArroyWrapper, Embedder};
+use crate::vector::scalar::ScalarQuantizedStore;
+use crate::vector::sparse::SparseVectorStore;
+use crate::vector::{ArroyWrapper, Distance, Embedder};
 use crate::{filtered_universe, DocumentId, Filter, Index, Result, SearchResult};
 
 pub struct Similar<'a> {
@@ -69,13 +71,43 @@ impl<'a> Similar<'a> {
                 || crate::UserError::InvalidSimilarEmbedder(self.embedder_name.to_owned()),
             )?;
 
-        let reader = ArroyWrapper::new(self.index.vector_arroy, embedder_index, self.quantized);
-        let results = reader.nns_by_item(
-            self.rtxn,
-            self.id,
-            self.limit + self.offset + 1,
-            Some(&universe),
-        )?;
+        let config = self
+            .index
+            .embedding_configs(self.rtxn)?
+            .into_iter()
+            .find(|config| config.name == self.embedder_name)
+            .map(|config| config.config)
+            .unwrap_or_default();
+        // the sparse embeddings are always compared by their cosine distance
+        let embedder_distance =
+            if self.embedder.is_sparse() { Distance::Cosine } else { config.distance() };
+
+        let results = if self.embedder.is_sparse() {
+            let store = SparseVectorStore::new(self.index, embedder_index);
+            match store.item_vector(self.rtxn, self.id)? {
+                Some(target) => store.nns_by_vector(
+                    self.rtxn,
+                    &target,
+                    self.limit + self.offset + 1,
+                    Some(&universe),
+                )?,
+                None => Vec::new(),
+            }
+        } else {
+            let reader = ArroyWrapper::new(
+                self.index.vector_arroy,
+                embedder_index,
+                self.quantized,
+                embedder_distance,
+            )
+            .with_scalar_quantization(
+                config
+                    .scalar_quantized()
+                    .then(|| ScalarQuantizedStore::new(self.index, embedder_index)),
+            )
+            .with_full_precision_rescoring(config.full_precision_rescoring());
+            reader.nns_by_item(self.rtxn, self.id, self.limit + self.offset + 1, Some(&universe))?
+        };
 
         let mut documents_ids = Vec::with_capacity(self.limit);
         let mut document_scores = Vec::with_capacity(self.limit);
@@ -94,15 +126,17 @@ impl<'a> Similar<'a> {
             // take **after** filter and skip so that we get exactly limit elements if available
             .take(self.limit)
         {
-            let score = 1.0 - distance;
+            let score = embedder_distance.similarity(distance);
             let score = self
                 .embedder
                 .distribution()
                 .map(|distribution| distribution.shift(score))
                 .unwrap_or(score);
 
-            let score_details =
-                vec![ScoreDetails::Vector(score_details::Vector { similarity: Some(score) })];
+            let score_details = vec![ScoreDetails::Vector(score_details::Vector {
+                similarity: Some(score),
+                matched_chunk: None,
+            })];
 
             let score = ScoreDetails::global_score(score_details.iter());
 
@@ -127,6 +161,7 @@ impl<'a> Similar<'a> {
             document_scores,
             degraded: false,
             used_negative_operator: false,
+            suggested_query: None,
         })
     }
 }
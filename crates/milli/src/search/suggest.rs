@@ -0,0 +1,194 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use charabia::TokenizerBuilder;
+use heed::types::Bytes;
+use heed::BytesDecode;
+use roaring::RoaringBitmap;
+
+use crate::{
+    filtered_universe, CboRoaringBitmapCodec, CboRoaringBitmapLenCodec, Filter, Index, Result,
+};
+
+/// The default number of suggestions returned.
+const DEFAULT_SUGGESTION_LIMIT: usize = 10;
+
+/// A completed query along with the number of documents it matches.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    pub query: String,
+    pub count: u64,
+}
+
+/// Completes a query with the words of the index.
+///
+/// The last word of the query, if not followed by a separator, is completed with the most
+/// frequent words of the index it prefixes among the documents matching the filter and
+/// the other words.
+/// The completions are then followed by the word that most often comes right after them,
+/// based on the word pair proximity database, to suggest multi-word queries.
+pub struct Suggest<'a> {
+    query: String,
+    filter: Option<Filter<'a>>,
+    limit: usize,
+    rtxn: &'a heed::RoTxn<'a>,
+    index: &'a Index,
+}
+
+impl<'a> Suggest<'a> {
+    pub fn new(query: impl Into<String>, index: &'a Index, rtxn: &'a heed::RoTxn<'a>) -> Self {
+        Self { query: query.into(), filter: None, limit: DEFAULT_SUGGESTION_LIMIT, rtxn, index }
+    }
+
+    pub fn filter(&mut self, filter: Filter<'a>) -> &mut Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    pub fn limit(&mut self, limit: usize) -> &mut Self {
+        self.limit = limit;
+        self
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::suggest")]
+    pub fn execute(&self) -> Result<Vec<Suggestion>> {
+        let (words, prefix) = self.tokenize()?;
+        if self.limit == 0 || (words.is_empty() && prefix.is_none()) {
+            return Ok(Vec::new());
+        }
+
+        let mut candidates = filtered_universe(self.index, self.rtxn, &self.filter)?;
+        for word in &words {
+            candidates &= self.index.word_docids.get(self.rtxn, word)?.unwrap_or_default();
+        }
+        if candidates.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut suggestions = Vec::new();
+        match prefix {
+            Some(prefix) => {
+                for (word, count) in self.completions(&prefix, &candidates)? {
+                    let mut query = words.clone();
+                    query.push(word.clone());
+                    suggestions.push(Suggestion { query: query.join(" "), count });
+                    if let Some((next, count)) =
+                        self.next_words(&word, &candidates)?.into_iter().next()
+                    {
+                        query.push(next);
+                        suggestions.push(Suggestion { query: query.join(" "), count });
+                    }
+                }
+            }
+            None => {
+                // the last word is complete, we suggest the words following it
+                let last = words.last().unwrap();
+                for (next, count) in self.next_words(last, &candidates)? {
+                    let mut query = words.clone();
+                    query.push(next);
+                    suggestions.push(Suggestion { query: query.join(" "), count });
+                }
+            }
+        }
+
+        // the stable sort keeps a completion before its longer version at equal count
+        suggestions.sort_by_key(|suggestion| Reverse(suggestion.count));
+        suggestions.truncate(self.limit);
+        Ok(suggestions)
+    }
+
+    /// Returns the normalized complete words of the query and its last word
+    /// if it must be completed, i.e. if it isn't followed by a separator.
+    fn tokenize(&self) -> Result<(Vec<String>, Option<String>)> {
+        let mut tokbuilder = TokenizerBuilder::new();
+
+        let separators = self.index.allowed_separators(self.rtxn)?;
+        let separators: Option<Vec<_>> =
+            separators.as_ref().map(|x| x.iter().map(String::as_str).collect());
+        if let Some(ref separators) = separators {
+            tokbuilder.separators(separators);
+        }
+
+        let dictionary = self.index.tokenizer_dictionary(self.rtxn)?;
+        let dictionary: Option<Vec<_>> =
+            dictionary.as_ref().map(|x| x.iter().map(String::as_str).collect());
+        if let Some(ref dictionary) = dictionary {
+            tokbuilder.words_dict(dictionary);
+        }
+
+        let tokenizer = tokbuilder.build();
+        let tokens: Vec<_> = tokenizer.tokenize(&self.query).collect();
+
+        let mut words: Vec<_> = tokens
+            .iter()
+            .filter(|token| token.is_word() && !token.lemma().is_empty())
+            .map(|token| token.lemma().to_string())
+            .collect();
+        let prefix = match tokens.last() {
+            Some(token) if token.is_word() && !token.lemma().is_empty() => words.pop(),
+            _ => None,
+        };
+
+        Ok((words, prefix))
+    }
+
+    /// Returns the most frequent words starting with the prefix among the candidates,
+    /// with the number of candidates containing them.
+    ///
+    /// All the words starting with the prefix are ranked, only the best ones being kept in a
+    /// min-heap. The equally frequent words are ranked in lexicographic order.
+    fn completions(&self, prefix: &str, candidates: &RoaringBitmap) -> Result<Vec<(String, u64)>> {
+        let mut completions = BinaryHeap::new();
+        let words = self.index.word_docids.remap_data_type::<Bytes>();
+        for result in words.prefix_iter(self.rtxn, prefix)? {
+            let (word, bytes) = result?;
+
+            // the number of documents containing the word bounds the number of candidates
+            // containing it, the bitmap is only read if the word can enter the heap
+            match completions.peek() {
+                Some(Reverse((min_count, _))) if completions.len() == self.limit => {
+                    let len = CboRoaringBitmapLenCodec::bytes_decode(bytes)
+                        .map_err(heed::Error::Decoding)?;
+                    if len <= *min_count {
+                        continue;
+                    }
+                }
+                _ => (),
+            }
+
+            let count =
+                CboRoaringBitmapCodec::intersection_with_serialized(bytes, candidates)?.len();
+            if count != 0 {
+                completions.push(Reverse((count, Reverse(word.to_string()))));
+                if completions.len() > self.limit {
+                    completions.pop();
+                }
+            }
+        }
+
+        Ok(completions
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((count, Reverse(word)))| (word, count))
+            .collect())
+    }
+
+    /// Returns the most frequent words directly following the word among the candidates,
+    /// with the number of candidates containing both words next to each other.
+    fn next_words(&self, word: &str, candidates: &RoaringBitmap) -> Result<Vec<(String, u64)>> {
+        let mut next_words = Vec::new();
+        for result in
+            self.index.word_pair_proximity_docids.prefix_iter(self.rtxn, &(1, word, ""))?
+        {
+            let ((_, _, right), docids) = result?;
+            let count = docids.intersection_len(candidates);
+            if count != 0 {
+                next_words.push((right.to_string(), count));
+            }
+        }
+
+        next_words.sort_by_key(|(_, count)| Reverse(*count));
+        next_words.truncate(self.limit);
+        Ok(next_words)
+    }
+}
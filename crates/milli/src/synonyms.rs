@@ -0,0 +1,158 @@
+//! The synonyms of an index.
+//!
+//! The synonyms of a word are either given as a plain list, the word then matching its synonyms
+//! but not the other way around, or as a rule telling how the words of the rule relate to each
+//! other and how the documents matching a synonym must be ranked.
+
+use deserr::{DeserializeError, Deserr, ValuePointerRef};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// The synonyms of a word.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum Synonyms {
+    /// The words matched when searching for the word, e.g. `"tv": ["television"]`.
+    Words(Vec<String>),
+    Rule(SynonymRule),
+}
+
+impl From<Vec<String>> for Synonyms {
+    fn from(synonyms: Vec<String>) -> Self {
+        Synonyms::Words(synonyms)
+    }
+}
+
+impl<E: DeserializeError> Deserr<E> for Synonyms {
+    fn deserialize_from_value<V: deserr::IntoValue>(
+        value: deserr::Value<V>,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        if value.kind() == deserr::ValueKind::Map {
+            Ok(Self::Rule(SynonymRule::deserialize_from_value(value, location)?))
+        } else {
+            Ok(Self::Words(Vec::<String>::deserialize_from_value(value, location)?))
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Deserr, ToSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct SynonymRule {
+    /// The synonyms of the word.
+    pub synonyms: Vec<String>,
+    /// How the word and its synonyms match each other.
+    #[serde(default)]
+    #[deserr(default)]
+    pub kind: SynonymKind,
+    /// Ranks the documents matching a synonym like the documents matching the word with one typo,
+    /// after the documents matching the word itself.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    #[deserr(default)]
+    pub rank_as_typo: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Deserr, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[deserr(rename_all = camelCase)]
+pub enum SynonymKind {
+    /// Searching for the word matches its synonyms, but not the other way around.
+    #[default]
+    OneWay,
+    /// The word and its synonyms all match each other.
+    MultiWay,
+    /// The word is a placeholder written in the documents, e.g. `<street>`, matched when
+    /// searching for any of its synonyms.
+    Placeholder,
+    /// Searching for the word matches its synonyms, always ranked like typos.
+    AlternativeCorrection,
+}
+
+impl Synonyms {
+    /// Returns the words of the query along with the synonyms they must match.
+    pub fn derivations<'a>(&'a self, word: &'a str) -> Vec<(&'a str, &'a str)> {
+        let (synonyms, kind) = match self {
+            Synonyms::Words(synonyms) => (synonyms, SynonymKind::OneWay),
+            Synonyms::Rule(SynonymRule { synonyms, kind, rank_as_typo: _ }) => (synonyms, *kind),
+        };
+
+        match kind {
+            SynonymKind::OneWay | SynonymKind::AlternativeCorrection => {
+                synonyms.iter().map(|synonym| (word, synonym.as_str())).collect()
+            }
+            SynonymKind::Placeholder => {
+                synonyms.iter().map(|synonym| (synonym.as_str(), word)).collect()
+            }
+            SynonymKind::MultiWay => {
+                let words: Vec<_> =
+                    std::iter::once(word).chain(synonyms.iter().map(String::as_str)).collect();
+                words
+                    .iter()
+                    .flat_map(|left| {
+                        words
+                            .iter()
+                            .filter(move |right| left != *right)
+                            .map(|right| (*left, *right))
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// Returns `true` if the word is a placeholder written in the documents.
+    pub fn is_placeholder(&self) -> bool {
+        matches!(self, Synonyms::Rule(SynonymRule { kind: SynonymKind::Placeholder, .. }))
+    }
+
+    /// Returns `true` if the documents matching the synonyms must be ranked like typos.
+    pub fn rank_as_typo(&self) -> bool {
+        match self {
+            Synonyms::Words(_) => false,
+            Synonyms::Rule(SynonymRule { kind: SynonymKind::AlternativeCorrection, .. }) => true,
+            Synonyms::Rule(SynonymRule { rank_as_typo, .. }) => *rank_as_typo,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(kind: SynonymKind) -> Synonyms {
+        Synonyms::Rule(SynonymRule {
+            synonyms: vec!["television".to_string(), "telly".to_string()],
+            kind,
+            rank_as_typo: false,
+        })
+    }
+
+    #[test]
+    fn derivations() {
+        let words = Synonyms::from(vec!["television".to_string()]);
+        assert_eq!(words.derivations("tv"), vec![("tv", "television")]);
+        assert!(!words.rank_as_typo());
+
+        assert_eq!(
+            rule(SynonymKind::OneWay).derivations("tv"),
+            vec![("tv", "television"), ("tv", "telly")]
+        );
+        assert_eq!(
+            rule(SynonymKind::Placeholder).derivations("tv"),
+            vec![("television", "tv"), ("telly", "tv")]
+        );
+        assert_eq!(
+            rule(SynonymKind::MultiWay).derivations("tv"),
+            vec![
+                ("tv", "television"),
+                ("tv", "telly"),
+                ("television", "tv"),
+                ("television", "telly"),
+                ("telly", "tv"),
+                ("telly", "television"),
+            ]
+        );
+        assert!(rule(SynonymKind::AlternativeCorrection).rank_as_typo());
+        assert!(!rule(SynonymKind::MultiWay).rank_as_typo());
+    }
+}
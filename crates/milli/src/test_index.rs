@@ -8,7 +8,7 @@ use maplit::btreemap;
 use memmap2::Mmap;
 use tempfile::TempDir;
 
-use crate::constants::RESERVED_GEO_FIELD_NAME;
+use crate::constants::{RESERVED_GEOJSON_FIELD_NAME, RESERVED_GEO_FIELD_NAME};
 use crate::error::{Error, InternalError};
 use crate::index::{DEFAULT_MIN_WORD_LEN_ONE_TYPO, DEFAULT_MIN_WORD_LEN_TWO_TYPOS};
 use crate::progress::Progress;
@@ -557,6 +557,336 @@ fn test_basic_geo_bounding_box() {
     );
 }
 
+#[test]
+fn test_basic_geo_polygon() {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|settings| {
+            settings.set_filterable_fields(vec![FilterableAttributesRule::Field(
+                RESERVED_GEO_FIELD_NAME.to_string(),
+            )]);
+        })
+        .unwrap();
+    index
+        .add_documents(documents!([
+            { "id": 0, RESERVED_GEO_FIELD_NAME: { "lat": "0", "lng": "0" } },
+            { "id": 1, RESERVED_GEO_FIELD_NAME: { "lat": 0, "lng": "-175" } },
+            { "id": 2, RESERVED_GEO_FIELD_NAME: { "lat": "0", "lng": 175 } },
+            { "id": 3, RESERVED_GEO_FIELD_NAME: { "lat": 85, "lng": 0 } },
+            { "id": 4, RESERVED_GEO_FIELD_NAME: { "lat": "-85", "lng": "0" } },
+        ]))
+        .unwrap();
+
+    let rtxn = index.read_txn().unwrap();
+    let mut search = index.search(&rtxn);
+
+    // match a document in the middle of a triangle
+    let search_result = search
+        .filter(Filter::from_str("_geoPolygon([10, -10], [10, 10], [-10, 0])").unwrap().unwrap())
+        .execute()
+        .unwrap();
+    insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[0]>");
+
+    // the polygon can be explicitly closed
+    let search_result = search
+        .filter(
+            Filter::from_str("_geoPolygon([10, -10], [10, 10], [-10, 0], [10, -10])")
+                .unwrap()
+                .unwrap(),
+        )
+        .execute()
+        .unwrap();
+    insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[0]>");
+
+    // a document outside of a concave polygon but inside of its bounding box
+    let search_result = search
+        .filter(
+            Filter::from_str("_geoPolygon([10, -10], [10, 10], [-10, 10], [5, 0], [-10, -10])")
+                .unwrap()
+                .unwrap(),
+        )
+        .execute()
+        .unwrap();
+    insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[]>");
+
+    // go on the edge of the latitude
+    let search_result = search
+        .filter(
+            Filter::from_str("_geoPolygon([80, -10], [90, -10], [90, 10], [80, 10])")
+                .unwrap()
+                .unwrap(),
+        )
+        .execute()
+        .unwrap();
+    insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[3]>");
+
+    // select everything
+    let search_result = search
+        .filter(
+            Filter::from_str("_geoPolygon([90, -180], [90, 180], [-90, 180], [-90, -180])")
+                .unwrap()
+                .unwrap(),
+        )
+        .execute()
+        .unwrap();
+    insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[0, 1, 2, 3, 4]>");
+
+    // the requests that don't make sense
+
+    // the same point repeated doesn't make a polygon
+    let error = search
+        .filter(Filter::from_str("_geoPolygon([0, 0], [0, 0], [10, 10])").unwrap().unwrap())
+        .execute()
+        .unwrap_err();
+    insta::assert_snapshot!(
+        error,
+        @r###"
+        A polygon must be made of at least three distinct points but only `2` were provided.
+        14:15 _geoPolygon([0, 0], [0, 0], [10, 10])
+        "###
+    );
+
+    // aligned points don't make a polygon either
+    let error = search
+        .filter(Filter::from_str("_geoPolygon([0, 0], [5, 5], [10, 10])").unwrap().unwrap())
+        .execute()
+        .unwrap_err();
+    insta::assert_snapshot!(
+        error,
+        @r###"
+        The points of the polygon are all aligned and don't enclose any area.
+        14:15 _geoPolygon([0, 0], [5, 5], [10, 10])
+        "###
+    );
+}
+
+#[test]
+fn test_basic_geojson() {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|settings| {
+            settings.set_filterable_fields(vec![FilterableAttributesRule::Field(
+                RESERVED_GEO_FIELD_NAME.to_string(),
+            )]);
+        })
+        .unwrap();
+    index
+        .add_documents(documents!([
+            { "id": 0, RESERVED_GEO_FIELD_NAME: { "lat": 0, "lng": 0 } },
+            { "id": 1, RESERVED_GEOJSON_FIELD_NAME: { "type": "Point", "coordinates": [10, 20] } },
+            { "id": 2, RESERVED_GEOJSON_FIELD_NAME: { "type": "Polygon", "coordinates": [[[30, 30], [40, 30], [40, 40], [30, 40], [30, 30]]] } },
+            { "id": 3, RESERVED_GEOJSON_FIELD_NAME: { "type": "MultiPoint", "coordinates": [[-50, -50], [50, -50]] } },
+            { "id": 4, RESERVED_GEOJSON_FIELD_NAME: { "type": "MultiPolygon", "coordinates": [
+                [[[-10, 60], [10, 60], [10, 80], [-10, 80], [-10, 60]], [[-5, 65], [5, 65], [5, 75], [-5, 75], [-5, 65]]],
+            ] } },
+            { "id": 5, RESERVED_GEOJSON_FIELD_NAME: null },
+        ]))
+        .unwrap();
+
+    let rtxn = index.read_txn().unwrap();
+    let mut search = index.search(&rtxn);
+
+    // the center of the circle is in the polygon
+    let search_result = search
+        .filter(Filter::from_str("_geoRadius(35, 35, 1000)").unwrap().unwrap())
+        .execute()
+        .unwrap();
+    insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[2]>");
+
+    // the circle only crosses an edge of the polygon
+    let search_result = search
+        .filter(Filter::from_str("_geoRadius(35, 40.5, 100000)").unwrap().unwrap())
+        .execute()
+        .unwrap();
+    insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[2]>");
+
+    // the circle is in the hole of the polygon
+    let search_result = search
+        .filter(Filter::from_str("_geoRadius(70, 0, 1000)").unwrap().unwrap())
+        .execute()
+        .unwrap();
+    insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[]>");
+
+    // a single point
+    let search_result = search
+        .filter(Filter::from_str("_geoRadius(20, 10, 10)").unwrap().unwrap())
+        .execute()
+        .unwrap();
+    insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[1]>");
+
+    // the bounding box is entirely contained in the polygon
+    let search_result = search
+        .filter(Filter::from_str("_geoBoundingBox([36, 36], [34, 34])").unwrap().unwrap())
+        .execute()
+        .unwrap();
+    insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[2]>");
+
+    // one of the points of a multi point
+    let search_result = search
+        .filter(Filter::from_str("_geoBoundingBox([-40, 60], [-60, 40])").unwrap().unwrap())
+        .execute()
+        .unwrap();
+    insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[3]>");
+
+    // a bounding box wrapping around the earth
+    let search_result = search
+        .filter(Filter::from_str("_geoBoundingBox([25, -170], [15, 5])").unwrap().unwrap())
+        .execute()
+        .unwrap();
+    insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[1]>");
+
+    // everything
+    let search_result = search
+        .filter(Filter::from_str("_geoBoundingBox([90, 180], [-90, -180])").unwrap().unwrap())
+        .execute()
+        .unwrap();
+    insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[0, 1, 2, 3, 4]>");
+
+    // the polygon surrounds the hole and overlaps the polygon
+    let search_result = search
+        .filter(
+            Filter::from_str("_geoPolygon([64, -6], [64, 6], [76, 6], [76, -6])").unwrap().unwrap(),
+        )
+        .execute()
+        .unwrap();
+    insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[4]>");
+
+    // the polygon is in the hole
+    let search_result = search
+        .filter(
+            Filter::from_str("_geoPolygon([66, -4], [66, 4], [74, 4], [74, -4])").unwrap().unwrap(),
+        )
+        .execute()
+        .unwrap();
+    insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[]>");
+
+    // the documents can be updated and deleted
+    index
+        .add_documents(documents!([
+            { "id": 2, RESERVED_GEOJSON_FIELD_NAME: { "type": "Point", "coordinates": [35, 35] } },
+        ]))
+        .unwrap();
+    index.delete_documents(vec!["4".to_string()]);
+
+    let rtxn = index.read_txn().unwrap();
+    let mut search = index.search(&rtxn);
+    let search_result = search
+        .filter(Filter::from_str("_geoRadius(35, 40.5, 100000)").unwrap().unwrap())
+        .execute()
+        .unwrap();
+    insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[]>");
+    let search_result = search
+        .filter(Filter::from_str("_geoBoundingBox([90, 180], [-90, -180])").unwrap().unwrap())
+        .execute()
+        .unwrap();
+    insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[0, 1, 2, 3]>");
+}
+
+#[test]
+fn invalid_geojson_field() {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|settings| {
+            settings.set_filterable_fields(vec![FilterableAttributesRule::Field(
+                RESERVED_GEO_FIELD_NAME.to_string(),
+            )]);
+        })
+        .unwrap();
+
+    let err = index
+        .add_documents(documents!({ "id": 0, RESERVED_GEOJSON_FIELD_NAME: [1, 2] }))
+        .unwrap_err();
+    insta::assert_snapshot!(err, @r###"The `_geojson` field in the document with the id: `"0"` is not an object. Was expecting a GeoJSON geometry object with the `type` and `coordinates` fields but instead got `[1,2]`."###);
+
+    let err = index
+        .add_documents(documents!({ "id": 0, RESERVED_GEOJSON_FIELD_NAME: { "type": "LineString", "coordinates": [[1, 2], [3, 4]] } }))
+        .unwrap_err();
+    insta::assert_snapshot!(err, @r###"The `_geojson` field in the document with the id: `"0"` has an unsupported type. Was expecting one of `Point`, `MultiPoint`, `Polygon` or `MultiPolygon` but instead got `"LineString"`."###);
+
+    let err = index
+        .add_documents(documents!({ "id": 0, RESERVED_GEOJSON_FIELD_NAME: { "type": "Point", "coordinates": [12, 95] } }))
+        .unwrap_err();
+    insta::assert_snapshot!(err, @r###"The `_geojson` field in the document with the id: `"0"` contains an invalid position. Was expecting a `[longitude, latitude]` array with a longitude between -180 and 180 and a latitude between -90 and 90 but instead got `[12,95]`."###);
+
+    let err = index
+        .add_documents(documents!({ "id": 0, RESERVED_GEOJSON_FIELD_NAME: { "type": "Polygon", "coordinates": [[[0, 0], [1, 0], [1, 1], [0, 1]]] } }))
+        .unwrap_err();
+    insta::assert_snapshot!(err, @r###"Could not parse the coordinates of the `_geojson` Polygon in the document with the id: `"0"`. Was expecting an array of closed rings made of at least four `[longitude, latitude]` positions but instead got `[[[0,0],[1,0],[1,1],[0,1]]]`."###);
+}
+
+#[test]
+fn test_many_geo_points() {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|settings| {
+            settings.set_filterable_fields(vec![FilterableAttributesRule::Field(
+                RESERVED_GEO_FIELD_NAME.to_string(),
+            )]);
+        })
+        .unwrap();
+    index
+        .add_documents(documents!([
+            { "id": 0, RESERVED_GEO_FIELD_NAME: [{ "lat": 0, "lng": 0 }, { "lat": 10, "lng": 10 }] },
+            { "id": 1, RESERVED_GEO_FIELD_NAME: [{ "lat": 0, "lng": 20 }, { "lat": 20, "lng": 0 }] },
+            { "id": 2, RESERVED_GEO_FIELD_NAME: { "lat": 10, "lng": 10 } },
+            { "id": 3, RESERVED_GEO_FIELD_NAME: [] },
+        ]))
+        .unwrap();
+
+    let rtxn = index.read_txn().unwrap();
+    let mut search = index.search(&rtxn);
+
+    // any of the points can match
+    let search_result = search
+        .filter(Filter::from_str("_geoRadius(0, 0, 1000)").unwrap().unwrap())
+        .execute()
+        .unwrap();
+    insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[0]>");
+    let search_result = search
+        .filter(Filter::from_str("_geoRadius(10, 10, 1000)").unwrap().unwrap())
+        .execute()
+        .unwrap();
+    insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[0, 2]>");
+
+    // the latitude of a point and the longitude of another one must not be matched together
+    let search_result = search
+        .filter(Filter::from_str("_geoBoundingBox([21, 21], [19, 19])").unwrap().unwrap())
+        .execute()
+        .unwrap();
+    insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[]>");
+    let search_result = search
+        .filter(Filter::from_str("_geoBoundingBox([21, 1], [19, -1])").unwrap().unwrap())
+        .execute()
+        .unwrap();
+    insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[1]>");
+
+    // a point can be removed from a document
+    index
+        .add_documents(documents!([
+            { "id": 0, RESERVED_GEO_FIELD_NAME: [{ "lat": 0, "lng": 0 }] },
+        ]))
+        .unwrap();
+
+    let rtxn = index.read_txn().unwrap();
+    let mut search = index.search(&rtxn);
+    let search_result = search
+        .filter(Filter::from_str("_geoRadius(10, 10, 1000)").unwrap().unwrap())
+        .execute()
+        .unwrap();
+    insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[2]>");
+
+    let err = index
+        .add_documents(
+            documents!({ "id": 0, RESERVED_GEO_FIELD_NAME: [{ "lat": 0, "lng": 0 }, 12] }),
+        )
+        .unwrap_err();
+    insta::assert_snapshot!(err, @r###"The `_geo` field in the document with the id: `"0"` is not an object. Was expecting an object with the `_geo.lat` and `_geo.lng` fields but instead got `12`."###);
+}
+
 #[test]
 fn test_contains() {
     let index = TempIndex::new();
@@ -1118,6 +1448,7 @@ fn bug_3021_fourth() {
         mut documents_ids,
         degraded: _,
         used_negative_operator: _,
+        suggested_query: _,
     } = search.execute().unwrap();
     let primary_key_id = index.fields_ids_map(&rtxn).unwrap().id("primary_key").unwrap();
     documents_ids.sort_unstable();
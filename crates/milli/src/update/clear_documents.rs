@@ -30,6 +30,7 @@ impl<'t, 'i> ClearDocuments<'t, 'i> {
             exact_word_docids,
             word_prefix_docids,
             exact_word_prefix_docids,
+            stem_words,
             word_pair_proximity_docids,
             word_position_docids,
             word_fid_docids,
@@ -46,6 +47,10 @@ impl<'t, 'i> ClearDocuments<'t, 'i> {
             field_id_docid_facet_f64s,
             field_id_docid_facet_strings,
             vector_arroy,
+            sparse_vector_token_postings,
+            sparse_vectors,
+            scalar_quantized_vectors,
+            full_precision_vectors,
             embedder_category_id: _,
             documents,
         } = self.index;
@@ -61,6 +66,7 @@ impl<'t, 'i> ClearDocuments<'t, 'i> {
         self.index.put_documents_ids(self.wtxn, &empty_roaring)?;
         self.index.put_field_distribution(self.wtxn, &FieldDistribution::default())?;
         self.index.delete_geo_rtree(self.wtxn)?;
+        self.index.delete_geojson_rtree(self.wtxn)?;
         self.index.delete_geo_faceted_documents_ids(self.wtxn)?;
 
         // Remove all user-provided bits from the configs
@@ -76,6 +82,7 @@ impl<'t, 'i> ClearDocuments<'t, 'i> {
         exact_word_docids.clear(self.wtxn)?;
         word_prefix_docids.clear(self.wtxn)?;
         exact_word_prefix_docids.clear(self.wtxn)?;
+        stem_words.clear(self.wtxn)?;
         word_pair_proximity_docids.clear(self.wtxn)?;
         word_position_docids.clear(self.wtxn)?;
         word_fid_docids.clear(self.wtxn)?;
@@ -93,6 +100,10 @@ impl<'t, 'i> ClearDocuments<'t, 'i> {
         field_id_docid_facet_strings.clear(self.wtxn)?;
         // vector
         vector_arroy.clear(self.wtxn)?;
+        sparse_vector_token_postings.clear(self.wtxn)?;
+        sparse_vectors.clear(self.wtxn)?;
+        scalar_quantized_vectors.clear(self.wtxn)?;
+        full_precision_vectors.clear(self.wtxn)?;
 
         documents.clear(self.wtxn)?;
 
@@ -135,6 +146,7 @@ mod tests {
         assert!(index.documents_ids(&rtxn).unwrap().is_empty());
         assert!(index.field_distribution(&rtxn).unwrap().is_empty());
         assert!(index.geo_rtree(&rtxn).unwrap().is_none());
+        assert!(index.geojson_rtree(&rtxn).unwrap().is_none());
         assert!(index.geo_faceted_documents_ids(&rtxn).unwrap().is_empty());
 
         assert!(index.word_docids.is_empty(&rtxn).unwrap());
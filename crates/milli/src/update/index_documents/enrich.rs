@@ -234,6 +234,19 @@ pub fn validate_geo_from_json(id: &DocumentId, bytes: &[u8]) -> Result<StdResult
         serde_json::from_slice(id.value().as_bytes()).unwrap_or_else(|_| Value::from(id.debug()))
     };
     match serde_json::from_slice(bytes).map_err(InternalError::SerdeJson)? {
+        // a document can have many points, each of them must be valid
+        Value::Array(values) => {
+            for value in values {
+                if !value.is_object() {
+                    return Ok(Err(NotAnObject { document_id: debug_id(), value }));
+                }
+                let bytes = serde_json::to_vec(&value).map_err(InternalError::SerdeJson)?;
+                if let Err(error) = validate_geo_from_json(id, &bytes)? {
+                    return Ok(Err(error));
+                }
+            }
+            Ok(Ok(()))
+        }
         Value::Object(mut object) => match (object.remove("lat"), object.remove("lng")) {
             (Some(lat), Some(lng)) => {
                 match (extract_finite_float_from_value(lat), extract_finite_float_from_value(lng)) {
@@ -115,6 +115,7 @@ pub fn extract_fid_docid_facet_values<R: io::Read + io::Seek>(
                         // during settings update, recompute the changing settings only unless a global change is detected.
                         if settings_diff.settings_update_only
                             && !settings_diff.global_facet_settings_changed()
+                            && !settings_diff.date_field_changed(field_id)
                         {
                             continue;
                         }
@@ -165,10 +166,12 @@ pub fn extract_fid_docid_facet_values<R: io::Read + io::Seek>(
                         .new
                         .geo_fields_ids
                         .is_some_and(|(lat, lng)| field_id == lat || field_id == lng);
-                    let del_filterable_values =
-                        del_value.map(|value| extract_facet_values(&value, del_geo_support));
-                    let add_filterable_values =
-                        add_value.map(|value| extract_facet_values(&value, add_geo_support));
+                    let del_date_field = settings_diff.old.is_date_field(field_id);
+                    let add_date_field = settings_diff.new.is_date_field(field_id);
+                    let del_filterable_values = del_value
+                        .map(|value| extract_facet_values(&value, del_geo_support, del_date_field));
+                    let add_filterable_values = add_value
+                        .map(|value| extract_facet_values(&value, add_geo_support, add_date_field));
 
                     // Those closures are just here to simplify things a bit.
                     let mut insert_numbers_diff = |del_numbers, add_numbers| {
@@ -513,13 +516,14 @@ enum FilterableValues {
 }
 
 /// Extracts the facet values of a JSON field.
-fn extract_facet_values(value: &Value, geo_field: bool) -> FilterableValues {
+fn extract_facet_values(value: &Value, geo_field: bool, date_field: bool) -> FilterableValues {
     fn inner_extract_facet_values(
         value: &Value,
         can_recurse: bool,
         output_numbers: &mut Vec<f64>,
         output_strings: &mut Vec<(String, String)>,
         geo_field: bool,
+        date_field: bool,
     ) {
         match value {
             Value::Null => (),
@@ -541,6 +545,12 @@ fn extract_facet_values(value: &Value, geo_field: bool) -> FilterableValues {
                         )
                     }
                 }
+                // the dates are also stored as timestamps to be compared with the date filters
+                if date_field {
+                    if let Some(timestamp) = crate::date::parse_date(original) {
+                        output_numbers.push(timestamp);
+                    }
+                }
                 let normalized = crate::normalize_facet(original);
                 output_strings.push((normalized, original.clone()));
             }
@@ -553,6 +563,7 @@ fn extract_facet_values(value: &Value, geo_field: bool) -> FilterableValues {
                             output_numbers,
                             output_strings,
                             geo_field,
+                            date_field,
                         );
                     }
                 }
@@ -569,7 +580,14 @@ fn extract_facet_values(value: &Value, geo_field: bool) -> FilterableValues {
         otherwise => {
             let mut numbers = Vec::new();
             let mut strings = Vec::new();
-            inner_extract_facet_values(otherwise, true, &mut numbers, &mut strings, geo_field);
+            inner_extract_facet_values(
+                otherwise,
+                true,
+                &mut numbers,
+                &mut strings,
+                geo_field,
+                date_field,
+            );
             FilterableValues::Values { numbers, strings }
         }
     }
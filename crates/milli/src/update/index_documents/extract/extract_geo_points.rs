@@ -1,19 +1,21 @@
 use std::fs::File;
 use std::io::{self, BufReader};
 
-use concat_arrays::concat_arrays;
 use serde_json::Value;
 
 use super::helpers::{create_writer, writer_into_reader, GrenadParameters};
-use crate::error::GeoError;
+use crate::constants::RESERVED_GEO_FIELD_NAME;
+use crate::geometry::DocumentGeometry;
 use crate::update::del_add::{DelAdd, KvReaderDelAdd, KvWriterDelAdd};
-use crate::update::index_documents::extract_finite_float_from_value;
+use crate::update::new::{extract_geo_coordinates_from_value, extract_geojson_geometry_from_value};
 use crate::update::settings::{InnerIndexSettings, InnerIndexSettingsDiff};
 use crate::{FieldId, InternalError, Result};
 
-/// Extracts the geographical coordinates contained in each document under the `_geo` field.
+/// Extracts the geographical coordinates contained in each document under the `_geo` field
+/// and the geometries contained under the `_geojson` field.
 ///
-/// Returns the generated grenad reader containing the docid as key associated to the (latitude, longitude)
+/// Returns the generated grenad reader containing the docid as key associated to the
+/// bincode serialized [`DocumentGeometry`].
 #[tracing::instrument(level = "trace", skip_all, target = "indexing::extract")]
 pub fn extract_geo_points<R: io::Read + io::Seek>(
     obkv_documents: grenad::Reader<R>,
@@ -40,20 +42,22 @@ pub fn extract_geo_points<R: io::Read + io::Seek>(
         };
 
         // extract old version
-        let del_lat_lng = extract_lat_lng(obkv, &settings_diff.old, DelAdd::Deletion, document_id)?;
+        let del_geometry =
+            extract_geometry(obkv, &settings_diff.old, DelAdd::Deletion, document_id)?;
         // extract new version
-        let add_lat_lng = extract_lat_lng(obkv, &settings_diff.new, DelAdd::Addition, document_id)?;
+        let add_geometry =
+            extract_geometry(obkv, &settings_diff.new, DelAdd::Addition, document_id)?;
 
-        if del_lat_lng != add_lat_lng {
+        if del_geometry != add_geometry {
             let mut obkv = KvWriterDelAdd::memory();
-            if let Some([lat, lng]) = del_lat_lng {
-                #[allow(clippy::drop_non_drop)]
-                let bytes: [u8; 16] = concat_arrays![lat.to_ne_bytes(), lng.to_ne_bytes()];
+            if !del_geometry.is_empty() {
+                let bytes =
+                    bincode::serialize(&del_geometry).map_err(InternalError::BincodeError)?;
                 obkv.insert(DelAdd::Deletion, bytes)?;
             }
-            if let Some([lat, lng]) = add_lat_lng {
-                #[allow(clippy::drop_non_drop)]
-                let bytes: [u8; 16] = concat_arrays![lat.to_ne_bytes(), lng.to_ne_bytes()];
+            if !add_geometry.is_empty() {
+                let bytes =
+                    bincode::serialize(&add_geometry).map_err(InternalError::BincodeError)?;
                 obkv.insert(DelAdd::Addition, bytes)?;
             }
             let bytes = obkv.into_inner()?;
@@ -64,46 +68,43 @@ pub fn extract_geo_points<R: io::Read + io::Seek>(
     writer_into_reader(writer)
 }
 
-/// Extract the finite floats lat and lng from two bytes slices.
-fn extract_lat_lng(
+/// Extract the points and polygons of the `_geo` and `_geojson` fields of a document.
+fn extract_geometry(
     document: &obkv::KvReader<FieldId>,
     settings: &InnerIndexSettings,
     deladd: DelAdd,
     document_id: impl Fn() -> Value,
-) -> Result<Option<[f64; 2]>> {
-    match settings.geo_fields_ids {
-        Some((lat_fid, lng_fid)) => {
-            let lat =
-                document.get(lat_fid).map(KvReaderDelAdd::from_slice).and_then(|r| r.get(deladd));
-            let lng =
-                document.get(lng_fid).map(KvReaderDelAdd::from_slice).and_then(|r| r.get(deladd));
-            let (lat, lng) = match (lat, lng) {
-                (Some(lat), Some(lng)) => (lat, lng),
-                (Some(_), None) => {
-                    return Err(
-                        Box::new(GeoError::MissingLatitude { document_id: document_id() }).into()
-                    )
-                }
-                (None, Some(_)) => {
-                    return Err(
-                        Box::new(GeoError::MissingLongitude { document_id: document_id() }).into()
-                    )
-                }
-                (None, None) => return Ok(None),
-            };
-            let lat = extract_finite_float_from_value(
-                serde_json::from_slice(lat).map_err(InternalError::SerdeJson)?,
-            )
-            .map_err(|lat| GeoError::BadLatitude { document_id: document_id(), value: lat })
-            .map_err(Box::new)?;
+) -> Result<DocumentGeometry> {
+    let mut geometry = DocumentGeometry::default();
 
-            let lng = extract_finite_float_from_value(
-                serde_json::from_slice(lng).map_err(InternalError::SerdeJson)?,
-            )
-            .map_err(|lng| GeoError::BadLongitude { document_id: document_id(), value: lng })
-            .map_err(Box::new)?;
-            Ok(Some([lat, lng]))
+    // the `_geo` field is only indexed when the geo is enabled, i.e. when we have its `lat` and `lng`
+    let geo_fid =
+        settings.geo_fields_ids.and_then(|_| settings.fields_ids_map.id(RESERVED_GEO_FIELD_NAME));
+    if let Some(geo) = get_field_value(document, geo_fid, deladd) {
+        let geo = serde_json::from_slice(geo).map_err(InternalError::SerdeJson)?;
+        geometry.points.extend(extract_geo_coordinates_from_value(&document_id, geo)?);
+    }
+
+    if let Some(geojson) = get_field_value(document, settings.geojson_fid, deladd) {
+        let geojson = serde_json::from_slice(geojson).map_err(InternalError::SerdeJson)?;
+        if let Some(DocumentGeometry { points, polygons }) =
+            extract_geojson_geometry_from_value(&document_id, geojson)?
+        {
+            geometry.points.extend(points);
+            geometry.polygons.extend(polygons);
         }
-        None => Ok(None),
     }
+
+    Ok(geometry)
+}
+
+/// Returns the deletion or addition version of a field of the document.
+fn get_field_value<'a>(
+    document: &'a obkv::KvReader<FieldId>,
+    fid: Option<FieldId>,
+    deladd: DelAdd,
+) -> Option<&'a [u8]> {
+    fid.and_then(|fid| document.get(fid))
+        .map(KvReaderDelAdd::from_slice)
+        .and_then(|r| r.get(deladd))
 }
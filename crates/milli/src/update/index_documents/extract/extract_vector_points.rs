@@ -40,6 +40,7 @@ pub struct ExtractedVectorPoints {
     // embedder
     pub embedder_name: String,
     pub embedder: Arc<Embedder>,
+    pub prompt: Arc<Prompt>,
     pub add_to_user_provided: RoaringBitmap,
     pub remove_from_user_provided: RoaringBitmap,
 }
@@ -207,7 +208,7 @@ pub fn extract_vector_points<R: io::Read + io::Seek>(
     if reindex_vectors {
         for (name, action) in settings_diff.embedding_config_updates.iter() {
             if let Some(action) = action.reindex() {
-                let Some((embedder_name, (embedder, prompt, _quantized))) =
+                let Some((embedder_name, (embedder, prompt, _quantized, _distance))) =
                     configs.remove_entry(name)
                 else {
                     tracing::error!(embedder = name, "Requested embedder config not found");
@@ -238,7 +239,8 @@ pub fn extract_vector_points<R: io::Read + io::Seek>(
                 let action = match action {
                     ReindexAction::FullReindex => ExtractionAction::SettingsFullReindex,
                     ReindexAction::RegeneratePrompts => {
-                        let Some((_, old_prompt, _quantized)) = old_configs.get(name) else {
+                        let Some((_, old_prompt, _quantized, _distance)) = old_configs.get(name)
+                        else {
                             tracing::error!(embedder = name, "Old embedder config not found");
                             continue;
                         };
@@ -264,7 +266,7 @@ pub fn extract_vector_points<R: io::Read + io::Seek>(
     } else {
         // document operation
 
-        for (embedder_name, (embedder, prompt, _quantized)) in configs.into_iter() {
+        for (embedder_name, (embedder, prompt, _quantized, _distance)) in configs.into_iter() {
             // (docid, _index) -> KvWriterDelAdd -> Vector
             let manual_vectors_writer = create_writer(
                 indexer.chunk_compression_type,
@@ -340,6 +342,7 @@ pub fn extract_vector_points<R: io::Read + io::Seek>(
         } in extractors.iter_mut()
         {
             let embedder_is_manual = matches!(**embedder, Embedder::UserProvided(_));
+            let embedder_is_sparse = embedder.is_sparse();
 
             let (old, new) = parsed_vectors.remove(embedder_name);
             let delta = match action {
@@ -353,7 +356,12 @@ pub fn extract_vector_points<R: io::Read + io::Seek>(
                             add_to_user_provided.insert(docid);
                         }
 
-                        match vectors.into_array_of_vectors() {
+                        match vectors.into_embeddings(embedder_name, embedder_is_sparse).map_err(
+                            |error| crate::UserError::InvalidVectorsEmbedderConf {
+                                document_id: document_id().to_string(),
+                                error,
+                            },
+                        )? {
                             Some(add_vectors) => {
                                 if add_vectors.len() > usize::from(u8::MAX) {
                                     return Err(crate::Error::UserError(
@@ -417,6 +425,7 @@ pub fn extract_vector_points<R: io::Read + io::Seek>(
                     document_id,
                     embedder_name,
                     embedder_is_manual,
+                    embedder_is_sparse,
                     &mut manual_errors,
                 )?,
             };
@@ -444,7 +453,7 @@ pub fn extract_vector_points<R: io::Read + io::Seek>(
     for EmbedderVectorExtractor {
         embedder_name,
         embedder,
-        prompt: _,
+        prompt,
         prompts_writer,
         remove_vectors_writer,
         action,
@@ -468,6 +477,7 @@ pub fn extract_vector_points<R: io::Read + io::Seek>(
             prompts: writer_into_reader(prompts_writer)?,
             embedder,
             embedder_name,
+            prompt,
             add_to_user_provided,
             remove_from_user_provided,
         })
@@ -487,6 +497,7 @@ fn extract_vector_document_diff(
     document_id: impl Fn() -> Value,
     embedder_name: &str,
     embedder_is_manual: bool,
+    embedder_is_sparse: bool,
     manual_errors: &mut Option<ManualEmbedderErrors>,
 ) -> Result<VectorStateDelta> {
     match (old.must_regenerate(), new.must_regenerate()) {
@@ -502,7 +513,12 @@ fn extract_vector_document_diff(
     let delta = match (old, new) {
         // regardless of the previous state, if a document now contains inline _vectors, they must
         // be extracted manually
-        (_old, VectorState::Inline(new)) => match new.into_array_of_vectors() {
+        (_old, VectorState::Inline(new)) => match new
+            .into_embeddings(embedder_name, embedder_is_sparse)
+            .map_err(|error| crate::UserError::InvalidVectorsEmbedderConf {
+                document_id: document_id().to_string(),
+                error,
+            })? {
             Some(add_vectors) => {
                 if add_vectors.len() > usize::from(u8::MAX) {
                     return Err(crate::Error::UserError(crate::UserError::TooManyVectors(
@@ -681,6 +697,7 @@ pub fn extract_embeddings<R: io::Read + io::Seek>(
     indexer: GrenadParameters,
     embedder: Arc<Embedder>,
     embedder_name: &str,
+    prompt: &Prompt,
     possible_embedding_mistakes: &PossibleEmbeddingMistakes,
     unused_vectors_distribution: &UnusedVectorsDistribution,
     request_threads: &ThreadPoolNoAbort,
@@ -704,8 +721,9 @@ pub fn extract_embeddings<R: io::Read + io::Seek>(
     while let Some((key, value)) = cursor.move_on_next()? {
         let docid = key.try_into().map(DocumentId::from_be_bytes).unwrap();
         // SAFETY: precondition, the grenad value was saved from a string
-        let prompt = unsafe { std::str::from_utf8_unchecked(value) };
-        if current_chunk.len() == current_chunk.capacity() {
+        let rendered = unsafe { std::str::from_utf8_unchecked(value) };
+        // the pieces of a document are always embedded in the same chunk
+        if current_chunk.len() >= n_vectors_per_chunk {
             chunks.push(std::mem::replace(
                 &mut current_chunk,
                 Vec::with_capacity(n_vectors_per_chunk),
@@ -715,8 +733,10 @@ pub fn extract_embeddings<R: io::Read + io::Seek>(
                 Vec::with_capacity(n_vectors_per_chunk),
             ));
         };
-        current_chunk.push(prompt.to_owned());
-        current_chunk_ids.push(docid);
+        for piece in prompt.chunks(rendered) {
+            current_chunk.push(piece.to_owned());
+            current_chunk_ids.push(docid);
+        }
 
         if chunks.len() == chunks.capacity() {
             let chunked_embeds = embed_chunks(
@@ -728,13 +748,11 @@ pub fn extract_embeddings<R: io::Read + io::Seek>(
                 request_threads,
             )?;
 
-            for (docid, embeddings) in chunks_ids
-                .iter()
-                .flat_map(|docids| docids.iter())
-                .zip(chunked_embeds.iter().flat_map(|embeds| embeds.iter()))
-            {
-                state_writer.insert(docid.to_be_bytes(), cast_slice(embeddings))?;
-            }
+            write_embeddings(
+                &mut state_writer,
+                chunks_ids.iter().flatten(),
+                chunked_embeds.iter().flatten(),
+            )?;
             chunks_ids.clear();
         }
     }
@@ -749,13 +767,11 @@ pub fn extract_embeddings<R: io::Read + io::Seek>(
             unused_vectors_distribution,
             request_threads,
         )?;
-        for (docid, embeddings) in chunks_ids
-            .iter()
-            .flat_map(|docids| docids.iter())
-            .zip(chunked_embeds.iter().flat_map(|embeds| embeds.iter()))
-        {
-            state_writer.insert(docid.to_be_bytes(), cast_slice(embeddings))?;
-        }
+        write_embeddings(
+            &mut state_writer,
+            chunks_ids.iter().flatten(),
+            chunked_embeds.iter().flatten(),
+        )?;
     }
 
     if !current_chunk.is_empty() {
@@ -769,15 +785,41 @@ pub fn extract_embeddings<R: io::Read + io::Seek>(
         )?;
 
         if let Some(embeds) = embeds.first() {
-            for (docid, embeddings) in current_chunk_ids.iter().zip(embeds.iter()) {
-                state_writer.insert(docid.to_be_bytes(), cast_slice(embeddings))?;
-            }
+            write_embeddings(&mut state_writer, &current_chunk_ids, embeds)?;
         }
     }
 
     writer_into_reader(state_writer)
 }
 
+/// Writes the embeddings along with their document ids.
+///
+/// The embeddings of the pieces of a document are contiguous and are concatenated
+/// to be written once as the vectors of the document.
+fn write_embeddings<'a>(
+    state_writer: &mut Writer<BufWriter<File>>,
+    docids: impl IntoIterator<Item = &'a DocumentId>,
+    embeddings: impl IntoIterator<Item = &'a Embedding>,
+) -> Result<()> {
+    let mut current: Option<(DocumentId, Vec<f32>)> = None;
+    for (&docid, embedding) in docids.into_iter().zip(embeddings) {
+        match &mut current {
+            Some((current_docid, vectors)) if *current_docid == docid => {
+                vectors.extend_from_slice(embedding)
+            }
+            _ => {
+                if let Some((docid, vectors)) = current.replace((docid, embedding.clone())) {
+                    state_writer.insert(docid.to_be_bytes(), cast_slice(&vectors))?;
+                }
+            }
+        }
+    }
+    if let Some((docid, vectors)) = current {
+        state_writer.insert(docid.to_be_bytes(), cast_slice(&vectors))?;
+    }
+    Ok(())
+}
+
 fn embed_chunks(
     embedder: &Embedder,
     text_chunks: Vec<Vec<String>>,
@@ -4,7 +4,7 @@ mod helpers;
 mod transform;
 mod typed_chunk;
 
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 use std::io::{Read, Seek};
 use std::iter;
 use std::num::NonZeroU32;
@@ -15,7 +15,7 @@ use enrich::enrich_documents_batch;
 use grenad::{Merger, MergerBuilder};
 use hashbrown::HashMap;
 use heed::types::Str;
-use heed::Database;
+use heed::{BytesDecode, Database};
 use rand::SeedableRng as _;
 use roaring::RoaringBitmap;
 use serde::{Deserialize, Serialize};
@@ -31,13 +31,16 @@ use super::new::StdResult;
 use crate::database_stats::DatabaseStats;
 use crate::documents::{obkv_to_object, DocumentsBatchReader};
 use crate::error::{Error, InternalError};
+use crate::heed_codec::StrBEU16Codec;
 use crate::index::{PrefixSearch, PrefixSettings};
 use crate::progress::Progress;
 use crate::thread_pool_no_abort::ThreadPoolNoAbortBuilder;
 pub use crate::update::index_documents::helpers::CursorClonableMmap;
 use crate::update::{
-    IndexerConfig, UpdateIndexingStep, WordPrefixDocids, WordPrefixIntegerDocids, WordsPrefixesFst,
+    compute_stem_words, recompute_stem_words, IndexerConfig, UpdateIndexingStep, WordPrefixDocids,
+    WordPrefixIntegerDocids, WordsPrefixesFst,
 };
+use crate::vector::scalar::ScalarQuantizedStore;
 use crate::vector::{ArroyWrapper, EmbeddingConfigs};
 use crate::{CboRoaringBitmapCodec, Index, Result, UserError};
 
@@ -303,6 +306,7 @@ where
         let mut word_fid_docids = None;
         let mut word_docids = None;
         let mut exact_word_docids = None;
+        let mut stem_words_delta = BTreeSet::new();
         let mut chunk_accumulator = ChunkAccumulator::default();
         let mut dimension = HashMap::new();
 
@@ -416,6 +420,14 @@ where
                                             )
                                         });
                                     exact_word_docids.push(cloneable_chunk.into_cursor()?);
+                                    let cloneable_chunk =
+                                        unsafe { as_cloneable_grenad(&word_fid_docids_reader)? };
+                                    let mut cursor = cloneable_chunk.into_cursor()?;
+                                    while let Some((key, _)) = cursor.move_on_next()? {
+                                        let (word, _fid) = StrBEU16Codec::bytes_decode(key)
+                                            .map_err(heed::Error::Decoding)?;
+                                        stem_words_delta.insert(word.to_string());
+                                    }
                                     let cloneable_chunk =
                                         unsafe { as_cloneable_grenad(&word_fid_docids_reader)? };
                                     let word_fid_docids = word_fid_docids.get_or_insert_with(|| {
@@ -489,23 +501,46 @@ where
         let mut rng = rand::rngs::StdRng::seed_from_u64(42);
 
         // If an embedder wasn't used in the typedchunk but must be binary quantized
-        // we should insert it in `dimension`
+        // or converted to another distance, we should insert it in `dimension`
         for (name, action) in settings_diff.embedding_config_updates.iter() {
-            if action.is_being_quantized && !dimension.contains_key(name.as_str()) {
+            let stored_distance = settings_diff.stored_embedder_distance(name);
+            let is_changing_distance = settings_diff.embedder_distance(name) != stored_distance;
+            if (action.is_being_quantized || is_changing_distance)
+                && !dimension.contains_key(name.as_str())
+            {
                 let index = self.index.embedder_category_id.get(self.wtxn, name)?.ok_or(
                     InternalError::DatabaseMissingEntry {
                         db_name: "embedder_category_id",
                         key: None,
                     },
                 )?;
-                let reader =
-                    ArroyWrapper::new(self.index.vector_arroy, index, action.was_quantized);
+                let reader = ArroyWrapper::new(
+                    self.index.vector_arroy,
+                    index,
+                    action.was_quantized,
+                    stored_distance,
+                )
+                .with_scalar_quantization(
+                    settings_diff
+                        .stored_embedder_scalar_quantized(name)
+                        .then(|| ScalarQuantizedStore::new(self.index, index)),
+                );
                 let dim = reader.dimensions(self.wtxn)?;
                 dimension.insert(name.to_string(), dim);
             }
         }
 
         for (embedder_name, dimension) in dimension {
+            // sparse embeddings are directly searchable in their inverted index
+            if settings_diff
+                .new
+                .embedding_configs
+                .get(&embedder_name)
+                .is_some_and(|(embedder, _, _, _)| embedder.is_sparse())
+            {
+                continue;
+            }
+
             let wtxn = &mut *self.wtxn;
             let vector_arroy = self.index.vector_arroy;
             let cancel = &self.should_abort;
@@ -516,10 +551,39 @@ where
             let embedder_config = settings_diff.embedding_config_updates.get(&embedder_name);
             let was_quantized =
                 settings_diff.old.embedding_configs.get(&embedder_name).is_some_and(|conf| conf.2);
-            let is_quantizing = embedder_config.is_some_and(|action| action.is_being_quantized);
+            let was_scalar_quantized =
+                settings_diff.stored_embedder_scalar_quantized(&embedder_name);
+            let scalar_quantized = settings_diff.embedder_scalar_quantized(&embedder_name);
+            // the trees of a scalar quantized embedder are binary quantized
+            let is_quantizing = embedder_config.is_some_and(|action| action.is_being_quantized)
+                && (scalar_quantized
+                    || settings_diff
+                        .new
+                        .embedding_configs
+                        .get(&embedder_name)
+                        .is_some_and(|conf| conf.2));
+            let stored_distance = settings_diff.stored_embedder_distance(&embedder_name);
+            let distance = settings_diff.embedder_distance(&embedder_name);
+            let scalar_store = ScalarQuantizedStore::new(self.index, embedder_index);
+            let documents = if was_scalar_quantized != scalar_quantized {
+                self.index.documents_ids(wtxn)?
+            } else {
+                RoaringBitmap::new()
+            };
 
             pool.install(|| {
-                let mut writer = ArroyWrapper::new(vector_arroy, embedder_index, was_quantized);
+                let mut writer =
+                    ArroyWrapper::new(vector_arroy, embedder_index, was_quantized, stored_distance)
+                        .with_scalar_quantization(was_scalar_quantized.then_some(scalar_store))
+                        .with_full_precision_rescoring(
+                            settings_diff.embedder_full_precision_rescoring(&embedder_name),
+                        );
+                writer.set_scalar_quantization(
+                    wtxn,
+                    dimension,
+                    &documents,
+                    scalar_quantized.then_some(scalar_store),
+                )?;
                 writer.build_and_quantize(
                     wtxn,
                     // In the settings we don't have any progress to share
@@ -527,6 +591,7 @@ where
                     &mut rng,
                     dimension,
                     is_quantizing,
+                    distance,
                     self.indexer_config.max_memory,
                     cancel,
                 )?;
@@ -535,6 +600,14 @@ where
             .map_err(InternalError::from)??;
         }
 
+        if settings_diff.old.localized_attributes_rules
+            != settings_diff.new.localized_attributes_rules
+        {
+            recompute_stem_words(self.wtxn, self.index)?;
+        } else {
+            compute_stem_words(self.wtxn, self.index, &stem_words_delta)?;
+        }
+
         self.execute_prefix_databases(
             word_docids.map(MergerBuilder::build),
             exact_word_docids.map(MergerBuilder::build),
@@ -2776,6 +2849,7 @@ mod tests {
                         dimensions: Setting::Set(3),
                         document_template: Setting::NotSet,
                         document_template_max_bytes: Setting::NotSet,
+                        document_template_chunking: Setting::NotSet,
                         url: Setting::NotSet,
                         request: Setting::NotSet,
                         response: Setting::NotSet,
@@ -2784,6 +2858,9 @@ mod tests {
                         search_embedder: Setting::NotSet,
                         indexing_embedder: Setting::NotSet,
                         binary_quantized: Setting::NotSet,
+                        distance: Setting::NotSet,
+                        scalar_quantized: Setting::NotSet,
+                        full_precision_rescoring: Setting::NotSet,
                     }),
                 );
                 settings.set_embedder_settings(embedders);
@@ -31,7 +31,9 @@ use crate::update::index_documents::GrenadParameters;
 use crate::update::settings::{InnerIndexSettings, InnerIndexSettingsDiff};
 use crate::update::{AvailableIds, UpdateIndexingStep};
 use crate::vector::parsed_vectors::{ExplicitVectors, VectorOrArrayOfVectors};
+use crate::vector::scalar::ScalarQuantizedStore;
 use crate::vector::settings::WriteBackToDocuments;
+use crate::vector::sparse::SparseVectorStore;
 use crate::vector::ArroyWrapper;
 use crate::{FieldDistribution, FieldId, FieldIdMapMissingEntry, Index, Result};
 
@@ -834,7 +836,9 @@ impl<'a, 'i> Transform<'a, 'i> {
             None
         };
 
-        let readers: BTreeMap<&str, (ArroyWrapper, &RoaringBitmap)> = settings_diff
+        // the sparse embedders are associated with their dimensions, used to write their vectors back
+        #[allow(clippy::type_complexity)]
+        let readers: BTreeMap<&str, (ArroyWrapper, bool, &RoaringBitmap)> = settings_diff
             .embedding_config_updates
             .iter()
             .filter_map(|(name, action)| {
@@ -845,8 +849,19 @@ impl<'a, 'i> Transform<'a, 'i> {
                         self.index.vector_arroy,
                         *embedder_id,
                         action.was_quantized,
+                        settings_diff.stored_embedder_distance(name),
+                    )
+                    .with_scalar_quantization(
+                        settings_diff
+                            .stored_embedder_scalar_quantized(name)
+                            .then(|| ScalarQuantizedStore::new(self.index, *embedder_id)),
                     );
-                    Some((name.as_str(), (reader, user_provided)))
+                    let sparse = settings_diff
+                        .old
+                        .embedding_configs
+                        .get(name)
+                        .is_some_and(|(embedder, _, _, _)| embedder.is_sparse());
+                    Some((name.as_str(), (reader, sparse, user_provided)))
                 } else {
                     None
                 }
@@ -882,23 +897,32 @@ impl<'a, 'i> Transform<'a, 'i> {
                     InternalError::DatabaseMissingEntry { db_name: db_name::DOCUMENTS, key: None },
                 )?;
 
-                let injected_vectors: std::result::Result<
-                    serde_json::Map<String, serde_json::Value>,
-                    arroy::Error,
-                > = readers
+                let injected_vectors: Result<serde_json::Map<String, serde_json::Value>> = readers
                     .iter()
-                    .filter_map(|(name, (reader, user_provided))| {
+                    .filter_map(|(name, (reader, sparse, user_provided))| {
                         if !user_provided.contains(docid) {
                             return None;
                         }
-                        match reader.item_vectors(wtxn, docid) {
-                            Ok(vectors) if vectors.is_empty() => None,
-                            Ok(vectors) => Some(Ok((
+                        let embeddings = if *sparse {
+                            SparseVectorStore::new(self.index, reader.embedder_index())
+                                .item_vector(wtxn, docid)
+                                .map(|embedding| {
+                                    embedding
+                                        .as_ref()
+                                        .map(VectorOrArrayOfVectors::from_sparse_embedding)
+                                })
+                        } else {
+                            reader.item_vectors(wtxn, docid).map_err(Into::into).map(|vectors| {
+                                (!vectors.is_empty())
+                                    .then(|| VectorOrArrayOfVectors::from_array_of_vectors(vectors))
+                            })
+                        };
+                        match embeddings {
+                            Ok(None) => None,
+                            Ok(embeddings @ Some(_)) => Some(Ok((
                                 name.to_string(),
                                 serde_json::to_value(ExplicitVectors {
-                                    embeddings: Some(
-                                        VectorOrArrayOfVectors::from_array_of_vectors(vectors),
-                                    ),
+                                    embeddings,
                                     regenerate: false,
                                 })
                                 .unwrap(),
@@ -932,7 +956,11 @@ impl<'a, 'i> Transform<'a, 'i> {
         }
 
         // delete all vectors from the embedders that need removal
-        for (_, (reader, _)) in readers {
+        for (_, (reader, sparse, _)) in readers {
+            if sparse.is_some() {
+                SparseVectorStore::new(self.index, reader.embedder_index()).clear(wtxn)?;
+                continue;
+            }
             let dimensions = reader.dimensions(wtxn)?;
             reader.clear(wtxn, dimensions)?;
         }
@@ -11,12 +11,12 @@ use obkv::{KvReader, KvWriter};
 use roaring::RoaringBitmap;
 
 use super::helpers::{
-    self, merge_deladd_cbo_roaring_bitmaps_into_cbo_roaring_bitmap, valid_lmdb_key,
-    CursorClonableMmap, KeepFirst, MergeDeladdBtreesetString, MergeDeladdCboRoaringBitmaps,
-    MergeIgnoreValues,
+    merge_deladd_cbo_roaring_bitmaps_into_cbo_roaring_bitmap, valid_lmdb_key, CursorClonableMmap,
+    KeepFirst, MergeDeladdBtreesetString, MergeDeladdCboRoaringBitmaps, MergeIgnoreValues,
 };
 use crate::external_documents_ids::{DocumentOperation, DocumentOperationKind};
 use crate::facet::FacetType;
+use crate::geometry::DocumentGeometry;
 use crate::index::db_name::DOCUMENTS;
 use crate::index::IndexEmbeddingConfig;
 use crate::proximity::MAX_DISTANCE;
@@ -26,10 +26,12 @@ use crate::update::index_documents::helpers::{
     as_cloneable_grenad, try_split_array_at, KeepLatestObkv,
 };
 use crate::update::settings::InnerIndexSettingsDiff;
+use crate::vector::scalar::ScalarQuantizedStore;
+use crate::vector::sparse::{SparseEmbedding, SparseVectorStore};
 use crate::vector::ArroyWrapper;
 use crate::{
-    lat_lng_to_xyz, CboRoaringBitmapCodec, DocumentId, FieldId, GeoPoint, Index, InternalError,
-    Result, SerializationError, U8StrStrCodec,
+    lat_lng_to_xyz, CboRoaringBitmapCodec, DocumentId, FieldId, GeoJsonPolygon, GeoPoint, Index,
+    InternalError, Result, SerializationError, U8StrStrCodec,
 };
 
 /// This struct accumulates and group the TypedChunks
@@ -588,6 +590,8 @@ pub(crate) fn write_typed_chunk_into_index(
             let merger = builder.build();
 
             let mut rtree = index.geo_rtree(wtxn)?.unwrap_or_default();
+            let mut geojson_rtree = index.geojson_rtree(wtxn)?.unwrap_or_default();
+            let mut geojson_updated = false;
             let mut geo_faceted_docids = index.geo_faceted_documents_ids(wtxn)?;
 
             let mut iter = merger.into_stream_merger_iter()?;
@@ -597,17 +601,32 @@ pub(crate) fn write_typed_chunk_into_index(
 
                 let deladd_obkv = KvReaderDelAdd::from_slice(value);
                 if let Some(value) = deladd_obkv.get(DelAdd::Deletion) {
-                    let geopoint = extract_geo_point(value, docid);
-                    rtree.remove(&geopoint);
+                    let geometry = extract_geometry(value)?;
+                    for point in geometry.points {
+                        rtree.remove(&GeoPoint::new(lat_lng_to_xyz(&point), (docid, point)));
+                    }
+                    for rings in geometry.polygons {
+                        geojson_rtree.remove(&GeoJsonPolygon::new(docid, rings));
+                        geojson_updated = true;
+                    }
                     geo_faceted_docids.remove(docid);
                 }
                 if let Some(value) = deladd_obkv.get(DelAdd::Addition) {
-                    let geopoint = extract_geo_point(value, docid);
-                    rtree.insert(geopoint);
+                    let geometry = extract_geometry(value)?;
+                    for point in geometry.points {
+                        rtree.insert(GeoPoint::new(lat_lng_to_xyz(&point), (docid, point)));
+                    }
+                    for rings in geometry.polygons {
+                        geojson_rtree.insert(GeoJsonPolygon::new(docid, rings));
+                        geojson_updated = true;
+                    }
                     geo_faceted_docids.insert(docid);
                 }
             }
             index.put_geo_rtree(wtxn, &rtree)?;
+            if geojson_updated {
+                index.put_geojson_rtree(wtxn, &geojson_rtree)?;
+            }
             index.put_geo_faceted_documents_ids(wtxn, &geo_faceted_docids)?;
         }
         TypedChunk::VectorPoints { .. } => {
@@ -663,15 +682,36 @@ pub(crate) fn write_typed_chunk_into_index(
             )?;
             let binary_quantized =
                 settings_diff.old.embedding_configs.get(&embedder_name).is_some_and(|conf| conf.2);
-            // FIXME: allow customizing distance
-            let writer = ArroyWrapper::new(index.vector_arroy, embedder_index, binary_quantized);
+            let distance = settings_diff.stored_embedder_distance(&embedder_name);
+            let writer =
+                ArroyWrapper::new(index.vector_arroy, embedder_index, binary_quantized, distance)
+                    .with_scalar_quantization(
+                        settings_diff
+                            .stored_embedder_scalar_quantized(&embedder_name)
+                            .then(|| ScalarQuantizedStore::new(index, embedder_index)),
+                    )
+                    .with_full_precision_rescoring(
+                        settings_diff.embedder_full_precision_rescoring(&embedder_name),
+                    );
+            // the sparse embeddings are stored in their inverted index rather than in arroy
+            let mut sparse_store = settings_diff
+                .new
+                .embedding_configs
+                .get(&embedder_name)
+                .is_some_and(|(embedder, _, _, _)| embedder.is_sparse())
+                .then(|| SparseVectorStore::new(index, embedder_index));
 
             // remove vectors for docids we want them removed
             let merger = remove_vectors_builder.build();
             let mut iter = merger.into_stream_merger_iter()?;
             while let Some((key, _)) = iter.next()? {
                 let docid = key.try_into().map(DocumentId::from_be_bytes).unwrap();
-                writer.del_items(wtxn, expected_dimension, docid)?;
+                match &mut sparse_store {
+                    Some(sparse_store) => {
+                        sparse_store.del_item(wtxn, docid)?;
+                    }
+                    None => writer.del_items(wtxn, expected_dimension, docid)?,
+                }
             }
 
             // add generated embeddings
@@ -680,6 +720,11 @@ pub(crate) fn write_typed_chunk_into_index(
             while let Some((key, value)) = iter.next()? {
                 let docid = key.try_into().map(DocumentId::from_be_bytes).unwrap();
                 let data = pod_collect_to_vec(value);
+                if let Some(sparse_store) = &mut sparse_store {
+                    // the packed embeddings of the chunks of the document are merged
+                    sparse_store.add_item(wtxn, docid, &SparseEmbedding::from_packed(&data))?;
+                    continue;
+                }
                 // it is a code error to have embeddings and not expected_dimension
                 let embeddings = crate::vector::Embeddings::from_inner(data, expected_dimension)
                     // code error if we somehow got the wrong dimension
@@ -711,6 +756,25 @@ pub(crate) fn write_typed_chunk_into_index(
                 let docid = DocumentId::from_be_bytes(left);
 
                 let vector_deladd_obkv = KvReaderDelAdd::from_slice(value);
+                if let Some(sparse_store) = &mut sparse_store {
+                    if vector_deladd_obkv.get(DelAdd::Deletion).is_some() {
+                        sparse_store.del_item(wtxn, docid)?;
+                    }
+                    // the user provided sparse embeddings, packed during the extraction
+                    if let Some(value) = vector_deladd_obkv.get(DelAdd::Addition) {
+                        let mut packed: Vec<f32> = pod_collect_to_vec(value);
+                        if let Some(existing) = sparse_store.item_vector(wtxn, docid)? {
+                            packed.extend(existing.to_packed());
+                        }
+                        sparse_store.add_item(
+                            wtxn,
+                            docid,
+                            &SparseEmbedding::from_packed(&packed),
+                        )?;
+                    }
+                    continue;
+                }
+
                 if let Some(value) = vector_deladd_obkv.get(DelAdd::Deletion) {
                     let vector: Vec<f32> = pod_collect_to_vec(value);
 
@@ -725,6 +789,10 @@ pub(crate) fn write_typed_chunk_into_index(
                 }
             }
 
+            if let Some(mut sparse_store) = sparse_store {
+                sparse_store.flush(wtxn)?;
+            }
+
             tracing::debug!("Finished vector chunk for {}", embedder_name);
         }
     }
@@ -732,13 +800,9 @@ pub(crate) fn write_typed_chunk_into_index(
     Ok((RoaringBitmap::new(), is_merged_database))
 }
 
-/// Converts the latitude and longitude back to an xyz GeoPoint.
-pub fn extract_geo_point(value: &[u8], docid: DocumentId) -> GeoPoint {
-    let (lat, tail) = helpers::try_split_array_at::<u8, 8>(value).unwrap();
-    let (lng, _) = helpers::try_split_array_at::<u8, 8>(tail).unwrap();
-    let point = [f64::from_ne_bytes(lat), f64::from_ne_bytes(lng)];
-    let xyz_point = lat_lng_to_xyz(&point);
-    GeoPoint::new(xyz_point, (docid, point))
+/// Deserializes the points and polygons of a document extracted by `extract_geo_points`.
+fn extract_geometry(value: &[u8]) -> Result<DocumentGeometry> {
+    bincode::deserialize(value).map_err(|e| InternalError::BincodeError(e).into())
 }
 
 fn merge_word_docids_reader_into_fst<MF>(
@@ -7,6 +7,7 @@ pub use self::index_documents::*;
 pub use self::indexer_config::IndexerConfig;
 pub use self::new::ChannelCongestion;
 pub use self::settings::{validate_embedding_settings, Setting, Settings};
+pub(crate) use self::stem_words::{compute_stem_words, recompute_stem_words};
 pub use self::update_step::UpdateIndexingStep;
 pub use self::word_prefix_docids::WordPrefixDocids;
 pub use self::words_prefix_integer_docids::WordPrefixIntegerDocids;
@@ -21,6 +22,7 @@ mod index_documents;
 mod indexer_config;
 pub mod new;
 pub(crate) mod settings;
+mod stem_words;
 mod update_step;
 pub mod upgrade;
 mod word_prefix_docids;
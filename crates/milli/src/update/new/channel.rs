@@ -24,7 +24,7 @@ use super::thread_local::{FullySend, ThreadLocal};
 use super::StdResult;
 use crate::heed_codec::facet::{FieldDocIdFacetF64Codec, FieldDocIdFacetStringCodec};
 use crate::index::db_name;
-use crate::index::main_key::{GEO_FACETED_DOCUMENTS_IDS_KEY, GEO_RTREE_KEY};
+use crate::index::main_key::{GEOJSON_RTREE_KEY, GEO_FACETED_DOCUMENTS_IDS_KEY, GEO_RTREE_KEY};
 use crate::update::new::KvReaderFieldId;
 use crate::vector::Embedding;
 use crate::{CboRoaringBitmapCodec, DocumentId, Error, Index, InternalError};
@@ -172,6 +172,10 @@ impl LargeVectors {
     pub fn read_embeddings(&self, dimensions: usize) -> impl Iterator<Item = &[f32]> {
         self.embeddings.chunks_exact(dimensions).map(bytemuck::cast_slice)
     }
+
+    pub fn read_all_embeddings(&self) -> &[f32] {
+        bytemuck::cast_slice(&self.embeddings)
+    }
 }
 
 impl<'a> WriterBbqueueReceiver<'a> {
@@ -963,6 +967,17 @@ impl GeoSender<'_, '_> {
             .map_err(|_| SendError(()))
     }
 
+    pub fn set_geojson_rtree(&self, value: Mmap) -> StdResult<(), SendError<()>> {
+        self.0
+            .sender
+            .send(ReceiverAction::LargeEntry(LargeEntry {
+                database: Database::Main,
+                key: GEOJSON_RTREE_KEY.to_string().into_bytes().into_boxed_slice(),
+                value,
+            }))
+            .map_err(|_| SendError(()))
+    }
+
     pub fn set_geo_faceted(&self, bitmap: &RoaringBitmap) -> crate::Result<()> {
         let database = Database::Main;
         let value_length = bitmap.serialized_size();
@@ -10,6 +10,7 @@ use serde_json::Value;
 use super::super::cache::BalancedCaches;
 use super::facet_document::extract_document_facets;
 use super::FacetKind;
+use crate::date::parse_date;
 use crate::fields_ids_map::metadata::Metadata;
 use crate::filterable_attributes_rules::match_faceted_field;
 use crate::heed_codec::facet::OrderedF64Codec;
@@ -251,30 +252,38 @@ impl FacetedDocidsExtractor {
         match value {
             // Number
             // key: fid - level - orderedf64 - originalf64
-            Value::Number(number) => {
-                let mut ordered = [0u8; 16];
-                if number
-                    .as_f64()
-                    .and_then(|n| OrderedF64Codec::serialize_into(n, &mut ordered).ok())
-                    .is_some()
-                {
-                    let mut number = BVec::with_capacity_in(16, doc_alloc);
-                    number.extend_from_slice(&ordered);
-                    facet_fn(del_add_facet_value, fid, number, FacetKind::Number);
-
-                    buffer.clear();
-                    buffer.push(FacetKind::Number as u8);
-                    buffer.extend_from_slice(&fid.to_be_bytes());
-                    buffer.push(0); // level 0
-                    buffer.extend_from_slice(&ordered);
-                    cache_fn(cached_sorter, &buffer, docid)
-                } else {
-                    Ok(())
-                }
-            }
+            Value::Number(number) => match number.as_f64() {
+                Some(number) => Self::facet_number(
+                    doc_alloc,
+                    cached_sorter,
+                    &cache_fn,
+                    del_add_facet_value,
+                    &facet_fn,
+                    &mut buffer,
+                    docid,
+                    fid,
+                    number,
+                ),
+                None => Ok(()),
+            },
             // String
             // key: fid - level - truncated_string
             Value::String(s) if !s.is_empty() => {
+                // The dates are also stored as timestamps in the numeric facets
+                if let Some(timestamp) = features.is_date().then(|| parse_date(s)).flatten() {
+                    Self::facet_number(
+                        doc_alloc,
+                        cached_sorter,
+                        &cache_fn,
+                        del_add_facet_value,
+                        &facet_fn,
+                        &mut buffer,
+                        docid,
+                        fid,
+                        timestamp,
+                    )?;
+                }
+
                 let mut string = BVec::new_in(doc_alloc);
                 string.extend_from_slice(s.as_bytes());
                 facet_fn(del_add_facet_value, fid, string, FacetKind::String);
@@ -346,6 +355,35 @@ impl FacetedDocidsExtractor {
             _ => Ok(()),
         }
     }
+
+    #[allow(clippy::too_many_arguments)]
+    fn facet_number<'extractor, 'doc>(
+        doc_alloc: &'doc Bump,
+        cached_sorter: &mut BalancedCaches<'extractor>,
+        cache_fn: &impl Fn(&mut BalancedCaches<'extractor>, &[u8], u32) -> Result<()>,
+        del_add_facet_value: &mut DelAddFacetValue<'doc>,
+        facet_fn: &impl Fn(&mut DelAddFacetValue<'doc>, FieldId, BVec<'doc, u8>, FacetKind),
+        buffer: &mut BVec<'doc, u8>,
+        docid: DocumentId,
+        fid: FieldId,
+        number: f64,
+    ) -> Result<()> {
+        let mut ordered = [0u8; 16];
+        if OrderedF64Codec::serialize_into(number, &mut ordered).is_err() {
+            return Ok(());
+        }
+
+        let mut number = BVec::with_capacity_in(16, doc_alloc);
+        number.extend_from_slice(&ordered);
+        facet_fn(del_add_facet_value, fid, number, FacetKind::Number);
+
+        buffer.clear();
+        buffer.push(FacetKind::Number as u8);
+        buffer.extend_from_slice(&fid.to_be_bytes());
+        buffer.push(0); // level 0
+        buffer.extend_from_slice(&ordered);
+        cache_fn(cached_sorter, buffer, docid)
+    }
 }
 
 struct DelAddFacetValue<'doc> {
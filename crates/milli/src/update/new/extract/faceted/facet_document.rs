@@ -104,7 +104,7 @@ pub fn extract_document_facets<'doc>(
 
     if is_geo_enabled {
         if let Some(geo_value) = document.geo_field()? {
-            if let Some([lat, lng]) = extract_geo_coordinates(external_document_id, geo_value)? {
+            for [lat, lng] in extract_geo_coordinates(external_document_id, geo_value)? {
                 let ((lat_fid, lat_meta), (lng_fid, lng_meta)) = field_id_map
                     .id_with_metadata_or_insert("_geo.lat")
                     .zip(field_id_map.id_with_metadata_or_insert("_geo.lng"))
@@ -9,14 +9,16 @@ use heed::RoTxn;
 use serde_json::value::RawValue;
 use serde_json::Value;
 
+use crate::constants::RESERVED_GEOJSON_FIELD_NAME;
 use crate::error::GeoError;
+use crate::geometry::DocumentGeometry;
 use crate::update::new::document::Document;
 use crate::update::new::indexer::document_changes::{DocumentChangeContext, Extractor};
 use crate::update::new::ref_cell_ext::RefCellExt as _;
 use crate::update::new::thread_local::MostlySend;
 use crate::update::new::DocumentChange;
 use crate::update::GrenadParameters;
-use crate::{lat_lng_to_xyz, DocumentId, GeoPoint, Index, InternalError, Result};
+use crate::{lat_lng_to_xyz, DocumentId, GeoJsonPolygon, GeoPoint, Index, InternalError, Result};
 
 pub struct GeoExtractor {
     grenad_parameters: GrenadParameters,
@@ -64,15 +66,56 @@ pub struct GeoExtractorData<'extractor> {
     /// Contains a packed list of `ExtractedGeoPoint` of the inserted geo points
     /// data structures if we have spilled to disk.
     spilled_inserted: Option<BufWriter<File>>,
+    /// The `_geojson` polygons of the documents, they are much less common
+    /// than the points and are therefore never spilled to disk.
+    removed_polygons: Vec<GeoJsonPolygon>,
+    inserted_polygons: Vec<GeoJsonPolygon>,
 }
 
 impl<'extractor> GeoExtractorData<'extractor> {
+    /// Registers the points and polygons of a document geometry to be removed from the rtrees.
+    fn remove_geometry(&mut self, docid: DocumentId, geometry: DocumentGeometry) -> Result<()> {
+        for lat_lng in geometry.points {
+            let geopoint = ExtractedGeoPoint { docid, lat_lng };
+            match &mut self.spilled_removed {
+                Some(file) => file.write_all(bytes_of(&geopoint))?,
+                None => self.removed.push(geopoint),
+            }
+        }
+        let polygons = geometry.polygons.into_iter().map(|rings| GeoJsonPolygon::new(docid, rings));
+        self.removed_polygons.extend(polygons);
+        Ok(())
+    }
+
+    /// Registers the points and polygons of a document geometry to be inserted in the rtrees.
+    fn insert_geometry(&mut self, docid: DocumentId, geometry: DocumentGeometry) -> Result<()> {
+        for lat_lng in geometry.points {
+            let geopoint = ExtractedGeoPoint { docid, lat_lng };
+            match &mut self.spilled_inserted {
+                Some(file) => file.write_all(bytes_of(&geopoint))?,
+                None => self.inserted.push(geopoint),
+            }
+        }
+        let polygons = geometry.polygons.into_iter().map(|rings| GeoJsonPolygon::new(docid, rings));
+        self.inserted_polygons.extend(polygons);
+        Ok(())
+    }
+
     pub fn freeze(self) -> Result<FrozenGeoExtractorData<'extractor>> {
-        let GeoExtractorData { removed, inserted, spilled_removed, spilled_inserted } = self;
+        let GeoExtractorData {
+            removed,
+            inserted,
+            spilled_removed,
+            spilled_inserted,
+            removed_polygons,
+            inserted_polygons,
+        } = self;
 
         Ok(FrozenGeoExtractorData {
             removed: removed.into_bump_slice(),
             inserted: inserted.into_bump_slice(),
+            removed_polygons,
+            inserted_polygons,
             spilled_removed: spilled_removed
                 .map(|bw| bw.into_inner().map(BufReader::new).map_err(|iie| iie.into_error()))
                 .transpose()?,
@@ -88,6 +131,8 @@ unsafe impl MostlySend for GeoExtractorData<'_> {}
 pub struct FrozenGeoExtractorData<'extractor> {
     pub removed: &'extractor [ExtractedGeoPoint],
     pub inserted: &'extractor [ExtractedGeoPoint],
+    pub removed_polygons: Vec<GeoJsonPolygon>,
+    pub inserted_polygons: Vec<GeoJsonPolygon>,
     pub spilled_removed: Option<BufReader<File>>,
     pub spilled_inserted: Option<BufReader<File>>,
 }
@@ -144,6 +189,8 @@ impl<'extractor> Extractor<'extractor> for GeoExtractor {
             inserted: bumpalo::collections::Vec::new_in(extractor_alloc),
             spilled_inserted: None,
             spilled_removed: None,
+            removed_polygons: Vec::new(),
+            inserted_polygons: Vec::new(),
         }))
     }
 
@@ -172,73 +219,35 @@ impl<'extractor> Extractor<'extractor> for GeoExtractor {
                     let docid = deletion.docid();
                     let external_id = deletion.external_document_id();
                     let current = deletion.current(rtxn, index, db_fields_ids_map)?;
-                    let current_geo = current
-                        .geo_field()?
-                        .map(|geo| extract_geo_coordinates(external_id, geo))
-                        .transpose()?;
-
-                    if let Some(lat_lng) = current_geo.flatten() {
-                        let geopoint = ExtractedGeoPoint { docid, lat_lng };
-                        match &mut data_ref.spilled_removed {
-                            Some(file) => file.write_all(bytes_of(&geopoint))?,
-                            None => data_ref.removed.push(geopoint),
-                        }
-                    }
+                    let current_geometry = extract_document_geometry(external_id, &current)?;
+                    data_ref.remove_geometry(docid, current_geometry)?;
                 }
                 DocumentChange::Update(update) => {
                     let current = update.current(rtxn, index, db_fields_ids_map)?;
                     let external_id = update.external_document_id();
                     let docid = update.docid();
 
-                    let current_geo = current
-                        .geo_field()?
-                        .map(|geo| extract_geo_coordinates(external_id, geo))
-                        .transpose()?;
-
-                    let updated_geo = update
-                        .merged(rtxn, index, db_fields_ids_map)?
-                        .geo_field()?
-                        .map(|geo| extract_geo_coordinates(external_id, geo))
-                        .transpose()?;
-
-                    if current_geo != updated_geo {
-                        // If the current and new geo points are different it means that
-                        // we need to replace the current by the new point and therefore
-                        // delete the current point from the RTree.
-                        if let Some(lat_lng) = current_geo.flatten() {
-                            let geopoint = ExtractedGeoPoint { docid, lat_lng };
-                            match &mut data_ref.spilled_removed {
-                                Some(file) => file.write_all(bytes_of(&geopoint))?,
-                                None => data_ref.removed.push(geopoint),
-                            }
-                        }
-
-                        if let Some(lat_lng) = updated_geo.flatten() {
-                            let geopoint = ExtractedGeoPoint { docid, lat_lng };
-                            match &mut data_ref.spilled_inserted {
-                                Some(file) => file.write_all(bytes_of(&geopoint))?,
-                                None => data_ref.inserted.push(geopoint),
-                            }
-                        }
+                    let current_geometry = extract_document_geometry(external_id, &current)?;
+                    let updated_geometry = extract_document_geometry(
+                        external_id,
+                        &update.merged(rtxn, index, db_fields_ids_map)?,
+                    )?;
+
+                    if current_geometry != updated_geometry {
+                        // If the current and new geometries are different it means that
+                        // we need to replace the current by the new points and polygons and
+                        // therefore delete all the current ones from the RTrees.
+                        data_ref.remove_geometry(docid, current_geometry)?;
+                        data_ref.insert_geometry(docid, updated_geometry)?;
                     }
                 }
                 DocumentChange::Insertion(insertion) => {
                     let external_id = insertion.external_document_id();
                     let docid = insertion.docid();
 
-                    let inserted_geo = insertion
-                        .inserted()
-                        .geo_field()?
-                        .map(|geo| extract_geo_coordinates(external_id, geo))
-                        .transpose()?;
-
-                    if let Some(lat_lng) = inserted_geo.flatten() {
-                        let geopoint = ExtractedGeoPoint { docid, lat_lng };
-                        match &mut data_ref.spilled_inserted {
-                            Some(file) => file.write_all(bytes_of(&geopoint))?,
-                            None => data_ref.inserted.push(geopoint),
-                        }
-                    }
+                    let inserted_geometry =
+                        extract_document_geometry(external_id, &insertion.inserted())?;
+                    data_ref.insert_geometry(docid, inserted_geometry)?;
                 }
             }
         }
@@ -247,22 +256,56 @@ impl<'extractor> Extractor<'extractor> for GeoExtractor {
     }
 }
 
-/// Extracts and validates the latitude and latitude from a document geo field.
-///
-/// It can be of the form `{ "lat": 0.0, "lng": "1.0" }`.
-pub fn extract_geo_coordinates(
+/// Extracts the geographical data of a document, its `_geo` points and its `_geojson` geometry.
+fn extract_document_geometry<'doc>(
     external_id: &str,
-    raw_value: &RawValue,
-) -> Result<Option<[f64; 2]>> {
-    let mut geo = match serde_json::from_str(raw_value.get()).map_err(InternalError::SerdeJson)? {
-        Value::Null => return Ok(None),
+    document: &impl Document<'doc>,
+) -> Result<DocumentGeometry> {
+    let mut geometry = DocumentGeometry::default();
+
+    if let Some(geo) = document.geo_field()? {
+        geometry.points.extend(extract_geo_coordinates(external_id, geo)?);
+    }
+
+    let geojson = document
+        .top_level_field(RESERVED_GEOJSON_FIELD_NAME)?
+        .map(|geojson| extract_geojson_geometry(external_id, geojson));
+    if let Some(DocumentGeometry { points, polygons }) = geojson.transpose()?.flatten() {
+        geometry.points.extend(points);
+        geometry.polygons.extend(polygons);
+    }
+
+    Ok(geometry)
+}
+
+/// Extracts and validates the latitudes and longitudes from a document geo field.
+///
+/// It can be of the form `{ "lat": 0.0, "lng": "1.0" }` or an array of such objects.
+pub fn extract_geo_coordinates(external_id: &str, raw_value: &RawValue) -> Result<Vec<[f64; 2]>> {
+    let value = serde_json::from_str(raw_value.get()).map_err(InternalError::SerdeJson)?;
+    extract_geo_coordinates_from_value(|| Value::from(external_id), value)
+}
+
+/// Extracts and validates the latitudes and longitudes from the json value of a document geo field.
+pub fn extract_geo_coordinates_from_value(
+    document_id: impl Fn() -> Value,
+    value: Value,
+) -> Result<Vec<[f64; 2]>> {
+    match value {
+        Value::Null => Ok(Vec::new()),
+        Value::Array(values) => {
+            values.into_iter().map(|value| extract_geo_point(&document_id, value)).collect()
+        }
+        value => extract_geo_point(&document_id, value).map(|point| vec![point]),
+    }
+}
+
+/// Extracts and validates the latitude and longitude of a single `{ "lat": 0.0, "lng": "1.0" }` object.
+fn extract_geo_point(document_id: impl Fn() -> Value, value: Value) -> Result<[f64; 2]> {
+    let mut geo = match value {
         Value::Object(map) => map,
         value => {
-            return Err(Box::new(GeoError::NotAnObject {
-                document_id: Value::from(external_id),
-                value,
-            })
-            .into())
+            return Err(Box::new(GeoError::NotAnObject { document_id: document_id(), value }).into())
         }
     };
 
@@ -272,44 +315,36 @@ pub fn extract_geo_coordinates(
                 [lat, lng]
             } else {
                 return Err(Box::new(GeoError::UnexpectedExtraFields {
-                    document_id: Value::from(external_id),
+                    document_id: document_id(),
                     value: Value::from(geo),
                 })
                 .into());
             }
         }
         (Some(_), None) => {
-            return Err(Box::new(GeoError::MissingLongitude {
-                document_id: Value::from(external_id),
-            })
-            .into())
+            return Err(Box::new(GeoError::MissingLongitude { document_id: document_id() }).into())
         }
         (None, Some(_)) => {
-            return Err(Box::new(GeoError::MissingLatitude {
-                document_id: Value::from(external_id),
-            })
-            .into())
+            return Err(Box::new(GeoError::MissingLatitude { document_id: document_id() }).into())
         }
         (None, None) => {
             return Err(Box::new(GeoError::MissingLatitudeAndLongitude {
-                document_id: Value::from(external_id),
+                document_id: document_id(),
             })
             .into())
         }
     };
 
     match (extract_finite_float_from_value(lat), extract_finite_float_from_value(lng)) {
-        (Ok(lat), Ok(lng)) => Ok(Some([lat, lng])),
+        (Ok(lat), Ok(lng)) => Ok([lat, lng]),
         (Ok(_), Err(value)) => {
-            Err(Box::new(GeoError::BadLongitude { document_id: Value::from(external_id), value })
-                .into())
+            Err(Box::new(GeoError::BadLongitude { document_id: document_id(), value }).into())
         }
         (Err(value), Ok(_)) => {
-            Err(Box::new(GeoError::BadLatitude { document_id: Value::from(external_id), value })
-                .into())
+            Err(Box::new(GeoError::BadLatitude { document_id: document_id(), value }).into())
         }
         (Err(lat), Err(lng)) => Err(Box::new(GeoError::BadLatitudeAndLongitude {
-            document_id: Value::from(external_id),
+            document_id: document_id(),
             lat,
             lng,
         })
@@ -317,6 +352,144 @@ pub fn extract_geo_coordinates(
     }
 }
 
+/// Extracts and validates the geometry from a document geojson field.
+///
+/// It must be a GeoJSON geometry object of type `Point`, `MultiPoint`, `Polygon` or `MultiPolygon`
+/// like `{ "type": "Point", "coordinates": [2.35, 48.85] }`, where positions are `[lng, lat]`.
+pub fn extract_geojson_geometry(
+    external_id: &str,
+    raw_value: &RawValue,
+) -> Result<Option<DocumentGeometry>> {
+    let value = serde_json::from_str(raw_value.get()).map_err(InternalError::SerdeJson)?;
+    extract_geojson_geometry_from_value(|| Value::from(external_id), value)
+}
+
+/// Extracts and validates the geometry from the json value of a document geojson field.
+pub fn extract_geojson_geometry_from_value(
+    document_id: impl Fn() -> Value,
+    value: Value,
+) -> Result<Option<DocumentGeometry>> {
+    let mut geojson = match value {
+        Value::Null => return Ok(None),
+        Value::Object(map) => map,
+        value => {
+            return Err(Box::new(GeoError::GeoJsonNotAnObject {
+                document_id: document_id(),
+                value,
+            })
+            .into())
+        }
+    };
+
+    let coordinates = geojson.remove("coordinates").unwrap_or_default();
+    let bad_coordinates = |kind, expected| -> crate::Error {
+        Box::new(GeoError::GeoJsonBadCoordinates {
+            document_id: document_id(),
+            kind,
+            expected,
+            value: coordinates.clone(),
+        })
+        .into()
+    };
+
+    let mut geometry = DocumentGeometry::default();
+    match geojson.remove("type").unwrap_or_default() {
+        Value::String(kind) if kind == "Point" => {
+            geometry.points.push(extract_geojson_position(&document_id, &coordinates)?);
+        }
+        Value::String(kind) if kind == "MultiPoint" => match coordinates.as_array() {
+            Some(positions) => {
+                for position in positions {
+                    geometry.points.push(extract_geojson_position(&document_id, position)?);
+                }
+            }
+            None => {
+                return Err(bad_coordinates(
+                    "MultiPoint",
+                    "an array of `[longitude, latitude]` positions",
+                ))
+            }
+        },
+        Value::String(kind) if kind == "Polygon" => {
+            match extract_geojson_polygon(&document_id, &coordinates)? {
+                Some(rings) => geometry.push_polygon(rings),
+                None => {
+                    return Err(bad_coordinates(
+                        "Polygon",
+                        "an array of closed rings made of at least four `[longitude, latitude]` positions",
+                    ))
+                }
+            }
+        }
+        Value::String(kind) if kind == "MultiPolygon" => {
+            let bad_multi_polygon = || {
+                bad_coordinates(
+                    "MultiPolygon",
+                    "an array of polygons, themselves arrays of closed rings made of at least four `[longitude, latitude]` positions",
+                )
+            };
+            for polygon in coordinates.as_array().ok_or_else(bad_multi_polygon)? {
+                match extract_geojson_polygon(&document_id, polygon)? {
+                    Some(rings) => geometry.push_polygon(rings),
+                    None => return Err(bad_multi_polygon()),
+                }
+            }
+        }
+        value => {
+            return Err(Box::new(GeoError::GeoJsonUnsupportedType {
+                document_id: document_id(),
+                value,
+            })
+            .into())
+        }
+    }
+
+    Ok((!geometry.is_empty()).then_some(geometry))
+}
+
+/// Extracts the `[lat, lng]` point from a GeoJSON `[lng, lat]` position,
+/// an optional altitude can follow the longitude and latitude.
+fn extract_geojson_position(document_id: impl Fn() -> Value, value: &Value) -> Result<[f64; 2]> {
+    if let Some([lng, lat] | [lng, lat, _]) = value.as_array().map(Vec::as_slice) {
+        if let (Some(lng), Some(lat)) = (lng.as_f64(), lat.as_f64()) {
+            if (-180.0..=180.0).contains(&lng) && (-90.0..=90.0).contains(&lat) {
+                return Ok([lat, lng]);
+            }
+        }
+    }
+
+    Err(Box::new(GeoError::GeoJsonBadPosition { document_id: document_id(), value: value.clone() })
+        .into())
+}
+
+/// Extracts the rings of a GeoJSON polygon, returns `None` if they are not
+/// closed rings made of at least four positions.
+fn extract_geojson_polygon(
+    document_id: impl Fn() -> Value,
+    value: &Value,
+) -> Result<Option<Vec<Vec<[f64; 2]>>>> {
+    let Some(rings) = value.as_array().filter(|rings| !rings.is_empty()) else {
+        return Ok(None);
+    };
+
+    let mut polygon = Vec::with_capacity(rings.len());
+    for ring in rings {
+        let Some(positions) = ring.as_array().filter(|positions| positions.len() >= 4) else {
+            return Ok(None);
+        };
+        let ring = positions
+            .iter()
+            .map(|position| extract_geojson_position(&document_id, position))
+            .collect::<Result<Vec<_>>>()?;
+        if ring.first() != ring.last() {
+            return Ok(None);
+        }
+        polygon.push(ring);
+    }
+
+    Ok(Some(polygon))
+}
+
 /// Extracts and validate that a serde JSON Value is actually a finite f64.
 pub fn extract_finite_float_from_value(value: Value) -> result::Result<f64, Value> {
     let number = match value {
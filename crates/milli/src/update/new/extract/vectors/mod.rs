@@ -60,7 +60,7 @@ impl<'extractor> Extractor<'extractor> for EmbeddingExtractor<'_, '_> {
             UnusedVectorsDistributionBump::new_in(&context.doc_alloc);
 
         let mut all_chunks = BVec::with_capacity_in(embedders.len(), &context.doc_alloc);
-        for (embedder_name, (embedder, prompt, _is_quantized)) in embedders {
+        for (embedder_name, (embedder, prompt, _is_quantized, _distance)) in embedders {
             let embedder_id =
                 context.index.embedder_category_id.get(&context.rtxn, embedder_name)?.ok_or_else(
                     || InternalError::DatabaseMissingEntry {
@@ -124,10 +124,14 @@ impl<'extractor> Extractor<'extractor> for EmbeddingExtractor<'_, '_> {
                                     update.external_document_id(),
                                     update.docid(),
                                     embeddings
-                                        .into_vec(&context.doc_alloc, embedder_name)
+                                        .into_vec(
+                                            &context.doc_alloc,
+                                            embedder_name,
+                                            chunks.is_sparse(),
+                                        )
                                         .map_err(|error| UserError::InvalidVectorsEmbedderConf {
                                             document_id: update.external_document_id().to_string(),
-                                            error: error.to_string(),
+                                            error,
                                         })?,
                                 )?;
                             } else if new_vectors.regenerate {
@@ -213,12 +217,16 @@ impl<'extractor> Extractor<'extractor> for EmbeddingExtractor<'_, '_> {
                                     insertion.external_document_id(),
                                     insertion.docid(),
                                     embeddings
-                                        .into_vec(&context.doc_alloc, embedder_name)
+                                        .into_vec(
+                                            &context.doc_alloc,
+                                            embedder_name,
+                                            chunks.is_sparse(),
+                                        )
                                         .map_err(|error| UserError::InvalidVectorsEmbedderConf {
                                             document_id: insertion
                                                 .external_document_id()
                                                 .to_string(),
-                                            error: error.to_string(),
+                                            error,
                                         })?,
                                 )?;
                             } else if new_vectors.regenerate {
@@ -329,24 +337,30 @@ impl<'a, 'b, 'extractor> Chunks<'a, 'b, 'extractor> {
             self.has_manual_generation.get_or_insert(external_docid);
         }
 
-        if self.texts.len() < self.texts.capacity() {
-            self.texts.push(rendered);
+        // all the chunks of a document must be embedded in the same batch,
+        // so that they are written together as the vectors of the document
+        let pieces = self.prompt.chunks(rendered);
+        if !self.texts.is_empty() && self.texts.len() + pieces.len() > self.texts.capacity() {
+            Self::embed_chunks(
+                &mut self.texts,
+                &mut self.ids,
+                self.embedder,
+                self.embedder_id,
+                self.embedder_name,
+                self.possible_embedding_mistakes,
+                unused_vectors_distribution,
+                self.threads,
+                self.sender,
+                self.has_manual_generation.take(),
+            )?;
+        }
+
+        for piece in pieces {
+            self.texts.push(piece);
             self.ids.push(docid);
-            return Ok(());
         }
 
-        Self::embed_chunks(
-            &mut self.texts,
-            &mut self.ids,
-            self.embedder,
-            self.embedder_id,
-            self.embedder_name,
-            self.possible_embedding_mistakes,
-            unused_vectors_distribution,
-            self.threads,
-            self.sender,
-            self.has_manual_generation.take(),
-        )
+        Ok(())
     }
 
     pub fn drain(
@@ -423,8 +437,22 @@ impl<'a, 'b, 'extractor> Chunks<'a, 'b, 'extractor> {
 
         let res = match embedder.embed_index_ref(texts.as_slice(), threads) {
             Ok(embeddings) => {
-                for (docid, embedding) in ids.into_iter().zip(embeddings) {
-                    sender.set_vector(*docid, embedder_id, embedding).unwrap();
+                let mut embeddings = embeddings.into_iter();
+                // the chunks of a document are contiguous
+                for chunk_ids in ids.chunk_by(|left, right| left == right) {
+                    let docid = chunk_ids[0];
+                    if let [_] = chunk_ids {
+                        let embedding = embeddings.next().unwrap();
+                        sender.set_vector(docid, embedder_id, embedding).unwrap();
+                    } else if embedder.is_sparse() {
+                        // the packed sparse embeddings of the chunks are merged into a single one
+                        let embedding =
+                            embeddings.by_ref().take(chunk_ids.len()).flatten().collect();
+                        sender.set_vector(docid, embedder_id, embedding).unwrap();
+                    } else {
+                        let embeddings = embeddings.by_ref().take(chunk_ids.len()).collect();
+                        sender.set_vectors(docid, embedder_id, embeddings).unwrap();
+                    }
                 }
                 Ok(())
             }
@@ -484,6 +512,10 @@ impl<'a, 'b, 'extractor> Chunks<'a, 'b, 'extractor> {
         self.embedder_name
     }
 
+    pub fn is_sparse(&self) -> bool {
+        self.embedder.is_sparse()
+    }
+
     fn set_regenerate(&self, docid: DocumentId, regenerate: bool) {
         let mut user_provided = self.user_provided.borrow_mut();
         let user_provided = user_provided.0.entry_ref(self.embedder_name).or_default();
@@ -501,6 +533,12 @@ impl<'a, 'b, 'extractor> Chunks<'a, 'b, 'extractor> {
         docid: DocumentId,
         embeddings: Vec<Embedding>,
     ) -> Result<()> {
+        if self.embedder.is_sparse() {
+            // the packed sparse embeddings provided by the user are merged into a single one
+            let embedding = embeddings.into_iter().flatten().collect();
+            self.sender.set_vector(docid, self.embedder_id, embedding).unwrap();
+            return Ok(());
+        }
         for (embedding_index, embedding) in embeddings.iter().enumerate() {
             if embedding.len() != self.dimensions {
                 return Err(UserError::InvalidIndexingVectorDimensions {
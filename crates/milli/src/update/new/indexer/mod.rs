@@ -21,6 +21,7 @@ use crate::documents::PrimaryKey;
 use crate::fields_ids_map::metadata::{FieldIdMapWithMetadata, MetadataBuilder};
 use crate::progress::Progress;
 use crate::update::GrenadParameters;
+use crate::vector::scalar::ScalarQuantizedStore;
 use crate::vector::{ArroyWrapper, EmbeddingConfigs};
 use crate::{FieldsIdsMap, GlobalFieldsIdsMap, Index, InternalError, Result, ThreadPoolNoAbort};
 
@@ -166,10 +167,17 @@ where
         let global_fields_ids_map = GlobalFieldsIdsMap::new(&new_fields_ids_map);
 
         let vector_arroy = index.vector_arroy;
+        // maps the scalar quantized embedders to whether their original embeddings are kept
+        let scalar_quantized_embedders: HashMap<_, _> = index
+            .embedding_configs(wtxn)?
+            .into_iter()
+            .filter(|config| config.config.scalar_quantized())
+            .map(|config| (config.name, config.config.full_precision_rescoring()))
+            .collect();
         let arroy_writers: Result<HashMap<_, _>> = embedders
             .inner_as_ref()
             .iter()
-            .map(|(embedder_name, (embedder, _, was_quantized))| {
+            .map(|(embedder_name, (embedder, _, was_quantized, distance))| {
                 let embedder_index = index.embedder_category_id.get(wtxn, embedder_name)?.ok_or(
                     InternalError::DatabaseMissingEntry {
                         db_name: "embedder_category_id",
@@ -178,7 +186,14 @@ where
                 )?;
 
                 let dimensions = embedder.dimensions();
-                let writer = ArroyWrapper::new(vector_arroy, embedder_index, *was_quantized);
+                let full_precision = scalar_quantized_embedders.get(embedder_name);
+                let writer =
+                    ArroyWrapper::new(vector_arroy, embedder_index, *was_quantized, *distance)
+                        .with_scalar_quantization(
+                            full_precision
+                                .map(|_| ScalarQuantizedStore::new(index, embedder_index)),
+                        )
+                        .with_full_precision_rescoring(full_precision.copied().unwrap_or_default());
 
                 Ok((
                     embedder_index,
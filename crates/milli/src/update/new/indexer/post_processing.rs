@@ -1,11 +1,13 @@
 use std::cmp::Ordering;
+use std::collections::BTreeSet;
 
 use heed::types::{Bytes, DecodeIgnore, Str};
-use heed::RwTxn;
+use heed::{BytesDecode, RwTxn};
 use itertools::{merge_join_by, EitherOrBoth};
 
 use super::document_changes::IndexingContext;
 use crate::facet::FacetType;
+use crate::heed_codec::StrBEU16Codec;
 use crate::index::main_key::{WORDS_FST_KEY, WORDS_PREFIXES_FST_KEY};
 use crate::progress::Progress;
 use crate::update::del_add::DelAdd;
@@ -20,7 +22,7 @@ use crate::update::new::words_prefix_docids::{
     compute_word_prefix_position_docids,
 };
 use crate::update::new::FacetFieldIdsDelta;
-use crate::update::{FacetsUpdateBulk, GrenadParameters};
+use crate::update::{compute_stem_words, FacetsUpdateBulk, GrenadParameters};
 use crate::{GlobalFieldsIdsMap, Index, Result};
 
 pub(super) fn post_process<MSP>(
@@ -43,6 +45,7 @@ where
     )?;
     compute_facet_search_database(index, wtxn, global_fields_ids_map, indexing_context.progress)?;
     indexing_context.progress.update_progress(IndexingStep::PostProcessingWords);
+    let stem_words_delta = compute_stem_words_delta(index, wtxn)?;
     if let Some(prefix_delta) = compute_word_fst(index, wtxn, indexing_context.progress)? {
         compute_prefix_database(
             index,
@@ -52,6 +55,7 @@ where
             indexing_context.progress,
         )?;
     };
+    compute_stem_words(wtxn, index, &stem_words_delta)?;
     Ok(())
 }
 
@@ -78,6 +82,41 @@ fn compute_prefix_database(
     compute_word_prefix_position_docids(wtxn, index, &modified, &deleted, grenad_parameters)
 }
 
+/// Returns the words that were added to or removed from one of the fields of the index,
+/// when stemming is enabled for any of them.
+#[tracing::instrument(level = "trace", skip_all, target = "indexing::stem_words")]
+fn compute_stem_words_delta(index: &Index, wtxn: &RwTxn) -> Result<BTreeSet<String>> {
+    let mut words = BTreeSet::new();
+    let rules = index.localized_attributes_rules(wtxn)?.unwrap_or_default();
+    if !rules.iter().any(|rule| rule.stemming) {
+        return Ok(words);
+    }
+
+    let rtxn = index.read_txn()?;
+    let previous_keys = index.word_fid_docids.remap_types::<Bytes, Bytes>().iter(&rtxn)?;
+    let current_keys = index.word_fid_docids.remap_types::<Bytes, Bytes>().iter(wtxn)?;
+    for eob in merge_join_by(previous_keys, current_keys, |lhs, rhs| match (lhs, rhs) {
+        (Ok((l, _)), Ok((r, _))) => l.cmp(r),
+        (Err(_), _) | (_, Err(_)) => Ordering::Equal,
+    }) {
+        let key = match eob {
+            EitherOrBoth::Both(lhs, rhs) => {
+                let (key, lhs_bytes) = lhs?;
+                let (_, rhs_bytes) = rhs?;
+                if lhs_bytes == rhs_bytes {
+                    continue;
+                }
+                key
+            }
+            EitherOrBoth::Left(result) | EitherOrBoth::Right(result) => result?.0,
+        };
+        let (word, _fid) = StrBEU16Codec::bytes_decode(key).map_err(heed::Error::Decoding)?;
+        words.insert(word.to_string());
+    }
+
+    Ok(words)
+}
+
 #[tracing::instrument(level = "trace", skip_all, target = "indexing")]
 fn compute_word_fst(
     index: &Index,
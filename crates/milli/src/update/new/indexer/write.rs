@@ -13,6 +13,7 @@ use crate::fields_ids_map::metadata::FieldIdMapWithMetadata;
 use crate::index::IndexEmbeddingConfig;
 use crate::progress::Progress;
 use crate::update::settings::InnerIndexSettings;
+use crate::vector::sparse::{SparseEmbedding, SparseVectorStore};
 use crate::vector::{ArroyWrapper, Embedder, EmbeddingConfigs, Embeddings};
 use crate::{Error, Index, InternalError, Result, UserError};
 
@@ -26,6 +27,12 @@ pub fn write_to_db(
     // Used by by the ArroySetVector to copy the embedding into an
     // aligned memory area, required by arroy to accept a new vector.
     let mut aligned_embedding = Vec::new();
+    // The sparse embeddings buffer the changes of their posting lists until the end of the writes.
+    let mut sparse_stores: HashMap<u8, SparseVectorStore<'_>> = arroy_writers
+        .iter()
+        .filter(|(_, (_, embedder, _, _))| embedder.is_sparse())
+        .map(|(embedder_id, _)| (*embedder_id, SparseVectorStore::new(index, *embedder_id)))
+        .collect();
     let span = tracing::trace_span!(target: "indexing::write_db", "all");
     let _entered = span.enter();
     let span = tracing::trace_span!(target: "indexing::write_db", "post_merge");
@@ -55,12 +62,18 @@ pub fn write_to_db(
                 let LargeVectors { docid, embedder_id, .. } = large_vectors;
                 let (_, _, writer, dimensions) =
                     arroy_writers.get(&embedder_id).expect("requested a missing embedder");
-                let mut embeddings = Embeddings::new(*dimensions);
-                for embedding in large_vectors.read_embeddings(*dimensions) {
-                    embeddings.push(embedding.to_vec()).unwrap();
+                if let Some(sparse_store) = sparse_stores.get_mut(&embedder_id) {
+                    let embedding =
+                        SparseEmbedding::from_packed(large_vectors.read_all_embeddings());
+                    sparse_store.add_item(wtxn, docid, &embedding)?;
+                } else {
+                    let mut embeddings = Embeddings::new(*dimensions);
+                    for embedding in large_vectors.read_embeddings(*dimensions) {
+                        embeddings.push(embedding.to_vec()).unwrap();
+                    }
+                    writer.del_items(wtxn, *dimensions, docid)?;
+                    writer.add_items(wtxn, docid, &embeddings)?;
                 }
-                writer.del_items(wtxn, *dimensions, docid)?;
-                writer.add_items(wtxn, docid, &embeddings)?;
             }
         }
 
@@ -71,11 +84,23 @@ pub fn write_to_db(
             index,
             wtxn,
             arroy_writers,
+            &mut sparse_stores,
             &mut aligned_embedding,
         )?;
     }
 
-    write_from_bbqueue(&mut writer_receiver, index, wtxn, arroy_writers, &mut aligned_embedding)?;
+    write_from_bbqueue(
+        &mut writer_receiver,
+        index,
+        wtxn,
+        arroy_writers,
+        &mut sparse_stores,
+        &mut aligned_embedding,
+    )?;
+
+    for sparse_store in sparse_stores.values_mut() {
+        sparse_store.flush(wtxn)?;
+    }
 
     Ok(ChannelCongestion {
         attempts: writer_receiver.sent_messages_attempts(),
@@ -117,14 +142,20 @@ where
 
     let seed = rand::random();
     let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
-    for (_index, (_embedder_name, _embedder, writer, dimensions)) in arroy_writers {
+    for (_index, (_embedder_name, embedder, writer, dimensions)) in arroy_writers {
+        if embedder.is_sparse() {
+            // sparse embeddings are directly searchable in their inverted index
+            continue;
+        }
         let dimensions = *dimensions;
+        let distance = writer.distance();
         writer.build_and_quantize(
             wtxn,
             progress,
             &mut rng,
             dimensions,
             false,
+            distance,
             arroy_memory,
             must_stop_processing,
         )?;
@@ -167,6 +198,7 @@ pub fn write_from_bbqueue(
     index: &Index,
     wtxn: &mut RwTxn<'_>,
     arroy_writers: &HashMap<u8, (&str, &crate::vector::Embedder, ArroyWrapper, usize)>,
+    sparse_stores: &mut HashMap<u8, SparseVectorStore<'_>>,
     aligned_embedding: &mut Vec<f32>,
 ) -> crate::Result<()> {
     while let Some(frame_with_header) = writer_receiver.recv_frame() {
@@ -207,7 +239,11 @@ pub fn write_from_bbqueue(
                 }
             }
             EntryHeader::ArroyDeleteVector(ArroyDeleteVector { docid }) => {
-                for (_index, (_name, _embedder, writer, dimensions)) in arroy_writers {
+                for (index_id, (_name, _embedder, writer, dimensions)) in arroy_writers {
+                    if let Some(sparse_store) = sparse_stores.get_mut(index_id) {
+                        sparse_store.del_item(wtxn, docid)?;
+                        continue;
+                    }
                     let dimensions = *dimensions;
                     writer.del_items(wtxn, dimensions, docid)?;
                 }
@@ -217,8 +253,13 @@ pub fn write_from_bbqueue(
                 let frame = frame_with_header.frame();
                 let (_, _, writer, dimensions) =
                     arroy_writers.get(&embedder_id).expect("requested a missing embedder");
-                let mut embeddings = Embeddings::new(*dimensions);
                 let all_embeddings = asvs.read_all_embeddings_into_vec(frame, aligned_embedding);
+                if let Some(sparse_store) = sparse_stores.get_mut(&embedder_id) {
+                    let embedding = SparseEmbedding::from_packed(all_embeddings);
+                    sparse_store.add_item(wtxn, docid, &embedding)?;
+                    continue;
+                }
+                let mut embeddings = Embeddings::new(*dimensions);
                 if embeddings.append(all_embeddings.to_vec()).is_err() {
                     return Err(Error::UserError(UserError::InvalidVectorDimensions {
                         expected: *dimensions,
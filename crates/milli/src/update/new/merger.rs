@@ -27,6 +27,8 @@ where
     MSP: Fn() -> bool + Sync,
 {
     let mut rtree = index.geo_rtree(rtxn)?.unwrap_or_default();
+    let mut geojson_rtree = index.geojson_rtree(rtxn)?.unwrap_or_default();
+    let mut geojson_updated = false;
     let mut faceted = index.geo_faceted_documents_ids(rtxn)?;
 
     for data in datastore {
@@ -39,15 +41,25 @@ where
             let extracted_geo_point = result?;
             let removed = rtree.remove(&GeoPoint::from(extracted_geo_point));
             debug_assert!(removed.is_some());
-            let removed = faceted.remove(extracted_geo_point.docid);
-            debug_assert!(removed);
+            // a document can have many points, we only remove it once
+            faceted.remove(extracted_geo_point.docid);
         }
 
         for result in frozen.iter_and_clear_inserted()? {
             let extracted_geo_point = result?;
             rtree.insert(GeoPoint::from(extracted_geo_point));
-            let inserted = faceted.insert(extracted_geo_point.docid);
-            debug_assert!(inserted);
+            faceted.insert(extracted_geo_point.docid);
+        }
+
+        for polygon in frozen.removed_polygons.drain(..) {
+            let removed = geojson_rtree.remove(&polygon);
+            debug_assert!(removed.is_some());
+            geojson_updated = true;
+        }
+
+        for polygon in frozen.inserted_polygons.drain(..) {
+            geojson_rtree.insert(polygon);
+            geojson_updated = true;
         }
     }
 
@@ -57,6 +69,15 @@ where
 
     let rtree_mmap = unsafe { Mmap::map(&file)? };
     geo_sender.set_rtree(rtree_mmap).unwrap();
+
+    if geojson_updated {
+        let mut file = tempfile::tempfile()?;
+        bincode::serialize_into(&mut file, &geojson_rtree).map_err(InternalError::BincodeError)?;
+        file.sync_all()?;
+
+        let rtree_mmap = unsafe { Mmap::map(&file)? };
+        geo_sender.set_geojson_rtree(rtree_mmap).unwrap();
+    }
     geo_sender.set_geo_faceted(&faceted)?;
 
     Ok(())
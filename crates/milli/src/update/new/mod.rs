@@ -1,4 +1,6 @@
 pub use document_change::{Deletion, DocumentChange, Insertion, Update};
+pub(crate) use extract::extract_geo_coordinates_from_value;
+pub use extract::extract_geojson_geometry_from_value;
 pub use indexer::ChannelCongestion;
 pub use merger::{
     merge_and_send_docids, merge_and_send_facet_docids, FacetDatabases, FacetFieldIdsDelta,
@@ -14,6 +14,8 @@ use crate::constants::RESERVED_VECTORS_FIELD_NAME;
 use crate::documents::FieldIdMapper;
 use crate::index::IndexEmbeddingConfig;
 use crate::vector::parsed_vectors::{RawVectors, RawVectorsError, VectorOrArrayOfVectors};
+use crate::vector::scalar::ScalarQuantizedStore;
+use crate::vector::sparse::SparseVectorStore;
 use crate::vector::{ArroyWrapper, Embedding, EmbeddingConfigs};
 use crate::{DocumentId, Index, InternalError, Result, UserError};
 
@@ -25,11 +27,15 @@ pub enum Embeddings<'doc> {
     FromDb(Vec<Embedding>),
 }
 impl<'doc> Embeddings<'doc> {
+    /// Returns the embeddings, checking that they are sparse if and only if the embedder is.
+    ///
+    /// The sparse embeddings are packed with [`crate::vector::sparse::SparseEmbedding::to_packed`].
     pub fn into_vec(
         self,
         doc_alloc: &'doc Bump,
         embedder_name: &str,
-    ) -> std::result::Result<Vec<Embedding>, deserr::errors::JsonError> {
+        sparse: bool,
+    ) -> std::result::Result<Vec<Embedding>, String> {
         match self {
             Embeddings::FromJsonExplicit(value) => {
                 let vectors_ref = deserr::ValuePointerRef::Key {
@@ -45,8 +51,9 @@ impl<'doc> Embeddings<'doc> {
                 let v: VectorOrArrayOfVectors = VectorOrArrayOfVectors::deserialize_from_value(
                     DeserrRawValue::new_in(value, doc_alloc).into_value(),
                     embeddings_ref,
-                )?;
-                Ok(v.into_array_of_vectors().unwrap_or_default())
+                )
+                .map_err(|error: deserr::errors::JsonError| error.to_string())?;
+                v.into_embeddings(embedder_name, sparse)
             }
             Embeddings::FromJsonImplicityUserProvided(value) => {
                 let vectors_ref = deserr::ValuePointerRef::Key {
@@ -59,8 +66,9 @@ impl<'doc> Embeddings<'doc> {
                 let v: VectorOrArrayOfVectors = VectorOrArrayOfVectors::deserialize_from_value(
                     DeserrRawValue::new_in(value, doc_alloc).into_value(),
                     embedders_ref,
-                )?;
-                Ok(v.into_array_of_vectors().unwrap_or_default())
+                )
+                .map_err(|error: deserr::errors::JsonError| error.to_string())?;
+                v.into_embeddings(embedder_name, sparse)
             }
             Embeddings::FromDb(vec) => Ok(vec),
         }
@@ -119,8 +127,32 @@ impl<'t> VectorDocumentFromDb<'t> {
         embedder_id: u8,
         config: &IndexEmbeddingConfig,
     ) -> Result<VectorEntry<'t>> {
-        let reader =
-            ArroyWrapper::new(self.index.vector_arroy, embedder_id, config.config.quantized());
+        if config.config.is_sparse() {
+            let store = SparseVectorStore::new(self.index, embedder_id);
+            let vectors = store
+                .item_vector(self.rtxn, self.docid)?
+                .map(|embedding| vec![embedding.to_packed()])
+                .unwrap_or_default();
+            return Ok(VectorEntry {
+                has_configured_embedder: true,
+                embeddings: Some(Embeddings::FromDb(vectors)),
+                regenerate: !config.user_provided.contains(self.docid),
+                implicit: false,
+            });
+        }
+
+        let reader = ArroyWrapper::new(
+            self.index.vector_arroy,
+            embedder_id,
+            config.config.quantized(),
+            config.config.distance(),
+        )
+        .with_scalar_quantization(
+            config
+                .config
+                .scalar_quantized()
+                .then(|| ScalarQuantizedStore::new(self.index, embedder_id)),
+        );
         let vectors = reader.item_vectors(self.rtxn, self.docid)?;
 
         Ok(VectorEntry {
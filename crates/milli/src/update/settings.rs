@@ -15,7 +15,7 @@ use super::del_add::{DelAdd, DelAddOperation};
 use super::index_documents::{IndexDocumentsConfig, Transform};
 use super::IndexerConfig;
 use crate::attribute_patterns::PatternMatch;
-use crate::constants::RESERVED_GEO_FIELD_NAME;
+use crate::constants::{RESERVED_GEOJSON_FIELD_NAME, RESERVED_GEO_FIELD_NAME};
 use crate::criterion::Criterion;
 use crate::disabled_typos_terms::DisabledTyposTerms;
 use crate::error::UserError;
@@ -26,16 +26,20 @@ use crate::index::{
     DEFAULT_MIN_WORD_LEN_TWO_TYPOS,
 };
 use crate::order_by_map::OrderByMap;
-use crate::prompt::default_max_bytes;
+use crate::prompt::{default_max_bytes, Chunking};
 use crate::proximity::ProximityPrecision;
 use crate::update::index_documents::IndexDocumentsMethod;
 use crate::update::{IndexDocuments, UpdateIndexingStep};
+use crate::vector::rerank::{Reranker, RerankerSettings};
+use crate::vector::scalar::ScalarQuantizedStore;
 use crate::vector::settings::{
     EmbedderAction, EmbedderSource, EmbeddingSettings, NestingContext, ReindexAction,
     SubEmbeddingSettings, WriteBackToDocuments,
 };
-use crate::vector::{Embedder, EmbeddingConfig, EmbeddingConfigs};
-use crate::{FieldId, FilterableAttributesRule, Index, LocalizedAttributesRule, Result};
+use crate::vector::{Distance, Embedder, EmbeddingConfig, EmbeddingConfigs};
+use crate::{
+    FieldId, FilterableAttributesRule, Index, LocalizedAttributesRule, QueryRule, Result, Synonyms,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq, Copy)]
 pub enum Setting<T> {
@@ -167,7 +171,7 @@ pub struct Settings<'a, 't, 'i> {
     separator_tokens: Setting<BTreeSet<String>>,
     dictionary: Setting<BTreeSet<String>>,
     distinct_field: Setting<String>,
-    synonyms: Setting<BTreeMap<String, Vec<String>>>,
+    synonyms: Setting<BTreeMap<String, Synonyms>>,
     primary_key: Setting<String>,
     authorize_typos: Setting<bool>,
     disable_on_numbers: Setting<bool>,
@@ -185,6 +189,8 @@ pub struct Settings<'a, 't, 'i> {
     localized_attributes_rules: Setting<Vec<LocalizedAttributesRule>>,
     prefix_search: Setting<PrefixSearch>,
     facet_search: Setting<bool>,
+    query_rules: Setting<Vec<QueryRule>>,
+    rerankers: Setting<BTreeMap<String, RerankerSettings>>,
 }
 
 impl<'a, 't, 'i> Settings<'a, 't, 'i> {
@@ -223,6 +229,8 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
             localized_attributes_rules: Setting::NotSet,
             prefix_search: Setting::NotSet,
             facet_search: Setting::NotSet,
+            query_rules: Setting::NotSet,
+            rerankers: Setting::NotSet,
             indexer_config,
         }
     }
@@ -321,7 +329,9 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.synonyms = Setting::Reset;
     }
 
-    pub fn set_synonyms(&mut self, synonyms: BTreeMap<String, Vec<String>>) {
+    pub fn set_synonyms<S: Into<Synonyms>>(&mut self, synonyms: BTreeMap<String, S>) {
+        let synonyms: BTreeMap<_, _> =
+            synonyms.into_iter().map(|(word, synonyms)| (word, synonyms.into())).collect();
         self.synonyms = if synonyms.is_empty() { Setting::Reset } else { Setting::Set(synonyms) }
     }
 
@@ -445,6 +455,22 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.prefix_search = Setting::Reset;
     }
 
+    pub fn set_query_rules(&mut self, value: Vec<QueryRule>) {
+        self.query_rules = Setting::Set(value);
+    }
+
+    pub fn reset_query_rules(&mut self) {
+        self.query_rules = Setting::Reset;
+    }
+
+    pub fn set_rerankers(&mut self, value: BTreeMap<String, RerankerSettings>) {
+        self.rerankers = Setting::Set(value);
+    }
+
+    pub fn reset_rerankers(&mut self) {
+        self.rerankers = Setting::Reset;
+    }
+
     pub fn set_facet_search(&mut self, value: bool) {
         self.facet_search = Setting::Set(value);
     }
@@ -689,7 +715,13 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
                     builder.separators(separators);
                 }
 
-                let dictionary = self.index.dictionary(self.wtxn)?;
+                // The placeholders must keep their brackets to only match the placeholders
+                // written in the documents and not the words they contain.
+                let mut dictionary = self.index.dictionary(self.wtxn)?;
+                let placeholders = user_synonyms.iter().filter(|(_, s)| s.is_placeholder());
+                for (placeholder, _) in placeholders {
+                    dictionary.get_or_insert_with(BTreeSet::new).insert(placeholder.clone());
+                }
                 let dictionary: Option<Vec<_>> =
                     dictionary.as_ref().map(|x| x.iter().map(String::as_str).collect());
                 if let Some(ref dictionary) = dictionary {
@@ -699,33 +731,52 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
                 let tokenizer = builder.build();
 
                 let mut new_synonyms = HashMap::new();
+                let mut new_typo_synonyms = HashMap::new();
                 for (word, synonyms) in user_synonyms {
-                    // Normalize both the word and associated synonyms.
-                    let normalized_word = normalize(&tokenizer, word);
-                    let normalized_synonyms: Vec<_> = synonyms
-                        .iter()
-                        .map(|synonym| normalize(&tokenizer, synonym))
-                        .filter(|synonym| !synonym.is_empty())
-                        .collect();
-
-                    // Store the normalized synonyms under the normalized word,
-                    // merging the possible duplicate words.
-                    if !normalized_word.is_empty() && !normalized_synonyms.is_empty() {
-                        let entry = new_synonyms.entry(normalized_word).or_insert_with(Vec::new);
-                        entry.extend(normalized_synonyms.into_iter());
+                    // The synonyms ranked as typos are stored apart from the other ones.
+                    let new_synonyms = if synonyms.rank_as_typo() {
+                        &mut new_typo_synonyms
+                    } else {
+                        &mut new_synonyms
+                    };
+
+                    for (word, synonym) in synonyms.derivations(word) {
+                        // Normalize both the word and associated synonym.
+                        let normalized_word = normalize(&tokenizer, word);
+                        let normalized_synonym = normalize(&tokenizer, synonym);
+
+                        // Store the normalized synonym under the normalized word,
+                        // merging the possible duplicate words.
+                        if !normalized_word.is_empty() && !normalized_synonym.is_empty() {
+                            let entry =
+                                new_synonyms.entry(normalized_word).or_insert_with(Vec::new);
+                            entry.push(normalized_synonym);
+                        }
                     }
                 }
 
                 // Make sure that we don't have duplicate synonyms.
-                new_synonyms.iter_mut().for_each(|(_, synonyms)| {
-                    synonyms.sort_unstable();
-                    synonyms.dedup();
-                });
+                for new_synonyms in [&mut new_synonyms, &mut new_typo_synonyms] {
+                    new_synonyms.iter_mut().for_each(|(_, synonyms)| {
+                        synonyms.sort_unstable();
+                        synonyms.dedup();
+                    });
+                }
 
                 let old_synonyms = self.index.synonyms(self.wtxn)?;
+                let old_typo_synonyms = self.index.typo_synonyms(self.wtxn)?;
+                let old_user_synonyms = self.index.user_defined_synonyms(self.wtxn)?;
 
-                if new_synonyms != old_synonyms {
-                    self.index.put_synonyms(self.wtxn, &new_synonyms, user_synonyms)?;
+                if new_synonyms != old_synonyms
+                    || new_typo_synonyms != old_typo_synonyms
+                    || *user_synonyms != old_user_synonyms
+                {
+                    self.index.put_synonyms(
+                        self.wtxn,
+                        &new_synonyms,
+                        &new_typo_synonyms,
+                        user_synonyms,
+                    )?;
                     Ok(true)
                 } else {
                     Ok(false)
@@ -1059,6 +1110,28 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         }
     }
 
+    /// Deletes the original embeddings of a scalar quantized embedder once its
+    /// full-precision rescoring is disabled.
+    fn delete_unused_full_precision_vectors(
+        &mut self,
+        name: &str,
+        was_rescoring: bool,
+        new: &Setting<EmbeddingSettings>,
+    ) -> Result<()> {
+        let Setting::Set(new) = new else { return Ok(()) };
+        let is_rescoring = new.full_precision_rescoring.set().unwrap_or_default();
+        // when the scalar quantization is disabled too, the original embeddings are restored
+        // in arroy during the indexing, then deleted along with the scalar quantized ones
+        let is_scalar_quantized = new.scalar_quantized.set().unwrap_or_default();
+        if was_rescoring && !is_rescoring && is_scalar_quantized {
+            if let Some(embedder_id) = self.index.embedder_category_id.get(self.wtxn, name)? {
+                ScalarQuantizedStore::new(self.index, embedder_id)
+                    .clear_full_precision(self.wtxn)?;
+            }
+        }
+        Ok(())
+    }
+
     fn update_embedding_configs_set(
         &mut self,
         configs: BTreeMap<String, Setting<EmbeddingSettings>>,
@@ -1082,6 +1155,7 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
                 // updated config
                 EitherOrBoth::Both((name, (old, user_provided)), (_, new)) => {
                     let was_quantized = old.binary_quantized.set().unwrap_or_default();
+                    let was_rescoring = old.full_precision_rescoring.set().unwrap_or_default();
                     let settings_diff = SettingsDiff::from_settings(&name, old, new)?;
                     match settings_diff {
                         SettingsDiff::Remove => {
@@ -1121,9 +1195,15 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
                             );
                             let new =
                                 validate_embedding_settings(Setting::Set(updated_settings), &name)?;
+                            validate_quantization_settings(&name, &new)?;
+                            self.delete_unused_full_precision_vectors(&name, was_rescoring, &new)?;
                             updated_configs.insert(name, (new, user_provided));
                         }
-                        SettingsDiff::UpdateWithoutReindex { updated_settings, quantize } => {
+                        SettingsDiff::UpdateWithoutReindex {
+                            updated_settings,
+                            quantize,
+                            rebuild,
+                        } => {
                             tracing::debug!(
                                 embedder = name,
                                 user_provided = user_provided.len(),
@@ -1131,10 +1211,14 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
                             );
                             let new =
                                 validate_embedding_settings(Setting::Set(updated_settings), &name)?;
-                            if quantize {
+                            validate_quantization_settings(&name, &new)?;
+                            self.delete_unused_full_precision_vectors(&name, was_rescoring, &new)?;
+                            // the trees are rebuilt from the stored embeddings
+                            if quantize || rebuild {
                                 embedder_actions.insert(
                                     name.clone(),
-                                    EmbedderAction::default().with_is_being_quantized(true),
+                                    EmbedderAction { was_quantized, ..Default::default() }
+                                        .with_is_being_quantized(quantize),
                                 );
                             }
                             updated_configs.insert(name, (new, user_provided));
@@ -1155,6 +1239,7 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
                         &mut setting,
                     );
                     let setting = validate_embedding_settings(setting, &name)?;
+                    validate_quantization_settings(&name, &setting)?;
                     embedder_actions.insert(
                         name.clone(),
                         EmbedderAction::with_reindex(ReindexAction::FullReindex, false),
@@ -1239,6 +1324,44 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         Ok(())
     }
 
+    fn update_query_rules(&mut self) -> Result<()> {
+        match &self.query_rules {
+            Setting::Set(new) => {
+                let conditions = QueryRule::parse_conditions(new).map_err(UserError::from)?;
+                let old = self.index.query_rules(self.wtxn)?;
+                if old.as_ref() != Some(new) {
+                    self.index.put_query_rules(self.wtxn, new.clone())?;
+                    self.index.put_query_rules_conditions(self.wtxn, conditions)?;
+                }
+            }
+            Setting::Reset => {
+                self.index.delete_query_rules(self.wtxn)?;
+                self.index.delete_query_rules_conditions(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
+    fn update_rerankers(&mut self) -> Result<()> {
+        match &self.rerankers {
+            Setting::Set(new) => {
+                Reranker::validate(new).map_err(UserError::from)?;
+                let old = self.index.rerankers(self.wtxn)?;
+                if old.as_ref() != Some(new) {
+                    self.index.put_rerankers(self.wtxn, new.clone())?;
+                }
+            }
+            Setting::Reset => {
+                self.index.delete_rerankers(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
     pub fn execute<FP, FA>(mut self, progress_callback: FP, should_abort: FA) -> Result<()>
     where
         FP: Fn(UpdateIndexingStep) + Sync,
@@ -1260,6 +1383,8 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.update_sort_facet_values_by()?;
         self.update_pagination_max_total_hits()?;
         self.update_search_cutoff()?;
+        self.update_query_rules()?;
+        self.update_rerankers()?;
 
         // could trigger re-indexing
         self.update_filterable()?;
@@ -1380,7 +1505,7 @@ impl InnerIndexSettingsDiff {
 
         // if the user-defined searchables changed, then we need to reindex prompts.
         if cache_user_defined_searchables {
-            for (embedder_name, (config, _, _quantized)) in
+            for (embedder_name, (config, _, _quantized, _distance)) in
                 new_settings.embedding_configs.inner_as_ref()
             {
                 let was_quantized =
@@ -1514,6 +1639,12 @@ impl InnerIndexSettingsDiff {
                     if old_facet_level_database != new_facet_level_database {
                         return true;
                     }
+
+                    // Check if the field is a date field in the old and new settings.
+                    // If there is a difference, we need to reindex the numeric facet databases.
+                    if old_filterable_features.is_date() != new_filterable_features.is_date() {
+                        return true;
+                    }
                 }
             }
         }
@@ -1521,6 +1652,11 @@ impl InnerIndexSettingsDiff {
         false
     }
 
+    /// Returns `true` if the field became a date field or stopped being one.
+    pub fn date_field_changed(&self, field_id: FieldId) -> bool {
+        self.old.is_date_field(field_id) != self.new.is_date_field(field_id)
+    }
+
     pub fn global_facet_settings_changed(&self) -> bool {
         self.old.localized_attributes_rules != self.new.localized_attributes_rules
             || self.old.facet_search != self.new.facet_search
@@ -1540,7 +1676,49 @@ impl InnerIndexSettingsDiff {
 
     pub fn run_geo_indexing(&self) -> bool {
         self.old.geo_fields_ids != self.new.geo_fields_ids
-            || (!self.settings_update_only && self.new.geo_fields_ids.is_some())
+            || self.old.geojson_fid != self.new.geojson_fid
+            || (!self.settings_update_only
+                && (self.new.geo_fields_ids.is_some() || self.new.geojson_fid.is_some()))
+    }
+
+    /// The distance of the embeddings currently stored in arroy for the embedder.
+    ///
+    /// The embeddings are written with this distance during the indexing, and converted
+    /// to the distance of the new settings when the trees are built.
+    pub fn stored_embedder_distance(&self, embedder_name: &str) -> Distance {
+        self.old
+            .embedding_configs
+            .get(embedder_name)
+            .or_else(|| self.new.embedding_configs.get(embedder_name))
+            .map(|(_, _, _, distance)| distance)
+            .unwrap_or_default()
+    }
+
+    /// The distance of the embedder in the new settings.
+    pub fn embedder_distance(&self, embedder_name: &str) -> Distance {
+        match self.new.embedding_configs.get(embedder_name) {
+            Some((_, _, _, distance)) => distance,
+            None => self.stored_embedder_distance(embedder_name),
+        }
+    }
+
+    /// Whether the embeddings currently stored for the embedder are scalar quantized.
+    pub fn stored_embedder_scalar_quantized(&self, embedder_name: &str) -> bool {
+        if self.old.embedding_configs.contains(embedder_name) {
+            self.old.scalar_quantized_embedders.contains(embedder_name)
+        } else {
+            self.new.scalar_quantized_embedders.contains(embedder_name)
+        }
+    }
+
+    /// Whether the embedder is scalar quantized in the new settings.
+    pub fn embedder_scalar_quantized(&self, embedder_name: &str) -> bool {
+        self.new.scalar_quantized_embedders.contains(embedder_name)
+    }
+
+    /// Whether the original embeddings of the embedder are kept in the new settings.
+    pub fn embedder_full_precision_rescoring(&self, embedder_name: &str) -> bool {
+        self.new.full_precision_rescoring_embedders.contains(embedder_name)
     }
 }
 
@@ -1560,7 +1738,10 @@ pub(crate) struct InnerIndexSettings {
     pub disabled_typos_terms: DisabledTyposTerms,
     pub proximity_precision: ProximityPrecision,
     pub embedding_configs: EmbeddingConfigs,
+    pub scalar_quantized_embedders: BTreeSet<String>,
+    pub full_precision_rescoring_embedders: BTreeSet<String>,
     pub geo_fields_ids: Option<(FieldId, FieldId)>,
+    pub geojson_fid: Option<FieldId>,
     pub prefix_search: PrefixSearch,
     pub facet_search: bool,
 }
@@ -1574,13 +1755,24 @@ impl InnerIndexSettings {
         let stop_words = index.stop_words(rtxn)?;
         let stop_words = stop_words.map(|sw| sw.map_data(Vec::from).unwrap());
         let allowed_separators = index.allowed_separators(rtxn)?;
-        let dictionary = index.dictionary(rtxn)?;
+        let dictionary = index.tokenizer_dictionary(rtxn)?;
         let mut fields_ids_map = index.fields_ids_map(rtxn)?;
         let exact_attributes = index.exact_attributes_ids(rtxn)?;
         let proximity_precision = index.proximity_precision(rtxn)?.unwrap_or_default();
+        let index_embedding_configs = index.embedding_configs(rtxn)?;
+        let scalar_quantized_embedders = index_embedding_configs
+            .iter()
+            .filter(|config| config.config.scalar_quantized())
+            .map(|config| config.name.clone())
+            .collect();
+        let full_precision_rescoring_embedders = index_embedding_configs
+            .iter()
+            .filter(|config| config.config.full_precision_rescoring())
+            .map(|config| config.name.clone())
+            .collect();
         let embedding_configs = match embedding_configs {
             Some(embedding_configs) => embedding_configs,
-            None => embedders(index.embedding_configs(rtxn)?)?,
+            None => embedders(index_embedding_configs)?,
         };
         let prefix_search = index.prefix_search(rtxn)?.unwrap_or_default();
         let facet_search = index.facet_search(rtxn)?;
@@ -1595,6 +1787,10 @@ impl InnerIndexSettings {
             }
             _ => None,
         };
+        let geojson_fid = match fields_ids_map.id(RESERVED_GEOJSON_FIELD_NAME) {
+            Some(fid) if index.is_geo_enabled(rtxn)? => Some(fid),
+            _ => None,
+        };
         let localized_attributes_rules =
             index.localized_attributes_rules(rtxn)?.unwrap_or_default();
         let filterable_attributes_rules = index.filterable_attributes_rules(rtxn)?;
@@ -1621,7 +1817,10 @@ impl InnerIndexSettings {
             exact_attributes,
             proximity_precision,
             embedding_configs,
+            scalar_quantized_embedders,
+            full_precision_rescoring_embedders,
             geo_fields_ids,
+            geojson_fid,
             prefix_search,
             facet_search,
             disabled_typos_terms,
@@ -1638,6 +1837,13 @@ impl InnerIndexSettings {
         )
     }
 
+    /// Returns `true` if the string values of the field must be parsed as dates.
+    pub fn is_date_field(&self, field_id: FieldId) -> bool {
+        self.fields_ids_map.metadata(field_id).is_some_and(|metadata| {
+            metadata.filterable_attributes_features(&self.filterable_attributes_rules).is_date()
+        })
+    }
+
     // find and insert the new field ids
     pub fn recompute_searchables(
         &mut self,
@@ -1668,7 +1874,7 @@ fn embedders(embedding_configs: Vec<IndexEmbeddingConfig>) -> Result<EmbeddingCo
         .map(
             |IndexEmbeddingConfig {
                  name,
-                 config: EmbeddingConfig { embedder_options, prompt, quantized },
+                 config: EmbeddingConfig { embedder_options, prompt, quantized, distance, .. },
                  ..
              }| {
                 let prompt = Arc::new(prompt.try_into().map_err(crate::Error::from)?);
@@ -1679,7 +1885,10 @@ fn embedders(embedding_configs: Vec<IndexEmbeddingConfig>) -> Result<EmbeddingCo
                         .map_err(crate::vector::Error::from)
                         .map_err(crate::Error::from)?,
                 );
-                Ok((name, (embedder, prompt, quantized.unwrap_or_default())))
+                Ok((
+                    name,
+                    (embedder, prompt, quantized.unwrap_or_default(), distance.unwrap_or_default()),
+                ))
             },
         )
         .collect();
@@ -1717,6 +1926,20 @@ fn validate_prompt(
     }
 }
 
+fn validate_chunking(name: &str, chunking: &Setting<Chunking>) -> Result<()> {
+    if let Setting::Set(Chunking { max_bytes, overlap_bytes }) = chunking {
+        if *max_bytes == 0 || overlap_bytes >= max_bytes {
+            return Err(crate::error::UserError::InvalidSettingsDocumentTemplateChunking {
+                embedder_name: name.to_owned(),
+                max_bytes: *max_bytes,
+                overlap_bytes: *overlap_bytes,
+            }
+            .into());
+        }
+    }
+    Ok(())
+}
+
 pub fn validate_embedding_settings(
     settings: Setting<EmbeddingSettings>,
     name: &str,
@@ -1731,6 +1954,7 @@ pub fn validate_embedding_settings(
         dimensions,
         document_template,
         document_template_max_bytes,
+        document_template_chunking,
         url,
         request,
         response,
@@ -1739,9 +1963,34 @@ pub fn validate_embedding_settings(
         distribution,
         headers,
         binary_quantized: binary_quantize,
+        distance,
+        scalar_quantized,
+        full_precision_rescoring,
     } = settings;
 
     let document_template = validate_prompt(name, document_template, document_template_max_bytes)?;
+    validate_chunking(name, &document_template_chunking)?;
+
+    if let Some(distance) = distance.set() {
+        if !distance.supports_binary_quantization() && binary_quantize.set() == Some(true) {
+            return Err(crate::UserError::InvalidSettingsEmbedder {
+                embedder_name: name.to_owned(),
+                message: format!(
+                    "`binaryQuantized` is not available for the `{distance}` distance"
+                ),
+            }
+            .into());
+        }
+        if !distance.supports_binary_quantization() && scalar_quantized.set() == Some(true) {
+            return Err(crate::UserError::InvalidSettingsEmbedder {
+                embedder_name: name.to_owned(),
+                message: format!(
+                    "`scalarQuantized` is not available for the `{distance}` distance"
+                ),
+            }
+            .into());
+        }
+    }
 
     if let Some(0) = dimensions.set() {
         return Err(crate::error::UserError::InvalidSettingsDimensions {
@@ -1762,8 +2011,31 @@ pub fn validate_embedding_settings(
         let request = crate::vector::rest::Request::new(request.to_owned())
             .map_err(|error| crate::UserError::VectorEmbeddingError(error.into()))?;
         if let Some(response) = response.as_ref().set() {
-            crate::vector::rest::Response::new(response.to_owned(), &request)
+            let response = crate::vector::rest::Response::new(response.to_owned(), &request)
                 .map_err(|error| crate::UserError::VectorEmbeddingError(error.into()))?;
+            if response.is_sparse() && binary_quantize.set() == Some(true) {
+                return Err(crate::UserError::InvalidSettingsEmbedder {
+                    embedder_name: name.to_owned(),
+                    message: "`binaryQuantized` is not available for embedders returning a `{{sparseEmbedding}}`".to_owned(),
+                }
+                .into());
+            }
+            if response.is_sparse() && scalar_quantized.set() == Some(true) {
+                return Err(crate::UserError::InvalidSettingsEmbedder {
+                    embedder_name: name.to_owned(),
+                    message: "`scalarQuantized` is not available for embedders returning a `{{sparseEmbedding}}`".to_owned(),
+                }
+                .into());
+            }
+            if let Some(distance) = distance.set().filter(|_| response.is_sparse()) {
+                if distance != Distance::Cosine {
+                    return Err(crate::UserError::InvalidSettingsEmbedder {
+                        embedder_name: name.to_owned(),
+                        message: format!("the `{distance}` distance is not available for embedders returning a `{{{{sparseEmbedding}}}}`, which always use the `cosine` distance"),
+                    }
+                    .into());
+                }
+            }
         }
     }
 
@@ -1778,6 +2050,7 @@ pub fn validate_embedding_settings(
             dimensions,
             document_template,
             document_template_max_bytes,
+            document_template_chunking,
             url,
             request,
             response,
@@ -1786,6 +2059,9 @@ pub fn validate_embedding_settings(
             distribution,
             headers,
             binary_quantized: binary_quantize,
+            distance,
+            scalar_quantized,
+            full_precision_rescoring,
         }));
     };
     EmbeddingSettings::check_settings(
@@ -1802,10 +2078,14 @@ pub fn validate_embedding_settings(
         &response,
         &document_template,
         &document_template_max_bytes,
+        &document_template_chunking,
         &headers,
         &search_embedder,
         &indexing_embedder,
         &binary_quantize,
+        &distance,
+        &scalar_quantized,
+        &full_precision_rescoring,
         &distribution,
     )?;
     match inferred_source {
@@ -1880,10 +2160,14 @@ pub fn validate_embedding_settings(
                         &embedder.response,
                         &embedder.document_template,
                         &embedder.document_template_max_bytes,
+                        &embedder.document_template_chunking,
                         &embedder.headers,
                         &search_embedder,
                         &indexing_embedder,
                         &embedder.binary_quantized,
+                        &embedder.distance,
+                        &embedder.scalar_quantized,
+                        &embedder.full_precision_rescoring,
                         &embedder.distribution,
                     )?;
                 } else {
@@ -1900,6 +2184,7 @@ pub fn validate_embedding_settings(
                     embedder.document_template,
                     embedder.document_template_max_bytes,
                 )?;
+                validate_chunking(name, &embedder.document_template_chunking)?;
 
                 if let Some(source) = embedder.source.set() {
                     let search_embedder = match embedder.search_embedder.clone() {
@@ -1935,10 +2220,14 @@ pub fn validate_embedding_settings(
                         &embedder.response,
                         &embedder.document_template,
                         &embedder.document_template_max_bytes,
+                        &embedder.document_template_chunking,
                         &embedder.headers,
                         &search_embedder,
                         &indexing_embedder,
                         &embedder.binary_quantized,
+                        &embedder.distance,
+                        &embedder.scalar_quantized,
+                        &embedder.full_precision_rescoring,
                         &embedder.distribution,
                     )?;
                 } else {
@@ -1962,6 +2251,7 @@ pub fn validate_embedding_settings(
         dimensions,
         document_template,
         document_template_max_bytes,
+        document_template_chunking,
         url,
         request,
         response,
@@ -1970,9 +2260,41 @@ pub fn validate_embedding_settings(
         distribution,
         headers,
         binary_quantized: binary_quantize,
+        distance,
+        scalar_quantized,
+        full_precision_rescoring,
     }))
 }
 
+/// Checks the quantization settings that depend on each other.
+///
+/// Unlike [`validate_embedding_settings`], this must only be called on the settings
+/// merged with the current ones, as a partial update may only set one of them.
+fn validate_quantization_settings(name: &str, settings: &Setting<EmbeddingSettings>) -> Result<()> {
+    let Setting::Set(settings) = settings else { return Ok(()) };
+    let scalar_quantized = settings.scalar_quantized.set() == Some(true);
+
+    if settings.binary_quantized.set() == Some(true) && scalar_quantized {
+        return Err(crate::UserError::InvalidSettingsEmbedder {
+            embedder_name: name.to_owned(),
+            message: "`binaryQuantized` and `scalarQuantized` cannot be enabled together"
+                .to_owned(),
+        }
+        .into());
+    }
+
+    if settings.full_precision_rescoring.set() == Some(true) && !scalar_quantized {
+        return Err(crate::UserError::InvalidSettingsEmbedder {
+            embedder_name: name.to_owned(),
+            message: "`fullPrecisionRescoring` is only available when `scalarQuantized` is `true`"
+                .to_owned(),
+        }
+        .into());
+    }
+
+    Ok(())
+}
+
 fn deserialize_sub_embedder(
     sub_embedder: serde_json::Value,
     embedder_name: &str,
@@ -0,0 +1,111 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use charabia::Language;
+use heed::types::{Bytes, DecodeIgnore};
+use heed::{BytesDecode, RwTxn};
+use rust_stemmers::Stemmer;
+
+use crate::heed_codec::StrBEU16Codec;
+use crate::localized_attributes_rules::{stem_words_key, stemmer, StemmedFieldIds};
+use crate::{Index, Result};
+
+/// Updates the database mapping the stems to the words of the attributes for which
+/// stemming is enabled, from the words that were added to or removed from the index.
+///
+/// A word is kept under the stem of a locale as long as it is indexed in one of the
+/// attributes stemmed in this locale.
+#[tracing::instrument(level = "trace", skip_all, target = "indexing::stem_words")]
+pub(crate) fn compute_stem_words(
+    wtxn: &mut RwTxn<'_>,
+    index: &Index,
+    words: &BTreeSet<String>,
+) -> Result<()> {
+    let (stemmed_fields, stemmers) = stemmed_fields(wtxn, index)?;
+    if stemmers.is_empty() {
+        return Ok(());
+    }
+
+    let word_fid_docids = index.word_fid_docids.remap_types::<Bytes, DecodeIgnore>();
+    let mut changes: BTreeMap<String, Vec<(&str, bool)>> = BTreeMap::new();
+    for word in words {
+        // the locales of the stemmed fields in which the word is still indexed
+        let mut indexed_locales = BTreeSet::new();
+        let mut prefix = word.as_bytes().to_vec();
+        prefix.push(0);
+        for result in word_fid_docids.prefix_iter(wtxn, &prefix)? {
+            let (key, ()) = result?;
+            let (_word, field_id) =
+                StrBEU16Codec::bytes_decode(key).map_err(heed::Error::Decoding)?;
+            indexed_locales.extend(stemmed_fields.locales(field_id));
+        }
+
+        for (locale, stemmer) in &stemmers {
+            let indexed = indexed_locales.contains(locale);
+            let key = stem_words_key(*locale, &stemmer.stem(word));
+            changes.entry(key).or_default().push((word.as_str(), indexed));
+        }
+    }
+
+    for (key, changes) in changes {
+        let mut stem_words = index.stem_words.get(wtxn, &key)?.unwrap_or_default();
+        for (word, indexed) in changes {
+            if indexed {
+                stem_words.insert(word.to_string());
+            } else {
+                stem_words.remove(word);
+            }
+        }
+
+        if stem_words.is_empty() {
+            index.stem_words.delete(wtxn, &key)?;
+        } else {
+            index.stem_words.put(wtxn, &key, &stem_words)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Rebuilds the database mapping the stems to the words of the attributes for which
+/// stemming is enabled, from all the words indexed in each attribute.
+#[tracing::instrument(level = "trace", skip_all, target = "indexing::stem_words")]
+pub(crate) fn recompute_stem_words(wtxn: &mut RwTxn<'_>, index: &Index) -> Result<()> {
+    index.stem_words.clear(wtxn)?;
+
+    let (stemmed_fields, stemmers) = stemmed_fields(wtxn, index)?;
+    if stemmers.is_empty() {
+        return Ok(());
+    }
+
+    let mut stem_words: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    let iter = index.word_fid_docids.remap_data_type::<DecodeIgnore>().iter(wtxn)?;
+    for result in iter {
+        let ((word, fid), ()) = result?;
+        for locale in stemmed_fields.locales(fid) {
+            let key = stem_words_key(*locale, &stemmers[locale].stem(word));
+            stem_words.entry(key).or_default().insert(word.to_string());
+        }
+    }
+
+    for (key, words) in stem_words {
+        index.stem_words.put(wtxn, &key, &words)?;
+    }
+
+    Ok(())
+}
+
+fn stemmed_fields(
+    wtxn: &RwTxn<'_>,
+    index: &Index,
+) -> Result<(StemmedFieldIds, BTreeMap<Language, Stemmer>)> {
+    let rules = index.localized_attributes_rules(wtxn)?.unwrap_or_default();
+    let fields_ids_map = index.fields_ids_map(wtxn)?;
+    let stemmed_fields = StemmedFieldIds::new(&rules, &fields_ids_map);
+    let stemmers = stemmed_fields
+        .all_locales()
+        .into_iter()
+        .filter_map(|locale| stemmer(locale).map(|stemmer| (locale, stemmer)))
+        .collect();
+
+    Ok((stemmed_fields, stemmers))
+}
@@ -7,7 +7,7 @@ use super::*;
 use crate::error::Error;
 use crate::index::tests::TempIndex;
 use crate::update::ClearDocuments;
-use crate::{db_snap, Criterion, Filter, SearchResult};
+use crate::{db_snap, Criterion, Filter, SearchResult, SynonymKind, SynonymRule};
 
 #[test]
 fn set_and_reset_searchable_fields() {
@@ -620,6 +620,89 @@ fn thai_synonyms() {
     assert_eq!(result.documents_ids.len(), 2);
 }
 
+#[test]
+fn synonym_rules() {
+    let index = TempIndex::new();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "name": "television show" },
+            { "id": 1, "name": "tv show" },
+            { "id": 2, "name": "telly show" },
+            { "id": 3, "name": "the <number> street" },
+        ]))
+        .unwrap();
+
+    let rule = |synonyms: &[&str], kind, rank_as_typo| {
+        Synonyms::Rule(SynonymRule {
+            synonyms: synonyms.iter().map(|s| S(*s)).collect(),
+            kind,
+            rank_as_typo,
+        })
+    };
+    index
+        .update_settings(|settings| {
+            settings.set_synonyms(btreemap! {
+                S("tv") => rule(&["television"], SynonymKind::OneWay, true),
+                S("telly") => rule(&["television"], SynonymKind::MultiWay, false),
+                S("<number>") => rule(&["one", "two"], SynonymKind::Placeholder, false),
+            });
+        })
+        .unwrap();
+
+    let rtxn = index.read_txn().unwrap();
+    let user_defined = index.user_defined_synonyms(&rtxn).unwrap();
+    assert_eq!(user_defined["tv"], rule(&["television"], SynonymKind::OneWay, true));
+
+    let search = |query: &str| {
+        let result = index.search(&rtxn).query(query).execute().unwrap();
+        result.documents_ids
+    };
+
+    // the documents matching the synonym ranked as a typo come after the exact matches
+    assert_eq!(search("tv"), vec![1, 0]);
+    // the one-way synonyms don't apply the other way around
+    let mut documents_ids = search("television");
+    documents_ids.sort_unstable();
+    assert_eq!(documents_ids, vec![0, 2]);
+    let mut documents_ids = search("telly");
+    documents_ids.sort_unstable();
+    assert_eq!(documents_ids, vec![0, 2]);
+    // the placeholder written in the documents is matched by its synonyms
+    assert_eq!(search("two"), vec![3]);
+    assert_eq!(search("<number>"), vec![3]);
+    // but not by the word between its brackets
+    assert!(search("number").is_empty());
+}
+
+#[test]
+fn one_way_synonyms() {
+    let index = TempIndex::new();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "name": "tv" },
+            { "id": 1, "name": "television" },
+        ]))
+        .unwrap();
+
+    // `"tv" -> "television"` without the reverse
+    index
+        .update_settings(|settings| {
+            settings.set_synonyms(btreemap! { S("tv") => vec![S("television")] });
+        })
+        .unwrap();
+
+    let rtxn = index.read_txn().unwrap();
+    let search = |query: &str| {
+        let result = index.search(&rtxn).query(query).execute().unwrap();
+        result.documents_ids
+    };
+
+    assert_eq!(search("tv"), vec![0, 1]);
+    assert_eq!(search("television"), vec![1]);
+}
+
 #[test]
 fn setting_searchable_recomputes_other_settings() {
     let index = TempIndex::new();
@@ -896,6 +979,8 @@ fn test_correct_settings_init() {
                 localized_attributes_rules,
                 prefix_search,
                 facet_search,
+                query_rules,
+                rerankers,
                 disable_on_numbers,
             } = settings;
             assert!(matches!(searchable_fields, Setting::NotSet));
@@ -924,6 +1009,8 @@ fn test_correct_settings_init() {
             assert!(matches!(localized_attributes_rules, Setting::NotSet));
             assert!(matches!(prefix_search, Setting::NotSet));
             assert!(matches!(facet_search, Setting::NotSet));
+            assert!(matches!(query_rules, Setting::NotSet));
+            assert!(matches!(rerankers, Setting::NotSet));
             assert!(matches!(disable_on_numbers, Setting::NotSet));
         })
         .unwrap();
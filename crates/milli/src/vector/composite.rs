@@ -65,6 +65,14 @@ impl Embedder {
         // cache is only used at search
         let index = SubEmbedder::new(index, 0)?;
 
+        // sparse embeddings are not stored in arroy and cannot be compared to dense embeddings
+        if search.is_sparse() {
+            return Err(NewEmbedderError::composite_sparse_embedder("search"));
+        }
+        if index.is_sparse() {
+            return Err(NewEmbedderError::composite_sparse_embedder("indexing"));
+        }
+
         // check dimensions
         if search.dimensions() != index.dimensions() {
             return Err(NewEmbedderError::composite_dimensions_mismatch(
@@ -256,6 +264,16 @@ impl SubEmbedder {
         }
     }
 
+    pub fn is_sparse(&self) -> bool {
+        match self {
+            SubEmbedder::Rest(embedder) => embedder.is_sparse(),
+            SubEmbedder::HuggingFace(_)
+            | SubEmbedder::OpenAi(_)
+            | SubEmbedder::Ollama(_)
+            | SubEmbedder::UserProvided(_) => false,
+        }
+    }
+
     /// An optional distribution used to apply an affine transformation to the similarity score of a document.
     pub fn distribution(&self) -> Option<DistributionShift> {
         match self {
@@ -6,6 +6,7 @@ use hf_hub::api::sync::ApiError;
 
 use super::parsed_vectors::ParsedVectorsDiff;
 use super::rest::ConfigurationSource;
+use super::sparse::MAX_SPARSE_DIMENSIONS;
 use super::MAX_COMPOSITE_DISTANCE;
 use crate::error::FaultSource;
 use crate::update::new::vector_document::VectorDocument;
@@ -97,8 +98,14 @@ pub enum EmbedErrorKind {
     RestExtractionError(String),
     #[error("was expecting embeddings of dimension `{0}`, got embeddings of dimensions `{1}`")]
     UnexpectedDimension(usize, usize),
+    #[error("was expecting sparse embeddings with token ids lower than `{1}`, got token id `{0}`")]
+    UnexpectedSparseToken(u32, usize),
     #[error("no embedding was produced")]
     MissingEmbedding,
+    #[error("expected a response containing {0} scores, got {1}")]
+    RerankScoreCount(usize, usize),
+    #[error("received a score for the document at index `{0}`, but only {1} documents were sent")]
+    RerankUnexpectedIndex(usize, usize),
     #[error(transparent)]
     PanicInThreadPool(#[from] PanicCatched),
 }
@@ -203,6 +210,14 @@ impl EmbedError {
             fault: FaultSource::Runtime,
         }
     }
+
+    pub(crate) fn rest_unexpected_sparse_token(token: u32, dimensions: usize) -> EmbedError {
+        Self {
+            kind: EmbedErrorKind::UnexpectedSparseToken(token, dimensions),
+            fault: FaultSource::Runtime,
+        }
+    }
+
     pub(crate) fn missing_embedding() -> EmbedError {
         Self { kind: EmbedErrorKind::MissingEmbedding, fault: FaultSource::Undecided }
     }
@@ -210,6 +225,17 @@ impl EmbedError {
     pub(crate) fn rest_extraction_error(error: String) -> EmbedError {
         Self { kind: EmbedErrorKind::RestExtractionError(error), fault: FaultSource::Runtime }
     }
+
+    pub(crate) fn rerank_score_count(expected: usize, got: usize) -> EmbedError {
+        Self { kind: EmbedErrorKind::RerankScoreCount(expected, got), fault: FaultSource::Runtime }
+    }
+
+    pub(crate) fn rerank_unexpected_index(index: usize, count: usize) -> EmbedError {
+        Self {
+            kind: EmbedErrorKind::RerankUnexpectedIndex(index, count),
+            fault: FaultSource::Runtime,
+        }
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -382,6 +408,20 @@ impl NewEmbedderError {
             fault: FaultSource::User,
         }
     }
+
+    pub(crate) fn composite_sparse_embedder(sparse_embedder: &'static str) -> NewEmbedderError {
+        Self {
+            kind: NewEmbedderErrorKind::CompositeSparseEmbedder { sparse_embedder },
+            fault: FaultSource::User,
+        }
+    }
+
+    pub(crate) fn sparse_dimensions_too_large(dimensions: usize) -> NewEmbedderError {
+        Self {
+            kind: NewEmbedderErrorKind::SparseDimensionsTooLarge(dimensions),
+            fault: FaultSource::User,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -499,6 +539,10 @@ pub enum NewEmbedderErrorKind {
     CompositeEmbeddingCountMismatch { search_count: usize, index_count: usize },
     #[error("error while generating test embeddings.\n  - the embeddings produced at search time and indexing time are not similar enough.\n  - angular distance {distance:.2}\n  - Meilisearch requires a maximum distance of {MAX_COMPOSITE_DISTANCE}.\n  - Note: check that both embedders produce similar embeddings.{hint}")]
     CompositeEmbeddingValueMismatch { distance: f32, hint: CompositeEmbedderContainsHuggingFace },
+    #[error("the embedder used at {sparse_embedder} time produces sparse embeddings.\n  - Note: composite embedders only support dense embeddings.")]
+    CompositeSparseEmbedder { sparse_embedder: &'static str },
+    #[error("the vocabulary of a sparse embedder must contain at most {MAX_SPARSE_DIMENSIONS} tokens, got `dimensions: {0}`.")]
+    SparseDimensionsTooLarge(usize),
 }
 
 pub struct PossibleEmbeddingMistakes {
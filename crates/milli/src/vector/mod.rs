@@ -3,7 +3,10 @@ use std::num::NonZeroUsize;
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
 
-use arroy::distances::{BinaryQuantizedCosine, Cosine};
+use arroy::distances::{
+    BinaryQuantizedCosine, BinaryQuantizedEuclidean, BinaryQuantizedManhattan, Cosine, DotProduct,
+    Euclidean, Manhattan,
+};
 use arroy::ItemId;
 use deserr::{DeserializeError, Deserr};
 use heed::{RoTxn, RwTxn, Unspecified};
@@ -13,6 +16,7 @@ use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
 use self::error::{EmbedError, NewEmbedderError};
+use self::scalar::ScalarQuantizedStore;
 use crate::progress::Progress;
 use crate::prompt::{Prompt, PromptData};
 use crate::ThreadPoolNoAbort;
@@ -24,7 +28,10 @@ pub mod json_template;
 pub mod manual;
 pub mod openai;
 pub mod parsed_vectors;
+pub mod rerank;
+pub mod scalar;
 pub mod settings;
+pub mod sparse;
 
 pub mod ollama;
 pub mod rest;
@@ -38,8 +45,90 @@ pub const MAX_COMPOSITE_DISTANCE: f32 = 0.01;
 
 pub struct ArroyWrapper {
     quantized: bool,
+    distance: Distance,
     embedder_index: u8,
     database: arroy::Database<Unspecified>,
+    /// The store of the embeddings of a scalar quantized embedder, searched instead of its trees
+    /// that are binary quantized.
+    scalar_store: Option<ScalarQuantizedStore>,
+    full_precision_rescoring: bool,
+}
+
+/// Evaluates `$body` with `$db` bound to the arroy database remapped to the distance of the wrapper.
+///
+/// The body is expanded once for each distance, so that it can use the generic arroy methods,
+/// as well as `?`, `break` and `return`.
+macro_rules! with_arroy_db {
+    ($this:expr, |$db:ident| $body:expr) => {
+        match ($this.distance, $this.quantized) {
+            (Distance::Cosine, false) => {
+                let $db = $this.database.remap_data_type::<Cosine>();
+                $body
+            }
+            (Distance::Cosine, true) => {
+                let $db = $this.database.remap_data_type::<BinaryQuantizedCosine>();
+                $body
+            }
+            // the dot product cannot be binary quantized, which is rejected by the settings
+            (Distance::DotProduct, _) => {
+                let $db = $this.database.remap_data_type::<DotProduct>();
+                $body
+            }
+            (Distance::Euclidean, false) => {
+                let $db = $this.database.remap_data_type::<Euclidean>();
+                $body
+            }
+            (Distance::Euclidean, true) => {
+                let $db = $this.database.remap_data_type::<BinaryQuantizedEuclidean>();
+                $body
+            }
+            (Distance::Manhattan, false) => {
+                let $db = $this.database.remap_data_type::<Manhattan>();
+                $body
+            }
+            (Distance::Manhattan, true) => {
+                let $db = $this.database.remap_data_type::<BinaryQuantizedManhattan>();
+                $body
+            }
+        }
+    };
+}
+
+/// Evaluates `$body` with `$writer` bound to the arroy writer `$from` converted to the given distance,
+/// binary quantized or not.
+macro_rules! with_changed_distance {
+    ($from:expr, $wtxn:expr, $distance:expr, $quantized:expr, |$writer:ident| $body:expr) => {
+        match ($distance, $quantized) {
+            (Distance::Cosine, false) => {
+                let $writer = $from.prepare_changing_distance::<Cosine>($wtxn)?;
+                $body
+            }
+            (Distance::Cosine, true) => {
+                let $writer = $from.prepare_changing_distance::<BinaryQuantizedCosine>($wtxn)?;
+                $body
+            }
+            (Distance::DotProduct, _) => {
+                let $writer = $from.prepare_changing_distance::<DotProduct>($wtxn)?;
+                $body
+            }
+            (Distance::Euclidean, false) => {
+                let $writer = $from.prepare_changing_distance::<Euclidean>($wtxn)?;
+                $body
+            }
+            (Distance::Euclidean, true) => {
+                let $writer = $from.prepare_changing_distance::<BinaryQuantizedEuclidean>($wtxn)?;
+                $body
+            }
+            (Distance::Manhattan, false) => {
+                let $writer = $from.prepare_changing_distance::<Manhattan>($wtxn)?;
+                $body
+            }
+            (Distance::Manhattan, true) => {
+                let $writer = $from.prepare_changing_distance::<BinaryQuantizedManhattan>($wtxn)?;
+                $body
+            }
+        }
+    };
 }
 
 impl ArroyWrapper {
@@ -47,14 +136,43 @@ impl ArroyWrapper {
         database: arroy::Database<Unspecified>,
         embedder_index: u8,
         quantized: bool,
+        distance: Distance,
     ) -> Self {
-        Self { database, embedder_index, quantized }
+        Self {
+            database,
+            embedder_index,
+            quantized,
+            distance,
+            scalar_store: None,
+            full_precision_rescoring: false,
+        }
+    }
+
+    /// Stores the embeddings in the given store when scalar quantized, the nearest neighbors
+    /// being then searched in the store, and the arroy trees only keeping binary quantized embeddings.
+    pub fn with_scalar_quantization(mut self, store: Option<ScalarQuantizedStore>) -> Self {
+        if store.is_some() {
+            self.quantized = true;
+        }
+        self.scalar_store = store;
+        self
+    }
+
+    /// Whether the best candidates of a scalar quantized embedder are rescored with their original embeddings,
+    /// which are then kept on disk along with the scalar quantized ones when writing the embeddings.
+    pub fn with_full_precision_rescoring(mut self, full_precision_rescoring: bool) -> Self {
+        self.full_precision_rescoring = full_precision_rescoring;
+        self
     }
 
     pub fn embedder_index(&self) -> u8 {
         self.embedder_index
     }
 
+    pub fn distance(&self) -> Distance {
+        self.distance
+    }
+
     fn readers<'a, D: arroy::Distance>(
         &'a self,
         rtxn: &'a RoTxn<'a>,
@@ -75,13 +193,13 @@ impl ArroyWrapper {
 
     pub fn dimensions(&self, rtxn: &RoTxn) -> Result<usize, arroy::Error> {
         let first_id = arroy_db_range_for_embedder(self.embedder_index).next().unwrap();
-        if self.quantized {
-            Ok(arroy::Reader::open(rtxn, first_id, self.quantized_db())?.dimensions())
-        } else {
-            Ok(arroy::Reader::open(rtxn, first_id, self.angular_db())?.dimensions())
-        }
+        with_arroy_db!(self, |db| Ok(arroy::Reader::open(rtxn, first_id, db)?.dimensions()))
     }
 
+    /// Builds the trees of the embedder.
+    ///
+    /// When `quantizing`, or when `distance` differs from the distance of the wrapper,
+    /// the stored embeddings are first converted to the new distance.
     #[allow(clippy::too_many_arguments)]
     pub fn build_and_quantize<R: rand::Rng + rand::SeedableRng>(
         &mut self,
@@ -90,43 +208,114 @@ impl ArroyWrapper {
         rng: &mut R,
         dimension: usize,
         quantizing: bool,
+        distance: Distance,
         arroy_memory: Option<usize>,
         cancel: &(impl Fn() -> bool + Sync + Send),
     ) -> Result<(), arroy::Error> {
+        let quantizing = quantizing && !self.quantized;
+        let changing_distance = quantizing || distance != self.distance;
         for index in arroy_db_range_for_embedder(self.embedder_index) {
-            if self.quantized {
-                let writer = arroy::Writer::new(self.quantized_db(), index, dimension);
-                if writer.need_build(wtxn)? {
-                    writer.builder(rng).build(wtxn)?
-                } else if writer.is_empty(wtxn)? {
-                    break;
-                }
-            } else {
-                let writer = arroy::Writer::new(self.angular_db(), index, dimension);
-                // If we are quantizing the databases, we can't know from meilisearch
+            with_arroy_db!(self, |db| {
+                let writer = arroy::Writer::new(db, index, dimension);
+                // If we are converting the databases, we can't know from meilisearch
                 // if the db was empty but still contained the wrong metadata, thus we need
-                // to quantize everything and can't stop early. Since this operation can
-                // only happens once in the life of an embedder, it's not very performances
+                // to convert everything and can't stop early. Since this operation can
+                // only happens when the settings change, it's not very performances
                 // sensitive.
-                if quantizing && !self.quantized {
-                    let writer = writer.prepare_changing_distance::<BinaryQuantizedCosine>(wtxn)?;
-                    writer
-                        .builder(rng)
-                        .available_memory(arroy_memory.unwrap_or(usize::MAX))
-                        .progress(|step| progress.update_progress_from_arroy(step))
-                        .cancel(cancel)
-                        .build(wtxn)?;
+                if changing_distance {
+                    let quantized = self.quantized || quantizing;
+                    with_changed_distance!(writer, wtxn, distance, quantized, |writer| {
+                        Self::build(&writer, wtxn, progress, rng, arroy_memory, cancel)?
+                    })
                 } else if writer.need_build(wtxn)? {
-                    writer
-                        .builder(rng)
-                        .available_memory(arroy_memory.unwrap_or(usize::MAX))
-                        .progress(|step| progress.update_progress_from_arroy(step))
-                        .cancel(cancel)
-                        .build(wtxn)?;
+                    if self.quantized {
+                        writer.builder(rng).build(wtxn)?
+                    } else {
+                        Self::build(&writer, wtxn, progress, rng, arroy_memory, cancel)?
+                    }
                 } else if writer.is_empty(wtxn)? {
                     break;
                 }
+            })
+        }
+        self.quantized |= quantizing;
+        self.distance = distance;
+        Ok(())
+    }
+
+    fn build<D: arroy::Distance, R: rand::Rng + rand::SeedableRng>(
+        writer: &arroy::Writer<D>,
+        wtxn: &mut RwTxn,
+        progress: &Progress,
+        rng: &mut R,
+        arroy_memory: Option<usize>,
+        cancel: &(impl Fn() -> bool + Sync + Send),
+    ) -> Result<(), arroy::Error> {
+        writer
+            .builder(rng)
+            .available_memory(arroy_memory.unwrap_or(usize::MAX))
+            .progress(|step| progress.update_progress_from_arroy(step))
+            .cancel(cancel)
+            .build(wtxn)
+    }
+
+    /// Starts storing the embeddings scalar quantized in the given store, or stops if it is `None`.
+    ///
+    /// When starting, the full precision embeddings of the `documents` are copied from arroy,
+    /// which must then be binary quantized with [`Self::build_and_quantize`].
+    /// When stopping, the embeddings are restored in arroy, at full precision if they were kept.
+    pub fn set_scalar_quantization(
+        &mut self,
+        wtxn: &mut RwTxn,
+        dimension: usize,
+        documents: &RoaringBitmap,
+        store: Option<ScalarQuantizedStore>,
+    ) -> Result<(), arroy::Error> {
+        match (self.scalar_store, store) {
+            (None, Some(store)) => {
+                for docid in documents {
+                    let mut vectors = Vec::new();
+                    for index in arroy_db_range_for_embedder(self.embedder_index) {
+                        let vector = with_arroy_db!(self, |db| {
+                            arroy::Writer::new(db, index, dimension).item_vector(wtxn, docid)?
+                        });
+                        match vector {
+                            Some(vector) => vectors.push(vector),
+                            None => break,
+                        }
+                    }
+                    store.put(
+                        wtxn,
+                        docid,
+                        vectors.iter().map(Vec::as_slice),
+                        self.full_precision_rescoring,
+                    )?;
+                }
+                self.scalar_store = Some(store);
+            }
+            (Some(store), None) => {
+                // the stored embeddings overwrite the binary quantized ones once converted back
+                for index in arroy_db_range_for_embedder(self.embedder_index) {
+                    with_arroy_db!(self, |db| {
+                        let writer = arroy::Writer::new(db, index, dimension);
+                        with_changed_distance!(writer, wtxn, self.distance, false, |_writer| {})
+                    })
+                }
+                self.quantized = false;
+                self.scalar_store = None;
+
+                for docid in store.documents(wtxn)? {
+                    let vectors = store.vectors(wtxn, docid)?.unwrap_or_default();
+                    for (index, vector) in
+                        arroy_db_range_for_embedder(self.embedder_index).zip(&vectors)
+                    {
+                        with_arroy_db!(self, |db| arroy::Writer::new(db, index, dimension)
+                            .add_item(wtxn, docid, vector)?)
+                    }
+                }
+                store.clear(wtxn)?;
             }
+            (Some(_), Some(_)) | (None, None) => (),
         }
         Ok(())
     }
@@ -145,13 +334,11 @@ impl ArroyWrapper {
         for (index, vector) in
             arroy_db_range_for_embedder(self.embedder_index).zip(embeddings.iter())
         {
-            if self.quantized {
-                arroy::Writer::new(self.quantized_db(), index, dimension)
-                    .add_item(wtxn, item_id, vector)?
-            } else {
-                arroy::Writer::new(self.angular_db(), index, dimension)
-                    .add_item(wtxn, item_id, vector)?
-            }
+            with_arroy_db!(self, |db| arroy::Writer::new(db, index, dimension)
+                .add_item(wtxn, item_id, vector)?)
+        }
+        if let Some(store) = &self.scalar_store {
+            store.put(wtxn, item_id, embeddings.iter(), self.full_precision_rescoring)?;
         }
         Ok(())
     }
@@ -163,11 +350,11 @@ impl ArroyWrapper {
         item_id: arroy::ItemId,
         vector: &[f32],
     ) -> Result<(), arroy::Error> {
-        if self.quantized {
-            self._add_item(wtxn, self.quantized_db(), item_id, vector)
-        } else {
-            self._add_item(wtxn, self.angular_db(), item_id, vector)
+        with_arroy_db!(self, |db| self._add_item(wtxn, db, item_id, vector))?;
+        if let Some(store) = &self.scalar_store {
+            store.push(wtxn, item_id, vector, self.full_precision_rescoring)?;
         }
+        Ok(())
     }
 
     fn _add_item<D: arroy::Distance>(
@@ -197,17 +384,15 @@ impl ArroyWrapper {
         item_id: arroy::ItemId,
     ) -> Result<(), arroy::Error> {
         for index in arroy_db_range_for_embedder(self.embedder_index) {
-            if self.quantized {
-                let writer = arroy::Writer::new(self.quantized_db(), index, dimension);
-                if !writer.del_item(wtxn, item_id)? {
-                    break;
-                }
-            } else {
-                let writer = arroy::Writer::new(self.angular_db(), index, dimension);
+            with_arroy_db!(self, |db| {
+                let writer = arroy::Writer::new(db, index, dimension);
                 if !writer.del_item(wtxn, item_id)? {
                     break;
                 }
-            }
+            })
+        }
+        if let Some(store) = &self.scalar_store {
+            store.delete(wtxn, item_id)?;
         }
 
         Ok(())
@@ -220,11 +405,11 @@ impl ArroyWrapper {
         item_id: arroy::ItemId,
         vector: &[f32],
     ) -> Result<bool, arroy::Error> {
-        if self.quantized {
-            self._del_item(wtxn, self.quantized_db(), item_id, vector)
-        } else {
-            self._del_item(wtxn, self.angular_db(), item_id, vector)
+        let deleted = with_arroy_db!(self, |db| self._del_item(wtxn, db, item_id, vector))?;
+        if let Some(store) = &self.scalar_store {
+            store.remove(wtxn, item_id, vector)?;
         }
+        Ok(deleted)
     }
 
     fn _del_item<D: arroy::Distance>(
@@ -273,19 +458,16 @@ impl ArroyWrapper {
 
     pub fn clear(&self, wtxn: &mut RwTxn, dimension: usize) -> Result<(), arroy::Error> {
         for index in arroy_db_range_for_embedder(self.embedder_index) {
-            if self.quantized {
-                let writer = arroy::Writer::new(self.quantized_db(), index, dimension);
-                if writer.is_empty(wtxn)? {
-                    break;
-                }
-                writer.clear(wtxn)?;
-            } else {
-                let writer = arroy::Writer::new(self.angular_db(), index, dimension);
+            with_arroy_db!(self, |db| {
+                let writer = arroy::Writer::new(db, index, dimension);
                 if writer.is_empty(wtxn)? {
                     break;
                 }
                 writer.clear(wtxn)?;
-            }
+            })
+        }
+        if let Some(store) = &self.scalar_store {
+            store.clear(wtxn)?;
         }
         Ok(())
     }
@@ -297,19 +479,13 @@ impl ArroyWrapper {
         item: arroy::ItemId,
     ) -> Result<bool, arroy::Error> {
         for index in arroy_db_range_for_embedder(self.embedder_index) {
-            let contains = if self.quantized {
-                let writer = arroy::Writer::new(self.quantized_db(), index, dimension);
-                if writer.is_empty(rtxn)? {
-                    break;
-                }
-                writer.contains_item(rtxn, item)?
-            } else {
-                let writer = arroy::Writer::new(self.angular_db(), index, dimension);
+            let contains = with_arroy_db!(self, |db| {
+                let writer = arroy::Writer::new(db, index, dimension);
                 if writer.is_empty(rtxn)? {
                     break;
                 }
                 writer.contains_item(rtxn, item)?
-            };
+            });
             if contains {
                 return Ok(contains);
             }
@@ -324,11 +500,25 @@ impl ArroyWrapper {
         limit: usize,
         filter: Option<&RoaringBitmap>,
     ) -> Result<Vec<(ItemId, f32)>, arroy::Error> {
-        if self.quantized {
-            self._nns_by_item(rtxn, self.quantized_db(), item, limit, filter)
-        } else {
-            self._nns_by_item(rtxn, self.angular_db(), item, limit, filter)
+        if let Some(store) = &self.scalar_store {
+            let mut results = Vec::new();
+            for target in store.vectors(rtxn, item)?.unwrap_or_default() {
+                let neighbors = store.nns_by_vector(
+                    rtxn,
+                    self.distance,
+                    &target,
+                    limit,
+                    filter,
+                    self.full_precision_rescoring,
+                )?;
+                results
+                    .extend(neighbors.into_iter().map(|(item, _index, distance)| (item, distance)));
+            }
+            results.sort_unstable_by_key(|(_, distance)| OrderedFloat(*distance));
+            return Ok(results);
         }
+
+        with_arroy_db!(self, |db| self._nns_by_item(rtxn, db, item, limit, filter))
     }
 
     fn _nns_by_item<D: arroy::Distance>(
@@ -365,11 +555,31 @@ impl ArroyWrapper {
         limit: usize,
         filter: Option<&RoaringBitmap>,
     ) -> Result<Vec<(ItemId, f32)>, arroy::Error> {
-        if self.quantized {
-            self._nns_by_vector(rtxn, self.quantized_db(), vector, limit, filter)
-        } else {
-            self._nns_by_vector(rtxn, self.angular_db(), vector, limit, filter)
+        let results = self.nns_by_vector_with_index(rtxn, vector, limit, filter)?;
+        Ok(results.into_iter().map(|(item, _index, distance)| (item, distance)).collect())
+    }
+
+    /// Returns the nearest neighbors along with the index of the matching vector
+    /// among the vectors of the document, e.g. the chunk of the document it was embedded from.
+    pub fn nns_by_vector_with_index(
+        &self,
+        rtxn: &RoTxn,
+        vector: &[f32],
+        limit: usize,
+        filter: Option<&RoaringBitmap>,
+    ) -> Result<Vec<(ItemId, usize, f32)>, arroy::Error> {
+        if let Some(store) = &self.scalar_store {
+            return Ok(store.nns_by_vector(
+                rtxn,
+                self.distance,
+                vector,
+                limit,
+                filter,
+                self.full_precision_rescoring,
+            )?);
         }
+
+        with_arroy_db!(self, |db| self._nns_by_vector(rtxn, db, vector, limit, filter))
     }
 
     fn _nns_by_vector<D: arroy::Distance>(
@@ -379,72 +589,56 @@ impl ArroyWrapper {
         vector: &[f32],
         limit: usize,
         filter: Option<&RoaringBitmap>,
-    ) -> Result<Vec<(ItemId, f32)>, arroy::Error> {
+    ) -> Result<Vec<(ItemId, usize, f32)>, arroy::Error> {
         let mut results = Vec::new();
 
-        for reader in self.readers(rtxn, db) {
+        for (index, reader) in self.readers(rtxn, db).enumerate() {
             let reader = reader?;
             let mut searcher = reader.nns(limit);
             if let Some(filter) = filter {
                 searcher.candidates(filter);
             }
 
-            results.append(&mut searcher.by_vector(rtxn, vector)?);
+            results.extend(
+                searcher
+                    .by_vector(rtxn, vector)?
+                    .into_iter()
+                    .map(|(item, distance)| (item, index, distance)),
+            );
         }
 
-        results.sort_unstable_by_key(|(_, distance)| OrderedFloat(*distance));
+        results.sort_unstable_by_key(|(_, _, distance)| OrderedFloat(*distance));
 
         Ok(results)
     }
 
     pub fn item_vectors(&self, rtxn: &RoTxn, item_id: u32) -> Result<Vec<Vec<f32>>, arroy::Error> {
+        // the trees of a scalar quantized embedder only contain binary quantized embeddings
+        if let Some(store) = &self.scalar_store {
+            return Ok(store.vectors(rtxn, item_id)?.unwrap_or_default());
+        }
+
         let mut vectors = Vec::new();
 
-        if self.quantized {
-            for reader in self.readers(rtxn, self.quantized_db()) {
-                if let Some(vec) = reader?.item_vector(rtxn, item_id)? {
-                    vectors.push(vec);
-                } else {
-                    break;
-                }
-            }
-        } else {
-            for reader in self.readers(rtxn, self.angular_db()) {
+        with_arroy_db!(self, |db| {
+            for reader in self.readers(rtxn, db) {
                 if let Some(vec) = reader?.item_vector(rtxn, item_id)? {
                     vectors.push(vec);
                 } else {
                     break;
                 }
             }
-        }
+        });
         Ok(vectors)
     }
 
-    fn angular_db(&self) -> arroy::Database<Cosine> {
-        self.database.remap_data_type()
-    }
-
-    fn quantized_db(&self) -> arroy::Database<BinaryQuantizedCosine> {
-        self.database.remap_data_type()
-    }
-
     pub fn aggregate_stats(
         &self,
         rtxn: &RoTxn,
         stats: &mut ArroyStats,
     ) -> Result<(), arroy::Error> {
-        if self.quantized {
-            for reader in self.readers(rtxn, self.quantized_db()) {
-                let reader = reader?;
-                let documents = reader.item_ids();
-                if documents.is_empty() {
-                    break;
-                }
-                stats.documents |= documents;
-                stats.number_of_embeddings += documents.len();
-            }
-        } else {
-            for reader in self.readers(rtxn, self.angular_db()) {
+        with_arroy_db!(self, |db| {
+            for reader in self.readers(rtxn, db) {
                 let reader = reader?;
                 let documents = reader.item_ids();
                 if documents.is_empty() {
@@ -453,7 +647,7 @@ impl ArroyWrapper {
                 stats.documents |= documents;
                 stats.number_of_embeddings += documents.len();
             }
-        }
+        });
 
         Ok(())
     }
@@ -606,24 +800,130 @@ pub struct EmbeddingConfig {
     pub prompt: PromptData,
     /// If this embedder is binary quantized
     pub quantized: Option<bool>,
-    // TODO: add metrics and anything needed
+    /// The distance used to compare the embeddings of this embedder
+    pub distance: Option<Distance>,
+    /// If this embedder is scalar quantized
+    pub scalar_quantized: Option<bool>,
+    /// If the best candidates of this scalar quantized embedder are rescored with their original embeddings
+    pub full_precision_rescoring: Option<bool>,
 }
 
 impl EmbeddingConfig {
     pub fn quantized(&self) -> bool {
         self.quantized.unwrap_or_default()
     }
+
+    pub fn distance(&self) -> Distance {
+        self.distance.unwrap_or_default()
+    }
+
+    pub fn scalar_quantized(&self) -> bool {
+        self.scalar_quantized.unwrap_or_default()
+    }
+
+    pub fn full_precision_rescoring(&self) -> bool {
+        self.full_precision_rescoring.unwrap_or_default()
+    }
+
+    /// Whether the embedder produces sparse embeddings, stored in an inverted index rather than in arroy.
+    pub fn is_sparse(&self) -> bool {
+        match &self.embedder_options {
+            EmbedderOptions::Rest(options) => options.is_sparse(),
+            _ => false,
+        }
+    }
+}
+
+/// The distance used to compare the embeddings of an embedder.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    serde::Deserialize,
+    serde::Serialize,
+    Deserr,
+    ToSchema,
+)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub enum Distance {
+    /// The angle between the embeddings, for embeddings whose norm is irrelevant.
+    #[default]
+    Cosine,
+    /// The inner product of the embeddings, for models trained to maximize it.
+    DotProduct,
+    /// The L2 distance between the embeddings.
+    Euclidean,
+    /// The L1 distance between the embeddings.
+    Manhattan,
+}
+
+impl Distance {
+    /// Whether the embeddings can be binary quantized with this distance.
+    pub fn supports_binary_quantization(&self) -> bool {
+        !matches!(self, Distance::DotProduct)
+    }
+
+    /// Converts a distance returned by arroy to a similarity between `0.0` and `1.0`,
+    /// the most similar embeddings having the highest similarity.
+    pub fn similarity(&self, distance: f32) -> f32 {
+        match self {
+            Distance::Cosine => 1.0 - distance,
+            // arroy returns the opposite of the dot product, which is squashed by a logistic function
+            Distance::DotProduct => 1.0 / (1.0 + distance.exp()),
+            Distance::Euclidean | Distance::Manhattan => 1.0 / (1.0 + distance),
+        }
+    }
+
+    /// Computes the distance between two embeddings as arroy does.
+    pub fn between(&self, left: &[f32], right: &[f32]) -> f32 {
+        let pairs = left.iter().zip(right);
+        match self {
+            Distance::Cosine => {
+                let (dot, left_norm, right_norm) =
+                    pairs.fold((0.0, 0.0, 0.0), |(dot, left_norm, right_norm), (l, r)| {
+                        (dot + l * r, left_norm + l * l, right_norm + r * r)
+                    });
+                let norms = (left_norm * right_norm).sqrt();
+                if norms > f32::EPSILON {
+                    // maps the cosine from [-1; 1] to [1; 0]
+                    (1.0 - (dot / norms).clamp(-1.0, 1.0)) / 2.0
+                } else {
+                    0.0
+                }
+            }
+            Distance::DotProduct => -pairs.map(|(l, r)| l * r).sum::<f32>(),
+            Distance::Euclidean => pairs.map(|(l, r)| (l - r) * (l - r)).sum::<f32>().sqrt(),
+            Distance::Manhattan => pairs.map(|(l, r)| (l - r).abs()).sum(),
+        }
+    }
+}
+
+impl std::fmt::Display for Distance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Distance::Cosine => "cosine",
+            Distance::DotProduct => "dotProduct",
+            Distance::Euclidean => "euclidean",
+            Distance::Manhattan => "manhattan",
+        };
+        f.write_str(s)
+    }
 }
 
 /// Map of embedder configurations.
 ///
 /// Each configuration is mapped to a name.
 #[derive(Clone, Default)]
-pub struct EmbeddingConfigs(HashMap<String, (Arc<Embedder>, Arc<Prompt>, bool)>);
+pub struct EmbeddingConfigs(HashMap<String, (Arc<Embedder>, Arc<Prompt>, bool, Distance)>);
 
 impl EmbeddingConfigs {
     /// Create the map from its internal component.s
-    pub fn new(data: HashMap<String, (Arc<Embedder>, Arc<Prompt>, bool)>) -> Self {
+    pub fn new(data: HashMap<String, (Arc<Embedder>, Arc<Prompt>, bool, Distance)>) -> Self {
         Self(data)
     }
 
@@ -632,24 +932,24 @@ impl EmbeddingConfigs {
     }
 
     /// Get an embedder configuration and template from its name.
-    pub fn get(&self, name: &str) -> Option<(Arc<Embedder>, Arc<Prompt>, bool)> {
+    pub fn get(&self, name: &str) -> Option<(Arc<Embedder>, Arc<Prompt>, bool, Distance)> {
         self.0.get(name).cloned()
     }
 
-    pub fn inner_as_ref(&self) -> &HashMap<String, (Arc<Embedder>, Arc<Prompt>, bool)> {
+    pub fn inner_as_ref(&self) -> &HashMap<String, (Arc<Embedder>, Arc<Prompt>, bool, Distance)> {
         &self.0
     }
 
-    pub fn into_inner(self) -> HashMap<String, (Arc<Embedder>, Arc<Prompt>, bool)> {
+    pub fn into_inner(self) -> HashMap<String, (Arc<Embedder>, Arc<Prompt>, bool, Distance)> {
         self.0
     }
 }
 
 impl IntoIterator for EmbeddingConfigs {
-    type Item = (String, (Arc<Embedder>, Arc<Prompt>, bool));
+    type Item = (String, (Arc<Embedder>, Arc<Prompt>, bool, Distance));
 
     type IntoIter =
-        std::collections::hash_map::IntoIter<String, (Arc<Embedder>, Arc<Prompt>, bool)>;
+        std::collections::hash_map::IntoIter<String, (Arc<Embedder>, Arc<Prompt>, bool, Distance)>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.0.into_iter()
@@ -822,6 +1122,19 @@ impl Embedder {
         }
     }
 
+    /// Whether the embedder produces [sparse embeddings](sparse::SparseEmbedding), stored in
+    /// an inverted index rather than in arroy.
+    pub fn is_sparse(&self) -> bool {
+        match self {
+            Embedder::Rest(embedder) => embedder.is_sparse(),
+            Embedder::HuggingFace(_)
+            | Embedder::OpenAi(_)
+            | Embedder::Ollama(_)
+            | Embedder::UserProvided(_)
+            | Embedder::Composite(_) => false,
+        }
+    }
+
     pub fn uses_document_template(&self) -> bool {
         match self {
             Embedder::HuggingFace(_)
@@ -5,6 +5,7 @@ use obkv::KvReader;
 use serde_json::value::RawValue;
 use serde_json::{from_slice, Value};
 
+use super::sparse::{SparseEmbedding, SparseValue, MAX_SPARSE_DIMENSIONS};
 use super::Embedding;
 use crate::index::IndexEmbeddingConfig;
 use crate::update::del_add::{DelAdd, KvReaderDelAdd};
@@ -300,14 +301,21 @@ impl Vectors {
         }
     }
 
-    pub fn into_array_of_vectors(self) -> Option<Vec<Embedding>> {
+    /// Returns the embeddings, checking that they are sparse if and only if the embedder is.
+    ///
+    /// The sparse embeddings are packed with [`SparseEmbedding::to_packed`].
+    pub fn into_embeddings(
+        self,
+        embedder_name: &str,
+        sparse: bool,
+    ) -> Result<Option<Vec<Embedding>>, String> {
         match self {
             Vectors::ImplicitlyUserProvided(embeddings) => {
-                Some(embeddings.into_array_of_vectors().unwrap_or_default())
-            }
-            Vectors::Explicit(ExplicitVectors { embeddings, regenerate: _ }) => {
-                embeddings.map(|embeddings| embeddings.into_array_of_vectors().unwrap_or_default())
+                Ok(Some(embeddings.into_embeddings(embedder_name, sparse)?))
             }
+            Vectors::Explicit(ExplicitVectors { embeddings, regenerate: _ }) => embeddings
+                .map(|embeddings| embeddings.into_embeddings(embedder_name, sparse))
+                .transpose(),
         }
     }
 }
@@ -516,11 +524,22 @@ fn to_vector_map(
 }
 
 /// Represents either a vector or an array of multiple vectors.
+///
+/// The vectors of the sparse embedders are arrays of `{ "index": <token>, "value": <weight> }` objects.
 #[derive(serde::Serialize, Debug)]
 #[serde(transparent)]
 pub struct VectorOrArrayOfVectors {
-    #[serde(with = "either::serde_untagged_optional")]
-    inner: Option<either::Either<Vec<Embedding>, Embedding>>,
+    inner: Option<Inner>,
+}
+
+#[derive(serde::Serialize, Debug)]
+#[serde(untagged)]
+enum Inner {
+    Dense(#[serde(with = "either::serde_untagged")] either::Either<Vec<Embedding>, Embedding>),
+    Sparse(
+        #[serde(with = "either::serde_untagged")]
+        either::Either<Vec<Vec<SparseValue>>, Vec<SparseValue>>,
+    ),
 }
 
 impl<E: DeserializeError> Deserr<E> for VectorOrArrayOfVectors {
@@ -536,41 +555,70 @@ impl<E: DeserializeError> Deserr<E> for VectorOrArrayOfVectors {
                     None => {
                         // With the strange way serde serialize the `Either`, we must send the left part
                         // otherwise it'll consider we returned [[]]
-                        Ok(VectorOrArrayOfVectors { inner: Some(either::Either::Left(Vec::new())) })
+                        Ok(VectorOrArrayOfVectors {
+                            inner: Some(Inner::Dense(either::Either::Left(Vec::new()))),
+                        })
                     }
-                    Some(val @ deserr::Value::Sequence(_)) => {
-                        let first = Embedding::deserialize_from_value(val, location.push_index(0))?;
-                        let mut collect = vec![first];
-                        let mut tail = iter
-                            .enumerate()
-                            .map(|(i, v)| {
-                                Embedding::deserialize_from_value(
-                                    v.into_value(),
-                                    location.push_index(i + 1),
-                                )
-                            })
-                            .collect::<Result<Vec<_>, _>>()?;
-                        collect.append(&mut tail);
-
-                        Ok(VectorOrArrayOfVectors { inner: Some(either::Either::Left(collect)) })
+                    Some(deserr::Value::Sequence(first)) => {
+                        let mut dense = Vec::new();
+                        let mut sparse = Vec::new();
+                        let first = deserr::Value::Sequence(first);
+                        for (i, value) in
+                            std::iter::once(first).chain(iter.map(|v| v.into_value())).enumerate()
+                        {
+                            let location = location.push_index(i);
+                            let deserr::Value::Sequence(seq) = value else {
+                                return Err(take_cf_content(E::error(
+                                    None,
+                                    deserr::ErrorKind::IncorrectValueKind {
+                                        actual: value,
+                                        accepted: &[deserr::ValueKind::Sequence],
+                                    },
+                                    location,
+                                )));
+                            };
+                            match deserialize_embedding(seq, location)? {
+                                OneEmbedding::Dense(embedding) if sparse.is_empty() => {
+                                    dense.push(embedding)
+                                }
+                                OneEmbedding::Sparse(embedding) if dense.is_empty() => {
+                                    sparse.push(embedding)
+                                }
+                                _ => {
+                                    return Err(take_cf_content(E::error::<V>(
+                                        None,
+                                        deserr::ErrorKind::Unexpected {
+                                            msg: "dense and sparse embeddings cannot be mixed"
+                                                .to_string(),
+                                        },
+                                        location,
+                                    )))
+                                }
+                            }
+                        }
+                        let inner = if sparse.is_empty() {
+                            Inner::Dense(either::Either::Left(dense))
+                        } else {
+                            Inner::Sparse(either::Either::Left(sparse))
+                        };
+                        Ok(VectorOrArrayOfVectors { inner: Some(inner) })
                     }
                     Some(
-                        val @ deserr::Value::Integer(_)
-                        | val @ deserr::Value::NegativeInteger(_)
-                        | val @ deserr::Value::Float(_),
+                        first @ (deserr::Value::Map(_)
+                        | deserr::Value::Integer(_)
+                        | deserr::Value::NegativeInteger(_)
+                        | deserr::Value::Float(_)),
                     ) => {
-                        let first = <f32>::deserialize_from_value(val, location.push_index(0))?;
-                        let mut embedding = iter
-                            .enumerate()
-                            .map(|(i, v)| {
-                                <f32>::deserialize_from_value(
-                                    v.into_value(),
-                                    location.push_index(i + 1),
-                                )
-                            })
-                            .collect::<Result<Vec<_>, _>>()?;
-                        embedding.insert(0, first);
-                        Ok(VectorOrArrayOfVectors { inner: Some(either::Either::Right(embedding)) })
+                        let values = std::iter::once(first).chain(iter.map(|v| v.into_value()));
+                        let inner = match deserialize_values(values, location)? {
+                            OneEmbedding::Dense(embedding) => {
+                                Inner::Dense(either::Either::Right(embedding))
+                            }
+                            OneEmbedding::Sparse(embedding) => {
+                                Inner::Sparse(either::Either::Right(embedding))
+                            }
+                        };
+                        Ok(VectorOrArrayOfVectors { inner: Some(inner) })
                     }
                     Some(value) => Err(take_cf_content(E::error(
                         None,
@@ -594,20 +642,166 @@ impl<E: DeserializeError> Deserr<E> for VectorOrArrayOfVectors {
     }
 }
 
+/// A single embedding, either dense or sparse.
+enum OneEmbedding {
+    Dense(Embedding),
+    Sparse(Vec<SparseValue>),
+}
+
+fn deserialize_embedding<V: deserr::IntoValue, E: DeserializeError>(
+    seq: V::Sequence,
+    location: deserr::ValuePointerRef<'_>,
+) -> Result<OneEmbedding, E> {
+    deserialize_values(seq.into_iter().map(|v| v.into_value()), location)
+}
+
+/// Deserializes the values of an embedding, which is sparse if its first value is an object.
+fn deserialize_values<V: deserr::IntoValue, E: DeserializeError>(
+    values: impl Iterator<Item = deserr::Value<V>>,
+    location: deserr::ValuePointerRef<'_>,
+) -> Result<OneEmbedding, E> {
+    let mut values = values.enumerate().peekable();
+    if let Some((_, deserr::Value::Map(_))) = values.peek() {
+        values
+            .map(|(i, v)| SparseValue::deserialize_from_value(v, location.push_index(i)))
+            .collect::<Result<_, _>>()
+            .map(OneEmbedding::Sparse)
+    } else {
+        values
+            .map(|(i, v)| <f32>::deserialize_from_value(v, location.push_index(i)))
+            .collect::<Result<_, _>>()
+            .map(OneEmbedding::Dense)
+    }
+}
+
+/// The `vector` of a search request, dense or sparse depending on the embedder.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, utoipa::ToSchema)]
+#[serde(untagged)]
+pub enum SearchVector {
+    /// An array of floats.
+    Dense(Embedding),
+    /// An array of `{"index": <token>, "value": <weight>}` objects.
+    Sparse(Vec<SparseValue>),
+}
+
+impl<E: DeserializeError> Deserr<E> for SearchVector {
+    fn deserialize_from_value<V: deserr::IntoValue>(
+        value: deserr::Value<V>,
+        location: deserr::ValuePointerRef<'_>,
+    ) -> Result<Self, E> {
+        match value {
+            deserr::Value::Sequence(seq) => {
+                match deserialize_values(seq.into_iter().map(|v| v.into_value()), location)? {
+                    OneEmbedding::Dense(embedding) => Ok(SearchVector::Dense(embedding)),
+                    OneEmbedding::Sparse(embedding) => Ok(SearchVector::Sparse(embedding)),
+                }
+            }
+            value => Err(take_cf_content(E::error(
+                None,
+                deserr::ErrorKind::IncorrectValueKind {
+                    actual: value,
+                    accepted: &[deserr::ValueKind::Sequence],
+                },
+                location,
+            ))),
+        }
+    }
+}
+
+impl SearchVector {
+    /// The number of dimensions of a dense vector, or of tokens of a sparse one.
+    pub fn len(&self) -> usize {
+        match self {
+            SearchVector::Dense(embedding) => embedding.len(),
+            SearchVector::Sparse(values) => values.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn is_sparse(&self) -> bool {
+        matches!(self, SearchVector::Sparse(_))
+    }
+
+    /// Returns the vector to give to the search, the sparse ones being packed with [`SparseEmbedding::to_packed`].
+    pub fn into_embedding(self) -> Embedding {
+        match self {
+            SearchVector::Dense(embedding) => embedding,
+            SearchVector::Sparse(values) => SparseEmbedding::from_values(values).to_packed(),
+        }
+    }
+}
+
 impl VectorOrArrayOfVectors {
+    /// Returns the embeddings, the sparse ones being packed with [`SparseEmbedding::to_packed`].
     pub fn into_array_of_vectors(self) -> Option<Vec<Embedding>> {
         match self.inner? {
-            either::Either::Left(vectors) => Some(vectors),
-            either::Either::Right(vector) => Some(vec![vector]),
+            Inner::Dense(either::Either::Left(vectors)) => Some(vectors),
+            Inner::Dense(either::Either::Right(vector)) => Some(vec![vector]),
+            Inner::Sparse(either::Either::Left(vectors)) => Some(
+                vectors
+                    .into_iter()
+                    .map(|vector| SparseEmbedding::from_values(vector).to_packed())
+                    .collect(),
+            ),
+            Inner::Sparse(either::Either::Right(vector)) => {
+                Some(vec![SparseEmbedding::from_values(vector).to_packed()])
+            }
+        }
+    }
+
+    /// Returns the embeddings like [`Self::into_array_of_vectors`], checking that they are
+    /// sparse if and only if the embedder is.
+    pub fn into_embeddings(
+        self,
+        embedder_name: &str,
+        sparse: bool,
+    ) -> Result<Vec<Embedding>, String> {
+        match &self.inner {
+            Some(Inner::Dense(either::Either::Left(vectors))) if vectors.is_empty() => {}
+            Some(Inner::Dense(_)) if sparse => {
+                return Err(format!(
+                    "Could not parse `._vectors.{embedder_name}`: the embedder returns sparse embeddings, \
+                     expected an array of `{{\"index\": <token>, \"value\": <weight>}}` objects, or an array of such arrays"
+                ))
+            }
+            Some(Inner::Sparse(_)) if !sparse => {
+                return Err(format!(
+                    "Could not parse `._vectors.{embedder_name}`: the embedder returns dense embeddings, \
+                     expected an array of floats, or an array of arrays of floats"
+                ))
+            }
+            Some(Inner::Sparse(vectors)) => {
+                let mut values = match vectors {
+                    either::Either::Left(vectors) => either::Either::Left(vectors.iter().flatten()),
+                    either::Either::Right(vector) => either::Either::Right(vector.iter()),
+                };
+                if let Some(SparseValue { index, .. }) =
+                    values.find(|value| value.index as usize >= MAX_SPARSE_DIMENSIONS)
+                {
+                    return Err(format!(
+                        "Could not parse `._vectors.{embedder_name}`: the token `{index}` is outside of the vocabulary, \
+                         which is limited to {MAX_SPARSE_DIMENSIONS} tokens"
+                    ));
+                }
+            }
+            _ => (),
         }
+        Ok(self.into_array_of_vectors().unwrap_or_default())
     }
 
     pub fn from_array_of_vectors(array_of_vec: Vec<Embedding>) -> Self {
-        Self { inner: Some(either::Either::Left(array_of_vec)) }
+        Self { inner: Some(Inner::Dense(either::Either::Left(array_of_vec))) }
     }
 
     pub fn from_vector(vec: Embedding) -> Self {
-        Self { inner: Some(either::Either::Right(vec)) }
+        Self { inner: Some(Inner::Dense(either::Either::Right(vec))) }
+    }
+
+    pub fn from_sparse_embedding(embedding: &SparseEmbedding) -> Self {
+        Self { inner: Some(Inner::Sparse(either::Either::Left(vec![embedding.to_values()]))) }
     }
 }
 
@@ -679,4 +873,47 @@ mod test {
         ]
         "###);
     }
+
+    #[test]
+    fn sparse_vectors() {
+        let one =
+            embedding_from_str(r#"[{"index": 3, "value": 0.5}, {"index": 1, "value": 0.25}]"#)
+                .unwrap();
+        let two = r#"[[{"index": 3, "value": 0.5}], [{"index": 1, "value": 0.25}]]"#;
+
+        // the sparse embeddings are packed, sorted by token
+        insta::assert_json_snapshot!(one.into_embeddings("rest", true).unwrap(), @r###"
+        [
+          [
+            1.0,
+            0.25,
+            3.0,
+            0.5
+          ]
+        ]
+        "###);
+        insta::assert_json_snapshot!(embedding_from_str(two).unwrap().into_embeddings("rest", true).unwrap(), @r###"
+        [
+          [
+            3.0,
+            0.5
+          ],
+          [
+            1.0,
+            0.25
+          ]
+        ]
+        "###);
+        insta::assert_snapshot!(embedding_from_str(two).unwrap().into_embeddings("manual", false).unwrap_err(), @"Could not parse `._vectors.manual`: the embedder returns dense embeddings, expected an array of floats, or an array of arrays of floats");
+
+        let dense = embedding_from_str("[0.1, 0.2]").unwrap();
+        insta::assert_snapshot!(dense.into_embeddings("rest", true).unwrap_err(), @r###"Could not parse `._vectors.rest`: the embedder returns sparse embeddings, expected an array of `{"index": <token>, "value": <weight>}` objects, or an array of such arrays"###);
+
+        let out_of_vocabulary =
+            embedding_from_str(r#"[{"index": 16777216, "value": 0.5}]"#).unwrap();
+        insta::assert_snapshot!(out_of_vocabulary.into_embeddings("rest", true).unwrap_err(), @"Could not parse `._vectors.rest`: the token `16777216` is outside of the vocabulary, which is limited to 16777216 tokens");
+
+        let mixed = embedding_from_str(r#"[[{"index": 3, "value": 0.5}], [0.1]]"#).unwrap_err();
+        assert!(mixed.to_string().contains("dense and sparse embeddings cannot be mixed"));
+    }
 }
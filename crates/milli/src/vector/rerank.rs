@@ -0,0 +1,407 @@
+//! Rerankers, reordering the best results of a search by scoring them against the query
+//! with a remote model, typically a cross-encoder.
+//!
+//! A reranker is configured like a `rest` embedder: the documents of the search, rendered with
+//! the document template, are injected in the `request` along with the `{{query}}`, and the
+//! relevance score of each document is extracted from the `response`.
+
+use std::collections::BTreeMap;
+use std::time::Instant;
+
+use deserr::Deserr;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::ToSchema;
+
+use super::json_template::ValueTemplate;
+use super::rest::{
+    check_response, contains_placeholder, ConfigurationSource, Retry, REPEAT_PLACEHOLDER,
+    REQUEST_PLACEHOLDER,
+};
+use super::EmbedError;
+use crate::prompt::{default_max_bytes, Prompt};
+
+/// The default number of best results of a search that are sent to the reranker.
+pub const DEFAULT_TOP_K: usize = 20;
+
+/// The maximum number of results that can be sent to the reranker.
+pub const MAX_TOP_K: usize = 1000;
+
+const QUERY_PLACEHOLDER: &str = "{{query}}";
+const SCORE_PLACEHOLDER: &str = "{{score}}";
+const INDEX_PLACEHOLDER: &str = "{{index}}";
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum RerankerError {
+    #[error("`.rerankers.{name}`: {message}")]
+    InvalidTemplate { name: String, message: String },
+    #[error("`.rerankers.{name}.documentTemplate`: {error}")]
+    InvalidDocumentTemplate { name: String, error: String },
+    #[error("`.rerankers.{name}.topK`: `topK` must be between 1 and {MAX_TOP_K}, got `{top_k}`.")]
+    InvalidTopK { name: String, top_k: usize },
+}
+
+/// A remote model reordering the best results of the searches using it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Deserr, ToSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct RerankerSettings {
+    /// The URL the requests are sent to.
+    pub url: String,
+    /// The API key sent as a bearer token, partially hidden when returned by the settings.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub api_key: Option<String>,
+    /// Additional headers sent with the requests.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    #[deserr(default)]
+    pub headers: BTreeMap<String, String>,
+    /// The template of the request, containing the `{{query}}` and an array of documents
+    /// repeating `{{text}}`.
+    #[schema(value_type = Object)]
+    pub request: serde_json::Value,
+    /// The template of the response, containing an array repeating the `{{score}}` of each
+    /// document, and optionally the `{{index}}` of the document in the request.
+    #[schema(value_type = Object)]
+    pub response: serde_json::Value,
+    /// The liquid template used to render the documents sent to the reranker.
+    ///
+    /// Defaults to the same template as the embedders.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub document_template: Option<String>,
+    /// The number of best results of the search that are reranked, defaults to 20.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub top_k: Option<usize>,
+}
+
+pub struct Reranker {
+    name: String,
+    client: ureq::Agent,
+    bearer: Option<String>,
+    headers: BTreeMap<String, String>,
+    url: String,
+    request: ValueTemplate,
+    scores: ValueTemplate,
+    /// Extracts the position of each score in the request, the scores being in the same order
+    /// as the documents when `None`.
+    indexes: Option<ValueTemplate>,
+    document_template: Prompt,
+    top_k: usize,
+}
+
+impl Reranker {
+    pub fn new(name: &str, settings: &RerankerSettings) -> Result<Self, RerankerError> {
+        let invalid_template =
+            |message| RerankerError::InvalidTemplate { name: name.to_owned(), message };
+
+        let request =
+            ValueTemplate::new(settings.request.clone(), REQUEST_PLACEHOLDER, REPEAT_PLACEHOLDER)
+                .map_err(|error| {
+                invalid_template(error.error_message(
+                    "request",
+                    REQUEST_PLACEHOLDER,
+                    REPEAT_PLACEHOLDER,
+                ))
+            })?;
+        if !request.has_array_value() {
+            return Err(invalid_template(format!(
+                r#"in `request`: Expected an array of documents, like ["{REQUEST_PLACEHOLDER}", "{REPEAT_PLACEHOLDER}"]"#
+            )));
+        }
+        if !contains_placeholder(&settings.request, QUERY_PLACEHOLDER) {
+            return Err(invalid_template(format!(
+                r#"in `request`: "{QUERY_PLACEHOLDER}" not found"#
+            )));
+        }
+
+        let scores =
+            response_template(&settings.response, SCORE_PLACEHOLDER).map_err(invalid_template)?;
+        let indexes = if contains_placeholder(&settings.response, INDEX_PLACEHOLDER) {
+            Some(
+                response_template(&settings.response, INDEX_PLACEHOLDER)
+                    .map_err(invalid_template)?,
+            )
+        } else {
+            None
+        };
+
+        let document_template =
+            match &settings.document_template {
+                Some(template) => Prompt::new(template.clone(), Some(default_max_bytes()))
+                    .map_err(|error| RerankerError::InvalidDocumentTemplate {
+                        name: name.to_owned(),
+                        error: error.to_string(),
+                    })?,
+                None => Prompt::default(),
+            };
+
+        let top_k = settings.top_k.unwrap_or(DEFAULT_TOP_K);
+        if !(1..=MAX_TOP_K).contains(&top_k) {
+            return Err(RerankerError::InvalidTopK { name: name.to_owned(), top_k });
+        }
+
+        let client = ureq::AgentBuilder::new().timeout(std::time::Duration::from_secs(30)).build();
+
+        Ok(Self {
+            name: name.to_owned(),
+            client,
+            bearer: settings.api_key.as_deref().map(|api_key| format!("Bearer {api_key}")),
+            headers: settings.headers.clone(),
+            url: settings.url.clone(),
+            request,
+            scores,
+            indexes,
+            document_template,
+            top_k,
+        })
+    }
+
+    /// Checks that all the rerankers can be built from their settings.
+    pub fn validate(rerankers: &BTreeMap<String, RerankerSettings>) -> Result<(), RerankerError> {
+        for (name, settings) in rerankers {
+            Self::new(name, settings)?;
+        }
+        Ok(())
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn document_template(&self) -> &Prompt {
+        &self.document_template
+    }
+
+    pub fn top_k(&self) -> usize {
+        self.top_k
+    }
+
+    /// Returns the relevance score of each document for the query, in the order of the documents.
+    pub fn rerank(
+        &self,
+        query: &str,
+        documents: &[&str],
+        deadline: Option<Instant>,
+    ) -> Result<Vec<f32>, EmbedError> {
+        let mut body = self
+            .request
+            .inject(documents.iter().map(|document| serde_json::json!(document)))
+            .unwrap();
+        inject_query(&mut body, query);
+
+        let request = self.client.post(&self.url);
+        let request = if let Some(bearer) = &self.bearer {
+            request.set("Authorization", bearer)
+        } else {
+            request
+        };
+        let mut request = request.set("Content-Type", "application/json");
+        for (header, value) in &self.headers {
+            request = request.set(header.as_str(), value.as_str());
+        }
+
+        for attempt in 0..3 {
+            let response = request.clone().send_json(&body);
+            let result = check_response(response, ConfigurationSource::User)
+                .and_then(|response| self.response_to_scores(response, documents.len()));
+
+            let retry_duration = match result {
+                Ok(scores) => return Ok(scores),
+                Err(retry) => {
+                    tracing::warn!("Failed: {}", retry.error);
+                    if let Some(deadline) = deadline {
+                        let now = std::time::Instant::now();
+                        if now > deadline {
+                            tracing::warn!("Could not rerank due to deadline");
+                            return Err(retry.into_error());
+                        }
+
+                        let duration_to_deadline = deadline - now;
+                        retry
+                            .into_duration(attempt)
+                            .map(|duration| duration.min(duration_to_deadline))
+                    } else {
+                        retry.into_duration(attempt)
+                    }
+                }
+            }?;
+
+            // randomly up to double the retry duration
+            let retry_duration = retry_duration
+                + rand::thread_rng().gen_range(std::time::Duration::ZERO..retry_duration);
+
+            tracing::warn!(
+                "Attempt #{}, retrying after {}ms.",
+                attempt,
+                retry_duration.as_millis()
+            );
+            std::thread::sleep(retry_duration);
+        }
+
+        let response = request.send_json(&body);
+        check_response(response, ConfigurationSource::User)
+            .and_then(|response| self.response_to_scores(response, documents.len()))
+            .map_err(Retry::into_error)
+    }
+
+    fn response_to_scores(
+        &self,
+        response: ureq::Response,
+        expected_count: usize,
+    ) -> Result<Vec<f32>, Retry> {
+        let response: serde_json::Value = response
+            .into_json()
+            .map_err(EmbedError::rest_response_deserialization)
+            .map_err(Retry::retry_later)?;
+
+        let indexes: Option<Vec<usize>> = match &self.indexes {
+            Some(template) => Some(template.extract(response.clone()).map_err(|error| {
+                Retry::give_up(EmbedError::rest_extraction_error(error.error_message(
+                    "response",
+                    INDEX_PLACEHOLDER,
+                    "an integer",
+                )))
+            })?),
+            None => None,
+        };
+        let extracted: Vec<f32> = self.scores.extract(response).map_err(|error| {
+            Retry::give_up(EmbedError::rest_extraction_error(error.error_message(
+                "response",
+                SCORE_PLACEHOLDER,
+                "a number",
+            )))
+        })?;
+
+        if extracted.len() != expected_count {
+            return Err(Retry::give_up(EmbedError::rerank_score_count(
+                expected_count,
+                extracted.len(),
+            )));
+        }
+
+        let Some(indexes) = indexes else { return Ok(extracted) };
+
+        // the scores are not necessarily returned in the order of the documents
+        let mut scores = vec![f32::NEG_INFINITY; expected_count];
+        for (index, score) in indexes.into_iter().zip(extracted) {
+            match scores.get_mut(index) {
+                Some(slot) => *slot = score,
+                None => {
+                    return Err(Retry::give_up(EmbedError::rerank_unexpected_index(
+                        index,
+                        expected_count,
+                    )))
+                }
+            }
+        }
+        Ok(scores)
+    }
+}
+
+fn response_template(
+    response: &serde_json::Value,
+    placeholder: &str,
+) -> Result<ValueTemplate, String> {
+    let template = ValueTemplate::new(response.clone(), placeholder, REPEAT_PLACEHOLDER)
+        .map_err(|error| error.error_message("response", placeholder, REPEAT_PLACEHOLDER))?;
+    if !template.has_array_value() {
+        return Err(format!(
+            r#"in `response`: Expected an array repeating "{placeholder}" for each document"#
+        ));
+    }
+    Ok(template)
+}
+
+fn inject_query(value: &mut serde_json::Value, query: &str) {
+    match value {
+        serde_json::Value::String(s) if s == QUERY_PLACEHOLDER => *s = query.to_owned(),
+        serde_json::Value::Array(values) => {
+            values.iter_mut().for_each(|value| inject_query(value, query))
+        }
+        serde_json::Value::Object(map) => {
+            map.values_mut().for_each(|value| inject_query(value, query))
+        }
+        _ => (),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    fn settings(request: serde_json::Value, response: serde_json::Value) -> RerankerSettings {
+        RerankerSettings {
+            url: "http://localhost".to_owned(),
+            api_key: None,
+            headers: BTreeMap::new(),
+            request,
+            response,
+            document_template: None,
+            top_k: None,
+        }
+    }
+
+    #[test]
+    fn templates() {
+        let reranker = Reranker::new(
+            "cohere",
+            &settings(
+                json!({ "query": "{{query}}", "documents": ["{{text}}", "{{..}}"] }),
+                json!({ "results": [{ "index": "{{index}}", "relevance_score": "{{score}}" }, "{{..}}"] }),
+            ),
+        )
+        .unwrap();
+
+        let mut body = reranker.request.inject([json!("kefir"), json!("intel")]).unwrap();
+        inject_query(&mut body, "dog");
+        insta::assert_snapshot!(body, @r#"{"query":"dog","documents":["kefir","intel"]}"#);
+
+        let response = json!({ "results": [
+            { "index": 1, "relevance_score": 0.9 },
+            { "index": 0, "relevance_score": 0.1 },
+        ]});
+        let indexes: Vec<usize> =
+            reranker.indexes.as_ref().unwrap().extract(response.clone()).unwrap();
+        let scores: Vec<f32> = reranker.scores.extract(response).unwrap();
+        assert_eq!(indexes, vec![1, 0]);
+        assert_eq!(scores, vec![0.9, 0.1]);
+
+        let error = Reranker::new(
+            "cohere",
+            &settings(
+                json!({ "documents": ["{{text}}", "{{..}}"] }),
+                json!(["{{score}}", "{{..}}"]),
+            ),
+        )
+        .err()
+        .unwrap();
+        insta::assert_snapshot!(error, @r#"`.rerankers.cohere`: in `request`: "{{query}}" not found"#);
+
+        let error = Reranker::new(
+            "cohere",
+            &settings(
+                json!({ "query": "{{query}}", "document": "{{text}}" }),
+                json!(["{{score}}", "{{..}}"]),
+            ),
+        )
+        .err()
+        .unwrap();
+        insta::assert_snapshot!(error, @r#"`.rerankers.cohere`: in `request`: Expected an array of documents, like ["{{text}}", "{{..}}"]"#);
+
+        let error = Reranker::new(
+            "cohere",
+            &settings(
+                json!({ "query": "{{query}}", "documents": ["{{text}}", "{{..}}"] }),
+                json!({ "score": "{{score}}" }),
+            ),
+        )
+        .err()
+        .unwrap();
+        insta::assert_snapshot!(error, @r#"`.rerankers.cohere`: in `response`: Expected an array repeating "{{score}}" for each document"#);
+    }
+}
@@ -9,6 +9,7 @@ use serde::{Deserialize, Serialize};
 
 use super::error::EmbedErrorKind;
 use super::json_template::ValueTemplate;
+use super::sparse::{SparseEmbedding, MAX_SPARSE_DIMENSIONS};
 use super::{
     DistributionShift, EmbedError, Embedding, EmbeddingCache, NewEmbedderError, REQUEST_PARALLELISM,
 };
@@ -103,6 +104,13 @@ pub struct EmbedderOptions {
     pub headers: BTreeMap<String, String>,
 }
 
+impl EmbedderOptions {
+    /// Whether the `response` contains a `{{sparseEmbedding}}` rather than an `{{embedding}}`.
+    pub fn is_sparse(&self) -> bool {
+        contains_placeholder(&self.response, SPARSE_RESPONSE_PLACEHOLDER)
+    }
+}
+
 impl std::hash::Hash for EmbedderOptions {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         self.api_key.hash(state);
@@ -150,7 +158,16 @@ impl Embedder {
             headers: options.headers,
         };
 
-        let dimensions = if let Some(dimensions) = options.dimensions {
+        let dimensions = if data.response.is_sparse() {
+            // the dimensions of a sparse embedder are the size of its vocabulary, they cannot be inferred
+            match options.dimensions {
+                Some(dimensions) if dimensions > MAX_SPARSE_DIMENSIONS => {
+                    return Err(NewEmbedderError::sparse_dimensions_too_large(dimensions))
+                }
+                Some(dimensions) => dimensions,
+                None => MAX_SPARSE_DIMENSIONS,
+            }
+        } else if let Some(dimensions) = options.dimensions {
             dimensions
         } else {
             infer_dimensions(&data)?
@@ -266,6 +283,11 @@ impl Embedder {
         self.distribution
     }
 
+    /// Whether the embedder produces sparse embeddings, packed with [`SparseEmbedding::to_packed`].
+    pub fn is_sparse(&self) -> bool {
+        self.data.response.is_sparse()
+    }
+
     pub(super) fn cache(&self) -> &EmbeddingCache {
         &self.cache
     }
@@ -344,7 +366,7 @@ where
     })
 }
 
-fn check_response(
+pub(super) fn check_response(
     response: Result<ureq::Response, ureq::Error>,
     configuration_source: ConfigurationSource,
 ) -> Result<ureq::Response, Retry> {
@@ -398,6 +420,20 @@ fn response_to_embedding(
     }
 
     if let Some(dimensions) = expected_dimensions {
+        if data.response.is_sparse() {
+            for embedding in &embeddings {
+                let embedding = SparseEmbedding::from_packed(embedding);
+                if let Some(token) =
+                    embedding.max_token().filter(|token| *token as usize >= dimensions)
+                {
+                    return Err(Retry::give_up(EmbedError::rest_unexpected_sparse_token(
+                        token, dimensions,
+                    )));
+                }
+            }
+            return Ok(embeddings);
+        }
+
         for embedding in &embeddings {
             if embedding.len() != dimensions {
                 return Err(Retry::give_up(EmbedError::rest_unexpected_dimension(
@@ -413,6 +449,7 @@ fn response_to_embedding(
 
 pub(super) const REQUEST_PLACEHOLDER: &str = "{{text}}";
 pub(super) const RESPONSE_PLACEHOLDER: &str = "{{embedding}}";
+pub(super) const SPARSE_RESPONSE_PLACEHOLDER: &str = "{{sparseEmbedding}}";
 pub(super) const REPEAT_PLACEHOLDER: &str = "{{..}}";
 
 #[derive(Debug)]
@@ -453,31 +490,48 @@ impl Request {
 #[derive(Debug)]
 pub struct Response {
     template: ValueTemplate,
+    sparse: bool,
+}
+
+/// A weighted token of a sparse embedding, as returned by e.g. Text Embeddings Inference.
+#[derive(Debug, Deserialize)]
+struct SparseValue {
+    index: u32,
+    value: f32,
 }
 
 impl Response {
     pub fn new(template: serde_json::Value, request: &Request) -> Result<Self, NewEmbedderError> {
-        let template = match ValueTemplate::new(template, RESPONSE_PLACEHOLDER, REPEAT_PLACEHOLDER)
-        {
+        let sparse = contains_placeholder(&template, SPARSE_RESPONSE_PLACEHOLDER);
+        let placeholder = if sparse { SPARSE_RESPONSE_PLACEHOLDER } else { RESPONSE_PLACEHOLDER };
+        let template = match ValueTemplate::new(template, placeholder, REPEAT_PLACEHOLDER) {
             Ok(template) => template,
             Err(error) => {
-                let message =
-                    error.error_message("response", RESPONSE_PLACEHOLDER, REPEAT_PLACEHOLDER);
+                let message = error.error_message("response", placeholder, REPEAT_PLACEHOLDER);
                 return Err(NewEmbedderError::rest_could_not_parse_template(message));
             }
         };
 
         match (template.has_array_value(), request.template.has_array_value()) {
-            (true, true) | (false, false) => Ok(Self {template}),
+            (true, true) | (false, false) => Ok(Self {template, sparse}),
             (true, false) => Err(NewEmbedderError::rest_could_not_parse_template("in `response`: `response` has multiple embeddings, but `request` has only one text to embed".to_string())),
             (false, true) => Err(NewEmbedderError::rest_could_not_parse_template("in `response`: `response` has a single embedding, but `request` has multiple texts to embed".to_string())),
         }
     }
 
+    /// Whether the response contains a `{{sparseEmbedding}}` rather than an `{{embedding}}`.
+    pub fn is_sparse(&self) -> bool {
+        self.sparse
+    }
+
     pub fn extract_embeddings(
         &self,
         response: serde_json::Value,
     ) -> Result<Vec<Embedding>, EmbedError> {
+        if self.sparse {
+            return self.extract_sparse_embeddings(response);
+        }
+
         let extracted_values: Vec<Embedding> = match self.template.extract(response) {
             Ok(extracted_values) => extracted_values,
             Err(error) => {
@@ -490,4 +544,44 @@ impl Response {
 
         Ok(embeddings)
     }
+
+    fn extract_sparse_embeddings(
+        &self,
+        response: serde_json::Value,
+    ) -> Result<Vec<Embedding>, EmbedError> {
+        let extracted_values: Vec<Vec<SparseValue>> = match self.template.extract(response) {
+            Ok(extracted_values) => extracted_values,
+            Err(error) => {
+                let error_message = error.error_message(
+                    "response",
+                    SPARSE_RESPONSE_PLACEHOLDER,
+                    "an array of objects with an `index` and a `value`",
+                );
+                return Err(EmbedError::rest_extraction_error(error_message));
+            }
+        };
+
+        Ok(extracted_values
+            .into_iter()
+            .map(|values| {
+                SparseEmbedding::new(
+                    values.into_iter().map(|SparseValue { index, value }| (index, value)),
+                )
+                .to_packed()
+            })
+            .collect())
+    }
+}
+
+pub(super) fn contains_placeholder(value: &serde_json::Value, placeholder: &str) -> bool {
+    match value {
+        serde_json::Value::String(s) => s == placeholder,
+        serde_json::Value::Array(values) => {
+            values.iter().any(|value| contains_placeholder(value, placeholder))
+        }
+        serde_json::Value::Object(map) => {
+            map.values().any(|value| contains_placeholder(value, placeholder))
+        }
+        _ => false,
+    }
 }
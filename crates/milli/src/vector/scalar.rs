@@ -0,0 +1,468 @@
+//! Scalar quantization of the embeddings, storing each of their dimensions in a single byte.
+//!
+//! The scalar quantized embeddings are stored in a separate database, taking about a fourth of the
+//! space of the original embeddings, and the nearest neighbors are found by comparing the target
+//! to the scalar quantized embeddings of all the candidates. The recall is then close to the one of
+//! the original embeddings, but a search reads the embeddings of all the candidates instead of
+//! walking a tree. The arroy trees of the embedder only contain binary quantized embeddings, a
+//! 32nd of the original size, and are only used to track the documents and their embeddings.
+//!
+//! When the full-precision rescoring is enabled, the original embeddings are also kept to rescore
+//! the best candidates, to be returned to the users, and to restore the embeddings when the scalar
+//! quantization is disabled. They are only read for the best candidates, but the index then takes
+//! more space on disk than without any quantization.
+
+use std::collections::BinaryHeap;
+
+use arroy::ItemId;
+use heed::types::{Bytes, DecodeIgnore, SerdeBincode};
+use heed::{Database, RoTxn, RwTxn};
+use ordered_float::OrderedFloat;
+use roaring::RoaringBitmap;
+use serde::{Deserialize, Serialize};
+
+use super::{arroy_db_range_for_embedder, Distance, Embedding};
+use crate::heed_codec::U8BEU32Codec;
+use crate::{DocumentId, Index};
+
+/// How many more candidates than requested are found with the scalar quantized embeddings
+/// to be rescored with their original embeddings.
+const CANDIDATES_OVERSAMPLING: usize = 4;
+
+/// An embedding whose dimensions are mapped to 256 evenly spaced values between its minimum and maximum.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScalarQuantizedEmbedding {
+    min: f32,
+    scale: f32,
+    values: Vec<i8>,
+}
+
+impl ScalarQuantizedEmbedding {
+    pub fn quantize(embedding: &[f32]) -> Self {
+        let min = embedding.iter().copied().fold(f32::INFINITY, f32::min);
+        let max = embedding.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        if !(min.is_finite() && max.is_finite()) || min == max {
+            // all the dimensions have the same value, that is exactly represented by the minimum
+            let min = if min.is_finite() { min } else { 0.0 };
+            return Self { min, scale: 0.0, values: vec![i8::MIN; embedding.len()] };
+        }
+
+        let scale = (max - min) / u8::MAX as f32;
+        let values = embedding
+            .iter()
+            .map(|value| {
+                let quantized = ((value - min) / scale).round().clamp(0.0, u8::MAX as f32);
+                (quantized as i16 + i8::MIN as i16) as i8
+            })
+            .collect();
+        Self { min, scale, values }
+    }
+
+    pub fn dequantize(&self) -> Embedding {
+        let mut embedding = Vec::with_capacity(self.values.len());
+        self.dequantize_into(&mut embedding);
+        embedding
+    }
+
+    /// Replaces the content of the buffer by the dequantized embedding.
+    pub fn dequantize_into(&self, buffer: &mut Embedding) {
+        buffer.clear();
+        buffer.extend(
+            self.values
+                .iter()
+                .map(|&value| self.min + (value as i16 - i8::MIN as i16) as f32 * self.scale),
+        );
+    }
+
+    pub fn values(&self) -> &[i8] {
+        &self.values
+    }
+}
+
+/// The scalar quantized and original embeddings of an embedder, indexed like its arroy trees.
+///
+/// The `n`-th embedding of a document is the one stored in the `n`-th arroy tree of the embedder.
+#[derive(Clone, Copy)]
+pub struct ScalarQuantizedStore {
+    embedder_index: u8,
+    quantized_vectors: Database<U8BEU32Codec, SerdeBincode<Vec<ScalarQuantizedEmbedding>>>,
+    vectors: Database<U8BEU32Codec, SerdeBincode<Vec<Embedding>>>,
+}
+
+impl ScalarQuantizedStore {
+    pub fn new(index: &Index, embedder_index: u8) -> Self {
+        Self {
+            embedder_index,
+            quantized_vectors: index.scalar_quantized_vectors,
+            vectors: index.full_precision_vectors,
+        }
+    }
+
+    /// Replaces the embeddings of the document, keeping the originals when `full_precision`.
+    pub fn put<'e>(
+        &self,
+        wtxn: &mut RwTxn,
+        docid: DocumentId,
+        embeddings: impl IntoIterator<Item = &'e [f32]>,
+        full_precision: bool,
+    ) -> heed::Result<()> {
+        // arroy can't store more embeddings per document than it has trees
+        let embeddings: Vec<_> = embeddings
+            .into_iter()
+            .zip(arroy_db_range_for_embedder(self.embedder_index))
+            .map(|(embedding, _)| embedding)
+            .collect();
+        let quantized = embeddings.iter().map(|e| ScalarQuantizedEmbedding::quantize(e)).collect();
+        let vectors = full_precision.then(|| embeddings.iter().map(|e| e.to_vec()).collect());
+        self.write(wtxn, docid, quantized, vectors)
+    }
+
+    /// Appends an embedding to the embeddings of the document, keeping the original when `full_precision`.
+    pub fn push(
+        &self,
+        wtxn: &mut RwTxn,
+        docid: DocumentId,
+        embedding: &[f32],
+        full_precision: bool,
+    ) -> heed::Result<()> {
+        let mut quantized = self.quantized_vectors(wtxn, docid)?.unwrap_or_default();
+        if quantized.len() >= arroy_db_range_for_embedder(self.embedder_index).count() {
+            return Ok(());
+        }
+        // the originals are only kept if all the embeddings of the document have theirs
+        let mut vectors = match self.full_precision_vectors(wtxn, docid)? {
+            _ if !full_precision => None,
+            Some(vectors) if vectors.len() == quantized.len() => Some(vectors),
+            None if quantized.is_empty() => Some(Vec::new()),
+            _ => None,
+        };
+
+        quantized.push(ScalarQuantizedEmbedding::quantize(embedding));
+        if let Some(vectors) = &mut vectors {
+            vectors.push(embedding.to_vec());
+        }
+        self.write(wtxn, docid, quantized, vectors)
+    }
+
+    /// Removes one embedding of the document, replacing it by its last embedding
+    /// as arroy does to keep the embeddings packed in its first trees.
+    pub fn remove(
+        &self,
+        wtxn: &mut RwTxn,
+        docid: DocumentId,
+        embedding: &[f32],
+    ) -> heed::Result<bool> {
+        let mut quantized = self.quantized_vectors(wtxn, docid)?.unwrap_or_default();
+        let mut vectors = self
+            .full_precision_vectors(wtxn, docid)?
+            .filter(|vectors| vectors.len() == quantized.len());
+        let position = match &vectors {
+            Some(vectors) => vectors.iter().position(|vector| vector == embedding),
+            None => {
+                let embedding = ScalarQuantizedEmbedding::quantize(embedding);
+                quantized.iter().position(|quantized| quantized == &embedding)
+            }
+        };
+        let Some(position) = position else {
+            return Ok(false);
+        };
+
+        quantized.swap_remove(position);
+        if let Some(vectors) = &mut vectors {
+            vectors.swap_remove(position);
+        }
+        self.write(wtxn, docid, quantized, vectors)?;
+        Ok(true)
+    }
+
+    /// Removes all the embeddings of the document, returns `false` if it had none.
+    pub fn delete(&self, wtxn: &mut RwTxn, docid: DocumentId) -> heed::Result<bool> {
+        let key = (self.embedder_index, docid);
+        self.vectors.delete(wtxn, &key)?;
+        self.quantized_vectors.delete(wtxn, &key)
+    }
+
+    /// Removes all the embeddings of the embedder.
+    pub fn clear(&self, wtxn: &mut RwTxn) -> heed::Result<()> {
+        let prefix = [self.embedder_index];
+
+        let quantized_vectors = self.quantized_vectors.remap_types::<Bytes, DecodeIgnore>();
+        let mut iter = quantized_vectors.prefix_iter_mut(wtxn, &prefix)?;
+        while iter.next().transpose()?.is_some() {
+            // safety: we do not keep a reference on database entries.
+            unsafe { iter.del_current()? };
+        }
+        drop(iter);
+
+        self.clear_full_precision(wtxn)
+    }
+
+    /// Removes the original embeddings of the embedder, keeping the scalar quantized ones.
+    pub fn clear_full_precision(&self, wtxn: &mut RwTxn) -> heed::Result<()> {
+        let vectors = self.vectors.remap_types::<Bytes, DecodeIgnore>();
+        let mut iter = vectors.prefix_iter_mut(wtxn, &[self.embedder_index])?;
+        while iter.next().transpose()?.is_some() {
+            // safety: we do not keep a reference on database entries.
+            unsafe { iter.del_current()? };
+        }
+
+        Ok(())
+    }
+
+    /// The embeddings of the document, the original ones if they were kept.
+    pub fn vectors(&self, rtxn: &RoTxn, docid: DocumentId) -> heed::Result<Option<Vec<Embedding>>> {
+        let Some(quantized) = self.quantized_vectors(rtxn, docid)? else {
+            return Ok(None);
+        };
+        match self.full_precision_vectors(rtxn, docid)? {
+            Some(vectors) if vectors.len() == quantized.len() => Ok(Some(vectors)),
+            _ => Ok(Some(quantized.iter().map(ScalarQuantizedEmbedding::dequantize).collect())),
+        }
+    }
+
+    pub fn quantized_vectors(
+        &self,
+        rtxn: &RoTxn,
+        docid: DocumentId,
+    ) -> heed::Result<Option<Vec<ScalarQuantizedEmbedding>>> {
+        self.quantized_vectors.get(rtxn, &(self.embedder_index, docid))
+    }
+
+    fn full_precision_vectors(
+        &self,
+        rtxn: &RoTxn,
+        docid: DocumentId,
+    ) -> heed::Result<Option<Vec<Embedding>>> {
+        self.vectors.get(rtxn, &(self.embedder_index, docid))
+    }
+
+    /// The documents with at least one embedding.
+    pub fn documents(&self, rtxn: &RoTxn) -> heed::Result<RoaringBitmap> {
+        let mut documents = RoaringBitmap::new();
+        let quantized_vectors = self.quantized_vectors.remap_types::<Bytes, DecodeIgnore>();
+        for result in quantized_vectors.prefix_iter(rtxn, &[self.embedder_index])? {
+            let (key, ()) = result?;
+            if let Ok(docid) = key[1..].try_into().map(u32::from_be_bytes) {
+                documents.insert(docid);
+            }
+        }
+        Ok(documents)
+    }
+
+    /// Finds the `limit` documents whose embeddings are the nearest to the `target`, among the
+    /// `candidates` or all the documents, along with the index of their nearest embedding.
+    ///
+    /// The distances are computed from the scalar quantized embeddings of all the candidates.
+    /// When `full_precision`, more candidates are kept to be rescored with their original
+    /// embedding, if it was kept.
+    pub fn nns_by_vector(
+        &self,
+        rtxn: &RoTxn,
+        distance: Distance,
+        target: &[f32],
+        limit: usize,
+        candidates: Option<&RoaringBitmap>,
+        full_precision: bool,
+    ) -> heed::Result<Vec<(ItemId, usize, f32)>> {
+        let candidates_limit =
+            if full_precision { limit.saturating_mul(CANDIDATES_OVERSAMPLING) } else { limit };
+        if candidates_limit == 0 {
+            return Ok(Vec::new());
+        }
+
+        // a max-heap of the nearest embeddings found so far, the farthest one on top
+        let mut nearest = BinaryHeap::new();
+        let mut buffer = Vec::with_capacity(target.len());
+        let mut insert = |docid: DocumentId, embeddings: Vec<ScalarQuantizedEmbedding>| {
+            let best = embeddings
+                .iter()
+                .enumerate()
+                .map(|(index, embedding)| {
+                    embedding.dequantize_into(&mut buffer);
+                    (OrderedFloat(distance.between(target, &buffer)), index)
+                })
+                .min();
+            if let Some((best_distance, index)) = best {
+                nearest.push((best_distance, docid, index));
+                if nearest.len() > candidates_limit {
+                    nearest.pop();
+                }
+            }
+        };
+
+        match candidates {
+            Some(candidates) => {
+                for docid in candidates {
+                    if let Some(embeddings) = self.quantized_vectors(rtxn, docid)? {
+                        insert(docid, embeddings);
+                    }
+                }
+            }
+            None => {
+                let quantized_vectors = self.quantized_vectors.remap_key_type::<Bytes>();
+                for result in quantized_vectors.prefix_iter(rtxn, &[self.embedder_index])? {
+                    let (key, embeddings) = result?;
+                    if let Ok(docid) = key[1..].try_into().map(u32::from_be_bytes) {
+                        insert(docid, embeddings);
+                    }
+                }
+            }
+        }
+
+        let mut results: Vec<_> = nearest
+            .into_sorted_vec()
+            .into_iter()
+            .map(|(distance, docid, index)| (docid, index, distance.0))
+            .collect();
+
+        if full_precision {
+            for (docid, index, result_distance) in results.iter_mut() {
+                let vectors = self.full_precision_vectors(rtxn, *docid)?;
+                if let Some(embedding) = vectors.and_then(|vectors| vectors.into_iter().nth(*index))
+                {
+                    *result_distance = distance.between(target, &embedding);
+                }
+            }
+            results.sort_unstable_by_key(|&(docid, _, distance)| (OrderedFloat(distance), docid));
+            results.truncate(limit);
+        }
+
+        Ok(results)
+    }
+
+    fn write(
+        &self,
+        wtxn: &mut RwTxn,
+        docid: DocumentId,
+        quantized: Vec<ScalarQuantizedEmbedding>,
+        vectors: Option<Vec<Embedding>>,
+    ) -> heed::Result<()> {
+        if quantized.is_empty() {
+            self.delete(wtxn, docid)?;
+            return Ok(());
+        }
+
+        let key = (self.embedder_index, docid);
+        self.quantized_vectors.put(wtxn, &key, &quantized)?;
+        match vectors {
+            Some(vectors) => self.vectors.put(wtxn, &key, &vectors),
+            None => self.vectors.delete(wtxn, &key).map(drop),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::index::tests::TempIndex;
+
+    #[test]
+    fn quantization() {
+        let embedding = [-1.0, -0.5, 0.0, 0.25, 1.0];
+        let quantized = ScalarQuantizedEmbedding::quantize(&embedding);
+        assert_eq!(quantized.values(), &[-128, -64, 0, 31, 127]);
+        for (original, dequantized) in embedding.iter().zip(quantized.dequantize()) {
+            // the error is at most half the step between two quantized values
+            assert!((original - dequantized).abs() < 0.005);
+        }
+
+        // a constant embedding is exactly restored
+        let quantized = ScalarQuantizedEmbedding::quantize(&[0.5, 0.5, 0.5]);
+        assert_eq!(quantized.dequantize(), vec![0.5, 0.5, 0.5]);
+    }
+
+    #[test]
+    fn quantized_distances() {
+        let target = [0.3, -0.2, 0.9, 0.1];
+        let embedding = [0.25, -0.15, 0.8, 0.05];
+        let dequantized = ScalarQuantizedEmbedding::quantize(&embedding).dequantize();
+        for distance in [Distance::Cosine, Distance::Euclidean, Distance::Manhattan] {
+            let exact = distance.between(&target, &embedding);
+            let approximate = distance.between(&target, &dequantized);
+            assert!((exact - approximate).abs() < 0.01, "{distance}: {exact} != {approximate}");
+        }
+    }
+
+    #[test]
+    fn originals_are_only_kept_for_full_precision() {
+        let index = TempIndex::new();
+        let store = ScalarQuantizedStore::new(&index, 0);
+        let mut wtxn = index.write_txn().unwrap();
+
+        let embedding = [-1.0, 0.123, 0.7];
+        store.put(&mut wtxn, 0, [embedding.as_slice()], false).unwrap();
+        store.put(&mut wtxn, 1, [embedding.as_slice()], true).unwrap();
+        store.push(&mut wtxn, 1, &[0.5, 0.5, 0.5], true).unwrap();
+
+        let dequantized = store.vectors(&wtxn, 0).unwrap().unwrap();
+        assert_ne!(dequantized, vec![embedding.to_vec()]);
+        assert_eq!(
+            store.vectors(&wtxn, 1).unwrap().unwrap(),
+            vec![embedding.to_vec(), vec![0.5, 0.5, 0.5]]
+        );
+
+        // once the originals are removed, the scalar quantized embeddings are returned
+        store.clear_full_precision(&mut wtxn).unwrap();
+        assert_eq!(store.vectors(&wtxn, 1).unwrap().unwrap()[0], dequantized[0]);
+
+        assert!(store.remove(&mut wtxn, 1, &[0.5, 0.5, 0.5]).unwrap());
+        assert_eq!(store.quantized_vectors(&wtxn, 1).unwrap().unwrap().len(), 1);
+        assert_eq!(store.documents(&wtxn).unwrap(), RoaringBitmap::from_iter([0, 1]));
+    }
+
+    #[test]
+    fn nns_by_vector() {
+        let index = TempIndex::new();
+        let store = ScalarQuantizedStore::new(&index, 0);
+        let mut wtxn = index.write_txn().unwrap();
+
+        let embeddings: Vec<Vec<f32>> = (0..50u32)
+            .map(|docid| {
+                let docid = docid as f32;
+                vec![(docid * 0.37).sin(), (docid * 0.11).cos(), docid / 50.0 - 0.5]
+            })
+            .collect();
+        for (docid, pair) in embeddings.chunks(2).enumerate() {
+            store.put(&mut wtxn, docid as u32, pair.iter().map(Vec::as_slice), true).unwrap();
+        }
+
+        let target = [0.2, -0.4, 0.1];
+        let candidates = RoaringBitmap::from_iter((0..25).step_by(3));
+        for distance in [Distance::Cosine, Distance::Euclidean, Distance::Manhattan] {
+            for filter in [None, Some(&candidates)] {
+                // the nearest embedding of each candidate, computed from the originals
+                let mut expected: Vec<_> = embeddings
+                    .chunks(2)
+                    .enumerate()
+                    .filter(|(docid, _)| filter.is_none_or(|f| f.contains(*docid as u32)))
+                    .map(|(docid, pair)| {
+                        pair.iter()
+                            .enumerate()
+                            .map(|(index, e)| (docid as u32, index, distance.between(&target, e)))
+                            .min_by_key(|&(_, _, distance)| OrderedFloat(distance))
+                            .unwrap()
+                    })
+                    .collect();
+                expected
+                    .sort_unstable_by_key(|&(docid, _, distance)| (OrderedFloat(distance), docid));
+                expected.truncate(5);
+
+                let results =
+                    store.nns_by_vector(&wtxn, distance, &target, 5, filter, true).unwrap();
+                assert_eq!(results, expected, "{distance}");
+
+                // without rescoring, the distances are close to the ones of the originals
+                let results =
+                    store.nns_by_vector(&wtxn, distance, &target, 5, filter, false).unwrap();
+                assert_eq!(results.len(), 5);
+                for ((docid, _, approximate), (_, _, exact)) in results.iter().zip(&expected) {
+                    assert!(filter.is_none_or(|f| f.contains(*docid)));
+                    assert!(
+                        (approximate - exact).abs() < 0.05,
+                        "{distance}: {approximate} != {exact}"
+                    );
+                }
+            }
+        }
+    }
+}
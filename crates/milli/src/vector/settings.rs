@@ -8,8 +8,8 @@ use utoipa::ToSchema;
 
 use super::composite::SubEmbedderOptions;
 use super::hf::OverridePooling;
-use super::{ollama, openai, DistributionShift, EmbedderOptions};
-use crate::prompt::{default_max_bytes, PromptData};
+use super::{ollama, openai, Distance, DistributionShift, EmbedderOptions};
+use crate::prompt::{default_max_bytes, Chunking, PromptData};
 use crate::update::Setting;
 use crate::vector::EmbeddingConfig;
 use crate::UserError;
@@ -169,6 +169,87 @@ pub struct EmbeddingSettings {
     pub binary_quantized: Setting<bool>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default)]
+    #[schema(value_type = Option<Distance>)]
+    /// The distance used to compare the embeddings of this embedder.
+    ///
+    /// Use the distance the model was trained for, e.g. `dotProduct` for models trained to maximize the inner product.
+    ///
+    /// # Availability
+    ///
+    /// - This parameter is available for all embedders
+    ///
+    /// # 🔄 Reindexing
+    ///
+    /// - 🏗️ Changing the value of this parameter always regenerates embeddings
+    ///
+    /// # Defaults
+    ///
+    /// - Defaults to `cosine`
+    ///
+    /// # Note
+    ///
+    /// The `dotProduct` distance cannot be used with `binaryQuantized`.
+    /// Embedders returning a `{{sparseEmbedding}}` always use the `cosine` distance.
+    pub distance: Setting<Distance>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    #[schema(value_type = Option<bool>)]
+    /// Whether to scalar quantize the embeddings of this embedder.
+    ///
+    /// Each dimension of the embeddings is stored on a single byte instead of four, which reduces
+    /// the size of the embeddings about 4 times. The nearest neighbors are found by comparing the query
+    /// to the scalar quantized embeddings of all the candidate documents, which keeps a relevancy close
+    /// to that of the original embeddings, but makes the search time grow with the number of candidates.
+    /// The original embeddings are only kept when `fullPrecisionRescoring` is enabled.
+    ///
+    /// # Availability
+    ///
+    /// - This parameter is available for all embedders
+    ///
+    /// # 🔄 Reindexing
+    ///
+    /// - 🏗️ When modified, embeddings are not regenerated, but they are quantized or restored, which takes time.
+    ///
+    /// # Defaults
+    ///
+    /// - Defaults to `false`
+    ///
+    /// # Note
+    ///
+    /// Unlike binary quantization, this setting can be disabled again. The embeddings are then restored at full precision
+    /// if `fullPrecisionRescoring` is enabled, and from their scalar quantized version otherwise.
+    ///
+    /// `scalarQuantized` cannot be used with `binaryQuantized`, nor with the `dotProduct` distance.
+    pub scalar_quantized: Setting<bool>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    #[schema(value_type = Option<bool>)]
+    /// Whether to rescore the best results of a scalar quantized embedder with their original embeddings.
+    ///
+    /// This improves the relevancy of the results, at the cost of keeping the original embeddings on disk
+    /// along with the scalar quantized ones, which then take more space than unquantized embeddings.
+    /// Only the original embeddings of the best candidates are read during a search.
+    /// The original embeddings are deleted when this parameter is disabled.
+    ///
+    /// # Availability
+    ///
+    /// - This parameter is only available when `scalarQuantized` is `true`
+    ///
+    /// # 🔄 Reindexing
+    ///
+    /// - 🌱 Modifying this value never causes a reindexing
+    ///
+    /// # Defaults
+    ///
+    /// - Defaults to `false`
+    ///
+    /// # Note
+    ///
+    /// Only the embeddings written while this parameter is enabled are kept at full precision.
+    /// Enable it along with `scalarQuantized` to rescore all the embeddings.
+    pub full_precision_rescoring: Setting<bool>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
     #[schema(value_type = Option<bool>)]
     /// A liquid template used to render documents to a text that can be embedded.
     ///
@@ -203,6 +284,25 @@ pub struct EmbeddingSettings {
     pub document_template_max_bytes: Setting<usize>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default)]
+    #[schema(value_type = Option<Chunking>)]
+    /// Splits the rendered texts into overlapping chunks that are embedded separately.
+    ///
+    /// Documents are ranked by their chunk most similar to the query.
+    ///
+    /// # Availability
+    ///
+    /// - This parameter is available for source `openAi`, `huggingFace`, `ollama` and `rest`
+    ///
+    /// # 🔄 Reindexing
+    ///
+    /// - 🏗️ Changing the value of this parameter always regenerates embeddings
+    ///
+    /// # Default
+    ///
+    /// - Rendered texts are not chunked
+    pub document_template_chunking: Setting<Chunking>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
     #[schema(value_type = Option<String>)]
     /// URL to reach the remote embedder.
     ///
@@ -455,6 +555,25 @@ pub struct SubEmbeddingSettings {
     pub document_template_max_bytes: Setting<usize>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default)]
+    #[schema(value_type = Option<Chunking>)]
+    /// Splits the rendered texts into overlapping chunks that are embedded separately.
+    ///
+    /// Documents are ranked by their chunk most similar to the query.
+    ///
+    /// # Availability
+    ///
+    /// - This parameter is available for source `openAi`, `huggingFace`, `ollama` and `rest`
+    ///
+    /// # 🔄 Reindexing
+    ///
+    /// - 🏗️ Changing the value of this parameter always regenerates embeddings
+    ///
+    /// # Default
+    ///
+    /// - Rendered texts are not chunked
+    pub document_template_chunking: Setting<Chunking>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
     #[schema(value_type = Option<String>)]
     /// URL to reach the remote embedder.
     ///
@@ -531,6 +650,21 @@ pub struct SubEmbeddingSettings {
     #[schema(ignore)]
     pub binary_quantized: Setting<bool>,
 
+    #[serde(default, skip_serializing)]
+    #[deserr(default)]
+    #[schema(ignore)]
+    pub distance: Setting<Distance>,
+
+    #[serde(default, skip_serializing)]
+    #[deserr(default)]
+    #[schema(ignore)]
+    pub scalar_quantized: Setting<bool>,
+
+    #[serde(default, skip_serializing)]
+    #[deserr(default)]
+    #[schema(ignore)]
+    pub full_precision_rescoring: Setting<bool>,
+
     #[serde(default, skip_serializing)]
     #[deserr(default)]
     #[schema(ignore)]
@@ -555,8 +689,17 @@ pub enum ReindexAction {
 
 pub enum SettingsDiff {
     Remove,
-    Reindex { action: ReindexAction, updated_settings: EmbeddingSettings, quantize: bool },
-    UpdateWithoutReindex { updated_settings: EmbeddingSettings, quantize: bool },
+    Reindex {
+        action: ReindexAction,
+        updated_settings: EmbeddingSettings,
+        quantize: bool,
+    },
+    UpdateWithoutReindex {
+        updated_settings: EmbeddingSettings,
+        quantize: bool,
+        /// The trees must be rebuilt from the stored embeddings, e.g. to change their distance.
+        rebuild: bool,
+    },
 }
 
 #[derive(Default, Debug)]
@@ -629,7 +772,11 @@ impl SettingsDiff {
                     mut distribution,
                     mut headers,
                     mut document_template_max_bytes,
+                    mut document_template_chunking,
                     binary_quantized: mut binary_quantize,
+                    mut distance,
+                    mut scalar_quantized,
+                    mut full_precision_rescoring,
                 } = old;
 
                 let EmbeddingSettings {
@@ -648,7 +795,11 @@ impl SettingsDiff {
                     distribution: new_distribution,
                     headers: new_headers,
                     document_template_max_bytes: new_document_template_max_bytes,
+                    document_template_chunking: new_document_template_chunking,
                     binary_quantized: new_binary_quantize,
+                    distance: new_distance,
+                    scalar_quantized: new_scalar_quantized,
+                    full_precision_rescoring: new_full_precision_rescoring,
                 } = new;
 
                 if matches!(binary_quantize, Setting::Set(true))
@@ -671,6 +822,7 @@ impl SettingsDiff {
                     &mut dimensions,
                     &mut document_template,
                     &mut document_template_max_bytes,
+                    &mut document_template_chunking,
                     &mut url,
                     &mut request,
                     &mut response,
@@ -683,6 +835,7 @@ impl SettingsDiff {
                     new_dimensions,
                     new_document_template,
                     new_document_template_max_bytes,
+                    new_document_template_chunking,
                     new_url,
                     new_request,
                     new_response,
@@ -690,6 +843,13 @@ impl SettingsDiff {
                 );
 
                 let binary_quantize_changed = binary_quantize.apply(new_binary_quantize);
+                let scalar_quantize_changed = scalar_quantized.apply(new_scalar_quantized);
+                // only read at search time
+                full_precision_rescoring.apply(new_full_precision_rescoring);
+
+                // the stored embeddings are converted to the new distance when rebuilding
+                // the trees, the documents don't need to be embedded again
+                let distance_changed = distance.apply(new_distance);
 
                 // changes to the *search* embedder never triggers any reindexing
                 search_embedder.apply(new_search_embedder);
@@ -717,25 +877,32 @@ impl SettingsDiff {
                     distribution,
                     headers,
                     document_template_max_bytes,
+                    document_template_chunking,
                     binary_quantized: binary_quantize,
+                    distance,
+                    scalar_quantized,
+                    full_precision_rescoring,
                 };
 
                 match reindex_action {
                     Some(action) => Self::Reindex {
                         action,
                         updated_settings,
-                        quantize: binary_quantize_changed,
+                        quantize: binary_quantize_changed || scalar_quantize_changed,
                     },
                     None => Self::UpdateWithoutReindex {
                         updated_settings,
-                        quantize: binary_quantize_changed,
+                        quantize: binary_quantize_changed || scalar_quantize_changed,
+                        rebuild: distance_changed,
                     },
                 }
             }
             Setting::Reset => Self::Remove,
-            Setting::NotSet => {
-                Self::UpdateWithoutReindex { updated_settings: old, quantize: false }
-            }
+            Setting::NotSet => Self::UpdateWithoutReindex {
+                updated_settings: old,
+                quantize: false,
+                rebuild: false,
+            },
         };
         Ok(ret)
     }
@@ -756,6 +923,7 @@ impl SettingsDiff {
                     mut dimensions,
                     mut document_template,
                     mut document_template_max_bytes,
+                    mut document_template_chunking,
                     mut url,
                     mut request,
                     mut response,
@@ -763,6 +931,9 @@ impl SettingsDiff {
                     // phony settings
                     mut distribution,
                     mut binary_quantized,
+                    mut distance,
+                    mut scalar_quantized,
+                    mut full_precision_rescoring,
                     mut search_embedder,
                     mut indexing_embedder,
                 }) = sub_embedder
@@ -781,12 +952,16 @@ impl SettingsDiff {
                     dimensions: new_dimensions,
                     document_template: new_document_template,
                     document_template_max_bytes: new_document_template_max_bytes,
+                    document_template_chunking: new_document_template_chunking,
                     url: new_url,
                     request: new_request,
                     response: new_response,
                     headers: new_headers,
                     distribution: new_distribution,
                     binary_quantized: new_binary_quantized,
+                    distance: new_distance,
+                    scalar_quantized: new_scalar_quantized,
+                    full_precision_rescoring: new_full_precision_rescoring,
                     search_embedder: new_search_embedder,
                     indexing_embedder: new_indexing_embedder,
                 } = new_sub_embedder;
@@ -801,6 +976,7 @@ impl SettingsDiff {
                     &mut dimensions,
                     &mut document_template,
                     &mut document_template_max_bytes,
+                    &mut document_template_chunking,
                     &mut url,
                     &mut request,
                     &mut response,
@@ -813,6 +989,7 @@ impl SettingsDiff {
                     new_dimensions,
                     new_document_template,
                     new_document_template_max_bytes,
+                    new_document_template_chunking,
                     new_url,
                     new_request,
                     new_response,
@@ -822,6 +999,9 @@ impl SettingsDiff {
                 // update phony settings, it is always an error to have them set.
                 distribution.apply(new_distribution);
                 binary_quantized.apply(new_binary_quantized);
+                distance.apply(new_distance);
+                scalar_quantized.apply(new_scalar_quantized);
+                full_precision_rescoring.apply(new_full_precision_rescoring);
                 search_embedder.apply(new_search_embedder);
                 indexing_embedder.apply(new_indexing_embedder);
 
@@ -838,8 +1018,12 @@ impl SettingsDiff {
                     response,
                     headers,
                     document_template_max_bytes,
+                    document_template_chunking,
                     distribution,
                     binary_quantized,
+                    distance,
+                    scalar_quantized,
+                    full_precision_rescoring,
                     search_embedder,
                     indexing_embedder,
                 };
@@ -862,6 +1046,7 @@ impl SettingsDiff {
         dimensions: &mut Setting<usize>,
         document_template: &mut Setting<String>,
         document_template_max_bytes: &mut Setting<usize>,
+        document_template_chunking: &mut Setting<Chunking>,
         url: &mut Setting<String>,
         request: &mut Setting<serde_json::Value>,
         response: &mut Setting<serde_json::Value>,
@@ -874,6 +1059,7 @@ impl SettingsDiff {
         new_dimensions: Setting<usize>,
         new_document_template: Setting<String>,
         new_document_template_max_bytes: Setting<usize>,
+        new_document_template_chunking: Setting<Chunking>,
         new_url: Setting<String>,
         new_request: Setting<serde_json::Value>,
         new_response: Setting<serde_json::Value>,
@@ -894,6 +1080,7 @@ impl SettingsDiff {
                 response,
                 document_template,
                 document_template_max_bytes,
+                document_template_chunking,
                 headers,
                 // send dummy values, the source cannot recursively be composite
                 &mut Setting::NotSet,
@@ -953,6 +1140,11 @@ impl SettingsDiff {
             }
         }
 
+        // the rendered texts don't change with the chunking, so all the documents must be embedded again
+        if document_template_chunking.apply(new_document_template_chunking) {
+            ReindexAction::push_action(reindex_action, ReindexAction::FullReindex);
+        }
+
         api_key.apply(new_api_key);
         headers.apply(new_headers);
     }
@@ -980,6 +1172,7 @@ fn apply_default_for_source(
     response: &mut Setting<serde_json::Value>,
     document_template: &mut Setting<String>,
     document_template_max_bytes: &mut Setting<usize>,
+    document_template_chunking: &mut Setting<Chunking>,
     headers: &mut Setting<BTreeMap<String, String>>,
     search_embedder: &mut Setting<SubEmbeddingSettings>,
     indexing_embedder: &mut Setting<SubEmbeddingSettings>,
@@ -1043,6 +1236,7 @@ fn apply_default_for_source(
             *response = Setting::NotSet;
             *document_template = Setting::NotSet;
             *document_template_max_bytes = Setting::NotSet;
+            *document_template_chunking = Setting::NotSet;
             *headers = Setting::NotSet;
             *search_embedder = Setting::NotSet;
             *indexing_embedder = Setting::NotSet;
@@ -1057,6 +1251,7 @@ fn apply_default_for_source(
             *response = Setting::NotSet;
             *document_template = Setting::NotSet;
             *document_template_max_bytes = Setting::NotSet;
+            *document_template_chunking = Setting::NotSet;
             *headers = Setting::NotSet;
             *search_embedder = Setting::Reset;
             *indexing_embedder = Setting::Reset;
@@ -1118,6 +1313,7 @@ pub enum MetaEmbeddingSetting {
     Dimensions,
     DocumentTemplate,
     DocumentTemplateMaxBytes,
+    DocumentTemplateChunking,
     Url,
     Request,
     Response,
@@ -1126,6 +1322,9 @@ pub enum MetaEmbeddingSetting {
     IndexingEmbedder,
     Distribution,
     BinaryQuantized,
+    Distance,
+    ScalarQuantized,
+    FullPrecisionRescoring,
 }
 
 impl MetaEmbeddingSetting {
@@ -1140,6 +1339,7 @@ impl MetaEmbeddingSetting {
             Dimensions => "dimensions",
             DocumentTemplate => "documentTemplate",
             DocumentTemplateMaxBytes => "documentTemplateMaxBytes",
+            DocumentTemplateChunking => "documentTemplateChunking",
             Url => "url",
             Request => "request",
             Response => "response",
@@ -1148,6 +1348,9 @@ impl MetaEmbeddingSetting {
             IndexingEmbedder => "indexingEmbedder",
             Distribution => "distribution",
             BinaryQuantized => "binaryQuantized",
+            Distance => "distance",
+            ScalarQuantized => "scalarQuantized",
+            FullPrecisionRescoring => "fullPrecisionRescoring",
         }
     }
 }
@@ -1168,10 +1371,14 @@ impl EmbeddingSettings {
         response: &Setting<serde_json::Value>,
         document_template: &Setting<String>,
         document_template_max_bytes: &Setting<usize>,
+        document_template_chunking: &Setting<Chunking>,
         headers: &Setting<BTreeMap<String, String>>,
         search_embedder: &Setting<SubEmbeddingSettings>,
         indexing_embedder: &Setting<SubEmbeddingSettings>,
         binary_quantized: &Setting<bool>,
+        distance: &Setting<Distance>,
+        scalar_quantized: &Setting<bool>,
+        full_precision_rescoring: &Setting<bool>,
         distribution: &Setting<DistributionShift>,
     ) -> Result<(), UserError> {
         Self::check_setting(embedder_name, source, MetaEmbeddingSetting::Model, context, model)?;
@@ -1226,6 +1433,13 @@ impl EmbeddingSettings {
             context,
             document_template_max_bytes,
         )?;
+        Self::check_setting(
+            embedder_name,
+            source,
+            MetaEmbeddingSetting::DocumentTemplateChunking,
+            context,
+            document_template_chunking,
+        )?;
         Self::check_setting(
             embedder_name,
             source,
@@ -1254,6 +1468,27 @@ impl EmbeddingSettings {
             context,
             binary_quantized,
         )?;
+        Self::check_setting(
+            embedder_name,
+            source,
+            MetaEmbeddingSetting::Distance,
+            context,
+            distance,
+        )?;
+        Self::check_setting(
+            embedder_name,
+            source,
+            MetaEmbeddingSetting::ScalarQuantized,
+            context,
+            scalar_quantized,
+        )?;
+        Self::check_setting(
+            embedder_name,
+            source,
+            MetaEmbeddingSetting::FullPrecisionRescoring,
+            context,
+            full_precision_rescoring,
+        )?;
         Self::check_setting(
             embedder_name,
             source,
@@ -1320,9 +1555,27 @@ impl EmbeddingSettings {
         use MetaEmbeddingSetting::*;
         use NestingContext::*;
         match (source, field, context) {
-            (_, Distribution | BinaryQuantized, NotNested) => FieldStatus::Allowed,
-            (_, Distribution | BinaryQuantized, _) => FieldStatus::Disallowed,
-            (_, DocumentTemplate | DocumentTemplateMaxBytes, Search) => FieldStatus::Disallowed,
+            (
+                _,
+                Distribution
+                | BinaryQuantized
+                | Distance
+                | ScalarQuantized
+                | FullPrecisionRescoring,
+                NotNested,
+            ) => FieldStatus::Allowed,
+            (
+                _,
+                Distribution
+                | BinaryQuantized
+                | Distance
+                | ScalarQuantized
+                | FullPrecisionRescoring,
+                _,
+            ) => FieldStatus::Disallowed,
+            (_, DocumentTemplate | DocumentTemplateMaxBytes | DocumentTemplateChunking, Search) => {
+                FieldStatus::Disallowed
+            }
             (
                 OpenAi,
                 Source
@@ -1330,6 +1583,7 @@ impl EmbeddingSettings {
                 | ApiKey
                 | DocumentTemplate
                 | DocumentTemplateMaxBytes
+                | DocumentTemplateChunking
                 | Dimensions
                 | Url,
                 _,
@@ -1342,7 +1596,13 @@ impl EmbeddingSettings {
             ) => FieldStatus::Disallowed,
             (
                 HuggingFace,
-                Source | Model | Revision | Pooling | DocumentTemplate | DocumentTemplateMaxBytes,
+                Source
+                | Model
+                | Revision
+                | Pooling
+                | DocumentTemplate
+                | DocumentTemplateMaxBytes
+                | DocumentTemplateChunking,
                 _,
             ) => FieldStatus::Allowed,
             (
@@ -1354,7 +1614,13 @@ impl EmbeddingSettings {
             (Ollama, Model, _) => FieldStatus::Mandatory,
             (
                 Ollama,
-                Source | DocumentTemplate | DocumentTemplateMaxBytes | Url | ApiKey | Dimensions,
+                Source
+                | DocumentTemplate
+                | DocumentTemplateMaxBytes
+                | DocumentTemplateChunking
+                | Url
+                | ApiKey
+                | Dimensions,
                 _,
             ) => FieldStatus::Allowed,
             (
@@ -1373,6 +1639,7 @@ impl EmbeddingSettings {
                 | ApiKey
                 | DocumentTemplate
                 | DocumentTemplateMaxBytes
+                | DocumentTemplateChunking
                 | Url
                 | Request
                 | Response
@@ -1389,6 +1656,7 @@ impl EmbeddingSettings {
                 | Dimensions
                 | DocumentTemplate
                 | DocumentTemplateMaxBytes
+                | DocumentTemplateChunking
                 | Headers,
                 _,
             ) => FieldStatus::Allowed,
@@ -1406,6 +1674,7 @@ impl EmbeddingSettings {
                 | Dimensions
                 | DocumentTemplate
                 | DocumentTemplateMaxBytes
+                | DocumentTemplateChunking
                 | Url
                 | Request
                 | Response
@@ -1510,6 +1779,7 @@ impl EmbeddingSettings {
     }: super::hf::EmbedderOptions,
         document_template: Setting<String>,
         document_template_max_bytes: Setting<usize>,
+        document_template_chunking: Setting<Chunking>,
         quantized: Option<bool>,
     ) -> Self {
         Self {
@@ -1521,6 +1791,7 @@ impl EmbeddingSettings {
             dimensions: Setting::NotSet,
             document_template,
             document_template_max_bytes,
+            document_template_chunking,
             url: Setting::NotSet,
             request: Setting::NotSet,
             response: Setting::NotSet,
@@ -1529,6 +1800,9 @@ impl EmbeddingSettings {
             indexing_embedder: Setting::NotSet,
             distribution: Setting::some_or_not_set(distribution),
             binary_quantized: Setting::some_or_not_set(quantized),
+            distance: Setting::NotSet,
+            scalar_quantized: Setting::NotSet,
+            full_precision_rescoring: Setting::NotSet,
         }
     }
 
@@ -1542,6 +1816,7 @@ impl EmbeddingSettings {
         }: super::openai::EmbedderOptions,
         document_template: Setting<String>,
         document_template_max_bytes: Setting<usize>,
+        document_template_chunking: Setting<Chunking>,
         quantized: Option<bool>,
     ) -> Self {
         Self {
@@ -1553,6 +1828,7 @@ impl EmbeddingSettings {
             dimensions: Setting::some_or_not_set(dimensions),
             document_template,
             document_template_max_bytes,
+            document_template_chunking,
             url: Setting::some_or_not_set(url),
             request: Setting::NotSet,
             response: Setting::NotSet,
@@ -1561,6 +1837,9 @@ impl EmbeddingSettings {
             indexing_embedder: Setting::NotSet,
             distribution: Setting::some_or_not_set(distribution),
             binary_quantized: Setting::some_or_not_set(quantized),
+            distance: Setting::NotSet,
+            scalar_quantized: Setting::NotSet,
+            full_precision_rescoring: Setting::NotSet,
         }
     }
 
@@ -1574,6 +1853,7 @@ impl EmbeddingSettings {
         }: super::ollama::EmbedderOptions,
         document_template: Setting<String>,
         document_template_max_bytes: Setting<usize>,
+        document_template_chunking: Setting<Chunking>,
         quantized: Option<bool>,
     ) -> Self {
         Self {
@@ -1585,6 +1865,7 @@ impl EmbeddingSettings {
             dimensions: Setting::some_or_not_set(dimensions),
             document_template,
             document_template_max_bytes,
+            document_template_chunking,
             url: Setting::some_or_not_set(url),
             request: Setting::NotSet,
             response: Setting::NotSet,
@@ -1593,6 +1874,9 @@ impl EmbeddingSettings {
             indexing_embedder: Setting::NotSet,
             distribution: Setting::some_or_not_set(distribution),
             binary_quantized: Setting::some_or_not_set(quantized),
+            distance: Setting::NotSet,
+            scalar_quantized: Setting::NotSet,
+            full_precision_rescoring: Setting::NotSet,
         }
     }
 
@@ -1609,6 +1893,7 @@ impl EmbeddingSettings {
             dimensions: Setting::Set(dimensions),
             document_template: Setting::NotSet,
             document_template_max_bytes: Setting::NotSet,
+            document_template_chunking: Setting::NotSet,
             url: Setting::NotSet,
             request: Setting::NotSet,
             response: Setting::NotSet,
@@ -1617,6 +1902,9 @@ impl EmbeddingSettings {
             indexing_embedder: Setting::NotSet,
             distribution: Setting::some_or_not_set(distribution),
             binary_quantized: Setting::some_or_not_set(quantized),
+            distance: Setting::NotSet,
+            scalar_quantized: Setting::NotSet,
+            full_precision_rescoring: Setting::NotSet,
         }
     }
 
@@ -1632,6 +1920,7 @@ impl EmbeddingSettings {
         }: super::rest::EmbedderOptions,
         document_template: Setting<String>,
         document_template_max_bytes: Setting<usize>,
+        document_template_chunking: Setting<Chunking>,
         quantized: Option<bool>,
     ) -> Self {
         Self {
@@ -1643,6 +1932,7 @@ impl EmbeddingSettings {
             dimensions: Setting::some_or_not_set(dimensions),
             document_template,
             document_template_max_bytes,
+            document_template_chunking,
             url: Setting::Set(url),
             request: Setting::Set(request),
             response: Setting::Set(response),
@@ -1651,32 +1941,46 @@ impl EmbeddingSettings {
             search_embedder: Setting::NotSet,
             indexing_embedder: Setting::NotSet,
             binary_quantized: Setting::some_or_not_set(quantized),
+            distance: Setting::NotSet,
+            scalar_quantized: Setting::NotSet,
+            full_precision_rescoring: Setting::NotSet,
         }
     }
 }
 
 impl From<EmbeddingConfig> for EmbeddingSettings {
     fn from(value: EmbeddingConfig) -> Self {
-        let EmbeddingConfig { embedder_options, prompt, quantized } = value;
+        let EmbeddingConfig {
+            embedder_options,
+            prompt,
+            quantized,
+            distance,
+            scalar_quantized,
+            full_precision_rescoring,
+        } = value;
         let document_template_max_bytes =
             Setting::Set(prompt.max_bytes.unwrap_or(default_max_bytes()).get());
-        match embedder_options {
+        let document_template_chunking = Setting::some_or_not_set(prompt.chunking);
+        let mut settings = match embedder_options {
             super::EmbedderOptions::HuggingFace(options) => Self::from_hugging_face(
                 options,
                 Setting::Set(prompt.template),
                 document_template_max_bytes,
+                document_template_chunking,
                 quantized,
             ),
             super::EmbedderOptions::OpenAi(options) => Self::from_openai(
                 options,
                 Setting::Set(prompt.template),
                 document_template_max_bytes,
+                document_template_chunking,
                 quantized,
             ),
             super::EmbedderOptions::Ollama(options) => Self::from_ollama(
                 options,
                 Setting::Set(prompt.template),
                 document_template_max_bytes,
+                document_template_chunking,
                 quantized,
             ),
             super::EmbedderOptions::UserProvided(options) => {
@@ -1686,6 +1990,7 @@ impl From<EmbeddingConfig> for EmbeddingSettings {
                 options,
                 Setting::Set(prompt.template),
                 document_template_max_bytes,
+                document_template_chunking,
                 quantized,
             ),
             super::EmbedderOptions::Composite(super::composite::EmbedderOptions {
@@ -1699,8 +2004,12 @@ impl From<EmbeddingConfig> for EmbeddingSettings {
                 api_key: Setting::NotSet,
                 dimensions: Setting::NotSet,
                 binary_quantized: Setting::some_or_not_set(quantized),
+                distance: Setting::NotSet,
+                scalar_quantized: Setting::NotSet,
+                full_precision_rescoring: Setting::NotSet,
                 document_template: Setting::NotSet,
                 document_template_max_bytes: Setting::NotSet,
+                document_template_chunking: Setting::NotSet,
                 url: Setting::NotSet,
                 request: Setting::NotSet,
                 response: Setting::NotSet,
@@ -1710,14 +2019,20 @@ impl From<EmbeddingConfig> for EmbeddingSettings {
                     search,
                     Setting::NotSet,
                     Setting::NotSet,
+                    Setting::NotSet,
                 )),
                 indexing_embedder: Setting::Set(SubEmbeddingSettings::from_options(
                     index,
                     Setting::Set(prompt.template),
                     document_template_max_bytes,
+                    document_template_chunking,
                 )),
             },
-        }
+        };
+        settings.distance = Setting::some_or_not_set(distance);
+        settings.scalar_quantized = Setting::some_or_not_set(scalar_quantized);
+        settings.full_precision_rescoring = Setting::some_or_not_set(full_precision_rescoring);
+        settings
     }
 }
 
@@ -1726,6 +2041,7 @@ impl SubEmbeddingSettings {
         options: SubEmbedderOptions,
         document_template: Setting<String>,
         document_template_max_bytes: Setting<usize>,
+        document_template_chunking: Setting<Chunking>,
     ) -> Self {
         let settings = match options {
             SubEmbedderOptions::HuggingFace(embedder_options) => {
@@ -1733,6 +2049,7 @@ impl SubEmbeddingSettings {
                     embedder_options,
                     document_template,
                     document_template_max_bytes,
+                    document_template_chunking,
                     None,
                 )
             }
@@ -1740,12 +2057,14 @@ impl SubEmbeddingSettings {
                 embedder_options,
                 document_template,
                 document_template_max_bytes,
+                document_template_chunking,
                 None,
             ),
             SubEmbedderOptions::Ollama(embedder_options) => EmbeddingSettings::from_ollama(
                 embedder_options,
                 document_template,
                 document_template_max_bytes,
+                document_template_chunking,
                 None,
             ),
             SubEmbedderOptions::UserProvided(embedder_options) => {
@@ -1755,6 +2074,7 @@ impl SubEmbeddingSettings {
                 embedder_options,
                 document_template,
                 document_template_max_bytes,
+                document_template_chunking,
                 None,
             ),
         };
@@ -1773,11 +2093,15 @@ impl From<EmbeddingSettings> for SubEmbeddingSettings {
             dimensions,
             document_template,
             document_template_max_bytes,
+            document_template_chunking,
             url,
             request,
             response,
             headers,
             binary_quantized: _,
+            distance: _,
+            scalar_quantized: _,
+            full_precision_rescoring: _,
             search_embedder: _,
             indexing_embedder: _,
             distribution: _,
@@ -1791,12 +2115,16 @@ impl From<EmbeddingSettings> for SubEmbeddingSettings {
             dimensions,
             document_template,
             document_template_max_bytes,
+            document_template_chunking,
             url,
             request,
             response,
             headers,
             distribution: Setting::NotSet,
             binary_quantized: Setting::NotSet,
+            distance: Setting::NotSet,
+            scalar_quantized: Setting::NotSet,
+            full_precision_rescoring: Setting::NotSet,
             search_embedder: Setting::NotSet,
             indexing_embedder: Setting::NotSet,
         }
@@ -1815,29 +2143,43 @@ impl From<EmbeddingSettings> for EmbeddingConfig {
             dimensions,
             document_template,
             document_template_max_bytes,
+            document_template_chunking,
             url,
             request,
             response,
             distribution,
             headers,
             binary_quantized,
+            distance,
+            scalar_quantized,
+            full_precision_rescoring,
             search_embedder,
             mut indexing_embedder,
         } = value;
 
         this.quantized = binary_quantized.set();
-        if let Some((template, document_template_max_bytes)) =
+        this.distance = distance.set();
+        this.scalar_quantized = scalar_quantized.set();
+        this.full_precision_rescoring = full_precision_rescoring.set();
+        if let Some((template, document_template_max_bytes, document_template_chunking)) =
             match (document_template, &mut indexing_embedder) {
-                (Setting::Set(template), _) => Some((template, document_template_max_bytes)),
+                (Setting::Set(template), _) => {
+                    Some((template, document_template_max_bytes, document_template_chunking))
+                }
                 // retrieve the prompt from the indexing embedder in case of a composite embedder
                 (
                     _,
                     Setting::Set(SubEmbeddingSettings {
                         document_template: Setting::Set(document_template),
                         document_template_max_bytes,
+                        document_template_chunking,
                         ..
                     }),
-                ) => Some((std::mem::take(document_template), *document_template_max_bytes)),
+                ) => Some((
+                    std::mem::take(document_template),
+                    *document_template_max_bytes,
+                    *document_template_chunking,
+                )),
                 _ => None,
             }
         {
@@ -1846,7 +2188,11 @@ impl From<EmbeddingSettings> for EmbeddingConfig {
                 .and_then(NonZeroUsize::new)
                 .unwrap_or(default_max_bytes());
 
-            this.prompt = PromptData { template, max_bytes: Some(max_bytes) }
+            this.prompt = PromptData {
+                template,
+                max_bytes: Some(max_bytes),
+                chunking: document_template_chunking.set(),
+            }
         }
 
         if let Some(source) = source.set() {
@@ -1909,6 +2255,7 @@ impl SubEmbedderOptions {
             // retrieved by the EmbeddingConfig
             document_template: _,
             document_template_max_bytes: _,
+            document_template_chunking: _,
             url,
             request,
             response,
@@ -1916,6 +2263,9 @@ impl SubEmbedderOptions {
             // phony parameters
             distribution: _,
             binary_quantized: _,
+            distance: _,
+            scalar_quantized: _,
+            full_precision_rescoring: _,
             search_embedder: _,
             indexing_embedder: _,
         } = settings;
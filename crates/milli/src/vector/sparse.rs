@@ -0,0 +1,528 @@
+//! Sparse embeddings, as produced by learned-sparse models such as SPLADE.
+//!
+//! A sparse embedding associates a weight to a small subset of the tokens of the vocabulary
+//! of the model. They are not stored in arroy but in an inverted index mapping each token
+//! to the documents containing it along with its weight, so that the documents are scored
+//! from the posting lists of the tokens of the query. A forward index stores the embedding
+//! of each document, to update the posting lists and return the embeddings.
+
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap};
+
+use deserr::Deserr;
+use heed::types::{Bytes, DecodeIgnore};
+use heed::{RoTxn, RwTxn};
+use itertools::{merge_join_by, EitherOrBoth};
+use ordered_float::OrderedFloat;
+use roaring::RoaringBitmap;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::Embedding;
+use crate::{DocumentId, Index, Result};
+
+/// The default size of the vocabulary of a sparse embedder.
+///
+/// This is also the maximum size as the token ids must be exactly representable
+/// by a `f32` when the sparse embeddings are packed into an [`Embedding`].
+pub const MAX_SPARSE_DIMENSIONS: usize = 1 << 24;
+
+/// The weight of a token of a sparse embedding, as given to and returned by the API,
+/// e.g. `{ "index": 1012, "value": 0.53 }`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Deserr, ToSchema)]
+#[serde(deny_unknown_fields)]
+#[deserr(deny_unknown_fields)]
+pub struct SparseValue {
+    /// The id of the token in the vocabulary of the embedder.
+    pub index: u32,
+    /// The weight of the token.
+    pub value: f32,
+}
+
+/// An embedding storing the weight of each token it contains, sorted by token.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SparseEmbedding {
+    values: Vec<(u32, f32)>,
+}
+
+impl SparseEmbedding {
+    /// Builds a sparse embedding from unordered `(token, weight)` pairs.
+    ///
+    /// The null and non-finite weights are ignored, and the highest weight is kept
+    /// when a token appears several times.
+    pub fn new(values: impl IntoIterator<Item = (u32, f32)>) -> Self {
+        let mut values: Vec<_> =
+            values.into_iter().filter(|(_, weight)| weight.is_finite() && *weight != 0.0).collect();
+        values.sort_unstable_by_key(|&(token, weight)| {
+            (token, std::cmp::Reverse(OrderedFloat(weight)))
+        });
+        values.dedup_by_key(|(token, _)| *token);
+        Self { values }
+    }
+
+    /// Builds a sparse embedding from the values given to the API.
+    pub fn from_values(values: impl IntoIterator<Item = SparseValue>) -> Self {
+        Self::new(values.into_iter().map(|SparseValue { index, value }| (index, value)))
+    }
+
+    /// Reads a sparse embedding packed by [`Self::to_packed`].
+    ///
+    /// Several packed embeddings can be concatenated, e.g. the embeddings of the chunks of a document,
+    /// in which case the highest weight of each token is kept.
+    pub fn from_packed(packed: &[f32]) -> Self {
+        Self::new(packed.chunks_exact(2).map(|pair| (pair[0] as u32, pair[1])))
+    }
+
+    /// Packs the embedding in an [`Embedding`] alternating the tokens and their weight,
+    /// so that it can be sent through the same channels as the dense embeddings.
+    pub fn to_packed(&self) -> Embedding {
+        self.values.iter().flat_map(|&(token, weight)| [token as f32, weight]).collect()
+    }
+
+    /// Returns the values of the embedding in the format of the API.
+    pub fn to_values(&self) -> Vec<SparseValue> {
+        self.values.iter().map(|&(index, value)| SparseValue { index, value }).collect()
+    }
+
+    pub fn values(&self) -> &[(u32, f32)] {
+        &self.values
+    }
+
+    pub fn tokens(&self) -> impl Iterator<Item = u32> + '_ {
+        self.values.iter().map(|(token, _)| *token)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn max_token(&self) -> Option<u32> {
+        self.values.last().map(|(token, _)| *token)
+    }
+
+    pub fn dot(&self, other: &Self) -> f32 {
+        let (mut left, mut right) = (self.values.iter().peekable(), other.values.iter().peekable());
+        let mut dot = 0.0;
+        while let (Some((ltoken, lweight)), Some((rtoken, rweight))) = (left.peek(), right.peek()) {
+            match ltoken.cmp(rtoken) {
+                std::cmp::Ordering::Less => {
+                    left.next();
+                }
+                std::cmp::Ordering::Greater => {
+                    right.next();
+                }
+                std::cmp::Ordering::Equal => {
+                    dot += lweight * rweight;
+                    left.next();
+                    right.next();
+                }
+            }
+        }
+        dot
+    }
+
+    pub fn norm(&self) -> f32 {
+        self.values.iter().map(|(_, weight)| weight * weight).sum::<f32>().sqrt()
+    }
+
+    /// The cosine similarity between the two embeddings, `0.0` if one of them is empty.
+    pub fn similarity(&self, other: &Self) -> f32 {
+        let norms = self.norm() * other.norm();
+        if norms == 0.0 {
+            0.0
+        } else {
+            (self.dot(other) / norms).clamp(-1.0, 1.0)
+        }
+    }
+}
+
+/// The documents containing a token along with the weight of the token in their embedding,
+/// sorted by document id.
+///
+/// The weights are divided by the norm of the embedding of each document, so that the cosine
+/// similarity of a document to a normalized query is the sum of the products of the weights.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SparsePostings {
+    postings: Vec<(DocumentId, f32)>,
+}
+
+impl SparsePostings {
+    pub fn postings(&self) -> &[(DocumentId, f32)] {
+        &self.postings
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.postings.is_empty()
+    }
+
+    /// Inserts, updates or removes, when `None`, the weights of the documents.
+    pub fn apply(&mut self, changes: &BTreeMap<DocumentId, Option<f32>>) {
+        let mut postings = Vec::with_capacity(self.postings.len() + changes.len());
+        let previous = self.postings.iter().map(|&(docid, weight)| (docid, Some(weight)));
+        let changes = changes.iter().map(|(&docid, &weight)| (docid, weight));
+        for eob in merge_join_by(previous, changes, |(l, _), (r, _)| l.cmp(r)) {
+            let (docid, weight) = match eob {
+                EitherOrBoth::Left(previous) => previous,
+                EitherOrBoth::Right(change) | EitherOrBoth::Both(_, change) => change,
+            };
+            postings.extend(weight.map(|weight| (docid, weight)));
+        }
+        self.postings = postings;
+    }
+}
+
+/// The maximum number of changes of the posting lists kept in memory
+/// before they are written, see [`SparseVectorStore::flush`].
+const MAX_PENDING_POSTINGS: usize = 1 << 20;
+
+/// The sparse embeddings of an embedder, stored in an inverted index of its tokens.
+///
+/// The changes of the posting lists are buffered by token, so that each posting list is read
+/// and written once for many documents. They must be written with [`Self::flush`] once all the
+/// documents are added or removed.
+pub struct SparseVectorStore<'a> {
+    index: &'a Index,
+    embedder_index: u8,
+    pending_postings: BTreeMap<u32, BTreeMap<DocumentId, Option<f32>>>,
+    pending_count: usize,
+}
+
+impl<'a> SparseVectorStore<'a> {
+    pub fn new(index: &'a Index, embedder_index: u8) -> Self {
+        Self { index, embedder_index, pending_postings: BTreeMap::new(), pending_count: 0 }
+    }
+
+    pub fn embedder_index(&self) -> u8 {
+        self.embedder_index
+    }
+
+    /// Replaces the sparse embedding of the document.
+    pub fn add_item(
+        &mut self,
+        wtxn: &mut RwTxn,
+        docid: DocumentId,
+        embedding: &SparseEmbedding,
+    ) -> Result<()> {
+        self.del_item(wtxn, docid)?;
+        if embedding.is_empty() {
+            return Ok(());
+        }
+
+        let norm = embedding.norm();
+        for &(token, weight) in embedding.values() {
+            self.push_posting(token, docid, Some(weight / norm));
+        }
+        self.index.sparse_vectors.put(wtxn, &(self.embedder_index, docid), embedding)?;
+
+        self.flush_if_full(wtxn)
+    }
+
+    /// Removes the sparse embedding of the document, returns `false` if it had none.
+    pub fn del_item(&mut self, wtxn: &mut RwTxn, docid: DocumentId) -> Result<bool> {
+        let key = (self.embedder_index, docid);
+        let Some(embedding) = self.index.sparse_vectors.get(wtxn, &key)? else {
+            return Ok(false);
+        };
+
+        for token in embedding.tokens() {
+            self.push_posting(token, docid, None);
+        }
+        self.index.sparse_vectors.delete(wtxn, &key)?;
+
+        self.flush_if_full(wtxn)?;
+        Ok(true)
+    }
+
+    fn push_posting(&mut self, token: u32, docid: DocumentId, weight: Option<f32>) {
+        self.pending_postings.entry(token).or_default().insert(docid, weight);
+        self.pending_count += 1;
+    }
+
+    fn flush_if_full(&mut self, wtxn: &mut RwTxn) -> Result<()> {
+        if self.pending_count >= MAX_PENDING_POSTINGS {
+            self.flush(wtxn)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the pending changes of the posting lists of the tokens.
+    pub fn flush(&mut self, wtxn: &mut RwTxn) -> Result<()> {
+        let token_postings = &self.index.sparse_vector_token_postings;
+        for (token, changes) in std::mem::take(&mut self.pending_postings) {
+            let key = (self.embedder_index, token);
+            let mut postings = token_postings.get(wtxn, &key)?.unwrap_or_default();
+            postings.apply(&changes);
+            if postings.is_empty() {
+                token_postings.delete(wtxn, &key)?;
+            } else {
+                token_postings.put(wtxn, &key, &postings)?;
+            }
+        }
+        self.pending_count = 0;
+
+        Ok(())
+    }
+
+    /// Removes all the sparse embeddings of the embedder.
+    pub fn clear(&mut self, wtxn: &mut RwTxn) -> Result<()> {
+        self.pending_postings.clear();
+        self.pending_count = 0;
+        let prefix = [self.embedder_index];
+
+        let token_postings =
+            self.index.sparse_vector_token_postings.remap_types::<Bytes, DecodeIgnore>();
+        let mut iter = token_postings.prefix_iter_mut(wtxn, &prefix)?;
+        while iter.next().transpose()?.is_some() {
+            // safety: we do not keep a reference on database entries.
+            unsafe { iter.del_current()? };
+        }
+        drop(iter);
+
+        let vectors = self.index.sparse_vectors.remap_types::<Bytes, DecodeIgnore>();
+        let mut iter = vectors.prefix_iter_mut(wtxn, &prefix)?;
+        while iter.next().transpose()?.is_some() {
+            // safety: we do not keep a reference on database entries.
+            unsafe { iter.del_current()? };
+        }
+
+        Ok(())
+    }
+
+    pub fn item_vector(&self, rtxn: &RoTxn, docid: DocumentId) -> Result<Option<SparseEmbedding>> {
+        Ok(self.index.sparse_vectors.get(rtxn, &(self.embedder_index, docid))?)
+    }
+
+    /// Returns the documents sharing at least one token with the query, sorted by
+    /// their cosine distance to it.
+    ///
+    /// The documents are scored from the posting lists of the tokens of the query with the
+    /// MaxScore algorithm: the tokens whose highest contributions can't sum above the score of
+    /// the worst of the best documents found so far don't bring new candidates, and their
+    /// posting lists are only searched for the documents that can still enter the results.
+    pub fn nns_by_vector(
+        &self,
+        rtxn: &RoTxn,
+        query: &SparseEmbedding,
+        limit: usize,
+        filter: Option<&RoaringBitmap>,
+    ) -> Result<Vec<(DocumentId, f32)>> {
+        let norm = query.norm();
+        if limit == 0 || norm == 0.0 {
+            return Ok(Vec::new());
+        }
+
+        let mut terms = Vec::new();
+        for &(token, weight) in query.values() {
+            let key = (self.embedder_index, token);
+            let Some(SparsePostings { mut postings }) =
+                self.index.sparse_vector_token_postings.get(rtxn, &key)?
+            else {
+                continue;
+            };
+            if let Some(filter) = filter {
+                postings.retain(|(docid, _)| filter.contains(*docid));
+            }
+            let weight = weight / norm;
+            let max_score = postings.iter().map(|(_, w)| weight * w).fold(0.0, f32::max);
+            terms.push(Term { postings, weight, max_score, position: 0 });
+        }
+
+        // `upper_bounds[i]` is the highest score a document can get from the terms `0..=i`
+        terms.sort_by_key(|term| OrderedFloat(term.max_score));
+        let upper_bounds: Vec<_> = terms
+            .iter()
+            .scan(0.0, |sum, term| {
+                *sum += term.max_score;
+                Some(*sum)
+            })
+            .collect();
+
+        // a min-heap of the best documents, the highest document id being the worst on ties
+        let mut best = BinaryHeap::new();
+        let mut threshold = f32::NEG_INFINITY;
+        // the terms before this one are not essential, they can't bring new candidates
+        let mut essential = 0;
+        while let Some(docid) = terms[essential..].iter().filter_map(Term::current).min() {
+            let mut score = 0.0;
+            for term in &mut terms[essential..] {
+                if term.current() == Some(docid) {
+                    score += term.weight * term.postings[term.position].1;
+                    term.position += 1;
+                }
+            }
+
+            for i in (0..essential).rev() {
+                if score + upper_bounds[i] <= threshold {
+                    break;
+                }
+                score += terms[i].seek(docid);
+            }
+
+            if best.len() < limit {
+                best.push(Reverse((OrderedFloat(score), Reverse(docid))));
+            } else if score > threshold {
+                best.pop();
+                best.push(Reverse((OrderedFloat(score), Reverse(docid))));
+            }
+
+            if best.len() == limit {
+                if let Some(Reverse((OrderedFloat(min_score), _))) = best.peek() {
+                    threshold = *min_score;
+                }
+                while essential < terms.len() && upper_bounds[essential] <= threshold {
+                    essential += 1;
+                }
+            }
+        }
+
+        Ok(best
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((OrderedFloat(score), Reverse(docid)))| {
+                (docid, 1.0 - score.clamp(-1.0, 1.0))
+            })
+            .collect())
+    }
+
+    /// The documents with a sparse embedding.
+    pub fn documents(&self, rtxn: &RoTxn) -> Result<RoaringBitmap> {
+        let mut documents = RoaringBitmap::new();
+        let vectors = self.index.sparse_vectors.remap_data_type::<DecodeIgnore>();
+        for result in vectors.remap_key_type::<Bytes>().prefix_iter(rtxn, &[self.embedder_index])? {
+            let (key, ()) = result?;
+            if let Ok(docid) = key[1..].try_into().map(u32::from_be_bytes) {
+                documents.insert(docid);
+            }
+        }
+        Ok(documents)
+    }
+}
+
+/// A token of the query, along with the position of the next document of its posting list.
+struct Term {
+    postings: Vec<(DocumentId, f32)>,
+    weight: f32,
+    max_score: f32,
+    position: usize,
+}
+
+impl Term {
+    fn current(&self) -> Option<DocumentId> {
+        self.postings.get(self.position).map(|(docid, _)| *docid)
+    }
+
+    /// Moves past the document and returns its contribution to the score.
+    fn seek(&mut self, docid: DocumentId) -> f32 {
+        self.position += self.postings[self.position..].partition_point(|(d, _)| *d < docid);
+        match self.postings.get(self.position) {
+            Some(&(d, weight)) if d == docid => {
+                self.position += 1;
+                self.weight * weight
+            }
+            _ => 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::index::tests::TempIndex;
+
+    #[test]
+    fn packing() {
+        let embedding =
+            SparseEmbedding::new([(12, 0.5), (3, 0.0), (7, 1.5), (12, 0.75), (4, f32::NAN)]);
+        assert_eq!(embedding.values(), &[(7, 1.5), (12, 0.75)]);
+        assert_eq!(embedding.to_packed(), vec![7.0, 1.5, 12.0, 0.75]);
+        assert_eq!(SparseEmbedding::from_packed(&embedding.to_packed()), embedding);
+
+        // concatenated chunks keep the highest weight of each token
+        let other = SparseEmbedding::new([(7, 0.5), (42, 2.0)]);
+        let mut packed = embedding.to_packed();
+        packed.extend(other.to_packed());
+        let merged = SparseEmbedding::from_packed(&packed);
+        assert_eq!(merged.values(), &[(7, 1.5), (12, 0.75), (42, 2.0)]);
+        assert_eq!(merged.max_token(), Some(42));
+
+        let values = [
+            SparseValue { index: 3, value: 1.0 },
+            SparseValue { index: 1, value: 0.25 },
+            SparseValue { index: 2, value: 0.0 },
+        ];
+        let embedding = SparseEmbedding::from_values(values);
+        assert_eq!(embedding.values(), &[(1, 0.25), (3, 1.0)]);
+        assert_eq!(
+            embedding.to_values(),
+            vec![SparseValue { index: 1, value: 0.25 }, SparseValue { index: 3, value: 1.0 }]
+        );
+    }
+
+    #[test]
+    fn similarity() {
+        let left = SparseEmbedding::new([(1, 1.0), (2, 1.0)]);
+        let right = SparseEmbedding::new([(2, 1.0), (3, 1.0)]);
+        assert_eq!(left.dot(&right), 1.0);
+        assert!((left.similarity(&right) - 0.5).abs() < f32::EPSILON);
+        assert!((left.similarity(&left) - 1.0).abs() < f32::EPSILON);
+        assert_eq!(left.similarity(&SparseEmbedding::default()), 0.0);
+    }
+
+    #[test]
+    fn postings() {
+        let mut postings = SparsePostings::default();
+        postings.apply(&BTreeMap::from([(3, Some(0.5)), (1, Some(0.25))]));
+        assert_eq!(postings.postings(), &[(1, 0.25), (3, 0.5)]);
+
+        postings.apply(&BTreeMap::from([(1, None), (2, Some(1.0)), (3, Some(0.75)), (4, None)]));
+        assert_eq!(postings.postings(), &[(2, 1.0), (3, 0.75)]);
+
+        postings.apply(&BTreeMap::from([(2, None), (3, None)]));
+        assert!(postings.is_empty());
+    }
+
+    #[test]
+    fn nns_by_vector() {
+        let index = TempIndex::new();
+        let mut wtxn = index.write_txn().unwrap();
+        let mut store = SparseVectorStore::new(&index, 0);
+
+        let mut embeddings: BTreeMap<DocumentId, SparseEmbedding> = (0..300)
+            .map(|docid: u32| {
+                let values =
+                    (0..8).map(|i| ((docid * 7 + i * 13) % 50, ((docid + i) % 5 + 1) as f32));
+                (docid, SparseEmbedding::new(values))
+            })
+            .collect();
+        for (docid, embedding) in &embeddings {
+            store.add_item(&mut wtxn, *docid, embedding).unwrap();
+        }
+        // the pending changes of a document are replaced by its last embedding
+        let replaced = embeddings[&1].clone();
+        store.add_item(&mut wtxn, 0, &replaced).unwrap();
+        embeddings.insert(0, replaced);
+        store.del_item(&mut wtxn, 2).unwrap();
+        embeddings.remove(&2);
+        store.flush(&mut wtxn).unwrap();
+
+        let query = SparseEmbedding::new([(0, 1.0), (13, 0.5), (26, 2.0), (49, 0.25)]);
+        let filter = RoaringBitmap::from_iter(0..250);
+        let mut expected: Vec<_> = embeddings
+            .iter()
+            .filter(|(docid, _)| filter.contains(**docid))
+            .map(|(docid, embedding)| (*docid, 1.0 - query.similarity(embedding)))
+            .filter(|(_, distance)| *distance < 1.0)
+            .collect();
+        expected.sort_by_key(|&(docid, distance)| (OrderedFloat(distance), docid));
+
+        for limit in [1, 10, 1000] {
+            let results = store.nns_by_vector(&wtxn, &query, limit, Some(&filter)).unwrap();
+            assert_eq!(results.len(), expected.len().min(limit));
+            for (&(docid, distance), &(_, expected_distance)) in results.iter().zip(&expected) {
+                assert!((distance - expected_distance).abs() < 1e-5);
+                let distance_of_docid = 1.0 - query.similarity(&embeddings[&docid]);
+                assert!((distance - distance_of_docid).abs() < 1e-5);
+            }
+        }
+    }
+}